@@ -0,0 +1,59 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use python3_config::{fixtures, parse_keys, Backend, ParseOptions, PythonConfig};
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (backend_name, backend) in [
+        ("rustpython", Backend::RustPython),
+        ("lightweight", Backend::Lightweight),
+    ] {
+        let options = ParseOptions::new().backend(backend);
+        for fixture in fixtures::all() {
+            group.bench_with_input(
+                BenchmarkId::new(backend_name, fixture.name),
+                fixture.source,
+                |b, src| b.iter(|| PythonConfig::parse_with_options(src, &options).unwrap()),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_parse_keys(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_keys");
+    for fixture in fixtures::all() {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(fixture.name),
+            fixture.source,
+            |b, src| b.iter(|| parse_keys(src, &["VERSION", "SOABI", "EXT_SUFFIX"]).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+fn bench_derived(c: &mut Criterion) {
+    let mut group = c.benchmark_group("derived");
+    for fixture in fixtures::all() {
+        let config = fixture.config();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(fixture.name),
+            &config,
+            |b, config| {
+                b.iter(|| {
+                    // `config` itself is never queried, so every clone starts
+                    // without memoized values
+                    let config = config.clone();
+                    (
+                        config.abi_tag().len(),
+                        config.get_platform().len(),
+                        config.type_sizes().len(),
+                    )
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_parse_keys, bench_derived);
+criterion_main!(benches);
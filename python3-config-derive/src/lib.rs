@@ -0,0 +1,136 @@
+//! `#[derive(FromPythonConfig)]` for the `python3-config` crate, enable its
+//! `derive` feature instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, ExprPath, Field, Fields, GenericArgument, LitStr,
+    PathArguments, Type,
+};
+
+/// Implements `FromPythonConfig` for a struct with named fields, reading each
+/// field from the key named like it or given by `#[python_config(key = "...")]`.
+///
+/// Fields are converted with `TryFrom<&ConfigValue>`. `Option` fields are
+/// `None` for missing keys and `None` values, fields marked
+/// `#[python_config(default)]` or `#[python_config(default = "path::to::fn")]`
+/// fall back to `Default::default()` or the function for missing keys.
+#[proc_macro_derive(FromPythonConfig, attributes(python_config))]
+pub fn derive_from_python_config(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Fallback of a field whose key is missing
+enum FieldDefault {
+    Required,
+    Trait,
+    Function(ExprPath),
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "FromPythonConfig requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "FromPythonConfig can only be derived for structs",
+            ))
+        }
+    };
+    let initializers = fields
+        .iter()
+        .map(initializer)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::python3_config::FromPythonConfig for #name #ty_generics #where_clause {
+            fn from_python_config(
+                config: &::python3_config::PythonConfig,
+            ) -> ::std::result::Result<Self, ::python3_config::Error> {
+                ::std::result::Result::Ok(Self {
+                    #(#initializers,)*
+                })
+            }
+        }
+    })
+}
+
+/// `field: value` reading `field` from `config`
+fn initializer(field: &Field) -> syn::Result<TokenStream2> {
+    let ident = field.ident.as_ref().unwrap();
+    let mut key = None;
+    let mut default = FieldDefault::Required;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("python_config"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                key = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = if meta.input.peek(syn::Token![=]) {
+                    FieldDefault::Function(meta.value()?.parse::<LitStr>()?.parse()?)
+                } else {
+                    FieldDefault::Trait
+                };
+                Ok(())
+            } else {
+                Err(meta.error("unknown python_config attribute, expected `key` or `default`"))
+            }
+        })?;
+    }
+    let key = key.unwrap_or_else(|| ident.to_string());
+    let value = if is_option(&field.ty) {
+        quote! {
+            match config.get(#key) {
+                ::std::option::Option::Some(value) if !value.is_none() => {
+                    ::std::option::Option::Some(::std::convert::TryFrom::try_from(value)?)
+                }
+                _ => ::std::option::Option::None,
+            }
+        }
+    } else {
+        let fallback = match default {
+            FieldDefault::Required => return Ok(quote! { #ident: config.get_as(#key)? }),
+            FieldDefault::Trait => quote! { ::std::default::Default::default() },
+            FieldDefault::Function(path) => quote! { #path() },
+        };
+        quote! {
+            match config.get(#key) {
+                ::std::option::Option::Some(value) => ::std::convert::TryFrom::try_from(value)?,
+                ::std::option::Option::None => #fallback,
+            }
+        }
+    };
+    Ok(quote! { #ident: #value })
+}
+
+/// Whether `ty` is spelled `Option<T>`
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last().is_some_and(|x| {
+            x.ident == "Option"
+                && matches!(
+                    &x.arguments,
+                    PathArguments::AngleBracketed(args)
+                        if matches!(args.args.first(), Some(GenericArgument::Type(_)))
+                )
+        }),
+        _ => false,
+    }
+}
@@ -0,0 +1,105 @@
+use crate::registry::Registry;
+use crate::PythonConfig;
+
+/// What a letter of `ABIFLAGS` means for the wheel ABI tag, see
+/// [`register_abiflag`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AbiFlagMeaning {
+    /// `d`, `Py_DEBUG` build
+    Debug,
+    /// `t`, free-threaded build
+    FreeThreaded,
+    /// `m`, pymalloc build before Python 3.8
+    Pymalloc,
+    /// `u`, wide unicode build before Python 3.3
+    WideUnicode,
+    /// Vendor variant with its own ABI, kept in the ABI tag
+    Variant,
+    /// Vendor marker that doesn't change the ABI, such as a build with
+    /// tracemalloc hooks, left out of the ABI tag so wheels built for the
+    /// upstream interpreter install
+    Compatible,
+}
+
+const BUILTIN: &[(char, AbiFlagMeaning)] = &[
+    ('d', AbiFlagMeaning::Debug),
+    ('m', AbiFlagMeaning::Pymalloc),
+    ('t', AbiFlagMeaning::FreeThreaded),
+    ('u', AbiFlagMeaning::WideUnicode),
+];
+
+/// The built-in letters and the ones added with [`register_abiflag`]
+static REGISTRY: Registry<(char, AbiFlagMeaning)> = Registry::new(BUILTIN);
+
+/// Gives the `ABIFLAGS` letter `letter` of a vendor spin a meaning, letters
+/// without one are passed through to the ABI tag with a
+/// [`WarningKind::UnknownAbiFlags`](crate::WarningKind::UnknownAbiFlags)
+/// warning.
+///
+/// Registered letters take precedence over the built-in ones.
+pub fn register_abiflag(letter: char, meaning: AbiFlagMeaning) {
+    REGISTRY.register((letter, meaning));
+}
+
+/// Returns the meaning of the `ABIFLAGS` letter `letter`, `None` if unknown
+pub fn abiflag_meaning(letter: char) -> Option<AbiFlagMeaning> {
+    REGISTRY
+        .find(|(known, _)| *known == letter)
+        .map(|(_, meaning)| meaning)
+}
+
+/// The ABI flags spelled in wheel ABI tags: [`AbiFlagMeaning::Compatible`]
+/// letters are dropped, unknown ones are lowercased and characters that
+/// can't appear in tags are dropped
+pub(crate) fn tag_abiflags(abiflags: &str) -> String {
+    abiflags
+        .chars()
+        .filter(|letter| abiflag_meaning(*letter) != Some(AbiFlagMeaning::Compatible))
+        .filter(char::is_ascii_alphanumeric)
+        .map(|letter| letter.to_ascii_lowercase())
+        .collect()
+}
+
+/// The letters of `abiflags` without a meaning
+pub(crate) fn unknown_abiflags(abiflags: &str) -> Vec<char> {
+    abiflags
+        .chars()
+        .filter(|letter| abiflag_meaning(*letter).is_none())
+        .collect()
+}
+
+impl PythonConfig {
+    /// Returns the letters of `ABIFLAGS` without a built-in or registered
+    /// meaning, see [`register_abiflag`]
+    pub fn unknown_abiflags(&self) -> Vec<char> {
+        unknown_abiflags(self.abiflags())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{abiflag_meaning, tag_abiflags, AbiFlagMeaning};
+    use crate::{PythonConfig, WarningKind};
+
+    #[test]
+    fn vendor_abiflags() {
+        let src = "build_time_vars = {'VERSION': '3.12', 'ABIFLAGS': 'dq', \
+                   'SOABI': 'cpython-312dq-x86_64-linux-gnu'}";
+        let (config, report) = PythonConfig::parse_with_report(src).unwrap();
+        assert_eq!(config.unknown_abiflags(), vec!['q']);
+        assert_eq!(config.abi_tag(), "cp312dq");
+        assert_eq!(report.warnings[0].kind, WarningKind::UnknownAbiFlags);
+        assert_eq!(report.warnings[0].key, "ABIFLAGS");
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'ABIFLAGS': 'k+', \
+             'SOABI': 'cpython-312k+-x86_64-linux-gnu'}",
+        )
+        .unwrap();
+        assert_eq!(config.abi_tag(), "cp312k");
+
+        assert_eq!(abiflag_meaning('t'), Some(AbiFlagMeaning::FreeThreaded));
+        assert_eq!(abiflag_meaning('q'), None);
+        assert_eq!(tag_abiflags("td"), "td");
+    }
+}
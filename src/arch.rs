@@ -0,0 +1,160 @@
+use crate::registry::Registry;
+
+/// CPU architecture as spelled by Debian multiarch tuples, Rust target
+/// triples, cargo and `uname -m`, one row of the table behind
+/// [`expected_for_target`](crate::expected_for_target), pointer size
+/// inference and platform tags.
+///
+/// The table covers the Debian and Fedora architectures, [`register_arch`]
+/// adds experimental ports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arch {
+    /// Architecture part of `MULTIARCH` and the platform part of `SOABI`,
+    /// `powerpc64le` in `powerpc64le-linux-gnu`
+    pub multiarch: &'static str,
+    /// Architecture parts of Rust target triples, `riscv64gc`
+    pub rust_triples: &'static [&'static str],
+    /// `CARGO_CFG_TARGET_ARCH`, empty without a Rust target
+    pub cargo: &'static str,
+    /// `uname -m` of Linux, used by `sysconfig.get_platform()` and wheel
+    /// platform tags, `ppc64le`
+    pub machine: &'static str,
+    /// Other names of the architecture in `HOST_GNU_TYPE` or `uname -m`
+    pub aliases: &'static [&'static str],
+    /// Size of a pointer in bytes
+    pub pointer_size: u32,
+}
+
+const fn arch(
+    multiarch: &'static str,
+    rust_triples: &'static [&'static str],
+    cargo: &'static str,
+    machine: &'static str,
+    aliases: &'static [&'static str],
+    pointer_size: u32,
+) -> Arch {
+    Arch {
+        multiarch,
+        rust_triples,
+        cargo,
+        machine,
+        aliases,
+        pointer_size,
+    }
+}
+
+const ARCHS: &[Arch] = &[
+    arch("x86_64", &["x86_64"], "x86_64", "x86_64", &["amd64"], 8),
+    arch(
+        "i386",
+        &["i386", "i586", "i686"],
+        "x86",
+        "i686",
+        &["i486", "i586", "x86"],
+        4,
+    ),
+    arch(
+        "aarch64",
+        &["aarch64", "arm64"],
+        "aarch64",
+        "aarch64",
+        &["arm64"],
+        8,
+    ),
+    arch(
+        "arm",
+        &["arm", "armv6", "armv7", "armv7a", "thumbv7neon"],
+        "arm",
+        "armv7l",
+        &["armv6l", "armv8l"],
+        4,
+    ),
+    // Debian armel, lacking the ARMv7 instructions of armv7l wheels
+    arch("arm", &["armv5te"], "arm", "armv5tel", &[], 4),
+    arch("powerpc", &["powerpc"], "powerpc", "ppc", &[], 4),
+    arch("powerpc64", &["powerpc64"], "powerpc64", "ppc64", &[], 8),
+    arch(
+        "powerpc64le",
+        &["powerpc64le"],
+        "powerpc64",
+        "ppc64le",
+        &[],
+        8,
+    ),
+    arch("s390x", &["s390x"], "s390x", "s390x", &[], 8),
+    arch(
+        "riscv64",
+        &["riscv64gc", "riscv64"],
+        "riscv64",
+        "riscv64",
+        &[],
+        8,
+    ),
+    arch(
+        "loongarch64",
+        &["loongarch64"],
+        "loongarch64",
+        "loongarch64",
+        &[],
+        8,
+    ),
+    arch("mips", &["mips"], "mips", "mips", &[], 4),
+    arch("mipsel", &["mipsel"], "mips", "mips", &[], 4),
+    arch("mips64", &["mips64"], "mips64", "mips64", &[], 8),
+    arch("mips64el", &["mips64el"], "mips64", "mips64", &[], 8),
+    arch("sparc64", &["sparc64"], "sparc64", "sparc64", &[], 8),
+    arch("m68k", &["m68k"], "m68k", "m68k", &[], 4),
+    arch("alpha", &[], "", "alpha", &[], 8),
+    arch("hppa", &[], "", "parisc", &[], 4),
+    arch("ia64", &[], "", "ia64", &[], 8),
+    arch("sh4", &[], "", "sh4", &[], 4),
+    arch("wasm32", &["wasm32"], "wasm32", "wasm32", &[], 4),
+];
+
+/// The built-in architectures and the ones added with [`register_arch`]
+static REGISTRY: Registry<Arch> = Registry::new(ARCHS);
+
+/// Adds an architecture to the table, for example an experimental port.
+///
+/// Registered architectures take precedence over the built-in ones with the
+/// same names.
+pub fn register_arch(arch: Arch) {
+    REGISTRY.register(arch);
+}
+
+/// Returns the table of known architectures, registered ones first
+pub fn archs() -> Vec<Arch> {
+    REGISTRY.entries()
+}
+
+/// The architecture named `name` in a multiarch tuple, `HOST_GNU_TYPE` or
+/// `uname -m`
+pub(crate) fn find_by_name(name: &str) -> Option<Arch> {
+    REGISTRY.find(|arch| has_name(arch, name))
+}
+
+/// The architecture of the first part of a Rust target triple
+pub(crate) fn find_by_rust_triple(name: &str) -> Option<Arch> {
+    REGISTRY.find(|arch| arch.rust_triples.contains(&name))
+}
+
+fn has_name(arch: &Arch, name: &str) -> bool {
+    arch.multiarch == name || arch.machine == name || arch.aliases.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_by_name, find_by_rust_triple};
+
+    #[test]
+    fn arch_table() {
+        assert_eq!(find_by_name("ppc64le").unwrap().multiarch, "powerpc64le");
+        assert_eq!(find_by_name("amd64").unwrap().machine, "x86_64");
+        assert_eq!(find_by_rust_triple("riscv64gc").unwrap().pointer_size, 8);
+        assert_eq!(find_by_name("hppa").unwrap().pointer_size, 4);
+        assert_eq!(find_by_rust_triple("armv5te").unwrap().machine, "armv5tel");
+        assert_eq!(find_by_name("armv5tel").unwrap().machine, "armv5tel");
+        assert_eq!(find_by_name("arm").unwrap().machine, "armv7l");
+        assert!(find_by_name("e2k").is_none());
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::paths::windows_join;
+use crate::{LinkMode, LinkModel, PythonConfig};
+
+/// Files providing libpython, as `cc_import` names them
+#[derive(Debug, Default)]
+struct LibpythonFiles {
+    shared_library: Option<PathBuf>,
+    interface_library: Option<PathBuf>,
+    static_library: Option<PathBuf>,
+}
+
+impl PythonConfig {
+    /// Returns a Bazel `BUILD` file registering this distribution as a
+    /// Python toolchain: a `py_runtime` with its `py_runtime_pair` and
+    /// `toolchain`, a `cc_import` of libpython and a `python_headers`
+    /// `cc_library` carrying the `copts` and `linkopts` of the build.
+    ///
+    /// The file is meant for a `new_local_repository` rooted at `prefix`,
+    /// paths below it are relative, others are left absolute.
+    pub fn to_bazel_python_toolchain(&self) -> String {
+        let prefix = self
+            .prefix_path()
+            .map(|prefix| prefix.to_string_lossy().replace('\\', "/"));
+        let relative = |path: &Path| -> String {
+            let path = path.to_string_lossy().replace('\\', "/");
+            prefix
+                .as_deref()
+                .and_then(|prefix| path.strip_prefix(prefix)?.strip_prefix('/'))
+                .unwrap_or(&path)
+                .to_string()
+        };
+        let includes: Vec<String> = self
+            .include_dirs()
+            .iter()
+            .map(|dir| relative(dir))
+            .collect();
+        let hdrs: Vec<String> = includes
+            .iter()
+            .map(|dir| format!("{}/**/*.h", dir))
+            .collect();
+        let (copts, linkopts) = self.toolchain_flags();
+        let libpython = self.libpython_files();
+
+        let mut out = self.generated_comment("#");
+        out.push_str(
+            "load(\"@rules_cc//cc:defs.bzl\", \"cc_import\", \"cc_library\")\n\
+             load(\"@rules_python//python:defs.bzl\", \"py_runtime\", \"py_runtime_pair\")\n\n\
+             package(default_visibility = [\"//visibility:public\"])\n\n",
+        );
+        out.push_str("py_runtime(\n    name = \"python3_runtime\",\n");
+        if let Some(python) = self.python_executable_path() {
+            writeln!(
+                out,
+                "    interpreter = {},",
+                starlark_str(&relative(&python))
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "    python_version = \"PY3\",\n)\n\n\
+             py_runtime_pair(\n    name = \"python3_runtime_pair\",\n    \
+             py3_runtime = \":python3_runtime\",\n)\n\n\
+             toolchain(\n    name = \"python3_toolchain\",\n    \
+             toolchain = \":python3_runtime_pair\",\n    \
+             toolchain_type = \"@rules_python//python:toolchain_type\",\n)\n"
+        )
+        .unwrap();
+        out.push_str("cc_import(\n    name = \"libpython\",\n");
+        for (attr, path) in [
+            ("interface_library", &libpython.interface_library),
+            ("shared_library", &libpython.shared_library),
+            ("static_library", &libpython.static_library),
+        ] {
+            if let Some(path) = path {
+                writeln!(out, "    {} = {},", attr, starlark_str(&relative(path))).unwrap();
+            }
+        }
+        out.push_str(")\n\ncc_library(\n    name = \"python_headers\",\n");
+        writeln!(out, "    hdrs = glob({}),", starlark_list(&hdrs)).unwrap();
+        writeln!(out, "    includes = {},", starlark_list(&includes)).unwrap();
+        writeln!(out, "    copts = {},", starlark_list(&copts)).unwrap();
+        writeln!(out, "    linkopts = {},", starlark_list(&linkopts)).unwrap();
+        out.push_str("    deps = [\":libpython\"],\n)\n");
+        out
+    }
+
+    /// Returns a Buck2 `BUCK` file with a `system_python_toolchain` and
+    /// `system_python_bootstrap_toolchain` for the interpreter and a
+    /// `prebuilt_cxx_library` exporting the compile and link flags of
+    /// libpython, with the absolute paths of the installation
+    pub fn to_buck2_toolchain(&self) -> String {
+        let (mut copts, mut linkopts) = self.toolchain_flags();
+        let includes = self.include_dirs();
+        for dir in includes.iter().rev() {
+            copts.insert(0, format!("-I{}", dir.display()));
+        }
+        let libpython = self.libpython_files();
+        let library = match self.link_model() {
+            LinkModel::Static => libpython.static_library,
+            _ => libpython.interface_library.or(libpython.shared_library),
+        };
+        if let Some(library) = library {
+            linkopts.insert(0, library.display().to_string());
+        }
+
+        let mut out = self.generated_comment("#");
+        out.push_str(
+            "load(\"@prelude//toolchains:python.bzl\", \
+             \"system_python_bootstrap_toolchain\", \"system_python_toolchain\")\n\n",
+        );
+        let interpreter = self
+            .python_executable_path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "python3".to_string());
+        for (rule, name) in [
+            ("system_python_bootstrap_toolchain", "python_bootstrap"),
+            ("system_python_toolchain", "python"),
+        ] {
+            writeln!(
+                out,
+                "{}(\n    name = {},\n    interpreter = {},\n    \
+                 visibility = [\"PUBLIC\"],\n)\n",
+                rule,
+                starlark_str(name),
+                starlark_str(&interpreter)
+            )
+            .unwrap();
+        }
+        out.push_str("prebuilt_cxx_library(\n    name = \"libpython\",\n    header_only = True,\n");
+        writeln!(
+            out,
+            "    exported_preprocessor_flags = {},",
+            starlark_list(&copts)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    exported_linker_flags = {},",
+            starlark_list(&linkopts)
+        )
+        .unwrap();
+        out.push_str("    visibility = [\"PUBLIC\"],\n)\n");
+        out
+    }
+
+    /// `INCLUDEPY` and `CONFINCLUDEPY`, the directories of
+    /// [`PythonConfig::include_flags`]
+    fn include_dirs(&self) -> Vec<PathBuf> {
+        self.include_flags()
+            .iter()
+            .map(|flag| PathBuf::from(&flag[2..]))
+            .collect()
+    }
+
+    /// Compile flags without include directories and link options: the
+    /// libraries of [`PythonConfig::lib_flags`] followed by the linker
+    /// directives of `LDFLAGS`, each once
+    fn toolchain_flags(&self) -> (Vec<String>, Vec<String>) {
+        let copts = self
+            .compile_flags()
+            .into_iter()
+            .filter(|flag| !flag.starts_with("-I"))
+            .collect();
+        let mut linkopts: Vec<String> = Vec::new();
+        for flag in self
+            .lib_flags(LinkMode::ExtensionModule)
+            .into_iter()
+            .chain(self.linker_args().iter().map(|arg| arg.to_cc_flag()))
+        {
+            if !linkopts.contains(&flag) {
+                linkopts.push(flag);
+            }
+        }
+        (copts, linkopts)
+    }
+
+    fn libpython_files(&self) -> LibpythonFiles {
+        let vars = &self.sys_config_data.build_time_vars;
+        let mut files = LibpythonFiles::default();
+        if self.link_model() == LinkModel::Static {
+            files.static_library = self.static_library_path();
+            return files;
+        }
+        if self.sys_platform() == "win32" && !self.is_mingw() {
+            let name = format!("python{}{}", self.major_minor().0, self.major_minor().1);
+            let lib = format!("{}.lib", name);
+            let dll = format!("{}.dll", name);
+            files.interface_library = Some(windows_join(self.prefix_str(), &["libs", &lib]));
+            files.shared_library = Some(windows_join(self.prefix_str(), &[&dll]));
+            return files;
+        }
+        if self.is_mingw() || self.is_cygwin() {
+            let ldlibrary = vars.get_str("LDLIBRARY");
+            if !ldlibrary.is_empty() && !self.lib_dir_str().is_empty() {
+                files.interface_library = Some(if self.is_mingw() {
+                    windows_join(self.lib_dir_str(), &[ldlibrary])
+                } else {
+                    Path::new(self.lib_dir_str()).join(ldlibrary)
+                });
+            }
+        }
+        files.shared_library = self.dylib_install_name();
+        files
+    }
+}
+
+/// Starlark string literal of `value`
+fn starlark_str(value: &str) -> String {
+    format!("{:?}", value)
+}
+
+/// Starlark list of string literals
+fn starlark_list(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|value| starlark_str(value)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures;
+
+    #[test]
+    fn bazel_toolchain() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let build = config.to_bazel_python_toolchain();
+        assert!(
+            build.starts_with("# Generated by python3-config for CPython 3.11 (linux-x86_64)\n")
+        );
+        assert!(build.contains("    interpreter = \"bin/python3.11\",\n"));
+        assert!(build.contains("    shared_library = \"lib/libpython3.11.so.1.0\",\n"));
+        assert!(build.contains("    includes = [\"include/python3.11\"],\n"));
+        assert!(build.contains("    hdrs = glob([\"include/python3.11/**/*.h\"]),\n"));
+        assert!(build.contains(
+            "    linkopts = [\"-ldl\", \"-lm\", \"-pthread\", \"-Wl,-O1\", \"-Wl,-Bsymbolic-functions\"],\n"
+        ));
+        assert!(!build.contains("-I"));
+
+        let config = fixtures::get("cpython311-mingw").unwrap().config();
+        let build = config.to_bazel_python_toolchain();
+        assert!(build.contains("    interface_library = \"lib/libpython3.11.dll.a\",\n"));
+        assert!(build.contains("    shared_library = \"bin/libpython3.11.dll\",\n"));
+    }
+
+    #[test]
+    fn buck2_toolchain() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let buck = config.to_buck2_toolchain();
+        assert!(buck.contains(
+            "system_python_toolchain(\n    name = \"python\",\n    \
+             interpreter = \"/usr/local/bin/python3.11\",\n"
+        ));
+        assert!(buck.contains(
+            "    exported_preprocessor_flags = [\"-I/usr/local/include/python3.11\", \
+             \"-Wsign-compare\""
+        ));
+        assert!(buck.contains(
+            "    exported_linker_flags = [\"/usr/local/lib/libpython3.11.so.1.0\", \"-ldl\""
+        ));
+    }
+}
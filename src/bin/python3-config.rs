@@ -0,0 +1,888 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use python3_config::{
+    AbiVariant, Decision, KnownKey, Limits, LinkMode, ParseOptions, PythonConfig, PythonVersion,
+    SupportMatrix,
+};
+
+const USAGE: &str = "\
+Usage: python3-config [<guards>] [--json] [--embed | --embed-static] <options> <source>
+       python3-config [<guards>] <command> [options]
+
+Guards, bounding the work done on untrusted <source> files:
+  --max-bytes <n>
+      Fail when the file is larger than <n> bytes, without reading the rest,
+      also for downloads of --from-url
+  --timeout <secs>
+      Fail when parsing takes longer than <secs> seconds, which may be
+      fractional, with --from-url also when connecting or reading takes longer
+
+Options, printed one per line in the order given:
+  --prefix --exec-prefix --includes --libs --cflags --ldflags
+  --extension-suffix --abiflags --configdir
+  --embed includes libpython in --libs and --ldflags, --embed-static its
+  static library, --json prints a single object with flags as arrays instead
+
+Commands:
+  dump [--anonymize] [--annotated] <source>
+      Print the distribution as a _sysconfigdata.py file, with --anonymize
+      replace user names in paths for sharing in bug reports, with
+      --annotated describe each well-known key in a comment
+  env <source>
+      Print export lines describing the distribution to child build
+      processes such as autotools or meson
+  scan [--json] [--target <triple>] <dir>
+      Parse every _sysconfigdata*.py file under <dir>, with --target also
+      check that each matches the Rust target triple
+  verify [--target <triple>] [--min-version <X.Y>] [--sysroot <dir>] <source>
+      Check that the file parses, matches the Rust target triple, is at
+      least the given Python version and that its include and library
+      paths exist, under <dir> with --sysroot
+  lint [--fix] <source>
+      Report problems like a stale prefix or -march=native in the flags,
+      with --fix print the file with fixable ones corrected as a
+      _sysconfigdata.py file and report the rest on stderr
+  self-test [--python <path>]
+      Parse the sysconfigdata of a local interpreter, python3 by default,
+      and compare the derived values with what its sysconfig module reports
+  select [--variant <variant>] <dir>
+      Print the path of the only _sysconfigdata*.py file under <dir> of the
+      build variant, one of default (the default), debug, free-threaded or
+      free-threaded-debug
+  matrix [--json] <dir>
+      Print the versions and build flavors of the _sysconfigdata*.py files
+      under <dir> as CSV, or JSON with --json
+  inventory <dir>
+      Print the path, version, implementation, ABI tag, platform, prefix and
+      link model of the _sysconfigdata*.py files under <dir> as CSV
+  get <key> <source>
+      Print the value of a build_time_vars key, matched ignoring case
+  grep <text> <source>
+      Print the keys whose values contain <text>, such as a flag like
+      -march=native or a stale prefix, with their values
+  watch [--variant <variant>] --emit <format> <out>... <dir>
+      Write the artifacts derived from the sysconfigdata of the variant
+      under <dir> to the <out> files, again whenever it changes, until
+      interrupted. <format> is one of pyo3-config, sysconfigdata, env or
+      report, --emit can be repeated
+  explain [--json] <decision> <source>
+      Explain how a derived value was decided, <decision> is one of
+      link-model, implementation, abi-tag or platform
+  report [--html] <source>
+      Print a Markdown report of the version, ABI, layout, toolchain, flags
+      and lint findings of the distribution, a HTML page with --html
+
+<source> is the path of a _sysconfigdata.py file or --from-url <url> to
+download one, append #sha256=<hex> to the URL to verify its checksum
+
+Exit status is 0 on success, 1 when some files of a scan or some checks of
+verify or self-test failed or lint found problems it didn't fix and 2 on
+errors";
+
+const EXIT_OK: i32 = 0;
+const EXIT_PARTIAL: i32 = 1;
+const EXIT_FATAL: i32 = 2;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => process::exit(code),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(EXIT_FATAL);
+        }
+    }
+}
+
+/// Limits set by the guard options, applied when loading a `<source>`
+static LIMITS: OnceLock<Limits> = OnceLock::new();
+
+fn run(args: &[String]) -> Result<i32, String> {
+    let mut limits = Limits::new();
+    let mut args = args;
+    loop {
+        match args {
+            [option, value, rest @ ..] if option == "--max-bytes" => {
+                let max = value
+                    .parse()
+                    .map_err(|_| format!("invalid --max-bytes {}", value))?;
+                limits = limits.max_file_size(max);
+                args = rest;
+            }
+            [option, value, rest @ ..] if option == "--timeout" => {
+                let timeout = value
+                    .parse()
+                    .ok()
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .ok_or_else(|| format!("invalid --timeout {}", value))?;
+                limits = limits.timeout(timeout);
+                args = rest;
+            }
+            _ => break,
+        }
+    }
+    LIMITS.get_or_init(|| limits);
+    match args.first().map(String::as_str) {
+        Some("scan") => scan(&args[1..]).map(|all_ok| if all_ok { EXIT_OK } else { EXIT_PARTIAL }),
+        Some("verify") => {
+            verify(&args[1..]).map(|all_ok| if all_ok { EXIT_OK } else { EXIT_PARTIAL })
+        }
+        Some("lint") => lint(&args[1..]).map(|clean| if clean { EXIT_OK } else { EXIT_PARTIAL }),
+        Some("self-test") => {
+            self_test(&args[1..]).map(|all_ok| if all_ok { EXIT_OK } else { EXIT_PARTIAL })
+        }
+        _ => run_command(args).map(|()| EXIT_OK),
+    }
+}
+
+fn run_command(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("dump") => dump(&args[1..]),
+        Some("env") => build_env(&args[1..]),
+        Some("explain") => explain(&args[1..]),
+        Some("get") => get(&args[1..]),
+        Some("grep") => grep(&args[1..]),
+        Some("inventory") => inventory(&args[1..]),
+        Some("matrix") => matrix(&args[1..]),
+        Some("report") => report(&args[1..]),
+        Some("select") => select(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(option) if option.starts_with("--") => python3_config(args),
+        Some(command) => Err(format!("unknown command {}\n\n{}", command, USAGE)),
+        None => Err(USAGE.to_string()),
+    }
+}
+
+fn load(source: &[&str]) -> Result<PythonConfig, String> {
+    let options = ParseOptions::new().limits(LIMITS.get().copied().unwrap_or_default());
+    match source {
+        ["--from-url", url] => load_url(url, &options),
+        [path] => fs::File::open(path)
+            .map_err(python3_config::Error::from)
+            .and_then(|file| PythonConfig::read_with_options(file, &options))
+            .map_err(|err| format!("{}: {}", path, err)),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+#[cfg(feature = "http")]
+fn load_url(url: &str, options: &ParseOptions) -> Result<PythonConfig, String> {
+    PythonConfig::from_url_with_options(url, options).map_err(|err| format!("{}: {}", url, err))
+}
+
+#[cfg(not(feature = "http"))]
+fn load_url(_url: &str, _options: &ParseOptions) -> Result<PythonConfig, String> {
+    Err("--from-url requires the http feature".to_string())
+}
+
+/// Scans a directory, returns whether every file found is fine
+fn scan(args: &[String]) -> Result<bool, String> {
+    let mut json = false;
+    let mut target = None;
+    let mut dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--target" => target = Some(args.next().ok_or_else(|| USAGE.to_string())?),
+            _ if dir.is_none() => dir = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let dir = dir.ok_or_else(|| USAGE.to_string())?;
+    let results: Vec<ScanResult> = python3_config::scan(dir)
+        .map_err(|err| format!("{}: {}", dir, err))?
+        .into_iter()
+        .map(|(path, config)| {
+            let config = config.and_then(|config| {
+                if let Some(target) = target {
+                    config.validate_target(target)?;
+                }
+                Ok(config)
+            });
+            ScanResult {
+                path: path.display().to_string(),
+                outcome: config
+                    .map(|config| format!("{} {}", version(&config), config.get_platform()))
+                    .map_err(|err| err.to_string()),
+            }
+        })
+        .collect();
+    let all_ok = results.iter().all(|result| result.outcome.is_ok());
+    if json {
+        print_scan_json(&results)?;
+    } else {
+        for result in &results {
+            match &result.outcome {
+                Ok(summary) => println!("ok {} ({})", result.path, summary),
+                Err(err) => println!("failed {}: {}", result.path, err),
+            }
+        }
+    }
+    Ok(all_ok)
+}
+
+/// Verifies a configuration, returns whether every check passed
+fn verify(args: &[String]) -> Result<bool, String> {
+    let mut target = None;
+    let mut min_version = None;
+    let mut sysroot = None;
+    let mut source = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--target" => target = Some(args.next().ok_or_else(|| USAGE.to_string())?),
+            "--min-version" => {
+                let version = args.next().ok_or_else(|| USAGE.to_string())?;
+                min_version = Some(parse_version(version)?);
+            }
+            "--sysroot" => sysroot = Some(args.next().ok_or_else(|| USAGE.to_string())?),
+            _ => source.push(arg.as_str()),
+        }
+    }
+    let config = load(&source)?;
+    let mut checks = Vec::new();
+    if let Some((major, minor)) = min_version {
+        let minimum = PythonVersion::new(major, minor);
+        checks.push((
+            "version",
+            match config.python_version() {
+                Some(found) if found >= minimum => {
+                    Ok(format!("{} >= {}", version(&config), minimum))
+                }
+                Some(_) => Err(format!("{} is older than {}", version(&config), minimum)),
+                None => Err(format!("invalid VERSION {}", version(&config))),
+            },
+        ));
+    }
+    if let Some(target) = target {
+        checks.push((
+            "target",
+            config
+                .validate_target(target)
+                .map(|()| format!("{} matches {}", config.get_platform(), target))
+                .map_err(|err| err.to_string()),
+        ));
+    }
+    let missing = config.missing_paths(sysroot.map(Path::new));
+    checks.push((
+        "paths",
+        if missing.is_empty() {
+            Ok("include and library paths exist".to_string())
+        } else {
+            let missing: Vec<String> = missing.iter().map(|x| x.display().to_string()).collect();
+            Err(format!("missing {}", missing.join(", ")))
+        },
+    ));
+    for (name, outcome) in &checks {
+        match outcome {
+            Ok(summary) => println!("ok {}: {}", name, summary),
+            Err(err) => println!("failed {}: {}", name, err),
+        }
+    }
+    Ok(checks.iter().all(|(_, outcome)| outcome.is_ok()))
+}
+
+/// Lints a configuration, returns whether no problem is left
+fn lint(args: &[String]) -> Result<bool, String> {
+    let fix = args.iter().any(|arg| arg == "--fix");
+    let source: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--fix")
+        .collect();
+    let config = load(&source)?;
+    if !fix {
+        let findings = config.lint();
+        for finding in &findings {
+            println!("{}", finding);
+        }
+        return Ok(findings.is_empty());
+    }
+    let (config, findings) = config.lint_fix().map_err(|err| err.to_string())?;
+    let mut clean = true;
+    for finding in &findings {
+        if finding.fix.is_some() {
+            eprintln!("fixed {}", finding);
+        } else {
+            eprintln!("{}", finding);
+            clean = false;
+        }
+    }
+    print!("{}", config.to_sysconfigdata());
+    Ok(clean)
+}
+
+/// Prints the values `self_test` compares, as reported by the interpreter,
+/// one `name<TAB>value` line each
+const SELF_TEST_SCRIPT: &str = "\
+import importlib, struct, sys, sysconfig
+name = sysconfig._get_sysconfigdata_name()
+var = lambda key: sysconfig.get_config_var(key) or ''
+values = [
+    ('sysconfigdata', importlib.import_module(name).__file__),
+    ('sysconfigdata name', name),
+    ('version', sysconfig.get_python_version()),
+    ('implementation', sys.implementation.name),
+    ('platform', sysconfig.get_platform()),
+    ('abiflags', getattr(sys, 'abiflags', '')),
+    ('multiarch', getattr(sys.implementation, '_multiarch', '')),
+    ('pointer size', struct.calcsize('P')),
+    ('extension suffix', var('EXT_SUFFIX')),
+    ('soabi', var('SOABI')),
+    ('ldversion', var('LDVERSION')),
+    ('prefix', var('prefix')),
+    ('libdir', var('LIBDIR')),
+    ('configdir', var('LIBPL')),
+    ('include', sysconfig.get_path('include')),
+]
+for key, value in values:
+    print('%s\\t%s' % (key, value))
+";
+
+/// Compares the derived values of an interpreter's sysconfigdata with what
+/// it reports itself, returns whether all match
+fn self_test(args: &[String]) -> Result<bool, String> {
+    let python = match args {
+        [] => "python3",
+        [option, python] if option == "--python" => python,
+        _ => return Err(USAGE.to_string()),
+    };
+    let output = process::Command::new(python)
+        .args(["-c", SELF_TEST_SCRIPT])
+        .output()
+        .map_err(|err| format!("{}: {}", python, err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{}: {}",
+            python,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let expected: Vec<(&str, &str)> = stdout
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+    let path = expected
+        .iter()
+        .find(|(name, _)| *name == "sysconfigdata")
+        .map(|(_, path)| *path)
+        .ok_or_else(|| format!("{}: no sysconfigdata reported", python))?;
+    println!("{} reads {}", python, path);
+    let config = load(&[path])?;
+    let scheme = config.install_scheme();
+    let derived = |name: &str| -> Option<String> {
+        Some(match name {
+            "sysconfigdata name" => config.sysconfigdata_name().unwrap_or_default(),
+            "version" => version(&config),
+            "implementation" => config.implementation().to_string().to_lowercase(),
+            "platform" => config.get_platform().to_string(),
+            "abiflags" => config.abiflags().to_string(),
+            "multiarch" => config.multiarch().to_string(),
+            "pointer size" => config.pointer_size().to_string(),
+            "extension suffix" => config.ext_suffix().to_string(),
+            "soabi" => config.soabi().to_string(),
+            "ldversion" => config.ld_version().to_string(),
+            "prefix" => config.get_known(KnownKey::Prefix).to_string(),
+            "libdir" => config.get_known(KnownKey::Libdir).to_string(),
+            "configdir" => config.get_known(KnownKey::Libpl).to_string(),
+            "include" => scheme.include.display().to_string(),
+            _ => return None,
+        })
+    };
+    let mut all_ok = true;
+    for (name, expected) in &expected {
+        let value = match derived(name) {
+            Some(value) => value,
+            None => continue,
+        };
+        if value == *expected {
+            println!("ok {}: {}", name, value);
+        } else {
+            println!(
+                "failed {}: {}, {} reports {}",
+                name, value, python, expected
+            );
+            all_ok = false;
+        }
+    }
+    Ok(all_ok)
+}
+
+/// `VERSION` as written
+fn version(config: &PythonConfig) -> String {
+    config.get_known(KnownKey::Version).to_string()
+}
+
+/// Parses a `X.Y` Python version
+fn parse_version(version: &str) -> Result<(u32, u32), String> {
+    version
+        .split_once('.')
+        .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+        .ok_or_else(|| format!("invalid Python version {}, expected X.Y", version))
+}
+
+/// Outcome of one file of a scan, a summary of the configuration or the error
+struct ScanResult {
+    path: String,
+    outcome: Result<String, String>,
+}
+
+#[cfg(feature = "serde")]
+fn print_scan_json(results: &[ScanResult]) -> Result<(), String> {
+    let failed = results.iter().filter(|x| x.outcome.is_err()).count();
+    let files: Vec<serde_json::Value> = results
+        .iter()
+        .map(|result| match &result.outcome {
+            Ok(summary) => serde_json::json!({
+                "path": result.path,
+                "ok": true,
+                "summary": summary,
+            }),
+            Err(err) => serde_json::json!({
+                "path": result.path,
+                "ok": false,
+                "error": err,
+            }),
+        })
+        .collect();
+    let object = serde_json::json!({
+        "ok": results.len() - failed,
+        "failed": failed,
+        "files": files,
+    });
+    let json = serde_json::to_string_pretty(&object).map_err(|err| err.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_scan_json(_results: &[ScanResult]) -> Result<(), String> {
+    Err("--json requires the serde feature".to_string())
+}
+
+/// Output of a `python3-config` option
+enum Output {
+    Value(String),
+    Flags(Vec<String>),
+}
+
+fn python3_config(args: &[String]) -> Result<(), String> {
+    let (options, source): (Vec<&str>, Vec<&str>) = args
+        .iter()
+        .map(String::as_str)
+        .partition(|arg| arg.starts_with("--") && *arg != "--from-url");
+    let config = load(&source)?;
+    let json = options.contains(&"--json");
+    let mode = if options.contains(&"--embed-static") {
+        LinkMode::EmbedStatic
+    } else if options.contains(&"--embed") {
+        LinkMode::Embed
+    } else {
+        LinkMode::ExtensionModule
+    };
+    let mut outputs = Vec::new();
+    for option in options {
+        let output = match option {
+            "--json" | "--embed" | "--embed-static" => continue,
+            "--prefix" => Output::Value(config.get_known(KnownKey::Prefix).to_string()),
+            "--exec-prefix" => Output::Value(config.get_known(KnownKey::ExecPrefix).to_string()),
+            "--includes" => Output::Flags(config.include_flags()),
+            "--libs" => Output::Flags(config.lib_flags(mode)),
+            "--cflags" => Output::Flags(config.compile_flags()),
+            "--ldflags" => Output::Flags(config.link_flags(mode)),
+            "--extension-suffix" => Output::Value(config.ext_suffix().to_string()),
+            "--abiflags" => Output::Value(config.abiflags().to_string()),
+            "--configdir" => Output::Value(config.get_known(KnownKey::Libpl).to_string()),
+            _ => return Err(format!("unknown option {}\n\n{}", option, USAGE)),
+        };
+        outputs.push((option.trim_start_matches('-').replace('-', "_"), output));
+    }
+    if json {
+        return print_flags_json(&outputs);
+    }
+    for (_, output) in outputs {
+        match output {
+            Output::Value(value) => println!("{}", value),
+            Output::Flags(flags) => println!("{}", flags.join(" ")),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+fn print_flags_json(outputs: &[(String, Output)]) -> Result<(), String> {
+    let object: serde_json::Map<String, serde_json::Value> = outputs
+        .iter()
+        .map(|(name, output)| {
+            let value = match output {
+                Output::Value(value) => serde_json::Value::from(value.as_str()),
+                Output::Flags(flags) => serde_json::Value::from(flags.clone()),
+            };
+            (name.clone(), value)
+        })
+        .collect();
+    let json = serde_json::to_string_pretty(&object).map_err(|err| err.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_flags_json(_outputs: &[(String, Output)]) -> Result<(), String> {
+    Err("--json requires the serde feature".to_string())
+}
+
+fn dump(args: &[String]) -> Result<(), String> {
+    let anonymize = args.iter().any(|arg| arg == "--anonymize");
+    let annotated = args.iter().any(|arg| arg == "--annotated");
+    let source: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--anonymize" && *arg != "--annotated")
+        .collect();
+    let mut config = load(&source)?;
+    if anonymize {
+        config = config.anonymize().map_err(|err| err.to_string())?;
+    }
+    if annotated {
+        print!("{}", config.to_sysconfigdata_annotated());
+    } else {
+        print!("{}", config.to_sysconfigdata());
+    }
+    Ok(())
+}
+
+fn build_env(args: &[String]) -> Result<(), String> {
+    let source: Vec<&str> = args.iter().map(String::as_str).collect();
+    print!("{}", export_lines(&load(&source)?));
+    Ok(())
+}
+
+/// `export` lines of [`PythonConfig::to_build_env`] for a shell
+fn export_lines(config: &PythonConfig) -> String {
+    config
+        .to_build_env()
+        .into_iter()
+        .map(|(key, value)| format!("export {}='{}'\n", key, value.replace('\'', "'\\''")))
+        .collect()
+}
+
+fn report(args: &[String]) -> Result<(), String> {
+    let html = args.iter().any(|arg| arg == "--html");
+    let source: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--html")
+        .collect();
+    let config = load(&source)?;
+    if html {
+        print!("{}", config.to_html_report());
+    } else {
+        print!("{}", config.to_markdown_report());
+    }
+    Ok(())
+}
+
+fn select(args: &[String]) -> Result<(), String> {
+    let mut variant = AbiVariant::Default;
+    let mut dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--variant" => {
+                let name = args.next().ok_or_else(|| USAGE.to_string())?;
+                variant = name.parse().map_err(|err| format!("{}", err))?;
+            }
+            _ if dir.is_none() => dir = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let dir = dir.ok_or_else(|| USAGE.to_string())?;
+    let (path, _) = find_variant(dir, variant)?;
+    println!("{}", path.display());
+    Ok(())
+}
+
+/// The only sysconfigdata of `variant` under `dir`
+fn find_variant(dir: &str, variant: AbiVariant) -> Result<(PathBuf, PythonConfig), String> {
+    let mut variants =
+        python3_config::discover_variants(dir).map_err(|err| format!("{}: {}", dir, err))?;
+    let mut found = variants.remove(&variant).unwrap_or_default();
+    match found.len() {
+        0 => Err(format!("no {} sysconfigdata found in {}", variant, dir)),
+        1 => Ok(found.remove(0)),
+        _ => {
+            let paths: Vec<String> = found
+                .iter()
+                .map(|(path, _)| path.display().to_string())
+                .collect();
+            Err(format!(
+                "several {} sysconfigdata found: {}",
+                variant,
+                paths.join(", ")
+            ))
+        }
+    }
+}
+
+fn matrix(args: &[String]) -> Result<(), String> {
+    let json = args.iter().any(|arg| arg == "--json");
+    let dir = match args
+        .iter()
+        .filter(|arg| *arg != "--json")
+        .collect::<Vec<_>>()[..]
+    {
+        [dir] => dir,
+        _ => return Err(USAGE.to_string()),
+    };
+    let configs: Vec<PythonConfig> = python3_config::discover_all(dir)
+        .map_err(|err| format!("{}: {}", dir, err))?
+        .into_iter()
+        .map(|(_, config)| config)
+        .collect();
+    let matrix = SupportMatrix::build(&configs);
+    if json {
+        print_matrix_json(&matrix)
+    } else {
+        print!("{}", matrix.to_csv());
+        Ok(())
+    }
+}
+
+fn inventory(args: &[String]) -> Result<(), String> {
+    let dir = match args {
+        [dir] => dir,
+        _ => return Err(USAGE.to_string()),
+    };
+    let configs = python3_config::discover_all(dir).map_err(|err| format!("{}: {}", dir, err))?;
+    print!("{}", python3_config::export_csv(&configs));
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+fn print_matrix_json(matrix: &SupportMatrix) -> Result<(), String> {
+    let json = matrix.to_json().map_err(|err| err.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_matrix_json(_matrix: &SupportMatrix) -> Result<(), String> {
+    Err("--json requires the serde feature".to_string())
+}
+
+fn get(args: &[String]) -> Result<(), String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (key, source) = match args.split_first() {
+        Some((key, source)) => (key, source),
+        None => return Err(USAGE.to_string()),
+    };
+    let config = load(source)?;
+    let value = config.try_get(key).map_err(|err| err.to_string())?;
+    println!("{}", value);
+    Ok(())
+}
+
+fn grep(args: &[String]) -> Result<(), String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (needle, source) = match args.split_first() {
+        Some((needle, source)) => (needle, source),
+        None => return Err(USAGE.to_string()),
+    };
+    let config = load(source)?;
+    for key in config.find_references(needle) {
+        if let Some(value) = config.get(key) {
+            println!("{}: {}", key, value);
+        }
+    }
+    Ok(())
+}
+
+fn explain(args: &[String]) -> Result<(), String> {
+    let json = args.iter().any(|arg| arg == "--json");
+    let args: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--json")
+        .collect();
+    let (decision, source) = match args.split_first() {
+        Some((decision, source)) => (decision, source),
+        None => return Err(USAGE.to_string()),
+    };
+    let decision: Decision = decision.parse().map_err(|err| format!("{}", err))?;
+    let explanation = load(source)?.explain(decision);
+    if json {
+        print_json(&explanation)
+    } else {
+        println!("{}", explanation);
+        for input in &explanation.inputs {
+            match (&input.value, &input.source) {
+                (Some(value), Some(source)) => {
+                    println!("  {} = {} (from {})", input.key, value, source)
+                }
+                _ => println!("  {} is not set", input.key),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+fn print_json(explanation: &python3_config::Explanation) -> Result<(), String> {
+    let json = explanation.to_json().map_err(|err| err.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_json(_explanation: &python3_config::Explanation) -> Result<(), String> {
+    Err("--json requires the serde feature".to_string())
+}
+
+/// An artifact `watch` writes
+#[derive(Debug, Clone, Copy)]
+enum Emit {
+    Env,
+    Pyo3Config,
+    Report,
+    SysConfigData,
+}
+
+impl Emit {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "env" => Ok(Emit::Env),
+            "pyo3-config" if cfg!(feature = "pyo3-build-config") => Ok(Emit::Pyo3Config),
+            "pyo3-config" => Err("pyo3-config requires the pyo3-build-config feature".to_string()),
+            "report" => Ok(Emit::Report),
+            "sysconfigdata" => Ok(Emit::SysConfigData),
+            _ => Err(format!("unknown format {}", name)),
+        }
+    }
+
+    fn render(self, config: &PythonConfig) -> Result<String, String> {
+        match self {
+            Emit::Env => Ok(export_lines(config)),
+            Emit::Pyo3Config => pyo3_config_file(config),
+            Emit::Report => Ok(config.to_markdown_report()),
+            Emit::SysConfigData => Ok(config.to_sysconfigdata()),
+        }
+    }
+}
+
+/// The `PYO3_CONFIG_FILE` contents of `config`
+#[cfg(feature = "pyo3-build-config")]
+fn pyo3_config_file(config: &PythonConfig) -> Result<String, String> {
+    let mut out = Vec::new();
+    config
+        .to_pyo3_config()
+        .to_writer(&mut out)
+        .map_err(|err| err.to_string())?;
+    String::from_utf8(out).map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "pyo3-build-config"))]
+fn pyo3_config_file(_config: &PythonConfig) -> Result<String, String> {
+    Err("pyo3-config requires the pyo3-build-config feature".to_string())
+}
+
+fn watch(args: &[String]) -> Result<(), String> {
+    let mut variant = AbiVariant::Default;
+    let mut outputs = Vec::new();
+    let mut dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--variant" => {
+                let name = args.next().ok_or_else(|| USAGE.to_string())?;
+                variant = name.parse().map_err(|err| format!("{}", err))?;
+            }
+            "--emit" => {
+                let format = Emit::parse(args.next().ok_or_else(|| USAGE.to_string())?)?;
+                let out = args.next().ok_or_else(|| USAGE.to_string())?;
+                outputs.push((format, PathBuf::from(out)));
+            }
+            _ if dir.is_none() => dir = Some(arg.as_str()),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let dir = dir.ok_or_else(|| USAGE.to_string())?;
+    if outputs.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    watch_sysconfigdata(dir, || {
+        if let Err(err) = regenerate(dir, variant, &outputs) {
+            eprintln!("error: {}", err);
+        }
+    })
+}
+
+/// Writes the `outputs` of the sysconfigdata of `variant` under `dir`,
+/// leaving files with the same contents untouched so their readers don't
+/// rebuild
+fn regenerate(dir: &str, variant: AbiVariant, outputs: &[(Emit, PathBuf)]) -> Result<(), String> {
+    let (path, config) = find_variant(dir, variant)?;
+    for (format, out) in outputs {
+        let contents = format.render(&config)?;
+        if fs::read_to_string(out).ok().as_deref() == Some(contents.as_str()) {
+            continue;
+        }
+        // readers never see a partly written file
+        let tmp = out.with_extension("tmp");
+        fs::write(&tmp, contents)
+            .and_then(|()| fs::rename(&tmp, out))
+            .map_err(|err| format!("{}: {}", out.display(), err))?;
+        eprintln!("wrote {} from {}", out.display(), path.display());
+    }
+    Ok(())
+}
+
+/// Runs `on_change` once watching started and then whenever a
+/// `_sysconfigdata*.py` or `build-details.json` file under `dir` is
+/// created, modified or removed, once per burst of events
+#[cfg(feature = "watch")]
+fn watch_sysconfigdata(dir: &str, on_change: impl Fn()) -> Result<(), String> {
+    use notify::{RecursiveMode, Watcher};
+    use std::sync::mpsc;
+
+    let is_sysconfigdata = |path: &Path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                (name.contains("_sysconfigdata") && name.ends_with(".py"))
+                    || name == "build-details.json"
+            })
+    };
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(|err| err.to_string())?;
+    watcher
+        .watch(Path::new(dir), RecursiveMode::Recursive)
+        .map_err(|err| format!("{}: {}", dir, err))?;
+    on_change();
+    while let Ok(event) = receiver.recv() {
+        let event = event.map_err(|err| err.to_string())?;
+        if event.kind.is_access() || !event.paths.iter().any(|path| is_sysconfigdata(path)) {
+            continue;
+        }
+        // editors and installers write in several steps
+        std::thread::sleep(Duration::from_millis(200));
+        receiver.try_iter().for_each(drop);
+        on_change();
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "watch"))]
+fn watch_sysconfigdata(_dir: &str, _on_change: impl Fn()) -> Result<(), String> {
+    Err("watch requires the watch feature".to_string())
+}
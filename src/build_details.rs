@@ -0,0 +1,181 @@
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::{ConfigValue, Error, PythonConfig};
+
+/// File name of the PEP 739 static description of an installation
+pub const BUILD_DETAILS_FILE: &str = "build-details.json";
+
+impl PythonConfig {
+    /// Builds a configuration from a PEP 739 `build-details.json`, the static
+    /// description of an installation Python 3.14 ships next to its standard
+    /// library.
+    ///
+    /// The file describes less than sysconfigdata does: compiler and linker
+    /// flags are missing, and the pointer size is inferred from the platform.
+    pub fn from_build_details(src: &str) -> Result<Self, Error> {
+        let details: Value =
+            serde_json::from_str(src).map_err(|err| Error::InvalidBuildDetails(err.to_string()))?;
+        let str_at = |pointer: &str| details.pointer(pointer).and_then(Value::as_str);
+        let major = str_at("/schema_version")
+            .and_then(|x| x.split('.').next())
+            .ok_or_else(|| Error::InvalidBuildDetails("missing schema_version".to_string()))?;
+        if major != "1" {
+            return Err(Error::InvalidBuildDetails(format!(
+                "unsupported schema_version {}",
+                str_at("/schema_version").unwrap_or_default()
+            )));
+        }
+        let version = str_at("/language/version")
+            .ok_or_else(|| Error::InvalidBuildDetails("missing language.version".to_string()))?;
+
+        let mut vars: Vec<(&str, ConfigValue)> = Vec::new();
+        let mut set = |key, value: &str| vars.push((key, ConfigValue::String(value.into())));
+        set("VERSION", version);
+        let abiflags: String = details
+            .pointer("/abi/flags")
+            .and_then(Value::as_array)
+            .map(|flags| flags.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        set("ABIFLAGS", &abiflags);
+        if let Some(prefix) = str_at("/base_prefix") {
+            set("prefix", prefix);
+            set("exec_prefix", prefix);
+        }
+        if let Some(bindir) = str_at("/base_interpreter").and_then(parent) {
+            set("BINDIR", bindir);
+        }
+        if let Some(multiarch) = str_at("/implementation/_multiarch") {
+            set("MULTIARCH", multiarch);
+        }
+        if let Some(ext_suffix) = str_at("/abi/extension_suffix") {
+            set("EXT_SUFFIX", ext_suffix);
+            if let Some((soabi, shlib_suffix)) = ext_suffix
+                .strip_prefix('.')
+                .and_then(|x| x.rsplit_once('.'))
+            {
+                set("SOABI", soabi);
+                set("SHLIB_SUFFIX", &format!(".{}", shlib_suffix));
+            }
+        }
+        if let Some(headers) = str_at("/c_api/headers") {
+            set("INCLUDEPY", headers);
+            set("INCLUDEDIR", parent(headers).unwrap_or(headers));
+        }
+        let dynamic = str_at("/libpython/dynamic");
+        let static_lib = str_at("/libpython/static");
+        if let Some(library) = dynamic.or(static_lib) {
+            set("LIBDIR", parent(library).unwrap_or_default());
+            set("LDLIBRARY", file_name(library));
+        }
+        if let Some(dynamic) = dynamic {
+            set("INSTSONAME", file_name(dynamic));
+        }
+        if let Some(static_lib) = static_lib {
+            set("LIBRARY", file_name(static_lib));
+            set("LIBPL", parent(static_lib).unwrap_or_default());
+        }
+        let platform = str_at("/platform").unwrap_or_default();
+        let (machdep, arch) = match platform.split_once('-') {
+            _ if platform.starts_with("win") => ("win32", platform),
+            Some(("macosx", rest)) => {
+                let (release, arch) = rest.rsplit_once('-').unwrap_or((rest, ""));
+                set("MACOSX_DEPLOYMENT_TARGET", release);
+                match arch {
+                    "universal2" => set("CFLAGS", "-arch arm64 -arch x86_64"),
+                    "" => {}
+                    arch => set("HOST_GNU_TYPE", &format!("{}-apple-darwin", arch)),
+                }
+                ("darwin", arch)
+            }
+            Some((os, arch)) => (os, arch),
+            None => (platform, ""),
+        };
+        if !machdep.is_empty() {
+            set("MACHDEP", machdep);
+        }
+        let flag = |c| ConfigValue::Int(abiflags.contains(c) as i64);
+        vars.push(("Py_DEBUG", flag('d')));
+        vars.push(("Py_GIL_DISABLED", flag('t')));
+        vars.push((
+            "Py_ENABLE_SHARED",
+            ConfigValue::Int(dynamic.is_some() as i64),
+        ));
+        if !arch.is_empty() {
+            let size = if arch.contains("64") || arch == "universal2" {
+                8
+            } else {
+                4
+            };
+            vars.push(("SIZEOF_VOID_P", ConfigValue::Int(size)));
+        }
+        PythonConfig::from_vars(
+            vars.into_iter()
+                .map(|(key, value)| (key.to_string(), value)),
+        )
+    }
+}
+
+fn parent(path: &str) -> Option<&str> {
+    Path::new(path).parent().and_then(|x| x.to_str())
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|x| x.to_str())
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, LinkModel, PythonConfig};
+
+    const BUILD_DETAILS: &str = r#"{
+  "schema_version": "1.0",
+  "base_prefix": "/usr",
+  "base_interpreter": "/usr/bin/python3.14",
+  "platform": "linux-x86_64",
+  "language": {"version": "3.14", "version_info": {"major": 3, "minor": 14, "micro": 0}},
+  "implementation": {"name": "cpython", "cache_tag": "cpython-314", "_multiarch": "x86_64-linux-gnu"},
+  "abi": {"flags": ["t"], "extension_suffix": ".cpython-314t-x86_64-linux-gnu.so", "stable_abi_suffix": ".abi3.so"},
+  "libpython": {
+    "dynamic": "/usr/lib/libpython3.14t.so.1.0",
+    "static": "/usr/lib/python3.14t/config-3.14t-x86_64-linux-gnu/libpython3.14t.a",
+    "link_extensions": false
+  },
+  "c_api": {"headers": "/usr/include/python3.14t", "pkgconfig_path": "/usr/lib/pkgconfig"}
+}"#;
+
+    #[test]
+    fn from_build_details() {
+        let config = PythonConfig::from_build_details(BUILD_DETAILS).unwrap();
+        assert_eq!(config.version_str(), "3.14");
+        assert_eq!(config.abiflags(), "t");
+        assert_eq!(config.soabi(), "cpython-314t-x86_64-linux-gnu");
+        assert_eq!(config.get_platform(), "linux-x86_64");
+        assert_eq!(config.abi_tag(), "cp314t");
+        assert_eq!(config.pointer_size(), 8);
+        assert_eq!(config.link_model(), LinkModel::Shared);
+        assert_eq!(config.lib_dir_str(), "/usr/lib");
+        assert_eq!(config.bindir_str(), "/usr/bin");
+        assert_eq!(
+            config.extension_filename("foo"),
+            "foo.cpython-314t-x86_64-linux-gnu.so"
+        );
+
+        let macos = BUILD_DETAILS
+            .replace("linux-x86_64", "macosx-11.0-arm64")
+            .replace("x86_64-linux-gnu", "darwin");
+        let config = PythonConfig::from_build_details(&macos).unwrap();
+        assert_eq!(config.sys_platform(), "darwin");
+        assert_eq!(config.get_platform(), "macosx-11.0-arm64");
+
+        assert!(matches!(
+            PythonConfig::from_build_details(r#"{"schema_version": "2.0"}"#),
+            Err(Error::InvalidBuildDetails(_))
+        ));
+        assert!(PythonConfig::from_build_details("{").is_err());
+    }
+}
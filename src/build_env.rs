@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use crate::{LinkMode, PythonConfig};
+
+impl PythonConfig {
+    /// Returns environment variables describing this distribution to child
+    /// build processes such as autotools or meson, the way crossenv sets
+    /// them up for cross compilation:
+    ///
+    /// * `PYTHON_CFLAGS` and `PYTHON_LIBS`, the `python3-config --cflags` and
+    ///   `python3-config --ldflags --embed` equivalents
+    /// * `PYTHON_EXT_SUFFIX` and `PYTHON_VERSION`
+    /// * `_PYTHON_SYSCONFIGDATA_NAME` and `_PYTHON_HOST_PLATFORM`, making a
+    ///   host Python report the target's `sysconfig`
+    /// * `PYTHONHOME`
+    ///
+    /// Variables without a value for this distribution are left out, the
+    /// map iterates in sorted order so emitted environments are reproducible.
+    pub fn to_build_env(&self) -> BTreeMap<String, String> {
+        let mut env = BTreeMap::new();
+        env.insert("PYTHON_CFLAGS", self.compile_flags().join(" "));
+        env.insert("PYTHON_LIBS", self.link_flags(LinkMode::Embed).join(" "));
+        env.insert("PYTHON_EXT_SUFFIX", self.extension_suffix());
+        env.insert("PYTHON_VERSION", self.version_str().to_string());
+        if let Some(name) = self.sysconfigdata_name() {
+            env.insert("_PYTHON_SYSCONFIGDATA_NAME", name);
+        }
+        env.insert("_PYTHON_HOST_PLATFORM", self.get_platform().to_string());
+        let home =
+            if self.exec_prefix_str().is_empty() || self.exec_prefix_str() == self.prefix_str() {
+                self.prefix_str().to_string()
+            } else {
+                format!("{}:{}", self.prefix_str(), self.exec_prefix_str())
+            };
+        env.insert("PYTHONHOME", home);
+        env.into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| (key.to_string(), value))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures;
+
+    #[test]
+    fn to_build_env() {
+        let env = fixtures::get("cpython311-linux")
+            .unwrap()
+            .config()
+            .to_build_env();
+        assert_eq!(
+            env["PYTHON_CFLAGS"],
+            "-I/usr/local/include/python3.11 -Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall"
+        );
+        assert_eq!(
+            env["PYTHON_LIBS"],
+            "-L/usr/local/lib -lpython3.11 -ldl -lm -lm -pthread"
+        );
+        assert_eq!(env["PYTHON_EXT_SUFFIX"], ".cpython-311-x86_64-linux-gnu.so");
+        assert_eq!(
+            env["_PYTHON_SYSCONFIGDATA_NAME"],
+            "_sysconfigdata__linux_x86_64-linux-gnu"
+        );
+        assert_eq!(env["_PYTHON_HOST_PLATFORM"], "linux-x86_64");
+        assert_eq!(env["PYTHONHOME"], "/usr/local");
+        let keys: Vec<&str> = env.keys().map(String::as_str).collect();
+        assert_eq!(keys[0], "PYTHONHOME");
+        assert_eq!(keys[keys.len() - 1], "_PYTHON_SYSCONFIGDATA_NAME");
+    }
+}
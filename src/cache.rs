@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::SystemTime;
+
+use crate::error_context::load_path;
+use crate::{Error, Interner, ParseOptions, Phase, PythonConfig};
+
+/// Callback run with the path of every entry dropped from a [`ConfigCache`]
+type Hook = Arc<dyn Fn(&Path) + Send + Sync>;
+
+/// A parsed file with the metadata it was read with
+struct Entry {
+    modified: Option<SystemTime>,
+    len: u64,
+    config: Arc<PythonConfig>,
+}
+
+/// Parsed configurations keyed by path for long-lived processes such as
+/// language servers and build daemons, safe to share between threads.
+///
+/// An entry is parsed again when the modification time or size of its file
+/// changed, so updating a sysroot on disk is picked up without a restart.
+/// Clones share the same entries, see [`ConfigCache::global`] for the one of
+/// the process.
+#[derive(Clone, Default)]
+pub struct ConfigCache {
+    entries: Arc<RwLock<HashMap<PathBuf, Entry>>>,
+    hooks: Arc<RwLock<Vec<Hook>>>,
+    interner: Interner,
+}
+
+impl ConfigCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cache shared by the whole process
+    pub fn global() -> &'static ConfigCache {
+        static GLOBAL: OnceLock<ConfigCache> = OnceLock::new();
+        GLOBAL.get_or_init(ConfigCache::new)
+    }
+
+    /// Returns the configuration of the sysconfigdata or
+    /// `build-details.json` file at `path`, parsing it on first use and
+    /// whenever the file changed since. Parse errors are not cached.
+    ///
+    /// Threads asking for different paths parse concurrently, the lock is
+    /// not held while reading and parsing.
+    pub fn get(&self, path: impl AsRef<Path>) -> Result<Arc<PythonConfig>, Error> {
+        let path = path.as_ref();
+        let metadata = fs::metadata(path)
+            .map_err(|err| Error::from(err).with_phase(Phase::Read).with_path(path))?;
+        let modified = metadata.modified().ok();
+        let len = metadata.len();
+        let stale = match self.entries.read().unwrap().get(path) {
+            Some(entry) if entry.modified == modified && entry.len == len => {
+                return Ok(entry.config.clone())
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if stale {
+            self.invalidate(path);
+        }
+        let options = ParseOptions::new().interner(self.interner.clone());
+        let config = Arc::new(load_file(path, &options)?);
+        self.entries.write().unwrap().insert(
+            path.to_path_buf(),
+            Entry {
+                modified,
+                len,
+                config: config.clone(),
+            },
+        );
+        Ok(config)
+    }
+
+    /// Drops the entry of `path`, for files replaced within the resolution
+    /// of their modification time. Returns whether there was one.
+    pub fn invalidate(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        let removed = self.entries.write().unwrap().remove(path).is_some();
+        if removed {
+            self.run_hooks(path);
+        }
+        removed
+    }
+
+    /// Drops every entry
+    pub fn clear(&self) {
+        let paths: Vec<PathBuf> = self
+            .entries
+            .write()
+            .unwrap()
+            .drain()
+            .map(|(path, _)| path)
+            .collect();
+        for path in paths {
+            self.run_hooks(&path);
+        }
+    }
+
+    /// Runs `hook` with the path of every entry dropped from now on, by
+    /// [`ConfigCache::invalidate`], [`ConfigCache::clear`] or because its
+    /// file changed, to invalidate values derived from it
+    pub fn on_invalidate(&self, hook: impl Fn(&Path) + Send + Sync + 'static) {
+        self.hooks.write().unwrap().push(Arc::new(hook));
+    }
+
+    /// Number of cached configurations
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    /// Returns `true` if no configuration is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn run_hooks(&self, path: &Path) {
+        let hooks = self.hooks.read().unwrap().clone();
+        for hook in hooks {
+            hook(path);
+        }
+    }
+}
+
+impl fmt::Debug for ConfigCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigCache")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parses the sysconfigdata or `build-details.json` file at `path`
+pub(crate) fn load_file(path: &Path, options: &ParseOptions) -> Result<PythonConfig, Error> {
+    load_path(path, |src| {
+        if is_build_details(path) {
+            from_build_details(src)
+        } else {
+            PythonConfig::parse_with_options(src, options)
+        }
+    })
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn is_build_details(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == crate::BUILD_DETAILS_FILE)
+}
+
+#[cfg(not(feature = "serde"))]
+pub(crate) fn is_build_details(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(feature = "serde")]
+fn from_build_details(src: &str) -> Result<PythonConfig, Error> {
+    PythonConfig::from_build_details(src)
+}
+
+#[cfg(not(feature = "serde"))]
+fn from_build_details(_src: &str) -> Result<PythonConfig, Error> {
+    Err(Error::Unsupported(
+        "reading build-details.json requires the serde feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigCache;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    #[test]
+    fn cache_invalidation() {
+        let dir = std::env::temp_dir().join(format!("python3-config-cache-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("_sysconfigdata_test.py");
+        fs::write(&path, "build_time_vars = {'VERSION': '3.11'}").unwrap();
+
+        let cache = ConfigCache::new();
+        let dropped = Arc::new(Mutex::new(Vec::<PathBuf>::new()));
+        let hook_dropped = dropped.clone();
+        cache.on_invalidate(move |path| hook_dropped.lock().unwrap().push(path.to_path_buf()));
+
+        let first = cache.get(&path).unwrap();
+        assert_eq!(first.version_str(), "3.11");
+        assert!(Arc::ptr_eq(&first, &cache.get(&path).unwrap()));
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let cache = cache.clone();
+                let path = path.clone();
+                thread::spawn(move || cache.get(&path).unwrap().version_str().to_string())
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), "3.11");
+        }
+        assert_eq!(cache.len(), 1);
+
+        // a different size is picked up whatever the mtime resolution
+        fs::write(
+            &path,
+            "build_time_vars = {'VERSION': '3.12', 'ABIFLAGS': ''}",
+        )
+        .unwrap();
+        assert_eq!(cache.get(&path).unwrap().version_str(), "3.12");
+        assert_eq!(*dropped.lock().unwrap(), vec![path.clone()]);
+
+        assert!(cache.invalidate(&path));
+        assert!(!cache.invalidate(&path));
+        assert!(cache.is_empty());
+        assert_eq!(dropped.lock().unwrap().len(), 2);
+
+        fs::write(&path, "build_time_vars = {").unwrap();
+        assert!(cache.get(&path).is_err());
+        assert!(cache.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(cache.get(&path).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt::Write;
+
+use crate::{ConfigValue, Error, PythonConfig};
+
+const HEADER: &str = "# python3-config canonical v1";
+
+impl PythonConfig {
+    /// Dumps `build_time_vars` as sorted `KEY=value` lines meant to be
+    /// committed next to a project and diffed across Python upgrades.
+    ///
+    /// Strings are always double quoted with `\\`, `\"`, `\n`, `\r`, `\t`
+    /// and `\u{..}` escapes, numbers are bare and `None` is spelled
+    /// `None`, so every configuration has exactly one canonical text.
+    /// [`PythonConfig::from_canonical_text`] reads it back.
+    pub fn to_canonical_text(&self) -> String {
+        let mut out = String::from(HEADER);
+        out.push('\n');
+        for (key, value) in self.vars() {
+            if is_plain_key(key) {
+                out.push_str(key);
+            } else {
+                write_quoted(&mut out, key);
+            }
+            out.push('=');
+            match value {
+                ConfigValue::String(value) => write_quoted(&mut out, value),
+                ConfigValue::Int(value) => write!(out, "{}", value).unwrap(),
+                ConfigValue::Float(text) => out.push_str(text),
+                ConfigValue::None => out.push_str("None"),
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Parses the output of [`PythonConfig::to_canonical_text`].
+    ///
+    /// Blank lines and lines starting with `#` are skipped, lines may come
+    /// in any order but a key may appear only once.
+    pub fn from_canonical_text(src: &str) -> Result<Self, Error> {
+        let mut vars = Vec::new();
+        let mut seen = std::collections::BTreeSet::new();
+        for (i, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: &str| Error::InvalidSyntax {
+                line: i + 1,
+                message: message.to_string(),
+            };
+            let (key, rest) = if line.starts_with('"') {
+                unquote(line).ok_or_else(|| invalid("unterminated key"))?
+            } else {
+                let at = line
+                    .find('=')
+                    .ok_or_else(|| invalid("expected KEY=value"))?;
+                (line[..at].to_string(), &line[at..])
+            };
+            let value = rest
+                .strip_prefix('=')
+                .ok_or_else(|| invalid("expected = after key"))?;
+            let value = if value == "None" {
+                ConfigValue::None
+            } else if value.starts_with('"') {
+                match unquote(value) {
+                    Some((value, "")) => ConfigValue::String(value.into()),
+                    _ => return Err(invalid("invalid string value")),
+                }
+            } else if let Ok(int) = value.parse() {
+                ConfigValue::Int(int)
+            } else if is_float(value) {
+                ConfigValue::Float(value.into())
+            } else {
+                return Err(invalid("invalid value"));
+            };
+            if key.is_empty() || !seen.insert(key.clone()) {
+                return Err(invalid("empty or duplicate key"));
+            }
+            vars.push((key, value));
+        }
+        Self::from_vars(vars)
+    }
+}
+
+/// Whether `value` is the decimal text of a finite float, checked without
+/// `f64` parsing, which also takes `inf` and `nan`
+fn is_float(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .bytes()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-'))
+        && value.parse::<f64>().is_ok()
+}
+
+/// Whether `key` can be written without quotes
+fn is_plain_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-'))
+}
+
+fn write_quoted(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{{{:x}}}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// The string quoted at the start of `src` and the text after it
+fn unquote(src: &str) -> Option<(String, &str)> {
+    let mut out = String::new();
+    let mut chars = src.strip_prefix('"')?.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, &src[i + 2..])),
+            '\\' => match chars.next()?.1 {
+                '\\' => out.push('\\'),
+                '"' => out.push('"'),
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                'u' => {
+                    let rest = chars.as_str().strip_prefix('{')?;
+                    let end = rest.find('}')?;
+                    out.push(char::from_u32(u32::from_str_radix(&rest[..end], 16).ok()?)?);
+                    for _ in 0..end + 2 {
+                        chars.next();
+                    }
+                }
+                _ => return None,
+            },
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, ConfigValue, Error, PythonConfig};
+
+    #[test]
+    fn canonical_text_round_trip() {
+        for fixture in fixtures::all() {
+            let config = fixture.config();
+            let text = config.to_canonical_text();
+            let parsed = PythonConfig::from_canonical_text(&text).unwrap();
+            assert_eq!(parsed.to_canonical_text(), text, "{}", fixture.name);
+        }
+
+        let config = PythonConfig::from_vars(vec![
+            ("VERSION".to_string(), ConfigValue::String("3.12".into())),
+            (
+                "CONFIG_ARGS".to_string(),
+                ConfigValue::String("'CFLAGS=-O2 \"x\"'\n\t\u{1b}é".into()),
+            ),
+            ("Py_DEBUG".to_string(), ConfigValue::Int(-1)),
+            ("LIBPL".to_string(), ConfigValue::None),
+            ("odd key=".to_string(), ConfigValue::Int(1)),
+            ("RATIO".to_string(), ConfigValue::Float("1.50e-3".into())),
+        ])
+        .unwrap();
+        let text = config.to_canonical_text();
+        assert!(text.contains("CONFIG_ARGS=\"'CFLAGS=-O2 \\\"x\\\"'\\n\\t\\u{1b}é\"\n"));
+        assert!(text.contains("LIBPL=None\nPy_DEBUG=-1\n"));
+        assert!(text.contains("\"odd key=\"=1\n"));
+        let parsed = PythonConfig::from_canonical_text(&text).unwrap();
+        assert_eq!(parsed.get("CONFIG_ARGS"), config.get("CONFIG_ARGS"));
+        assert_eq!(parsed.get("odd key="), Some(&ConfigValue::Int(1)));
+        assert_eq!(parsed.get("RATIO"), config.get("RATIO"));
+        assert!(PythonConfig::from_canonical_text("VERSION=\"3.12\"\nX=inf").is_err());
+    }
+
+    #[test]
+    fn canonical_text_errors() {
+        let err = PythonConfig::from_canonical_text("VERSION=\"3.12\"\nCC=gcc").unwrap_err();
+        assert!(matches!(err, Error::InvalidSyntax { line: 2, .. }));
+        let err =
+            PythonConfig::from_canonical_text("VERSION=\"3.12\"\nVERSION=\"3.11\"").unwrap_err();
+        assert!(matches!(err, Error::InvalidSyntax { line: 2, .. }));
+        let err = PythonConfig::from_canonical_text("VERSION=\"3.12").unwrap_err();
+        assert!(matches!(err, Error::InvalidSyntax { line: 1, .. }));
+    }
+}
@@ -0,0 +1,354 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::error_context::load_path;
+use crate::framework::split_framework_flags;
+use crate::paths::windows_join;
+#[cfg(feature = "discovery")]
+use crate::{discover_all, AbiVariant};
+use crate::{Error, LinkMode, PythonConfig};
+
+/// Environment variable naming the `_sysconfigdata*.py` file, or a directory
+/// such as a sysroot to search for it, used by the quickstart helpers
+pub const SYSCONFIGDATA_ENV: &str = "PYTHON_SYSCONFIGDATA";
+
+impl PythonConfig {
+    /// Returns the `cargo:` build script directives for linking against this
+    /// distribution.
+    ///
+    /// Extension modules don't link libpython except where
+    /// [`PythonConfig::should_link_libpython`] requires it, on macOS
+    /// they are linked with `-undefined dynamic_lookup` and on AIX with the
+    /// import file of [`PythonConfig::aix_import_file`]. When embedding the
+    /// flags of [`PythonConfig::link_flags`] are translated, repeated
+    /// directives are emitted once, and [`LinkMode::EmbedStatic`] links the
+    /// static library with `static=`.
+    pub fn cargo_link_directives(&self, mode: LinkMode) -> Vec<String> {
+        let mut directives = Vec::new();
+        let embed = mode.is_embed();
+        let unix_links_libpython =
+            self.sys_platform() != "win32" && self.should_link_libpython(LinkMode::ExtensionModule);
+        if !embed && (self.is_mingw() || self.is_cygwin() || unix_links_libpython) {
+            // DLLs can't have undefined symbols, extension modules link the
+            // import library libpython3.X.dll.a in LIBDIR, as do those of
+            // Cygwin, Android and builds setting LIBPYTHON
+            if let Some(lib_dir) = self.lib_dir_path() {
+                directives.push(format!(
+                    "cargo:rustc-link-search=native={}",
+                    lib_dir.display()
+                ));
+            }
+            // without LDVERSION the library name isn't known
+            if !self.ld_version().is_empty() {
+                directives.push(format!("cargo:rustc-link-lib=python{}", self.ld_version()));
+            }
+        } else if self.sys_platform() == "win32" && !self.is_mingw() {
+            let libs = windows_join(self.prefix_str(), &["libs"]);
+            directives.push(format!("cargo:rustc-link-search=native={}", libs.display()));
+            directives.push(format!("cargo:rustc-link-lib={}", self.python_lib_name()));
+        } else if embed {
+            let static_library = match mode {
+                LinkMode::EmbedStatic => self.static_library_path(),
+                _ => None,
+            };
+            let (_, _, flags) = split_framework_flags(&self.link_flags(mode));
+            for flag in flags {
+                let directive = if let Some(library) = static_library
+                    .as_ref()
+                    .filter(|library| library.as_os_str() == flag.as_str())
+                {
+                    let name = library.file_stem().unwrap_or_default().to_string_lossy();
+                    let name = name.strip_prefix("lib").unwrap_or(&name);
+                    if let Some(dir) = library.parent() {
+                        directives
+                            .push(format!("cargo:rustc-link-search=native={}", dir.display()));
+                    }
+                    format!("cargo:rustc-link-lib=static={}", name)
+                } else if let Some(dir) = flag.strip_prefix("-L") {
+                    format!("cargo:rustc-link-search=native={}", dir)
+                } else if let Some(lib) = flag.strip_prefix("-l") {
+                    format!("cargo:rustc-link-lib={}", lib)
+                } else {
+                    format!("cargo:rustc-link-arg={}", flag)
+                };
+                if !directives.contains(&directive) {
+                    directives.push(directive);
+                }
+            }
+            for framework in self.frameworks() {
+                if let Some(dir) = &framework.search_path {
+                    let directive = format!("cargo:rustc-link-search=framework={}", dir.display());
+                    if !directives.contains(&directive) {
+                        directives.push(directive);
+                    }
+                }
+                directives.push(format!("cargo:rustc-link-lib=framework={}", framework.name));
+            }
+        } else if let Some(import_file) = self.aix_import_file() {
+            directives.push(format!(
+                "cargo:rustc-cdylib-link-arg=-Wl,-bI:{}",
+                import_file
+            ));
+        } else if self.sys_platform() == "darwin" {
+            directives.push("cargo:rustc-cdylib-link-arg=-undefined".to_string());
+            directives.push("cargo:rustc-cdylib-link-arg=dynamic_lookup".to_string());
+        }
+        directives
+    }
+}
+
+/// One-call setup for a build script of an extension module: finds the
+/// sysconfigdata named by [`SYSCONFIGDATA_ENV`], checks that it matches the
+/// cargo target and prints the link directives.
+///
+/// When the variable names a directory, the only default build variant
+/// matching the target is used, `_PYTHON_SYSCONFIGDATA_NAME` picks one when
+/// there are several. Directories need the `discovery` feature.
+pub fn configure_extension_module_build() -> Result<PythonConfig, Error> {
+    configure_build(LinkMode::ExtensionModule)
+}
+
+/// Like [`configure_extension_module_build`] for programs embedding Python,
+/// linking libpython and its dependencies
+pub fn configure_embedded_build() -> Result<PythonConfig, Error> {
+    configure_build(LinkMode::Embed)
+}
+
+fn configure_build(mode: LinkMode) -> Result<PythonConfig, Error> {
+    let (config, directives) = configure_build_from(mode, |name| env::var(name).ok())?;
+    for directive in directives {
+        println!("{}", directive);
+    }
+    Ok(config)
+}
+
+/// The configuration selected through `lookup` and the directives to print
+fn configure_build_from<F>(mode: LinkMode, lookup: F) -> Result<(PythonConfig, Vec<String>), Error>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut directives = vec![
+        format!("cargo:rerun-if-env-changed={}", SYSCONFIGDATA_ENV),
+        "cargo:rerun-if-env-changed=_PYTHON_SYSCONFIGDATA_NAME".to_string(),
+    ];
+    let location = lookup(SYSCONFIGDATA_ENV)
+        .map(PathBuf::from)
+        .ok_or_else(|| Error::Unsupported(format!("{} is not set", SYSCONFIGDATA_ENV)))?;
+    let (path, config) = if location.is_dir() {
+        find_for_target(&location, &lookup)?
+    } else {
+        let config = load_path(&location, PythonConfig::parse)?;
+        config.assert_matches_cargo_target_from(&lookup)?;
+        (location, config)
+    };
+    directives.push(format!("cargo:rerun-if-changed={}", path.display()));
+    directives.extend(config.cargo_link_directives(mode));
+    Ok((config, directives))
+}
+
+/// The only default variant configuration under `dir` matching the target
+#[cfg(feature = "discovery")]
+fn find_for_target<F>(dir: &Path, lookup: &F) -> Result<(PathBuf, PythonConfig), Error>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let name = lookup("_PYTHON_SYSCONFIGDATA_NAME");
+    let mut found: Vec<(PathBuf, PythonConfig)> = discover_all(dir)?
+        .into_iter()
+        .filter(|(path, config)| {
+            let stem = path.file_stem().and_then(|x| x.to_str());
+            config.abi_variant() == AbiVariant::Default
+                && config.assert_matches_cargo_target_from(lookup).is_ok()
+                && (name.is_none() || stem == name.as_deref())
+        })
+        .collect();
+    match found.len() {
+        1 => Ok(found.remove(0)),
+        0 => Err(Error::Unsupported(format!(
+            "no sysconfigdata for the target found in {}",
+            dir.display()
+        ))),
+        _ => Err(Error::Unsupported(format!(
+            "several sysconfigdata for the target found in {}, set _PYTHON_SYSCONFIGDATA_NAME",
+            dir.display()
+        ))),
+    }
+}
+
+#[cfg(not(feature = "discovery"))]
+fn find_for_target<F>(dir: &Path, _lookup: &F) -> Result<(PathBuf, PythonConfig), Error> {
+    Err(Error::Unsupported(format!(
+        "{} is a directory, searching it needs the discovery feature",
+        dir.display()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::configure_build_from;
+    use crate::{fixtures, LinkMode};
+
+    #[test]
+    fn cargo_link_directives() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert!(config
+            .cargo_link_directives(LinkMode::ExtensionModule)
+            .is_empty());
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::Embed),
+            vec![
+                "cargo:rustc-link-search=native=/usr/local/lib",
+                "cargo:rustc-link-lib=python3.11",
+                "cargo:rustc-link-lib=dl",
+                "cargo:rustc-link-lib=m",
+                "cargo:rustc-link-arg=-pthread",
+            ]
+        );
+        let directives = config.cargo_link_directives(LinkMode::EmbedStatic);
+        assert_eq!(
+            directives[1..3],
+            [
+                "cargo:rustc-link-search=native=/usr/local/lib/python3.11/config-3.11-x86_64-linux-gnu",
+                "cargo:rustc-link-lib=static=python3.11",
+            ]
+        );
+
+        let config = fixtures::get("cpython38-darwin").unwrap().config();
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::ExtensionModule),
+            vec![
+                "cargo:rustc-cdylib-link-arg=-undefined",
+                "cargo:rustc-cdylib-link-arg=dynamic_lookup"
+            ]
+        );
+        assert!(config
+            .cargo_link_directives(LinkMode::Embed)
+            .contains(&"cargo:rustc-link-lib=framework=CoreFoundation".to_string()));
+
+        let config = fixtures::get("cpython311-mingw").unwrap().config();
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::ExtensionModule),
+            vec![
+                "cargo:rustc-link-search=native=C:\\msys64\\mingw64\\lib",
+                "cargo:rustc-link-lib=python3.11",
+            ]
+        );
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::Embed)[1..3],
+            [
+                "cargo:rustc-link-lib=python3.11",
+                "cargo:rustc-link-lib=version"
+            ]
+        );
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::EmbedStatic)[1..4],
+            [
+                "cargo:rustc-link-search=native=C:/msys64/mingw64/lib/python3.11/config-3.11",
+                "cargo:rustc-link-lib=static=python3.11",
+                "cargo:rustc-link-lib=version",
+            ]
+        );
+
+        let config = fixtures::get("cpython39-cygwin").unwrap().config();
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::ExtensionModule),
+            vec![
+                "cargo:rustc-link-search=native=/usr/lib",
+                "cargo:rustc-link-lib=python3.9",
+            ]
+        );
+        assert!(config
+            .cargo_link_directives(LinkMode::EmbedStatic)
+            .contains(&"cargo:rustc-link-lib=static=python3.9".to_string()));
+        let config = crate::PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.9', 'MACHDEP': 'cygwin', 'LIBDIR': '/usr/lib'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::ExtensionModule),
+            vec!["cargo:rustc-link-search=native=/usr/lib"]
+        );
+
+        let msvc = |vars: &str| {
+            crate::PythonConfig::parse(&format!(
+                "build_time_vars = {{'VERSION': '3.13', 'MACHDEP': 'win32', \
+                 'prefix': 'C:\\\\Python313', {}}}",
+                vars
+            ))
+            .unwrap()
+            .cargo_link_directives(LinkMode::ExtensionModule)
+        };
+        assert_eq!(
+            msvc("'Py_DEBUG': 0"),
+            vec![
+                "cargo:rustc-link-search=native=C:\\Python313\\libs",
+                "cargo:rustc-link-lib=python313",
+            ]
+        );
+        assert_eq!(
+            msvc("'Py_GIL_DISABLED': 1")[1],
+            "cargo:rustc-link-lib=python313t"
+        );
+        assert_eq!(msvc("'Py_DEBUG': 1")[1], "cargo:rustc-link-lib=python313_d");
+
+        let config = crate::PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'MACHDEP': 'aix', \
+             'LDSHARED': '/opt/python/lib/python3.11/config/ld_so_aix gcc \
+             -bI:/opt/python/lib/python3.11/config/python.exp'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::ExtensionModule),
+            vec![
+                "cargo:rustc-cdylib-link-arg=-Wl,-bI:/opt/python/lib/python3.11/config/python.exp"
+            ]
+        );
+    }
+
+    #[test]
+    fn configure_build() {
+        let lookup = |name: &str| match name {
+            "PYTHON_SYSCONFIGDATA" => Some("tests/fixtures".to_string()),
+            "CARGO_CFG_TARGET_OS" => Some("linux".to_string()),
+            "CARGO_CFG_TARGET_ARCH" => Some("aarch64".to_string()),
+            "CARGO_CFG_TARGET_POINTER_WIDTH" => Some("64".to_string()),
+            _ => None,
+        };
+        let file = |name: &str| match name {
+            "PYTHON_SYSCONFIGDATA" => Some(
+                "tests/fixtures/cpython39_sysconfigdata__linux_aarch64-linux-gnu.py".to_string(),
+            ),
+            _ => lookup(name),
+        };
+        let (config, directives) = configure_build_from(LinkMode::Embed, file).unwrap();
+        assert_eq!(config.version_str(), "3.9");
+        assert!(directives.contains(
+            &"cargo:rerun-if-changed=tests/fixtures/cpython39_sysconfigdata__linux_aarch64-linux-gnu.py"
+                .to_string()
+        ));
+        assert!(directives.contains(&"cargo:rustc-link-lib=python3.9".to_string()));
+        assert!(configure_build_from(LinkMode::ExtensionModule, |_| None).is_err());
+
+        #[cfg(not(feature = "discovery"))]
+        assert!(configure_build_from(LinkMode::Embed, lookup).is_err());
+        #[cfg(feature = "discovery")]
+        {
+            let (config, _) = configure_build_from(LinkMode::Embed, lookup).unwrap();
+            assert_eq!(config.version_str(), "3.9");
+            let x86_64 = |name: &str| match name {
+                "CARGO_CFG_TARGET_ARCH" => Some("x86_64".to_string()),
+                _ => lookup(name),
+            };
+            assert!(configure_build_from(LinkMode::ExtensionModule, x86_64).is_err());
+            let x86_64_named = |name: &str| match name {
+                "_PYTHON_SYSCONFIGDATA_NAME" => {
+                    Some("cpython311_sysconfigdata__linux_x86_64-linux-gnu".to_string())
+                }
+                _ => x86_64(name),
+            };
+            let (config, _) =
+                configure_build_from(LinkMode::ExtensionModule, x86_64_named).unwrap();
+            assert_eq!(config.version_str(), "3.11");
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! The conformance suite of this crate for code built on it, such as a
+//! packaging tool wrapping the parser in its own loading and tagging layer.
+//!
+//! Every [bundled fixture](crate::fixtures) has golden data, the values
+//! this crate derives from it as rendered by [`render`]. Run [`run`] with
+//! the integration layer to catch divergence early, or compare the wheel
+//! tags alone with [`assert_tags`].
+
+use std::fmt::{self, Write};
+
+use crate::fixtures::{self, Fixture};
+use crate::PythonConfig;
+
+/// The wheel tags and extension suffix of a configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tags {
+    /// Python tag, `cp311`
+    pub python_tag: String,
+    /// ABI tag, `cp311`
+    pub abi_tag: String,
+    /// Platform tag, `manylinux_2_17_x86_64`
+    pub platform_tag: String,
+    /// `EXT_SUFFIX`, `.cpython-311-x86_64-linux-gnu.so`
+    pub ext_suffix: String,
+}
+
+impl Tags {
+    /// The tags this crate derives for `config`
+    pub fn of(config: &PythonConfig) -> Self {
+        Self {
+            python_tag: config.python_tag().to_string(),
+            abi_tag: config.abi_tag().to_string(),
+            platform_tag: config.platform_tag(),
+            ext_suffix: config.ext_suffix().to_string(),
+        }
+    }
+
+    /// `name: expected ..., found ...` for each tag differing from `actual`
+    fn differences(&self, actual: &Tags) -> Vec<String> {
+        [
+            ("python_tag", &self.python_tag, &actual.python_tag),
+            ("abi_tag", &self.abi_tag, &actual.abi_tag),
+            ("platform_tag", &self.platform_tag, &actual.platform_tag),
+            ("ext_suffix", &self.ext_suffix, &actual.ext_suffix),
+        ]
+        .iter()
+        .filter(|(_, expected, actual)| expected != actual)
+        .map(|(name, expected, actual)| {
+            format!("{}: expected {}, found {}", name, expected, actual)
+        })
+        .collect()
+    }
+}
+
+/// `cp311-cp311-manylinux_2_17_x86_64 (.cpython-311-x86_64-linux-gnu.so)`
+impl fmt::Display for Tags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{} ({})",
+            self.python_tag, self.abi_tag, self.platform_tag, self.ext_suffix
+        )
+    }
+}
+
+/// Panics listing the differences when the tags this crate derives for
+/// `config` aren't `expected`
+#[track_caller]
+pub fn assert_tags(config: &PythonConfig, expected: &Tags) {
+    let differences = expected.differences(&Tags::of(config));
+    assert!(
+        differences.is_empty(),
+        "tags differ:\n  {}",
+        differences.join("\n  ")
+    );
+}
+
+/// Renders the derived values of `config` covered by the golden data, one
+/// `name: value` line each with the value in its `Debug` form
+pub fn render(config: &PythonConfig) -> String {
+    let mut out = String::new();
+    let mut line = |name: &str, value: &dyn fmt::Debug| {
+        writeln!(out, "{}: {:?}", name, value).unwrap();
+    };
+    line("version", &config.version_str());
+    line("implementation", &config.implementation());
+    line("implementation_version", &config.implementation_version());
+    line("python_tag", &config.python_tag());
+    line("abi_tag", &config.abi_tag());
+    line("sys_platform", &config.sys_platform());
+    line("sysconfigdata_name", &config.sysconfigdata_name());
+    line("platform", &config.get_platform());
+    line("platform_tag", &config.platform_tag());
+    line("pointer_size", &config.pointer_size());
+    line("ext_suffix", &config.ext_suffix());
+    line("enable_shared", &config.enable_shared());
+    line("runtime_library", &config.runtime_library());
+    line("dylib_install_name", &config.dylib_install_name());
+    line("rpath_suggestions", &config.rpath_suggestions());
+    line("py_ssize_t_size", &config.py_ssize_t_size());
+    line("hash_algorithm", &config.hash_algorithm());
+    line("unicode_width", &config.unicode_width());
+    out
+}
+
+/// The expected values of a fixture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Golden {
+    /// The fixture
+    pub fixture: &'static Fixture,
+    /// Its values as rendered by [`render`]
+    pub data: &'static str,
+}
+
+impl Golden {
+    /// The `Debug` form of the value `name`, `"cp311"` for `python_tag`
+    pub fn value(&self, name: &str) -> Option<&'static str> {
+        self.data.lines().find_map(|line| {
+            let (key, value) = line.split_once(": ")?;
+            (key == name).then_some(value)
+        })
+    }
+
+    /// The expected tags
+    pub fn tags(&self) -> Tags {
+        let tag = |name| {
+            let value = self.value(name).unwrap_or_default();
+            value.trim_matches('"').to_string()
+        };
+        Tags {
+            python_tag: tag("python_tag"),
+            abi_tag: tag("abi_tag"),
+            platform_tag: tag("platform_tag"),
+            ext_suffix: tag("ext_suffix"),
+        }
+    }
+
+    /// Compares `config`, loaded from the fixture, with the golden data,
+    /// failing with the differing lines and the ones only one side has
+    pub fn check(&self, config: &PythonConfig) -> Result<(), String> {
+        let actual = render(config);
+        let (expected, actual): (Vec<_>, Vec<_>) =
+            (self.data.lines().collect(), actual.lines().collect());
+        let mut differences: Vec<String> = expected
+            .iter()
+            .zip(&actual)
+            .filter(|(expected, actual)| expected != actual)
+            .map(|(expected, actual)| format!("expected {}, found {}", expected, actual))
+            .collect();
+        // lines past the end of the shorter side
+        let common = expected.len().min(actual.len());
+        differences.extend(
+            expected[common..]
+                .iter()
+                .map(|line| format!("missing {}", line)),
+        );
+        differences.extend(
+            actual[common..]
+                .iter()
+                .map(|line| format!("unexpected {}", line)),
+        );
+        if differences.is_empty() {
+            return Ok(());
+        }
+        Err(format!(
+            "{}:\n  {}",
+            self.fixture.name,
+            differences.join("\n  ")
+        ))
+    }
+}
+
+macro_rules! golden {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!("../tests/snapshots/", $name, ".snap")),
+        )
+    };
+}
+
+const GOLDEN: &[(&str, &str)] = &[
+    golden!("cpython37m-linux"),
+    golden!("cpython38-darwin"),
+    golden!("cpython39-linux-aarch64"),
+    golden!("cpython39-cygwin"),
+    golden!("cpython310-conda"),
+    golden!("cpython311-freebsd"),
+    golden!("cpython311-mingw"),
+    golden!("cpython311-linux"),
+    golden!("cpython312-pyodide"),
+    golden!("cpython313t-linux"),
+    golden!("pypy310-linux"),
+    golden!("graalpy240-linux"),
+];
+
+/// Returns the golden data of the fixture named `name`
+pub fn golden(name: &str) -> Option<Golden> {
+    let (_, data) = GOLDEN.iter().find(|(golden, _)| *golden == name)?;
+    Some(Golden {
+        fixture: fixtures::get(name)?,
+        data,
+    })
+}
+
+/// Returns the golden data of every fixture
+pub fn goldens() -> Vec<Golden> {
+    fixtures::all()
+        .iter()
+        .filter_map(|fixture| golden(fixture.name))
+        .collect()
+}
+
+/// Runs the suite: `load` reads each fixture through the integration layer
+/// under test, for example from [`Fixture::source`] written to disk, and
+/// the result is checked against the golden data. Fails with the
+/// differences of every fixture that diverges.
+pub fn run(load: impl Fn(&Fixture) -> PythonConfig) -> Result<(), String> {
+    let failures: Vec<String> = goldens()
+        .iter()
+        .filter_map(|golden| golden.check(&load(golden.fixture)).err())
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_tags, golden, goldens, run, Golden, Tags};
+    use crate::{fixtures, ConfigValue};
+
+    #[test]
+    fn conformance_suite() {
+        assert_eq!(goldens().len(), fixtures::all().len());
+        run(|fixture| fixture.config()).unwrap();
+
+        let golden = golden("cpython311-linux").unwrap();
+        assert_eq!(golden.value("abi_tag"), Some("\"cp311\""));
+        let tags = golden.tags();
+        assert_eq!(
+            tags.to_string(),
+            "cp311-cp311-manylinux_2_17_x86_64 (.cpython-311-x86_64-linux-gnu.so)"
+        );
+        let config = golden.fixture.config();
+        assert_tags(&config, &tags);
+        assert_eq!(Tags::of(&config), tags);
+
+        let err = run(|fixture| {
+            let mut config = fixture.config();
+            if fixture.name == "cpython311-linux" {
+                config
+                    .set("EXT_SUFFIX", ConfigValue::String(".abi3.so".into()))
+                    .unwrap();
+            }
+            config
+        })
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "cpython311-linux:\n  \
+             expected abi_tag: \"cp311\", found abi_tag: \"abi3\"\n  \
+             expected ext_suffix: \".cpython-311-x86_64-linux-gnu.so\", \
+             found ext_suffix: \".abi3.so\""
+        );
+    }
+
+    #[test]
+    fn golden_line_counts() {
+        let golden = golden("cpython311-linux").unwrap();
+        let config = golden.fixture.config();
+        let data = golden.data;
+        let last = data.trim_end().rsplit('\n').next().unwrap();
+
+        let shorter = Golden {
+            data: data.strip_suffix(&format!("{}\n", last)).unwrap(),
+            ..golden
+        };
+        let err = shorter.check(&config).unwrap_err();
+        assert_eq!(err, format!("cpython311-linux:\n  unexpected {}", last));
+
+        let longer = Golden {
+            data: concat!(
+                include_str!("../tests/snapshots/cpython311-linux.snap"),
+                "extra: true\n"
+            ),
+            ..golden
+        };
+        let err = longer.check(&config).unwrap_err();
+        assert_eq!(err, "cpython311-linux:\n  missing extra: true");
+    }
+
+    #[test]
+    #[should_panic(expected = "tags differ:\n  abi_tag: expected abi3, found cp311")]
+    fn tag_mismatch() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let mut tags = Tags::of(&config);
+        tags.abi_tag = "abi3".to_string();
+        assert_tags(&config, &tags);
+    }
+}
@@ -0,0 +1,369 @@
+//! Finding Python installations in container images, either extracted to a
+//! root filesystem directory or stored as an OCI image layout.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+
+use crate::{discover_all, Error, Interner, ParseOptions, PythonConfig};
+
+/// Directories Python installations are commonly found in, `*` matches any
+/// single path component
+const WELL_KNOWN_DIRS: &[&str] = &[
+    // manylinux and musllinux, /opt/python/cp311-cp311 links here
+    "opt/_internal/*/lib/python3*",
+    "opt/python/*/lib/python3*",
+    "usr/lib/python3*",
+    "usr/lib64/python3*",
+    "usr/local/lib/python3*",
+    "opt/conda/lib/python3*",
+    "root/.pyenv/versions/*/lib/python3*",
+    "usr/local/pyenv/versions/*/lib/python3*",
+];
+
+/// A Python installation found in a container image
+#[derive(Debug, Clone)]
+pub struct Installation {
+    /// Path of the `_sysconfigdata.py` file, relative to the image root
+    pub sysconfigdata: PathBuf,
+    /// Installation prefix, relative to the image root
+    pub prefix: PathBuf,
+    /// The parsed configuration
+    pub config: PythonConfig,
+}
+
+impl Installation {
+    fn new(sysconfigdata: PathBuf, config: PythonConfig) -> Self {
+        let prefix = install_prefix(&sysconfigdata);
+        Self {
+            sysconfigdata,
+            prefix,
+            config,
+        }
+    }
+}
+
+/// Finds the Python installations of an extracted container root filesystem.
+///
+/// Well known locations of distribution, manylinux, conda and pyenv
+/// interpreters are searched first, the whole tree is searched with
+/// [`discover_all`] if none of them has a sysconfigdata file. Symbolic links
+/// aren't followed, as absolute links point outside of `root`.
+pub fn inspect_rootfs(root: impl AsRef<Path>) -> io::Result<Vec<Installation>> {
+    let root = root.as_ref();
+    let options = ParseOptions::new().interner(Interner::new());
+    let mut installations = Vec::new();
+    for pattern in WELL_KNOWN_DIRS {
+        for dir in expand(root, pattern)? {
+            let mut files: Vec<PathBuf> = fs::read_dir(&dir)?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| is_sysconfigdata(path))
+                .collect();
+            files.sort();
+            for path in files {
+                let src = fs::read_to_string(&path)?;
+                if let Ok(config) = PythonConfig::parse_with_options(&src, &options) {
+                    let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+                    installations.push(Installation::new(relative, config));
+                }
+            }
+        }
+    }
+    if installations.is_empty() {
+        for (path, config) in discover_all(root)? {
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            installations.push(Installation::new(relative, config));
+        }
+    }
+    Ok(installations)
+}
+
+/// Finds the Python installations of an OCI image layout directory, as
+/// written by `skopeo copy docker://... oci:DIR` for example.
+///
+/// The first image of `index.json` is inspected, its layers are applied in
+/// order including whiteouts. Layers must be uncompressed or gzip compressed
+/// tarballs.
+pub fn inspect_oci_layout(dir: impl AsRef<Path>) -> Result<Vec<Installation>, Error> {
+    let dir = dir.as_ref();
+    let mut manifest = read_json(&dir.join("index.json"))?;
+    // follow nested indexes down to the first image manifest
+    while let Some(descriptor) = manifest
+        .get("manifests")
+        .and_then(|manifests| manifests.get(0))
+    {
+        let digest = descriptor_digest(descriptor)?;
+        manifest = read_json(&blob_path(dir, digest)?)?;
+    }
+    let layers = manifest
+        .get("layers")
+        .and_then(|layers| layers.as_array())
+        .ok_or_else(|| Error::InvalidImage("image manifest has no layers".to_string()))?;
+    let mut files: BTreeMap<PathBuf, String> = BTreeMap::new();
+    for layer in layers {
+        let digest = descriptor_digest(layer)?;
+        let media_type = layer
+            .get("mediaType")
+            .and_then(|media_type| media_type.as_str())
+            .unwrap_or_default();
+        let file = BufReader::new(File::open(blob_path(dir, digest)?)?);
+        if media_type.ends_with("+gzip") || media_type.ends_with(".gzip") {
+            apply_layer(flate2::read::GzDecoder::new(file), &mut files)?;
+        } else if media_type.ends_with(".tar") || media_type.ends_with("tar") {
+            apply_layer(file, &mut files)?;
+        } else {
+            return Err(Error::InvalidImage(format!(
+                "unsupported layer media type {}",
+                media_type
+            )));
+        }
+    }
+    let options = ParseOptions::new().interner(Interner::new());
+    Ok(files
+        .into_iter()
+        .filter_map(|(path, src)| {
+            let config = PythonConfig::parse_with_options(&src, &options).ok()?;
+            Some(Installation::new(path, config))
+        })
+        .collect())
+}
+
+/// Applies the sysconfigdata files and whiteouts of a layer tarball to `files`
+fn apply_layer<R: Read>(reader: R, files: &mut BTreeMap<PathBuf, String>) -> Result<(), Error> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = normalize(&entry.path()?);
+        let name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+        let parent = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+        if name == ".wh..wh..opq" {
+            files.retain(|file, _| !file.starts_with(&parent));
+        } else if let Some(name) = name.strip_prefix(".wh.") {
+            let removed = parent.join(name);
+            files.retain(|file, _| !file.starts_with(&removed));
+        } else if entry.header().entry_type().is_file() && is_sysconfigdata(&path) {
+            let mut src = String::new();
+            if entry.read_to_string(&mut src).is_ok() {
+                files.insert(path, src);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_json(path: &Path) -> Result<serde_json::Value, Error> {
+    let src = fs::read_to_string(path)?;
+    serde_json::from_str(&src)
+        .map_err(|err| Error::InvalidImage(format!("{}: {}", path.display(), err)))
+}
+
+fn descriptor_digest(descriptor: &serde_json::Value) -> Result<&str, Error> {
+    descriptor
+        .get("digest")
+        .and_then(|digest| digest.as_str())
+        .ok_or_else(|| Error::InvalidImage("descriptor without digest".to_string()))
+}
+
+/// Path of the blob with `digest`, such as `sha256:abcd...`
+fn blob_path(dir: &Path, digest: &str) -> Result<PathBuf, Error> {
+    match digest.split_once(':') {
+        Some((algorithm, hash))
+            if !hash.is_empty() && hash.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            Ok(dir.join("blobs").join(algorithm).join(hash))
+        }
+        _ => Err(Error::InvalidImage(format!("invalid digest {}", digest))),
+    }
+}
+
+/// Strips `./` and leading `/` from tarball paths
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
+
+/// Expands a `WELL_KNOWN_DIRS` pattern under `root` to existing directories
+fn expand(root: &Path, pattern: &str) -> io::Result<Vec<PathBuf>> {
+    let mut dirs = vec![root.to_path_buf()];
+    for part in pattern.split('/') {
+        let mut next = Vec::new();
+        for dir in dirs {
+            if !part.contains('*') {
+                let path = dir.join(part);
+                if is_real_dir(&path) {
+                    next.push(path);
+                }
+                continue;
+            }
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            for entry in entries {
+                let path = entry?.path();
+                let matches = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| wildcard_match(part, name))
+                    .unwrap_or(false);
+                if matches && is_real_dir(&path) {
+                    next.push(path);
+                }
+            }
+        }
+        next.sort();
+        dirs = next;
+    }
+    Ok(dirs)
+}
+
+fn is_real_dir(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|metadata| metadata.is_dir())
+        .unwrap_or(false)
+}
+
+/// Matches `name` against `pattern` with a single trailing or lone `*`
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
+fn is_sysconfigdata(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.starts_with("_sysconfigdata") && name.ends_with(".py"))
+        .unwrap_or(false)
+}
+
+/// Prefix of an installation, the directory containing `lib/python3.X`
+fn install_prefix(sysconfigdata: &Path) -> PathBuf {
+    let components: Vec<_> = sysconfigdata.components().collect();
+    let lib = components.windows(2).rposition(|pair| {
+        let lib = pair[0].as_os_str().to_str().unwrap_or_default();
+        let python = pair[1].as_os_str().to_str().unwrap_or_default();
+        lib.starts_with("lib") && python.starts_with("python")
+    });
+    match lib {
+        Some(index) => components[..index].iter().collect(),
+        None => sysconfigdata
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{inspect_oci_layout, inspect_rootfs};
+    use std::env;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    const FIXTURE: &str = "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py";
+    const SYSCONFIGDATA: &str = "_sysconfigdata__linux_x86_64-linux-gnu.py";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("python3-config-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rootfs() {
+        let root = temp_dir("rootfs");
+        let lib = root.join("opt/_internal/cpython-3.11.9/lib/python3.11");
+        fs::create_dir_all(&lib).unwrap();
+        fs::copy(FIXTURE, lib.join(SYSCONFIGDATA)).unwrap();
+        let installations = inspect_rootfs(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(installations.len(), 1);
+        assert_eq!(
+            installations[0].prefix,
+            Path::new("opt/_internal/cpython-3.11.9")
+        );
+        assert_eq!(installations[0].config.version_str(), "3.11");
+    }
+
+    /// Writes a blob and returns its descriptor, digests aren't verified so
+    /// the blob number stands in for the hash
+    fn write_blob(dir: &Path, media_type: &str, data: &[u8]) -> String {
+        let count = fs::read_dir(dir.join("blobs/sha256")).unwrap().count();
+        let digest = format!("{:064x}", count);
+        fs::write(dir.join("blobs/sha256").join(&digest), data).unwrap();
+        format!(
+            r#"{{"mediaType": "{}", "digest": "sha256:{}", "size": {}}}"#,
+            media_type,
+            digest,
+            data.len()
+        )
+    }
+
+    fn layer(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, data) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn oci_layout() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let dir = temp_dir("oci");
+        fs::create_dir_all(dir.join("blobs/sha256")).unwrap();
+        let src = fs::read(FIXTURE).unwrap();
+        let base = layer(&[
+            (&format!("usr/local/lib/python3.11/{}", SYSCONFIGDATA), &src),
+            (&format!("usr/lib/python3.9/{}", SYSCONFIGDATA), &src),
+        ]);
+        let mut encoder = GzEncoder::new(Vec::new(), Default::default());
+        encoder.write_all(&base).unwrap();
+        let base = write_blob(
+            &dir,
+            "application/vnd.oci.image.layer.v1.tar+gzip",
+            &encoder.finish().unwrap(),
+        );
+        let top = write_blob(
+            &dir,
+            "application/vnd.oci.image.layer.v1.tar",
+            &layer(&[("usr/lib/.wh.python3.9", b"")]),
+        );
+        let config = write_blob(&dir, "application/vnd.oci.image.config.v1+json", b"{}");
+        let manifest = format!(
+            r#"{{"schemaVersion": 2, "config": {}, "layers": [{}, {}]}}"#,
+            config, base, top
+        );
+        let manifest = write_blob(
+            &dir,
+            "application/vnd.oci.image.manifest.v1+json",
+            manifest.as_bytes(),
+        );
+        fs::write(
+            dir.join("index.json"),
+            format!(r#"{{"schemaVersion": 2, "manifests": [{}]}}"#, manifest),
+        )
+        .unwrap();
+
+        let installations = inspect_oci_layout(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(installations.len(), 1);
+        assert_eq!(installations[0].prefix, Path::new("usr/local"));
+    }
+}
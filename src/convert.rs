@@ -0,0 +1,284 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::{ConfigValue, Error, PythonConfig};
+
+/// Type of a [`ConfigValue`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    /// String literal
+    String,
+    /// Integer literal
+    Int,
+    /// Float literal
+    Float,
+    /// `None`
+    None,
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueKind::String => write!(f, "string"),
+            ValueKind::Int => write!(f, "integer"),
+            ValueKind::Float => write!(f, "float"),
+            ValueKind::None => write!(f, "None"),
+        }
+    }
+}
+
+impl ConfigValue {
+    /// Returns the type of the value
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            ConfigValue::String(_) => ValueKind::String,
+            ConfigValue::Int(_) => ValueKind::Int,
+            ConfigValue::Float(_) => ValueKind::Float,
+            ConfigValue::None => ValueKind::None,
+        }
+    }
+
+    fn mismatch(&self, expected: ValueKind) -> Error {
+        Error::TypeMismatch {
+            expected,
+            found: self.kind(),
+        }
+    }
+}
+
+/// Types built from the values of several keys of a configuration, usually
+/// implemented with `#[derive(FromPythonConfig)]` of the `derive` feature
+pub trait FromPythonConfig: Sized {
+    /// Reads `Self` from `config`
+    fn from_python_config(config: &PythonConfig) -> Result<Self, Error>;
+}
+
+impl PythonConfig {
+    /// Returns `T` read from this configuration, see [`FromPythonConfig`]
+    pub fn extract<T: FromPythonConfig>(&self) -> Result<T, Error> {
+        T::from_python_config(self)
+    }
+
+    /// Returns the value of `key` converted to `T`, failing with
+    /// [`Error::UnknownKey`] when it is missing and [`Error::TypeMismatch`]
+    /// when it has another type
+    pub fn get_as<T>(&self, key: &str) -> Result<T, Error>
+    where
+        T: for<'a> TryFrom<&'a ConfigValue, Error = Error>,
+    {
+        T::try_from(self.try_get(key)?).map_err(|err| err.with_key(key))
+    }
+}
+
+impl TryFrom<&ConfigValue> for String {
+    type Error = Error;
+
+    fn try_from(value: &ConfigValue) -> Result<Self, Error> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| value.mismatch(ValueKind::String))
+    }
+}
+
+impl TryFrom<&ConfigValue> for i64 {
+    type Error = Error;
+
+    fn try_from(value: &ConfigValue) -> Result<Self, Error> {
+        value.as_int().ok_or_else(|| value.mismatch(ValueKind::Int))
+    }
+}
+
+/// Integers are widened, as Python does in arithmetic
+impl TryFrom<&ConfigValue> for f64 {
+    type Error = Error;
+
+    fn try_from(value: &ConfigValue) -> Result<Self, Error> {
+        value
+            .as_float()
+            .ok_or_else(|| value.mismatch(ValueKind::Float))
+    }
+}
+
+/// Integers are `true` when non-zero, like the `#define` switches they come
+/// from, and `None` is `false`
+impl TryFrom<&ConfigValue> for bool {
+    type Error = Error;
+
+    fn try_from(value: &ConfigValue) -> Result<Self, Error> {
+        match value {
+            ConfigValue::Int(value) => Ok(*value != 0),
+            ConfigValue::None => Ok(false),
+            ConfigValue::String(_) | ConfigValue::Float(_) => Err(value.mismatch(ValueKind::Int)),
+        }
+    }
+}
+
+impl TryFrom<&ConfigValue> for PathBuf {
+    type Error = Error;
+
+    fn try_from(value: &ConfigValue) -> Result<Self, Error> {
+        value
+            .as_str()
+            .map(PathBuf::from)
+            .ok_or_else(|| value.mismatch(ValueKind::String))
+    }
+}
+
+/// Splits the string into words the way a POSIX shell does, honouring quotes
+/// and backslashes, `None` gives no words
+impl TryFrom<&ConfigValue> for Vec<String> {
+    type Error = Error;
+
+    fn try_from(value: &ConfigValue) -> Result<Self, Error> {
+        match value {
+            ConfigValue::String(value) => Ok(shell_split(value)),
+            ConfigValue::None => Ok(Vec::new()),
+            ConfigValue::Int(_) | ConfigValue::Float(_) => Err(value.mismatch(ValueKind::String)),
+        }
+    }
+}
+
+/// Words of `s` after shell quote removal, an unterminated quote extends to
+/// the end of the string
+pub(crate) fn shell_split(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\\' => word.get_or_insert_with(String::new).extend(chars.next()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                word.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => word.push('\\'),
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{shell_split, ValueKind};
+    use crate::{fixtures, ConfigValue, Error};
+    use std::convert::TryFrom;
+    use std::path::PathBuf;
+
+    #[test]
+    fn conversions() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(config.get_as::<String>("VERSION").unwrap(), "3.11");
+        assert_eq!(config.get_as::<i64>("SIZEOF_VOID_P").unwrap(), 8);
+        assert!(config.get_as::<bool>("Py_ENABLE_SHARED").unwrap());
+        assert_eq!(
+            config.get_as::<PathBuf>("LIBDIR").unwrap(),
+            PathBuf::from("/usr/local/lib")
+        );
+        assert_eq!(
+            config.get_as::<Vec<String>>("LIBS").unwrap(),
+            vec!["-ldl", "-lm"]
+        );
+        assert!(matches!(
+            config.get_as::<i64>("VERSION").unwrap_err().root_cause(),
+            Error::TypeMismatch {
+                expected: ValueKind::Int,
+                found: ValueKind::String
+            }
+        ));
+        assert!(matches!(
+            config.get_as::<String>("VERSON"),
+            Err(Error::UnknownKey { .. })
+        ));
+
+        assert!(!bool::try_from(&ConfigValue::None).unwrap());
+        assert!(Vec::<String>::try_from(&ConfigValue::None)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            String::try_from(&ConfigValue::Int(1))
+                .unwrap_err()
+                .to_string(),
+            "expected string value, found integer"
+        );
+    }
+
+    #[test]
+    fn shell_words() {
+        assert_eq!(
+            shell_split(r#"  -DA='x y' -DB="a \"b\" \c" c\ d ''  "#),
+            vec!["-DA=x y", r#"-DB=a "b" \c"#, "c d", ""]
+        );
+        assert_eq!(shell_split("'open"), vec!["open"]);
+        assert!(shell_split(" \t").is_empty());
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derive() {
+        use crate::FromPythonConfig;
+
+        fn default_cc() -> String {
+            "cc".to_string()
+        }
+
+        #[derive(FromPythonConfig)]
+        struct Build {
+            #[python_config(key = "VERSION")]
+            version: String,
+            #[python_config(key = "Py_ENABLE_SHARED")]
+            shared: bool,
+            #[python_config(key = "LIBDIR")]
+            libdir: PathBuf,
+            #[python_config(key = "LIBS")]
+            libs: Vec<String>,
+            #[python_config(key = "Py_GIL_DISABLED")]
+            gil_disabled: Option<i64>,
+            #[python_config(key = "MISSING", default)]
+            missing: i64,
+            #[python_config(key = "MISSING_CC", default = "default_cc")]
+            cc: String,
+        }
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let build: Build = config.extract().unwrap();
+        assert_eq!(build.version, "3.11");
+        assert!(build.shared);
+        assert_eq!(build.libdir, PathBuf::from("/usr/local/lib"));
+        assert_eq!(build.libs, vec!["-ldl", "-lm"]);
+        assert_eq!(build.gil_disabled, None);
+        assert_eq!(build.missing, 0);
+        assert_eq!(build.cc, "cc");
+
+        #[derive(FromPythonConfig)]
+        struct Required {
+            #[allow(dead_code)]
+            #[python_config(key = "MISSING")]
+            missing: String,
+        }
+        assert!(matches!(
+            config.extract::<Required>(),
+            Err(Error::UnknownKey { .. })
+        ));
+    }
+}
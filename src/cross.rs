@@ -0,0 +1,126 @@
+use crate::arch;
+use crate::convert::shell_split;
+use crate::PythonConfig;
+
+/// `configure` arguments only cross builds use
+const CROSS_ARGS: &[&str] = &[
+    "--with-build-python",
+    "CROSS_COMPILE=",
+    "ac_cv_file__dev_ptmx=",
+];
+
+impl PythonConfig {
+    /// Returns whether this configuration was itself produced by a cross
+    /// build, see [`PythonConfig::cross_build_hints`].
+    ///
+    /// Configurations of cross builds may contain paths of the build machine
+    /// even when they look like native ones.
+    pub fn is_cross_build(&self) -> bool {
+        !self.cross_build_hints().is_empty()
+    }
+
+    /// Returns the evidence of a cross build found in `build_time_vars`:
+    /// different `BUILD_GNU_TYPE` and `HOST_GNU_TYPE` or `--build` and
+    /// `--host` `configure` arguments, a `PYTHON_FOR_BUILD` setting
+    /// `_PYTHON_HOST_PLATFORM`, and `configure` arguments like
+    /// `--with-build-python` or `CROSS_COMPILE=`
+    pub fn cross_build_hints(&self) -> Vec<String> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let mut hints = Vec::new();
+        let build = vars.get_str("BUILD_GNU_TYPE");
+        if !build.is_empty()
+            && !vars.host_gnu_type.is_empty()
+            && !same_system(build, &vars.host_gnu_type)
+        {
+            hints.push(format!(
+                "BUILD_GNU_TYPE {} differs from HOST_GNU_TYPE {}",
+                build, vars.host_gnu_type
+            ));
+        }
+        let args = shell_split(vars.get_str("CONFIG_ARGS"));
+        let arg = |name: &str| {
+            args.iter()
+                .find_map(|arg| arg.strip_prefix(name)?.strip_prefix('='))
+        };
+        if let (Some(build), Some(host)) = (arg("--build"), arg("--host")) {
+            if !same_system(build, host) {
+                hints.push(format!("configured with --build={} --host={}", build, host));
+            }
+        }
+        if vars
+            .get_str("PYTHON_FOR_BUILD")
+            .contains("_PYTHON_HOST_PLATFORM")
+        {
+            hints.push("PYTHON_FOR_BUILD sets _PYTHON_HOST_PLATFORM".to_string());
+        }
+        for cross_arg in CROSS_ARGS {
+            if let Some(arg) = args.iter().find(|arg| arg.starts_with(cross_arg)) {
+                hints.push(format!("configured with {}", arg));
+            }
+        }
+        hints
+    }
+}
+
+/// Whether the GNU triples `a` and `b` name the same architecture and
+/// system, ignoring vendors and OS versions: `x86_64-pc-linux-gnu` and
+/// `x86_64-linux-gnu` do
+fn same_system(a: &str, b: &str) -> bool {
+    system(a) == system(b)
+}
+
+/// Architecture and last component of a GNU triple
+fn system(triple: &str) -> (String, String) {
+    let machine = triple.split('-').next().unwrap_or_default();
+    let machine = arch::find_by_name(machine).map_or(machine, |arch| arch.multiarch);
+    let os = triple
+        .rsplit('-')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    (machine.to_string(), os.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, PythonConfig};
+
+    #[test]
+    fn cross_build() {
+        for name in [
+            "cpython311-linux",
+            "cpython311-mingw",
+            "cpython38-darwin",
+            "cpython310-conda",
+            "cpython311-freebsd",
+        ] {
+            let config = fixtures::get(name).unwrap().config();
+            assert!(!config.is_cross_build(), "{}", name);
+        }
+
+        let config = fixtures::get("cpython312-pyodide").unwrap().config();
+        assert_eq!(
+            config.cross_build_hints(),
+            vec![
+                "configured with --build=x86_64-pc-linux-gnu --host=wasm32-unknown-emscripten",
+                "configured with --with-build-python=/src/cpython/build/Python-3.12.1/python",
+            ]
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'BUILD_GNU_TYPE': 'x86_64-pc-linux-gnu', \
+             'HOST_GNU_TYPE': 'aarch64-unknown-linux-gnu', \
+             'PYTHON_FOR_BUILD': '_PYTHON_PROJECT_BASE=/build _PYTHON_HOST_PLATFORM=linux-aarch64 python3.11'}",
+        )
+        .unwrap();
+        assert!(config.is_cross_build());
+        assert_eq!(config.cross_build_hints().len(), 2);
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'BUILD_GNU_TYPE': 'amd64-portbld-freebsd14.0', \
+             'HOST_GNU_TYPE': 'x86_64-unknown-freebsd14.1'}",
+        )
+        .unwrap();
+        assert!(!config.is_cross_build());
+    }
+}
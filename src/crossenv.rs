@@ -0,0 +1,179 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::runner::shell_quote;
+use crate::{Error, PythonConfig};
+
+/// Files written by [`make_crossenv_layout`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossenvLayout {
+    /// Directory holding the layout
+    pub root: PathBuf,
+    /// `pyvenv.cfg` turning the directory into a virtual environment of the
+    /// host interpreter
+    pub pyvenv_cfg: PathBuf,
+    /// `_sysconfigdata` module of the target in `lib`
+    pub sysconfigdata: PathBuf,
+    /// `bin/activate`, a POSIX shell script to source
+    pub activate: PathBuf,
+    /// `bin/python3`, a symbolic link to the host interpreter on Unix,
+    /// `None` elsewhere
+    pub python: Option<PathBuf>,
+}
+
+/// Sets up `dir` so the interpreter of `host_python` reports the
+/// configuration of `target` from `sysconfig`, the trick crossenv uses to
+/// build extension modules for another platform with a Python running on
+/// the build machine:
+///
+/// * `lib/` holds the `_sysconfigdata` module of `target`
+/// * `pyvenv.cfg` makes `dir` a virtual environment of `host_python`, with
+///   `bin/python3` linking to its interpreter on Unix
+/// * `bin/activate` puts `bin` on `PATH` and `lib` on `PYTHONPATH`, and sets
+///   `_PYTHON_SYSCONFIGDATA_NAME` and `_PYTHON_HOST_PLATFORM` to load the
+///   target's module
+///
+/// Both configurations must be of the same Python version, the target's
+/// module is imported by the host interpreter.
+pub fn make_crossenv_layout(
+    host_python: &PythonConfig,
+    target: &PythonConfig,
+    dir: impl AsRef<Path>,
+) -> Result<CrossenvLayout, Error> {
+    let root = dir.as_ref().to_path_buf();
+    if host_python.sys_platform() == "win32" {
+        return Err(Error::Unsupported(
+            "crossenv layouts need a Unix host interpreter".to_string(),
+        ));
+    }
+    if host_python.version_str() != target.version_str() {
+        return Err(Error::Unsupported(format!(
+            "Python {} can't load the sysconfigdata of Python {}",
+            host_python.version_str(),
+            target.version_str()
+        )));
+    }
+    let name = target.sysconfigdata_name().ok_or_else(|| {
+        Error::Unsupported(format!("{} has no sysconfigdata", target.get_platform()))
+    })?;
+    let executable = host_python.python_executable_path().ok_or_else(|| {
+        Error::Unsupported("the host interpreter path is unknown without BINDIR".to_string())
+    })?;
+    let home = executable.parent().unwrap_or_else(|| Path::new("/"));
+
+    let lib = root.join("lib");
+    let bin = root.join("bin");
+    fs::create_dir_all(&lib)?;
+    fs::create_dir_all(&bin)?;
+
+    let sysconfigdata = lib.join(format!("{}.py", name));
+    fs::write(&sysconfigdata, target.to_sysconfigdata())?;
+
+    let pyvenv_cfg = root.join("pyvenv.cfg");
+    fs::write(
+        &pyvenv_cfg,
+        format!(
+            "home = {}\ninclude-system-site-packages = false\nversion = {}\n",
+            home.display(),
+            host_python.version_str()
+        ),
+    )?;
+
+    let mut script = String::from("# source this file to cross compile with the host Python\n");
+    let vars = [
+        ("_PYTHON_SYSCONFIGDATA_NAME", name),
+        ("_PYTHON_HOST_PLATFORM", target.get_platform().to_string()),
+    ];
+    for (key, value) in &vars {
+        writeln!(script, "export {}={}", key, shell_quote(value)).unwrap();
+    }
+    writeln!(
+        script,
+        "export PYTHONPATH={}${{PYTHONPATH:+:$PYTHONPATH}}",
+        shell_quote(&lib.display().to_string())
+    )
+    .unwrap();
+    writeln!(
+        script,
+        "export PATH={}:\"$PATH\"",
+        shell_quote(&bin.display().to_string())
+    )
+    .unwrap();
+    let activate = bin.join("activate");
+    fs::write(&activate, script)?;
+
+    let python = link_python(&executable, &bin.join("python3"))?;
+    Ok(CrossenvLayout {
+        root,
+        pyvenv_cfg,
+        sysconfigdata,
+        activate,
+        python,
+    })
+}
+
+/// Links `link` to the interpreter `executable`, replacing an older link
+#[cfg(unix)]
+fn link_python(executable: &Path, link: &Path) -> Result<Option<PathBuf>, Error> {
+    if fs::symlink_metadata(link).is_ok() {
+        fs::remove_file(link)?;
+    }
+    std::os::unix::fs::symlink(executable, link)?;
+    Ok(Some(link.to_path_buf()))
+}
+
+#[cfg(not(unix))]
+fn link_python(_executable: &Path, _link: &Path) -> Result<Option<PathBuf>, Error> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::make_crossenv_layout;
+    use crate::{fixtures, Error, PythonConfig};
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn crossenv_layout() {
+        let host = fixtures::get("cpython311-linux").unwrap().config();
+        let target = fixtures::get("cpython311-freebsd").unwrap().config();
+        let dir = env::temp_dir().join(format!("python3-config-crossenv-{}", std::process::id()));
+        let layout = make_crossenv_layout(&host, &target, &dir).unwrap();
+
+        assert_eq!(
+            layout.sysconfigdata,
+            dir.join("lib/_sysconfigdata__freebsd14_.py")
+        );
+        let written = fs::read_to_string(&layout.sysconfigdata).unwrap();
+        assert_eq!(
+            PythonConfig::parse(&written).unwrap().get_platform(),
+            target.get_platform()
+        );
+        assert_eq!(
+            fs::read_to_string(&layout.pyvenv_cfg).unwrap(),
+            "home = /usr/local/bin\ninclude-system-site-packages = false\nversion = 3.11\n"
+        );
+        let activate = fs::read_to_string(&layout.activate).unwrap();
+        assert!(activate.contains("export _PYTHON_SYSCONFIGDATA_NAME=_sysconfigdata__freebsd14_\n"));
+        assert!(activate.contains(&format!(
+            "export _PYTHON_HOST_PLATFORM={}\n",
+            target.get_platform()
+        )));
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(layout.python.unwrap()).unwrap(),
+            std::path::Path::new("/usr/local/bin/python3.11")
+        );
+        // a second run replaces the layout
+        make_crossenv_layout(&host, &target, &dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let target = fixtures::get("cpython39-linux-aarch64").unwrap().config();
+        assert!(matches!(
+            make_crossenv_layout(&host, &target, &dir),
+            Err(Error::Unsupported(_))
+        ));
+    }
+}
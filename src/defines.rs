@@ -0,0 +1,271 @@
+use std::fmt;
+
+use crate::{AbiVariant, ConfigValue, Error, PythonConfig, PythonImplementation, PythonVersion};
+
+/// A preprocessor macro C code must be compiled with, see
+/// [`PythonConfig::abi_defines`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Define {
+    /// Name of the macro, `Py_DEBUG`
+    pub name: String,
+    /// Value of the macro, `None` to define it without one
+    pub value: Option<String>,
+}
+
+impl Define {
+    fn new(name: &str, value: Option<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    /// The compiler flag, `-DPy_LIMITED_API=0x03090000`
+    pub fn flag(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// The compiler flag, `-DPy_DEBUG`
+impl fmt::Display for Define {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "-D{}", self.name)?;
+        if let Some(value) = &self.value {
+            write!(f, "={}", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl PythonConfig {
+    /// Returns the macros that select the ABI of this distribution in the
+    /// Python headers, which C code like a shim or bindgen input compiled
+    /// outside of `setuptools` must define: `Py_DEBUG` for debug builds and
+    /// `Py_GIL_DISABLED` for free-threaded ones. The Windows `pyconfig.h`
+    /// can't tell free-threaded builds apart, so the macro is required there.
+    ///
+    /// With `limited_api`, the oldest Python version to support, the code
+    /// targets the stable ABI with `Py_LIMITED_API`, see
+    /// [`PythonConfig::limited_api_hex`].
+    ///
+    /// The values map onto `cc::Build::define`, [`Define::flag`] is the
+    /// command line spelling for `bindgen` and other build systems.
+    pub fn abi_defines(&self, limited_api: Option<PythonVersion>) -> Result<Vec<Define>, Error> {
+        let variant = self.abi_variant();
+        let gil_disabled = self.get("Py_GIL_DISABLED").and_then(ConfigValue::as_int) == Some(1);
+        let free_threaded = gil_disabled
+            || matches!(
+                variant,
+                AbiVariant::FreeThreaded | AbiVariant::FreeThreadedDebug
+            );
+        let debug = matches!(variant, AbiVariant::Debug | AbiVariant::FreeThreadedDebug);
+        let mut defines = Vec::new();
+        if debug {
+            defines.push(Define::new("Py_DEBUG", None));
+        }
+        if free_threaded {
+            defines.push(Define::new("Py_GIL_DISABLED", Some("1".to_string())));
+        }
+        if let Some(version) = limited_api {
+            let hex = self.limited_api_hex(version)?;
+            defines.push(Define::new("Py_LIMITED_API", Some(hex)));
+        }
+        Ok(defines)
+    }
+
+    /// Returns the flags compiling C code for the stable ABI of `min_python`
+    /// and newer, such as the shim of an `abi3` extension module: the
+    /// [`compile_flags`](PythonConfig::compile_flags) without the macros
+    /// selecting the ABI of this build, `Py_DEBUG`, `Py_GIL_DISABLED` and
+    /// the like, followed by `-DPy_LIMITED_API`. It agrees with the
+    /// `abi3` tag and the suffix [`ToAbi3`](crate::ToAbi3) sets.
+    ///
+    /// Fails where [`PythonConfig::limited_api_hex`] does and for debug and
+    /// `Py_TRACE_REFS` builds, whose headers change the layout of objects
+    /// shared with the stable ABI.
+    pub fn limited_api_cflags(&self, min_python: PythonVersion) -> Result<Vec<String>, Error> {
+        let hex = self.limited_api_hex(min_python)?;
+        if matches!(
+            self.abi_variant(),
+            AbiVariant::Debug | AbiVariant::FreeThreadedDebug
+        ) {
+            return Err(Error::Unsupported(
+                "debug builds do not support the stable ABI".to_string(),
+            ));
+        }
+        if self.get("Py_TRACE_REFS").and_then(ConfigValue::as_int) == Some(1) {
+            return Err(Error::Unsupported(
+                "Py_TRACE_REFS builds do not support the stable ABI".to_string(),
+            ));
+        }
+        let mut flags = Vec::new();
+        let mut compile_flags = self.compile_flags().into_iter();
+        while let Some(flag) = compile_flags.next() {
+            let name = match flag.as_str() {
+                "-D" | "-U" => {
+                    let name = compile_flags.next().unwrap_or_default();
+                    if !is_abi_macro(&name) {
+                        flags.push(flag);
+                        flags.push(name);
+                    }
+                    continue;
+                }
+                flag => flag.strip_prefix("-D").or_else(|| flag.strip_prefix("-U")),
+            };
+            if !name.is_some_and(is_abi_macro) {
+                flags.push(flag);
+            }
+        }
+        flags.push(Define::new("Py_LIMITED_API", Some(hex)).flag());
+        Ok(flags)
+    }
+
+    /// Returns the value of `Py_LIMITED_API` for stable ABI extension
+    /// modules supporting `min_python` and newer, `0x03090000` for Python
+    /// 3.9, the `PY_VERSION_HEX` of its first release.
+    ///
+    /// Fails when this distribution can't build them: alternative
+    /// implementations and free-threaded builds have no stable ABI, Python
+    /// 3.2 introduced it, and `min_python` can't be newer than this
+    /// distribution.
+    pub fn limited_api_hex(&self, min_python: PythonVersion) -> Result<String, Error> {
+        if self.implementation() != PythonImplementation::CPython {
+            return Err(Error::Unsupported(format!(
+                "{} does not support the stable ABI",
+                self.implementation()
+            )));
+        }
+        if self.abiflags().contains('t')
+            || self.get("Py_GIL_DISABLED").and_then(ConfigValue::as_int) == Some(1)
+        {
+            return Err(Error::Unsupported(
+                "free-threaded builds do not support the stable ABI".to_string(),
+            ));
+        }
+        if !min_python.rules().stable_abi {
+            return Err(Error::Unsupported(format!(
+                "Python {} predates the stable ABI",
+                min_python
+            )));
+        }
+        match self.python_version() {
+            Some(version) if min_python <= version => {}
+            _ => {
+                return Err(Error::Unsupported(format!(
+                    "Python {} can't build stable ABI extension modules for Python {}",
+                    self.version_str(),
+                    min_python
+                )))
+            }
+        }
+        Ok(format!(
+            "0x{:02X}{:02X}0000",
+            min_python.major, min_python.minor
+        ))
+    }
+}
+
+/// Whether the macro of a `-D` or `-U` flag, `Py_DEBUG=1`, selects the ABI
+/// or the internal API and must not reach stable ABI code
+fn is_abi_macro(definition: &str) -> bool {
+    let name = definition.split('=').next().unwrap_or_default();
+    matches!(
+        name,
+        "Py_DEBUG" | "Py_REF_DEBUG" | "Py_TRACE_REFS" | "Py_GIL_DISABLED" | "Py_LIMITED_API"
+    ) || name.starts_with("Py_BUILD_CORE")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, Error, PythonConfig, PythonVersion};
+
+    #[test]
+    fn abi_defines() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert!(config.abi_defines(None).unwrap().is_empty());
+        let defines = config.abi_defines(Some(PythonVersion::new(3, 9))).unwrap();
+        assert_eq!(defines[0].name, "Py_LIMITED_API");
+        assert_eq!(defines[0].flag(), "-DPy_LIMITED_API=0x03090000");
+
+        let config = fixtures::get("cpython313t-linux").unwrap().config();
+        let flags: Vec<String> = config
+            .abi_defines(None)
+            .unwrap()
+            .iter()
+            .map(|define| define.flag())
+            .collect();
+        assert_eq!(flags, vec!["-DPy_GIL_DISABLED=1"]);
+        assert!(matches!(
+            config.abi_defines(Some(PythonVersion::new(3, 9))),
+            Err(Error::Unsupported(_))
+        ));
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'Py_DEBUG': 1, 'Py_GIL_DISABLED': 1}",
+        )
+        .unwrap();
+        let flags: Vec<String> = config
+            .abi_defines(None)
+            .unwrap()
+            .iter()
+            .map(|define| define.flag())
+            .collect();
+        assert_eq!(flags, vec!["-DPy_DEBUG", "-DPy_GIL_DISABLED=1"]);
+    }
+
+    #[test]
+    fn limited_api_hex() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let hex = |major, minor| config.limited_api_hex(PythonVersion::new(major, minor));
+        assert_eq!(hex(3, 2).unwrap(), "0x03020000");
+        assert_eq!(hex(3, 10).unwrap(), "0x030A0000");
+        assert_eq!(hex(3, 11).unwrap(), "0x030B0000");
+        assert!(matches!(hex(3, 1), Err(Error::Unsupported(_))));
+        assert!(matches!(hex(3, 12), Err(Error::Unsupported(_))));
+
+        for name in ["cpython313t-linux", "pypy310-linux"] {
+            let config = fixtures::get(name).unwrap().config();
+            assert!(config.limited_api_hex(PythonVersion::new(3, 8)).is_err());
+            assert!(!config.supports_abi3());
+        }
+    }
+
+    #[test]
+    fn limited_api_cflags() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let flags = config.limited_api_cflags(PythonVersion::new(3, 9)).unwrap();
+        assert_eq!(flags[0], "-I/usr/local/include/python3.11");
+        assert_eq!(flags.last().unwrap(), "-DPy_LIMITED_API=0x03090000");
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'INCLUDEPY': '/opt/include/python3.12', \
+             'CFLAGS': '-O2 -DPy_BUILD_CORE -D Py_LIMITED_API=0x03070000 -UPy_REF_DEBUG -DNDEBUG'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config
+                .limited_api_cflags(PythonVersion::new(3, 12))
+                .unwrap(),
+            vec![
+                "-I/opt/include/python3.12",
+                "-O2",
+                "-DNDEBUG",
+                "-DPy_LIMITED_API=0x030C0000"
+            ]
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'Py_DEBUG': 1, 'ABIFLAGS': 'd'}",
+        )
+        .unwrap();
+        let err = config
+            .limited_api_cflags(PythonVersion::new(3, 9))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported: debug builds do not support the stable ABI"
+        );
+        let config = fixtures::get("cpython313t-linux").unwrap().config();
+        assert!(config.limited_api_cflags(PythonVersion::new(3, 9)).is_err());
+    }
+}
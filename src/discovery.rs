@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cache::{is_build_details, load_file};
+use crate::{AbiVariant, Error, Interner, ParseOptions, PythonConfig};
+
+/// Like [`discover_all`] but groups the configurations by [`AbiVariant`]
+pub fn discover_variants(
+    root: impl AsRef<Path>,
+) -> io::Result<BTreeMap<AbiVariant, Vec<(PathBuf, PythonConfig)>>> {
+    let mut variants: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (path, config) in discover_all(root)? {
+        variants
+            .entry(config.abi_variant())
+            .or_default()
+            .push((path, config));
+    }
+    Ok(variants)
+}
+
+/// Recursively finds and parses every `_sysconfigdata*.py` file under `root`.
+/// With the `serde` feature a PEP 739 `build-details.json` is read instead
+/// of the sysconfigdata in the same directory.
+///
+/// All configurations share one [`Interner`], files that fail to parse are skipped.
+pub fn discover_all(root: impl AsRef<Path>) -> io::Result<Vec<(PathBuf, PythonConfig)>> {
+    Ok(scan(root)?
+        .into_iter()
+        .filter_map(|(path, config)| Some((path, config.ok()?)))
+        .collect())
+}
+
+/// Like [`discover_all`] but returns the outcome of every file found,
+/// including the ones that can't be read or parsed.
+///
+/// Fails only if the directory tree itself can't be walked.
+pub fn scan(root: impl AsRef<Path>) -> io::Result<Vec<(PathBuf, Result<PythonConfig, Error>)>> {
+    discover_all_stream(root).collect()
+}
+
+/// Like [`scan`] but returns an iterator walking the tree lazily, for tools
+/// showing progress while scanning large file systems.
+///
+/// Each call to `next` reads directories only until the next file is found
+/// and parsed, so dropping the iterator cancels the scan. Files come in the
+/// order of [`scan`], a directory that can't be read yields an error and
+/// the walk goes on with the next one.
+pub fn discover_all_stream(root: impl AsRef<Path>) -> DiscoverStream {
+    DiscoverStream {
+        root: Some(root.as_ref().to_path_buf()),
+        stack: Vec::new(),
+        options: ParseOptions::new().interner(Interner::new()),
+    }
+}
+
+/// Iterator returned by [`discover_all_stream`]
+#[derive(Debug)]
+pub struct DiscoverStream {
+    root: Option<PathBuf>,
+    /// Entries left to visit in each directory being walked, last first,
+    /// with whether they are directories
+    stack: Vec<Vec<(PathBuf, bool)>>,
+    options: ParseOptions,
+}
+
+impl DiscoverStream {
+    /// Queues the subdirectories and `_sysconfigdata*.py` files of `dir`,
+    /// or its `build-details.json` instead of the files when it has one
+    fn enter(&mut self, dir: &Path) -> io::Result<()> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                entries.push((path, true));
+            } else if is_sysconfigdata(&path) || is_build_details(&path) {
+                entries.push((path, false));
+            }
+        }
+        if entries.iter().any(|(path, _)| is_build_details(path)) {
+            entries.retain(|(path, is_dir)| *is_dir || is_build_details(path));
+        }
+        entries.sort();
+        entries.reverse();
+        self.stack.push(entries);
+        Ok(())
+    }
+}
+
+impl Iterator for DiscoverStream {
+    type Item = io::Result<(PathBuf, Result<PythonConfig, Error>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(root) = self.root.take() {
+            if let Err(err) = self.enter(&root) {
+                return Some(Err(err));
+            }
+        }
+        loop {
+            let (path, is_dir) = match self.stack.last_mut()?.pop() {
+                Some(entry) => entry,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            if !is_dir {
+                let config = load_file(&path, &self.options);
+                return Some(Ok((path, config)));
+            }
+            if let Err(err) = self.enter(&path) {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+fn is_sysconfigdata(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.contains("_sysconfigdata") && name.ends_with(".py"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{discover_all, discover_all_stream, discover_variants, scan};
+    use crate::AbiVariant;
+    use std::fs;
+
+    #[test]
+    fn discover_fixtures() {
+        let configs = discover_all("tests/fixtures").unwrap();
+        assert!(configs.len() >= 4);
+        assert!(configs
+            .iter()
+            .any(|(_, config)| config.soabi() == "cpython-311-x86_64-linux-gnu"));
+    }
+
+    #[test]
+    fn stream_matches_scan() {
+        let streamed: Vec<_> = discover_all_stream("tests/fixtures")
+            .map(|result| result.unwrap().0)
+            .collect();
+        let scanned: Vec<_> = scan("tests/fixtures")
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(streamed, scanned);
+        let mut sorted = streamed.clone();
+        sorted.sort();
+        assert_eq!(streamed, sorted);
+
+        let mut stream = discover_all_stream("tests/fixtures");
+        let (path, config) = stream.next().unwrap().unwrap();
+        assert_eq!(Some(&path), streamed.first());
+        assert!(config.is_ok());
+
+        let mut stream = discover_all_stream("tests/missing");
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn scan_reports_failures() {
+        let dir = std::env::temp_dir().join(format!("python3-config-scan-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("_sysconfigdata_ok.py"),
+            "build_time_vars = {'VERSION': '3.11'}",
+        )
+        .unwrap();
+        fs::write(dir.join("_sysconfigdata_broken.py"), "build_time_vars = {").unwrap();
+        let results = scan(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].0.ends_with("_sysconfigdata_broken.py"));
+        assert!(results[0].1.is_err());
+        assert!(results[1].1.is_ok());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn prefer_build_details() {
+        let dir = std::env::temp_dir().join(format!(
+            "python3-config-build-details-{}",
+            std::process::id()
+        ));
+        let stdlib = dir.join("lib").join("python3.14");
+        fs::create_dir_all(&stdlib).unwrap();
+        fs::write(
+            stdlib.join("_sysconfigdata__linux_x86_64-linux-gnu.py"),
+            "build_time_vars = {'VERSION': '3.14'}",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("_sysconfigdata__linux_x86_64-linux-gnu.py"),
+            "build_time_vars = {'VERSION': '3.13'}",
+        )
+        .unwrap();
+        fs::write(
+            stdlib.join("build-details.json"),
+            r#"{"schema_version": "1.0", "language": {"version": "3.14"},
+                "abi": {"flags": [], "extension_suffix": ".cpython-314-x86_64-linux-gnu.so"}}"#,
+        )
+        .unwrap();
+        let configs = discover_all(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let names: Vec<_> = configs
+            .iter()
+            .map(|(path, _)| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "_sysconfigdata__linux_x86_64-linux-gnu.py",
+                "build-details.json"
+            ]
+        );
+        assert_eq!(configs[1].1.soabi(), "cpython-314-x86_64-linux-gnu");
+    }
+
+    #[test]
+    fn variants() {
+        let variants = discover_variants("tests/fixtures").unwrap();
+        let free_threaded = &variants[&AbiVariant::FreeThreaded];
+        assert_eq!(free_threaded.len(), 1);
+        assert_eq!(free_threaded[0].1.abiflags(), "t");
+        assert!(variants[&AbiVariant::Default].len() >= 4);
+        assert!(!variants.contains_key(&AbiVariant::Debug));
+    }
+}
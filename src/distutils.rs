@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use crate::{ConfigValue, PythonConfig};
+
+impl PythonConfig {
+    /// Returns the variables `distutils.sysconfig.get_config_vars()` exposes,
+    /// for feeding frozen values into legacy `setup.py` builds, for example
+    /// under crossenv.
+    ///
+    /// These are `build_time_vars` plus the keys `sysconfig` computes at
+    /// runtime: `prefix`, `exec_prefix`, `base`, `platbase`, `installed_base`,
+    /// `installed_platbase`, `projectbase`, `platlibdir`, `abiflags`,
+    /// `py_version_short`, `py_version_nodot` and the deprecated `SO` alias of
+    /// `EXT_SUFFIX`. `py_version` and `userbase` depend on the running
+    /// interpreter and are left out.
+    pub fn to_distutils_vars(&self) -> BTreeMap<String, ConfigValue> {
+        let mut vars: BTreeMap<String, ConfigValue> = self
+            .vars()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect();
+        let exec_prefix = if self.exec_prefix_str().is_empty() {
+            self.prefix_str()
+        } else {
+            self.exec_prefix_str()
+        };
+        let computed = [
+            ("prefix", self.prefix_str().to_string()),
+            ("exec_prefix", exec_prefix.to_string()),
+            ("base", self.prefix_str().to_string()),
+            ("installed_base", self.prefix_str().to_string()),
+            ("platbase", exec_prefix.to_string()),
+            ("installed_platbase", exec_prefix.to_string()),
+            ("projectbase", self.bindir_str().to_string()),
+            ("platlibdir", self.platlibdir().to_string()),
+            ("abiflags", self.abiflags().to_string()),
+            ("py_version_short", self.version_str().to_string()),
+            (
+                "py_version_nodot",
+                format!("{}{}", self.major_minor().0, self.major_minor().1),
+            ),
+            ("SO", self.extension_suffix()),
+        ];
+        for (key, value) in computed {
+            vars.insert(key.to_string(), ConfigValue::String(value.into()));
+        }
+        if self.sys_platform() == "win32" {
+            vars.insert(
+                "py_version_nodot_plat".to_string(),
+                ConfigValue::String(self.version_str().replace('.', "").into()),
+            );
+        }
+        vars
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, ConfigValue};
+
+    #[test]
+    fn to_distutils_vars() {
+        let vars = fixtures::get("cpython311-linux")
+            .unwrap()
+            .config()
+            .to_distutils_vars();
+        let get = |key: &str| vars[key].as_str().unwrap();
+        assert_eq!(get("SO"), ".cpython-311-x86_64-linux-gnu.so");
+        assert_eq!(get("SO"), get("EXT_SUFFIX"));
+        assert_eq!(get("abiflags"), "");
+        assert_eq!(get("py_version_short"), "3.11");
+        assert_eq!(get("py_version_nodot"), "311");
+        assert_eq!(get("platbase"), "/usr/local");
+        assert_eq!(get("projectbase"), "/usr/local/bin");
+        assert_eq!(get("platlibdir"), "lib");
+        assert_eq!(vars["Py_ENABLE_SHARED"], ConfigValue::Int(1));
+        assert!(!vars.contains_key("py_version_nodot_plat"));
+
+        let vars = fixtures::get("cpython313t-linux")
+            .unwrap()
+            .config()
+            .to_distutils_vars();
+        assert_eq!(vars["abiflags"].as_str(), Some("t"));
+    }
+}
@@ -0,0 +1,154 @@
+use std::path::{Path, PathBuf};
+
+use crate::paths::{normalize_windows_path, windows_join};
+use crate::PythonConfig;
+
+/// Startup settings for an application embedding this distribution, see
+/// [`PythonConfig::embedded_init_hints`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedInitHints {
+    /// Value for `PyConfig.home`, `prefix` or `prefix:exec_prefix` when they
+    /// differ, like `PYTHONHOME`
+    pub home: String,
+    /// Value for `PyConfig.platlibdir`
+    pub platlibdir: String,
+    /// Value for `PyConfig.module_search_paths`, in the order `getpath`
+    /// computes them
+    pub module_search_paths: Vec<PathBuf>,
+    /// File whose presence marks the standard library, `os.py`
+    pub stdlib_landmark: PathBuf,
+    /// Directory whose presence marks the platform specific standard
+    /// library, `lib-dynload` or `DLLs` on Windows
+    pub platstdlib_landmark: PathBuf,
+}
+
+impl PythonConfig {
+    /// Returns the default `PYTHONPATH` of the build, `PYTHONPATH` in the
+    /// `Makefile`, entries relative to the standard library directory
+    pub fn default_pythonpath(&self) -> Vec<&str> {
+        let separator = if self.sys_platform() == "win32" {
+            ';'
+        } else {
+            ':'
+        };
+        self.sys_config_data
+            .build_time_vars
+            .get_str("PYTHONPATH")
+            .split(separator)
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+
+    /// Returns what `PyConfig.home`, `module_search_paths` and `platlibdir`
+    /// should be set to for an embedded interpreter to find the standard
+    /// library of this distribution at its installed location, as `getpath`
+    /// would compute them.
+    pub fn embedded_init_hints(&self) -> EmbeddedInitHints {
+        let prefix = Path::new(self.prefix_str());
+        let zip_name = format!(
+            "python{}{}{}.zip",
+            self.major_minor().0,
+            self.major_minor().1,
+            if self.abiflags().contains('t') {
+                "t"
+            } else {
+                ""
+            }
+        );
+        if self.sys_platform() == "win32" {
+            let dlls = windows_join(self.prefix_str(), &["DLLs"]);
+            return EmbeddedInitHints {
+                home: normalize_windows_path(self.prefix_str()),
+                platlibdir: "DLLs".to_string(),
+                module_search_paths: vec![
+                    windows_join(self.prefix_str(), &[&zip_name]),
+                    dlls.clone(),
+                    windows_join(self.prefix_str(), &["Lib"]),
+                ],
+                stdlib_landmark: windows_join(self.prefix_str(), &["Lib", "os.py"]),
+                platstdlib_landmark: dlls,
+            };
+        }
+        let home =
+            if self.exec_prefix_str().is_empty() || self.exec_prefix_str() == self.prefix_str() {
+                self.prefix_str().to_string()
+            } else {
+                format!("{}:{}", self.prefix_str(), self.exec_prefix_str())
+            };
+        let scheme = self.install_scheme();
+        let lib_dynload = self.extension_install_dir();
+        let mut module_search_paths = vec![prefix.join(self.platlibdir()).join(zip_name)];
+        module_search_paths.extend(
+            self.default_pythonpath()
+                .into_iter()
+                .map(|entry| scheme.stdlib.join(entry)),
+        );
+        module_search_paths.push(scheme.stdlib.clone());
+        module_search_paths.push(lib_dynload.clone());
+        module_search_paths.dedup();
+        EmbeddedInitHints {
+            home,
+            platlibdir: self.platlibdir().to_string(),
+            module_search_paths,
+            stdlib_landmark: scheme.stdlib.join("os.py"),
+            platstdlib_landmark: lib_dynload,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, PythonConfig};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn embedded_init_hints() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let hints = config.embedded_init_hints();
+        assert_eq!(hints.home, "/usr/local");
+        assert_eq!(hints.platlibdir, "lib");
+        assert_eq!(
+            hints.module_search_paths,
+            vec![
+                PathBuf::from("/usr/local/lib/python311.zip"),
+                PathBuf::from("/usr/local/lib/python3.11"),
+                PathBuf::from("/usr/local/lib/python3.11/lib-dynload"),
+            ]
+        );
+        assert_eq!(
+            hints.stdlib_landmark,
+            Path::new("/usr/local/lib/python3.11/os.py")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': '/usr', 'exec_prefix': '/usr/x86_64', \
+             'PLATLIBDIR': 'lib64', 'PYTHONPATH': ':plat-linux'}",
+        )
+        .unwrap();
+        assert_eq!(config.default_pythonpath(), vec!["plat-linux"]);
+        let hints = config.embedded_init_hints();
+        assert_eq!(hints.home, "/usr:/usr/x86_64");
+        assert_eq!(hints.platlibdir, "lib64");
+        assert_eq!(
+            hints.module_search_paths,
+            vec![
+                PathBuf::from("/usr/lib64/python312.zip"),
+                PathBuf::from("/usr/lib/python3.12/plat-linux"),
+                PathBuf::from("/usr/lib/python3.12"),
+                PathBuf::from("/usr/x86_64/lib64/python3.12/lib-dynload"),
+            ]
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': 'C:\\\\Python312', 'EXT_SUFFIX': '.cp312-win_amd64.pyd'}",
+        )
+        .unwrap();
+        let hints = config.embedded_init_hints();
+        assert_eq!(hints.platlibdir, "DLLs");
+        assert_eq!(hints.module_search_paths.len(), 3);
+        assert_eq!(
+            hints.stdlib_landmark.to_str(),
+            Some("C:\\Python312\\Lib\\os.py")
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt::Write;
+
+use crate::{key_info, lite, parse_keys, ConfigValue, Error, PythonConfig};
+
+const HEADER: &str = "# system configuration generated and used by the sysconfig module\n";
+
+impl PythonConfig {
+    /// Serializes `build_time_vars` into a `_sysconfigdata.py` file, laid out
+    /// like the ones `sysconfig` generates with one sorted key per line
+    pub fn to_sysconfigdata(&self) -> String {
+        let mut out = String::from(HEADER);
+        out.push_str("build_time_vars = {");
+        for (i, (key, value)) in self.vars().enumerate() {
+            if i > 0 {
+                out.push_str(",\n ");
+            }
+            write_entry(&mut out, key, value);
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Like [`PythonConfig::to_sysconfigdata`] with one key per line, each
+    /// key known to [`key_info`] preceded by a comment describing it
+    pub fn to_sysconfigdata_annotated(&self) -> String {
+        let mut out = String::from(HEADER);
+        out.push_str("build_time_vars = {\n");
+        for (key, value) in self.vars() {
+            if let Some(info) = key_info(key) {
+                write!(out, " # {} ({}", info.description, info.kind).unwrap();
+                if let Some((major, minor)) = info.since {
+                    write!(out, ", since Python {}.{}", major, minor).unwrap();
+                }
+                out.push_str(")\n");
+            }
+            out.push(' ');
+            write_entry(&mut out, key, value);
+            out.push_str(",\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Like [`PythonConfig::to_sysconfigdata`] but edits `original`, the file
+    /// this configuration was read from, in place: values that changed are
+    /// replaced and new keys are appended to the end of `build_time_vars`,
+    /// keeping comments, ordering and formatting of everything else so the
+    /// result diffs cleanly against the vendor file.
+    ///
+    /// Keys of `original` missing from this configuration are kept.
+    pub fn to_sysconfigdata_preserving(&self, original: &str) -> Result<String, Error> {
+        let spans = lite::build_time_vars_spans(original)?.ok_or(Error::MissingBuildTimeVars)?;
+        let keys: Vec<&str> = self.vars().map(|(key, _)| key).collect();
+        let current = parse_keys(original, &keys)?;
+        let mut replacements = Vec::new();
+        let mut additions = String::new();
+        for (key, value) in self.vars() {
+            if current.get(key) == Some(value) {
+                continue;
+            }
+            match spans.values.iter().rev().find(|(k, _)| k == key) {
+                Some((_, span)) => replacements.push((span.clone(), python_repr(value))),
+                None => {
+                    if !additions.is_empty() || !spans.values.is_empty() {
+                        additions.push_str(",\n ");
+                    }
+                    write_entry(&mut additions, key, value);
+                }
+            }
+        }
+        if !additions.is_empty() {
+            let at = spans.values.last().map_or(spans.open, |(_, span)| span.end);
+            replacements.push((at..at, additions));
+        }
+        replacements.sort_by_key(|(span, _)| span.start);
+        let mut out = original.to_string();
+        for (span, text) in replacements.into_iter().rev() {
+            out.replace_range(span, &text);
+        }
+        Ok(out)
+    }
+}
+
+impl PythonConfig {
+    /// `Generated by python3-config for CPython 3.11 (linux-x86_64)`, the
+    /// header of generated build system files
+    pub(crate) fn generated_by(&self) -> String {
+        format!(
+            "Generated by python3-config for {} {} ({})",
+            self.implementation(),
+            self.version_str(),
+            self.get_platform()
+        )
+    }
+
+    /// [`PythonConfig::generated_by`] as a `comment` line followed by an
+    /// empty line
+    pub(crate) fn generated_comment(&self, comment: &str) -> String {
+        format!("{} {}\n\n", comment, self.generated_by())
+    }
+}
+
+fn write_entry(out: &mut String, key: &str, value: &ConfigValue) {
+    write!(out, "{}: {}", python_str(key), python_repr(value)).unwrap();
+}
+
+/// Python literal of `value`, the way `repr()` spells it
+fn python_repr(value: &ConfigValue) -> String {
+    match value {
+        ConfigValue::String(value) => python_str(value),
+        ConfigValue::Int(value) => value.to_string(),
+        ConfigValue::Float(text) => text.to_string(),
+        ConfigValue::None => "None".to_string(),
+    }
+}
+
+/// Python string literal of `value`, quoted like `repr()` does
+fn python_str(value: &str) -> String {
+    let quote = if value.contains('\'') && !value.contains('"') {
+        '"'
+    } else {
+        '\''
+    };
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if (c as u32) < 0x20 || c as u32 == 0x7f => {
+                write!(out, "\\x{:02x}", c as u32).unwrap();
+            }
+            c => out.push(c),
+        }
+    }
+    out.push(quote);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, ConfigValue, PythonConfig, Relocate, Transform};
+
+    #[test]
+    fn to_sysconfigdata() {
+        for fixture in fixtures::all() {
+            let config = fixture.config();
+            let emitted = PythonConfig::parse(&config.to_sysconfigdata()).unwrap();
+            assert!(
+                config.vars().eq(emitted.vars()),
+                "{} does not round-trip",
+                fixture.name
+            );
+        }
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'A': 'it\\'s', 'B': 'say \"hi\" \\'\\n'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.to_sysconfigdata(),
+            "# system configuration generated and used by the sysconfig module\n\
+             build_time_vars = {'A': \"it's\",\n 'B': 'say \"hi\" \\'\\n',\n 'VERSION': '3.11'}\n"
+        );
+    }
+
+    #[test]
+    fn to_sysconfigdata_annotated() {
+        let config = fixtures::get("cpython313t-linux").unwrap().config();
+        let annotated = config.to_sysconfigdata_annotated();
+        assert!(annotated.contains(
+            "\n # 1 for free-threaded builds (integer, since Python 3.13)\n 'Py_GIL_DISABLED': 1,\n"
+        ));
+        let parsed = PythonConfig::parse(&annotated).unwrap();
+        assert!(config.vars().eq(parsed.vars()));
+    }
+
+    #[test]
+    fn to_sysconfigdata_preserving() {
+        let original = "# vendor comment\n\
+                        build_time_vars = {'prefix': '/usr',  # install prefix\n\
+                        \x20                  'VERSION': '3.11',\n\
+                        \x20                  'LIBDIR': '/usr' '/lib',\n\
+                        \x20                  'CONFIG_ARGS': compute(),\n\
+                        \x20                  }\n";
+        let mut config = PythonConfig::parse(original).unwrap();
+        let transforms: Vec<Box<dyn Transform>> = vec![Box::new(Relocate::new("/usr", "/opt"))];
+        config = config.apply(&transforms).unwrap();
+        config.set("Py_ENABLE_SHARED", ConfigValue::Int(1)).unwrap();
+        let patched = config.to_sysconfigdata_preserving(original).unwrap();
+        assert_eq!(
+            patched,
+            "# vendor comment\n\
+             build_time_vars = {'prefix': '/opt',  # install prefix\n\
+             \x20                  'VERSION': '3.11',\n\
+             \x20                  'LIBDIR': '/opt/lib',\n\
+             \x20                  'CONFIG_ARGS': compute(),\n 'Py_ENABLE_SHARED': 1,\n\
+             \x20                  }\n"
+        );
+        let reparsed = PythonConfig::parse(&patched).unwrap();
+        assert!(config.vars().eq(reparsed.vars()));
+
+        let unchanged = PythonConfig::parse(original).unwrap();
+        assert_eq!(
+            unchanged.to_sysconfigdata_preserving(original).unwrap(),
+            original
+        );
+    }
+}
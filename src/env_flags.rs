@@ -0,0 +1,118 @@
+use std::env;
+
+use crate::{ConfigValue, PythonConfig, Source};
+
+impl PythonConfig {
+    /// Returns the compiler and linker commands of this distribution
+    /// customized by the current environment, the way
+    /// `distutils.sysconfig.customize_compiler` does it for extension builds.
+    ///
+    /// `CC`, `CXX` and `LDSHARED` replace the configured commands, `CFLAGS`
+    /// and `CPPFLAGS` are appended to `CFLAGS` and `LDSHARED`, and `LDFLAGS`
+    /// is appended to `LDSHARED`.
+    pub fn with_env_flags(&self) -> Self {
+        self.with_env_flags_from(|name| env::var(name).ok())
+    }
+
+    /// Like [`PythonConfig::with_env_flags`] but reads variables through `lookup`
+    pub fn with_env_flags_from<F>(&self, lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut cc = self.cc().to_string();
+        let mut cxx = self.cxx().to_string();
+        let mut ldshared = self.ldshared().to_string();
+        let mut cflags = self.cflags().to_string();
+        let env_ldshared = lookup("LDSHARED");
+        if let Some(env_cc) = lookup("CC") {
+            if env_ldshared.is_none() && ldshared.starts_with(&cc) {
+                ldshared = format!("{}{}", env_cc, &ldshared[cc.len()..]);
+            }
+            cc = env_cc;
+        }
+        if let Some(env_cxx) = lookup("CXX") {
+            cxx = env_cxx;
+        }
+        if let Some(env_ldshared) = env_ldshared {
+            ldshared = env_ldshared;
+        }
+        if let Some(ldflags) = lookup("LDFLAGS") {
+            append_flags(&mut ldshared, &ldflags);
+        }
+        if let Some(env_cflags) = lookup("CFLAGS") {
+            append_flags(&mut cflags, &env_cflags);
+            append_flags(&mut ldshared, &env_cflags);
+        }
+        if let Some(cppflags) = lookup("CPPFLAGS") {
+            append_flags(&mut cflags, &cppflags);
+            append_flags(&mut ldshared, &cppflags);
+        }
+        let mut config = self.clone();
+        config.with_source(Source::Environment, |config| {
+            for (key, original, value) in [
+                ("CC", self.cc(), cc),
+                ("CXX", self.cxx(), cxx),
+                ("LDSHARED", self.ldshared(), ldshared),
+                ("CFLAGS", self.cflags(), cflags),
+            ] {
+                if original == value {
+                    continue;
+                }
+                config
+                    .set(key, ConfigValue::String(value.into()))
+                    .expect("string values are valid for compiler keys");
+            }
+        });
+        config
+    }
+}
+
+fn append_flags(flags: &mut String, extra: &str) {
+    flags.push(' ');
+    flags.push_str(extra);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PythonConfig;
+    use std::fs;
+
+    #[test]
+    fn with_env_flags() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        let config = config.with_env_flags_from(|name| match name {
+            "CC" => Some("clang".to_string()),
+            "CFLAGS" => Some("-O0".to_string()),
+            "LDFLAGS" => Some("-L/opt/lib".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.cc(), "clang");
+        assert_eq!(
+            config.cflags(),
+            "-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -O0"
+        );
+        assert_eq!(
+            config.ldshared(),
+            "x86_64-linux-gnu-gcc -shared -Wl,-O1 -Wl,-Bsymbolic-functions -L/opt/lib -O0"
+        );
+    }
+
+    #[test]
+    fn with_env_flags_invalidates_derived_values() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'MACHDEP': 'darwin', \
+             'MACOSX_DEPLOYMENT_TARGET': '11.0', 'CFLAGS': '-arch arm64'}",
+        )
+        .unwrap();
+        assert_eq!(config.get_platform(), "macosx-11.0-arm64");
+        let config = config.with_env_flags_from(|name| match name {
+            "CFLAGS" => Some("-arch x86_64".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.get_platform(), "macosx-11.0-universal2");
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+
+/// What was being done when an [`Error`] happened, see [`Error::phase`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Reading the file from disk
+    Read,
+    /// Parsing the file and storing its `build_time_vars`
+    Parse,
+    /// Running the registered
+    /// [`ConfigPostProcessor`](crate::ConfigPostProcessor)s
+    PostProcess,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Phase::Read => "reading",
+            Phase::Parse => "parsing",
+            Phase::PostProcess => "post-processing",
+        })
+    }
+}
+
+impl Error {
+    /// Records that the error happened while processing the file at `path`,
+    /// unless a path was recorded already
+    pub fn with_path(self, path: impl Into<PathBuf>) -> Self {
+        self.with_context(|context_path, _, _| {
+            context_path.get_or_insert_with(|| path.into());
+        })
+    }
+
+    /// Records what was being done, unless a phase was recorded already
+    pub fn with_phase(self, phase: Phase) -> Self {
+        self.with_context(|_, context_phase, _| {
+            context_phase.get_or_insert(phase);
+        })
+    }
+
+    /// Records the `build_time_vars` key being processed, unless the error
+    /// names a key already
+    pub fn with_key(self, key: &str) -> Self {
+        if self.key().is_some() {
+            return self;
+        }
+        self.with_context(|_, _, context_key| *context_key = Some(key.to_string()))
+    }
+
+    /// Wraps the error in [`Error::Context`] unless it is one, then lets
+    /// `update` fill in its fields
+    fn with_context(
+        self,
+        update: impl FnOnce(&mut Option<PathBuf>, &mut Option<Phase>, &mut Option<String>),
+    ) -> Self {
+        let mut err = match self {
+            err @ Error::Context { .. } => err,
+            err => Error::Context {
+                path: None,
+                phase: None,
+                key: None,
+                error: Box::new(err),
+            },
+        };
+        if let Error::Context {
+            path, phase, key, ..
+        } = &mut err
+        {
+            update(path, phase, key);
+        }
+        err
+    }
+
+    /// The error without the context added by [`Error::with_path`],
+    /// [`Error::with_phase`] and [`Error::with_key`], to match on its kind
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::Context { error, .. } => error.root_cause(),
+            error => error,
+        }
+    }
+
+    /// Path of the file being processed, if known
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::Context {
+                path: Some(path), ..
+            } => Some(path),
+            Error::Context { error, .. } => error.path(),
+            Error::HashMismatch { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// What was being done, if known
+    pub fn phase(&self) -> Option<Phase> {
+        match self {
+            Error::Context {
+                phase: Some(phase), ..
+            } => Some(*phase),
+            Error::Context { error, .. } => error.phase(),
+            _ => None,
+        }
+    }
+
+    /// The `build_time_vars` key at fault or being processed, if any
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Error::Context { key: Some(key), .. } => Some(key),
+            Error::Context { error, .. } => error.key(),
+            Error::KeyError(key) => Some(key),
+            Error::InvalidValue { key, .. } => Some(key),
+            Error::UnknownKey { key, .. } => Some(key),
+            Error::Warning(warning) => Some(&warning.key),
+            _ => None,
+        }
+    }
+
+    /// 1-based line of a syntax error in the file
+    pub fn line(&self) -> Option<usize> {
+        match self.root_cause() {
+            #[cfg(feature = "rustpython")]
+            Error::SyntaxError(err) => Some(err.location.row()),
+            Error::InvalidSyntax { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// 1-based column of a syntax error in the file, when the parser
+    /// reports one
+    pub fn column(&self) -> Option<usize> {
+        match self.root_cause() {
+            #[cfg(feature = "rustpython")]
+            Error::SyntaxError(err) => Some(err.location.column()),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the file at `path` and parses it with `parse`, recording the path
+/// and the phase in errors
+pub(crate) fn load_path<T>(
+    path: &Path,
+    parse: impl FnOnce(&str) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let src = fs::read_to_string(path)
+        .map_err(|err| Error::from(err).with_phase(Phase::Read).with_path(path))?;
+    parse(&src).map_err(|err| err.with_phase(Phase::Parse).with_path(path))
+}
+
+/// Stable codes like `python3_config::unknown_key` and help for errors with
+/// an obvious fix, for rendering with `miette`
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let code = match self.root_cause() {
+            #[cfg(feature = "rustpython")]
+            Error::SyntaxError(_) => "syntax_error",
+            Error::MissingBuildTimeVars => "missing_build_time_vars",
+            Error::KeyError(_) => "key_error",
+            Error::InvalidSyntax { .. } => "invalid_syntax",
+            Error::MissingKeys(_) => "missing_keys",
+            Error::UnknownTarget(_) => "unknown_target",
+            Error::UnknownDecision(_) => "unknown_decision",
+            Error::UnknownVariant(_) => "unknown_variant",
+            Error::UnsupportedPython(_) => "unsupported_python",
+            Error::InvalidVersion(_) => "invalid_version",
+            Error::LimitExceeded { .. } => "limit_exceeded",
+            Error::InvalidValue { .. } => "invalid_value",
+            Error::UnknownKey { .. } => "unknown_key",
+            Error::TypeMismatch { .. } => "type_mismatch",
+            Error::Warning(_) => "warning",
+            Error::Unsupported(_) => "unsupported",
+            Error::NoSource(_) => "no_source",
+            Error::Io(_) => "io",
+            Error::Download(_) => "download",
+            Error::InvalidImage(_) => "invalid_image",
+            Error::InvalidManifest(_) => "invalid_manifest",
+            Error::InvalidBuildDetails(_) => "invalid_build_details",
+            Error::HashMismatch { .. } => "hash_mismatch",
+            Error::TargetMismatch { .. } => "target_mismatch",
+            Error::Context { .. } => unreachable!("root_cause has no context"),
+        };
+        Some(Box::new(format!("python3_config::{}", code)))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let help = match self.root_cause() {
+            Error::LimitExceeded { .. } => "raise the limit with ParseOptions::limits",
+            Error::Warning(_) => "allow the warning with ParseOptions::warning",
+            Error::UnsupportedPython(_) => "only Python 3 sysconfigdata can be read",
+            _ => return None,
+        };
+        Some(Box::new(help))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn miette::Diagnostic> + 'a>> {
+        match self.root_cause() {
+            Error::NoSource(errors) => Some(Box::new(
+                errors.iter().map(|(_, err)| err as &dyn miette::Diagnostic),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Phase;
+    use crate::{ConfigCache, Error, PythonConfig};
+    use std::path::Path;
+
+    #[test]
+    fn error_context() {
+        let err = Error::InvalidSyntax {
+            line: 3,
+            message: "unexpected token".to_string(),
+        }
+        .with_phase(Phase::Parse)
+        .with_key("CC")
+        .with_path("/sysroot/_sysconfigdata.py")
+        .with_path("/elsewhere.py");
+        assert_eq!(err.path(), Some(Path::new("/sysroot/_sysconfigdata.py")));
+        assert_eq!(err.phase(), Some(Phase::Parse));
+        assert_eq!(err.key(), Some("CC"));
+        assert_eq!(err.line(), Some(3));
+        assert!(matches!(err.root_cause(), Error::InvalidSyntax { .. }));
+        assert_eq!(
+            err.to_string(),
+            "/sysroot/_sysconfigdata.py: CC: line 3: unexpected token"
+        );
+
+        // keys named by the error aren't repeated
+        let err = Error::KeyError("VERSION").with_key("VERSION");
+        assert!(matches!(err, Error::KeyError("VERSION")));
+
+        let config = PythonConfig::parse("build_time_vars = {'VERSION': '3.12'}").unwrap();
+        let err = config.get_as::<i64>("VERSION").unwrap_err();
+        assert_eq!(err.key(), Some("VERSION"));
+        assert!(matches!(err.root_cause(), Error::TypeMismatch { .. }));
+        assert_eq!(
+            err.to_string(),
+            "VERSION: expected integer value, found string"
+        );
+
+        let dir = std::env::temp_dir().join(format!(
+            "python3-config-error-context-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("_sysconfigdata_broken.py");
+        std::fs::write(&path, "build_time_vars = {'VERSION': '3.12',\n 'CC' 'cc'}").unwrap();
+        let err = ConfigCache::new().get(&path).unwrap_err();
+        assert_eq!(err.path(), Some(path.as_path()));
+        assert_eq!(err.phase(), Some(Phase::Parse));
+        assert_eq!(err.line(), Some(2));
+        assert!(err
+            .to_string()
+            .starts_with(&format!("{}: ", path.display())));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let err = ConfigCache::new().get(&path).unwrap_err();
+        assert_eq!(err.phase(), Some(Phase::Read));
+        assert!(matches!(err.root_cause(), Error::Io(_)));
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn miette_diagnostic() {
+        use miette::Diagnostic;
+
+        let err = Error::LimitExceeded {
+            limit: "entries",
+            max: 10,
+        }
+        .with_path("x.py");
+        assert_eq!(
+            err.code().unwrap().to_string(),
+            "python3_config::limit_exceeded"
+        );
+        assert!(err.help().is_some());
+    }
+}
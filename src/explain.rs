@@ -0,0 +1,225 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ConfigValue, Error, PythonConfig, PythonImplementation, Source};
+
+/// A derived value that [`PythonConfig::explain`] can explain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Decision {
+    /// [`PythonConfig::link_model`]
+    LinkModel,
+    /// [`PythonConfig::implementation`]
+    Implementation,
+    /// [`PythonConfig::abi_tag`]
+    AbiTag,
+    /// [`PythonConfig::get_platform`]
+    Platform,
+}
+
+impl Decision {
+    /// Name used on the command line, for example `link-model`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Decision::LinkModel => "link-model",
+            Decision::Implementation => "implementation",
+            Decision::AbiTag => "abi-tag",
+            Decision::Platform => "platform",
+        }
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Decision {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "link-model" => Ok(Decision::LinkModel),
+            "implementation" => Ok(Decision::Implementation),
+            "abi-tag" => Ok(Decision::AbiTag),
+            "platform" => Ok(Decision::Platform),
+            _ => Err(Error::UnknownDecision(s.to_string())),
+        }
+    }
+}
+
+/// Why a derived value is what it is
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Explanation {
+    /// The explained decision
+    pub decision: Decision,
+    /// The derived value
+    pub answer: String,
+    /// The rule that produced the answer
+    pub reason: String,
+    /// Variables consulted
+    pub inputs: Vec<Input>,
+}
+
+/// A variable consulted by an [`Explanation`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Input {
+    /// Key in `build_time_vars`
+    pub key: String,
+    /// Value, `None` for keys missing from `build_time_vars`
+    pub value: Option<ConfigValue>,
+    /// Where the value came from, see [`PythonConfig::provenance`]
+    pub source: Option<Source>,
+}
+
+impl Explanation {
+    /// Serialize to a JSON string
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} because {}", self.answer, self.reason)
+    }
+}
+
+impl PythonConfig {
+    /// Explains how `decision` was derived from `build_time_vars`
+    pub fn explain(&self, decision: Decision) -> Explanation {
+        let vars = &self.sys_config_data.build_time_vars;
+        let (answer, reason, keys): (String, String, &[&str]) = match decision {
+            Decision::LinkModel => {
+                let (model, reason) = self.link_model_with_reason();
+                (
+                    model.to_string(),
+                    reason,
+                    &["PYTHONFRAMEWORK", "Py_ENABLE_SHARED", "LDLIBRARY"],
+                )
+            }
+            Decision::Implementation => {
+                let implementation = self.implementation();
+                let source = if vars.soabi.is_empty() {
+                    "the EXT_SUFFIX tag"
+                } else {
+                    "SOABI"
+                };
+                let reason = match implementation {
+                    PythonImplementation::CPython => format!(
+                        "{} {} has no pypy or graalpy prefix",
+                        source,
+                        vars.soabi_or_ext_suffix()
+                    ),
+                    _ => format!("{} is {}", source, vars.soabi_or_ext_suffix()),
+                };
+                (implementation.to_string(), reason, &["SOABI", "EXT_SUFFIX"])
+            }
+            Decision::AbiTag => match self.implementation() {
+                PythonImplementation::CPython => (
+                    self.abi_tag().to_string(),
+                    format!(
+                        "CPython tags are cp followed by VERSION={} without the dot and ABIFLAGS={:?}",
+                        vars.version, vars.abiflags
+                    ),
+                    &["VERSION", "ABIFLAGS"],
+                ),
+                _ => (
+                    self.abi_tag().to_string(),
+                    format!(
+                        "{} tags are the ABI part {} of SOABI={} normalized",
+                        self.implementation(),
+                        vars.soabi_abi_parts(),
+                        vars.soabi_or_ext_suffix()
+                    ),
+                    &["SOABI", "EXT_SUFFIX"],
+                ),
+            },
+            Decision::Platform => {
+                let platform = self.get_platform().to_string();
+                let reason = if platform.starts_with("macosx-") {
+                    format!(
+                        "sys.platform is darwin, MACOSX_DEPLOYMENT_TARGET={} and the architecture \
+                         comes from the -arch flags in CFLAGS or HOST_GNU_TYPE",
+                        vars.macosx_deployment_target
+                    )
+                } else if platform.starts_with("win") || platform.starts_with("mingw") {
+                    format!("the platform part of the extension tag is {}", vars.platform_chunk())
+                } else {
+                    format!(
+                        "sys.platform is {} and the machine comes from HOST_GNU_TYPE, \
+                         MULTIARCH or SOABI",
+                        self.sys_platform()
+                    )
+                };
+                (
+                    platform,
+                    reason,
+                    &[
+                        "MACHDEP",
+                        "HOST_GNU_TYPE",
+                        "MULTIARCH",
+                        "SOABI",
+                        "EXT_SUFFIX",
+                        "MACOSX_DEPLOYMENT_TARGET",
+                        "CFLAGS",
+                    ],
+                )
+            }
+        };
+        Explanation {
+            decision,
+            answer,
+            reason,
+            inputs: keys
+                .iter()
+                .map(|key| Input {
+                    key: key.to_string(),
+                    value: vars.raw.get(*key).cloned(),
+                    source: self.provenance(key),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decision, Input};
+    use crate::{ConfigValue, PythonConfig, Source};
+    use std::fs;
+
+    #[test]
+    fn explain_link_model() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        let explanation = config.explain("link-model".parse().unwrap());
+        assert_eq!(explanation.decision, Decision::LinkModel);
+        assert_eq!(
+            explanation.to_string(),
+            "Shared because Py_ENABLE_SHARED=1 and LDLIBRARY ends with .so"
+        );
+        assert_eq!(
+            explanation.inputs[1],
+            Input {
+                key: "Py_ENABLE_SHARED".to_string(),
+                value: Some(ConfigValue::Int(1)),
+                source: Some(Source::SysConfigData),
+            }
+        );
+        assert_eq!(explanation.inputs[0].key, "PYTHONFRAMEWORK");
+        assert_eq!(explanation.inputs[0].value, None);
+
+        let explanation = config.explain(Decision::AbiTag);
+        assert_eq!(explanation.answer, "cp311");
+        assert!("linkmodel".parse::<Decision>().is_err());
+    }
+}
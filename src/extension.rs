@@ -0,0 +1,263 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, PythonConfig};
+
+/// Renames the compiled library at `path`, for example `libfoo.so`,
+/// `libfoo.dylib` or `foo.dll`, to the file name `for_config` imports the
+/// extension module from, see [`PythonConfig::extension_path`].
+///
+/// Returns the new path.
+pub fn rename_extension(
+    path: impl AsRef<Path>,
+    for_config: &PythonConfig,
+) -> Result<PathBuf, Error> {
+    let path = path.as_ref();
+    let target = for_config.extension_path(path)?;
+    fs::rename(path, &target)?;
+    Ok(target)
+}
+
+/// Components of an extension module suffix, see [`PythonConfig::ext_suffix_parts`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtSuffixParts {
+    /// Marker of Windows debug builds before the tag, `_d`
+    pub debug_marker: String,
+    /// ABI part of the tag, `cpython-37m`, `cp313t` or `pypy310-pp73`,
+    /// empty for a bare shared library suffix
+    pub abi: String,
+    /// ABI flags spelled in the tag, `m` for pymalloc builds before
+    /// Python 3.8, `d` or `t`
+    pub abiflags: String,
+    /// Platform part of the tag, `x86_64-linux-gnu` or `win_amd64`
+    pub platform: String,
+    /// File extension, `.so` or `.pyd`
+    pub extension: String,
+}
+
+impl PythonConfig {
+    /// Splits the extension module suffix, for example
+    /// `.cpython-37m-x86_64-linux-gnu.so` into `cpython-37m`, `m`,
+    /// `x86_64-linux-gnu` and `.so`
+    pub fn ext_suffix_parts(&self) -> ExtSuffixParts {
+        let suffix = self.extension_suffix();
+        let (debug_marker, rest) = match suffix.find('.') {
+            Some(index) => suffix.split_at(index),
+            None => ("", suffix.as_str()),
+        };
+        let (tag, extension) = match rest.get(1..).and_then(|x| x.rsplit_once('.')) {
+            Some((tag, extension)) => (tag, format!(".{}", extension)),
+            None => ("", rest.to_string()),
+        };
+        let abi_parts = if tag.starts_with("cpython") || tag.starts_with("pypy") {
+            2
+        } else if tag.starts_with("graalpy") {
+            3
+        } else {
+            1
+        };
+        let mut parts = tag.splitn(abi_parts + 1, '-');
+        let abi: Vec<&str> = parts.by_ref().take(abi_parts).collect();
+        let platform = parts.next().unwrap_or_default();
+        let abiflags = match tag.split('-').next().unwrap_or_default() {
+            "cpython" => abi.get(1).copied().unwrap_or_default(),
+            cp if cp.starts_with("cp") => &cp[2..],
+            _ => "",
+        };
+        ExtSuffixParts {
+            debug_marker: debug_marker.to_string(),
+            abi: abi.join("-"),
+            abiflags: abiflags
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .to_string(),
+            platform: platform.to_string(),
+            extension,
+        }
+    }
+
+    /// Returns the file name of extension module `module` for this
+    /// distribution, for example `foo.cpython-313t-x86_64-linux-gnu.so` or
+    /// `foo_d.cp311-win_amd64.pyd`
+    pub fn extension_filename(&self, module: &str) -> String {
+        format!("{}{}", module, self.extension_suffix())
+    }
+
+    /// Returns the path the compiled library at `path` has to be renamed to,
+    /// in the same directory, for this distribution to import it as an
+    /// extension module: `libfoo.so` becomes `foo.cpython-311-x86_64-linux-gnu.so`.
+    pub fn extension_path(&self, path: &Path) -> Result<PathBuf, Error> {
+        let file_name = path
+            .file_name()
+            .and_then(|x| x.to_str())
+            .unwrap_or_default();
+        let module = module_name(file_name).ok_or_else(|| {
+            Error::Unsupported(format!("{} is not a shared library", path.display()))
+        })?;
+        Ok(path.with_file_name(self.extension_filename(module)))
+    }
+
+    /// `EXT_SUFFIX`, or its value computed from `SOABI` and the ABI flags for
+    /// distributions predating it
+    pub(crate) fn extension_suffix(&self) -> String {
+        if !self.ext_suffix().is_empty() {
+            return self.ext_suffix().to_string();
+        }
+        if self.shlib_suffix() == ".pyd" || self.sys_platform() == "win32" {
+            let debug = if self.debug() { "_d" } else { "" };
+            if !self.version_rules().windows_ext_tag {
+                return format!("{}.pyd", debug);
+            }
+            let free_threaded = if self.abiflags().contains('t') {
+                "t"
+            } else {
+                ""
+            };
+            let platform = match self.sys_config_data.build_time_vars.platform_chunk() {
+                "" if self.pointer_size() == 4 => "win32",
+                "" => "win_amd64",
+                platform => platform,
+            };
+            return format!(
+                "{}.cp{}{}{}-{}.pyd",
+                debug,
+                self.major_minor().0,
+                self.major_minor().1,
+                free_threaded,
+                platform
+            );
+        }
+        let shlib_suffix = if self.shlib_suffix().is_empty() {
+            ".so"
+        } else {
+            self.shlib_suffix()
+        };
+        if self.soabi().is_empty() {
+            shlib_suffix.to_string()
+        } else {
+            format!(".{}{}", self.soabi(), shlib_suffix)
+        }
+    }
+}
+
+/// Module name of a library built by cargo, `None` if `file_name` isn't one
+fn module_name(file_name: &str) -> Option<&str> {
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    let module = match extension {
+        "so" | "dylib" => stem.strip_prefix("lib").unwrap_or(stem),
+        "dll" | "pyd" => stem,
+        _ => return None,
+    };
+    Some(module).filter(|x| !x.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rename_extension, ExtSuffixParts};
+    use crate::{fixtures, Error, PythonConfig};
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn extension_path() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython313t_sysconfigdata_t_linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(
+            config
+                .extension_path(Path::new("target/release/libfoo.so"))
+                .unwrap(),
+            Path::new("target/release/foo.cpython-313t-x86_64-linux-gnu.so")
+        );
+        assert!(matches!(
+            config.extension_path(Path::new("libfoo.rlib")),
+            Err(Error::Unsupported(_))
+        ));
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'SOABI': 'cpython-311d-darwin', \
+             'SHLIB_SUFFIX': '.so', 'Py_DEBUG': 1}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.extension_path(Path::new("libfoo.dylib")).unwrap(),
+            Path::new("foo.cpython-311d-darwin.so")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'ABIFLAGS': 'td', 'Py_DEBUG': 1, \
+             'SOABI': 'cp313t-win_amd64'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.extension_filename("foo"),
+            "foo_d.cp313t-win_amd64.pyd"
+        );
+    }
+
+    #[test]
+    fn rename() {
+        let dir = env::temp_dir().join(format!("python3-config-rename-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let lib = dir.join("libfoo.so");
+        fs::write(&lib, b"").unwrap();
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        let renamed = rename_extension(&lib, &config).unwrap();
+        assert_eq!(renamed, dir.join("foo.cpython-311-x86_64-linux-gnu.so"));
+        assert!(renamed.exists() && !lib.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn ext_suffix_parts() {
+        let config = fixtures::get("cpython37m-linux").unwrap().config();
+        assert_eq!(config.abiflags(), "m");
+        assert_eq!(config.abi_tag(), "cp37m");
+        assert_eq!(
+            config.ext_suffix_parts(),
+            ExtSuffixParts {
+                debug_marker: String::new(),
+                abi: "cpython-37m".to_string(),
+                abiflags: "m".to_string(),
+                platform: "x86_64-linux-gnu".to_string(),
+                extension: ".so".to_string(),
+            }
+        );
+
+        // sources predating ABIFLAGS, the flags come from SOABI
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.6', 'SOABI': 'cpython-36dm-darwin', \
+             'SHLIB_SUFFIX': '.so', 'Py_DEBUG': 1}",
+        )
+        .unwrap();
+        assert_eq!(config.abiflags(), "dm");
+        assert_eq!(config.abi_tag(), "cp36dm");
+        assert_eq!(config.ext_suffix_parts().abiflags, "dm");
+        assert_eq!(config.ext_suffix_parts().platform, "darwin");
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'ABIFLAGS': 'td', 'Py_DEBUG': 1, \
+             'SOABI': 'cp313t-win_amd64'}",
+        )
+        .unwrap();
+        let parts = config.ext_suffix_parts();
+        assert_eq!(parts.debug_marker, "_d");
+        assert_eq!(parts.abi, "cp313t");
+        assert_eq!(parts.abiflags, "t");
+        assert_eq!(parts.platform, "win_amd64");
+        assert_eq!(parts.extension, ".pyd");
+
+        let parts = fixtures::get("pypy310-linux")
+            .unwrap()
+            .config()
+            .ext_suffix_parts();
+        assert_eq!(parts.abi, "pypy310-pp73");
+        assert_eq!(parts.abiflags, "");
+    }
+}
@@ -0,0 +1,115 @@
+//! Sample `_sysconfigdata.py` files of several Python implementations,
+//! versions and platforms, for testing code built on this crate without
+//! access to real installations.
+
+use crate::PythonConfig;
+
+/// A bundled `_sysconfigdata.py` file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture {
+    /// Short name, for example `cpython311-linux`
+    pub name: &'static str,
+    /// File name in the `tests/fixtures` directory of this crate
+    pub file_name: &'static str,
+    /// File content
+    pub source: &'static str,
+}
+
+impl Fixture {
+    /// Parses the fixture
+    pub fn config(&self) -> PythonConfig {
+        PythonConfig::parse(self.source).expect("bundled fixtures are valid")
+    }
+}
+
+macro_rules! fixture {
+    ($name:literal, $file_name:literal) => {
+        Fixture {
+            name: $name,
+            file_name: $file_name,
+            source: include_str!(concat!("../tests/fixtures/", $file_name)),
+        }
+    };
+}
+
+const FIXTURES: &[Fixture] = &[
+    fixture!(
+        "cpython37m-linux",
+        "cpython37m_sysconfigdata_m_linux_x86_64-linux-gnu.py"
+    ),
+    fixture!(
+        "cpython38-darwin",
+        "cpython38_sysconfigdata__darwin_darwin.py"
+    ),
+    fixture!(
+        "cpython39-linux-aarch64",
+        "cpython39_sysconfigdata__linux_aarch64-linux-gnu.py"
+    ),
+    fixture!("cpython39-cygwin", "cpython39_sysconfigdata__cygwin_.py"),
+    fixture!(
+        "cpython310-conda",
+        "cpython310_sysconfigdata_x86_64_conda_linux_gnu.py"
+    ),
+    fixture!(
+        "cpython311-freebsd",
+        "cpython311_sysconfigdata__freebsd14_.py"
+    ),
+    fixture!("cpython311-mingw", "cpython311_sysconfigdata__win32_.py"),
+    fixture!(
+        "cpython311-linux",
+        "cpython311_sysconfigdata__linux_x86_64-linux-gnu.py"
+    ),
+    fixture!(
+        "cpython312-pyodide",
+        "cpython312_sysconfigdata__emscripten_wasm32-emscripten.py"
+    ),
+    fixture!(
+        "cpython313t-linux",
+        "cpython313t_sysconfigdata_t_linux_x86_64-linux-gnu.py"
+    ),
+    fixture!("pypy310-linux", "pypy310_pp73_sysconfigdata_linux.py"),
+    fixture!("graalpy240-linux", "graalpy240_310_sysconfigdata_linux.py"),
+];
+
+/// Returns every bundled fixture
+pub fn all() -> &'static [Fixture] {
+    FIXTURES
+}
+
+/// Returns the fixture named `name`, see [`Fixture::name`]
+pub fn get(name: &str) -> Option<&'static Fixture> {
+    FIXTURES.iter().find(|fixture| fixture.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::all;
+    use crate::conformance::render;
+    use std::env;
+    use std::fs;
+
+    /// Compares derived values against `tests/snapshots`, run with
+    /// `UPDATE_SNAPSHOTS=1` to accept changes
+    #[test]
+    fn snapshots() {
+        let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+        let mut mismatches = Vec::new();
+        for fixture in all() {
+            let actual = render(&fixture.config());
+            let path = format!("tests/snapshots/{}.snap", fixture.name);
+            if update {
+                fs::write(&path, &actual).unwrap();
+                continue;
+            }
+            let expected = fs::read_to_string(&path).unwrap_or_default();
+            if actual != expected {
+                mismatches.push(format!("{}:\n{}", path, actual));
+            }
+        }
+        assert!(
+            mismatches.is_empty(),
+            "snapshots differ, run with UPDATE_SNAPSHOTS=1 to accept:\n{}",
+            mismatches.join("\n")
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{BuildTimeVars, LinkMode, PythonConfig};
+
+impl PythonConfig {
+    /// Returns the `-I` flags for the Python headers, like
+    /// `python3-config --includes`
+    pub fn include_flags(&self) -> Vec<String> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let mut dirs = vec![vars.get_str("INCLUDEPY"), vars.get_str("CONFINCLUDEPY")];
+        dirs.retain(|dir| !dir.is_empty());
+        dirs.dedup();
+        dirs.iter().map(|dir| format!("-I{}", dir)).collect()
+    }
+
+    /// Returns `PY_CFLAGS`, the C flags of the interpreter build that
+    /// extension modules inherit
+    pub fn py_cflags(&self) -> &str {
+        self.sys_config_data.build_time_vars.get_str("PY_CFLAGS")
+    }
+
+    /// Returns `PY_CFLAGS_NODIST`, C flags only meant for building CPython
+    /// itself, such as `-flto` or `-I./Include/internal`
+    pub fn py_cflags_nodist(&self) -> &str {
+        self.sys_config_data
+            .build_time_vars
+            .get_str("PY_CFLAGS_NODIST")
+    }
+
+    /// Returns `PY_CPPFLAGS`, the preprocessor flags of the interpreter build
+    /// including its source tree include directories
+    pub fn py_cppflags(&self) -> &str {
+        self.sys_config_data.build_time_vars.get_str("PY_CPPFLAGS")
+    }
+
+    /// Returns `PY_LDFLAGS_NODIST`, linker flags only meant for building
+    /// CPython itself
+    pub fn py_ldflags_nodist(&self) -> &str {
+        self.sys_config_data
+            .build_time_vars
+            .get_str("PY_LDFLAGS_NODIST")
+    }
+
+    /// Returns `EXTRA_CFLAGS`, C flags some distributions add to every
+    /// build, referenced as `$(EXTRA_CFLAGS)` by the canonical flags
+    pub fn extra_cflags(&self) -> &str {
+        self.sys_config_data.build_time_vars.get_str("EXTRA_CFLAGS")
+    }
+
+    /// Returns `EXTRA_LDFLAGS`, the linker counterpart of
+    /// [`PythonConfig::extra_cflags`]
+    pub fn extra_ldflags(&self) -> &str {
+        self.sys_config_data
+            .build_time_vars
+            .get_str("EXTRA_LDFLAGS")
+    }
+
+    /// Returns the flags for compiling against Python, like
+    /// `python3-config --cflags`.
+    ///
+    /// Flags of `CFLAGS` that only appear in `PY_CFLAGS_NODIST` and not in
+    /// `PY_CFLAGS` are left out, some distributions leak them into `CFLAGS`
+    /// although they are not meant for extension modules. Flags of
+    /// [`PythonConfig::extra_cflags`] missing from `CFLAGS` are appended.
+    pub fn compile_flags(&self) -> Vec<String> {
+        let nodist: Vec<&str> = self.py_cflags_nodist().split_whitespace().collect();
+        let inherited: Vec<&str> = self.py_cflags().split_whitespace().collect();
+        let mut flags = self.include_flags();
+        flags.extend(
+            self.cflags()
+                .split_whitespace()
+                .filter(|flag| !nodist.contains(flag) || inherited.contains(flag))
+                .map(str::to_string),
+        );
+        append_missing(&mut flags, self.extra_cflags());
+        flags
+    }
+
+    /// Returns the flag making the linker pull in the threading library
+    /// libpython was built against: `-lpthread` when `LIBS` lists it,
+    /// `-pthread` when the compiler driver (`CC`, `LDSHARED`) or `CFLAGS`
+    /// use it, `None` when threads need no flag, as on macOS and Windows, or
+    /// the build has no thread support (`WITH_THREAD` is `0`)
+    pub fn thread_link_flag(&self) -> Option<&'static str> {
+        let vars = &self.sys_config_data.build_time_vars;
+        if vars.get_int("WITH_THREAD") == Some(0) {
+            return None;
+        }
+        let has_flag = |keys: &[&str], flag: &str| {
+            keys.iter()
+                .any(|key| vars.get_str(key).split_whitespace().any(|x| x == flag))
+        };
+        if has_flag(&["LIBS", "SYSLIBS"], "-lpthread") {
+            Some("-lpthread")
+        } else if has_flag(&["CC", "LDSHARED", "CFLAGS"], "-pthread") {
+            Some("-pthread")
+        } else {
+            None
+        }
+    }
+
+    /// Returns the libraries to link, like `python3-config --libs`. When
+    /// embedding libpython itself is included, as for `--embed`, before
+    /// Python 3.8 it always is. [`LinkMode::EmbedStatic`] names the static
+    /// library by its path.
+    ///
+    /// Unlike `python3-config`, [`PythonConfig::thread_link_flag`] is
+    /// included, exactly once.
+    pub fn lib_flags(&self, mode: LinkMode) -> Vec<String> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let mut flags = Vec::new();
+        let embed = mode.is_embed() || self.version_rules().extensions_link_libpython;
+        if embed && !self.version_str().is_empty() {
+            match self.static_library_path() {
+                Some(library) if mode == LinkMode::EmbedStatic => {
+                    flags.push(library.display().to_string())
+                }
+                _ => flags.push(format!("-l{}", self.python_lib_name())),
+            }
+        }
+        for libs in [self.libs(), vars.get_str("SYSLIBS")].iter() {
+            flags.extend(libs.split_whitespace().map(str::to_string));
+        }
+        if let Some(thread_flag) = self.thread_link_flag() {
+            if !flags.iter().any(|flag| flag == thread_flag) {
+                flags.push(thread_flag.to_string());
+            }
+        }
+        flags
+    }
+
+    /// Returns the flags for linking against Python, like
+    /// `python3-config --ldflags`, see [`PythonConfig::lib_flags`], followed
+    /// by the flags of [`PythonConfig::extra_ldflags`]
+    pub fn link_flags(&self, mode: LinkMode) -> Vec<String> {
+        let mut flags = Vec::new();
+        if !self.enable_shared() && !self.config_dir_str().is_empty() {
+            flags.push(format!("-L{}", self.config_dir_str()));
+        }
+        if !self.lib_dir_str().is_empty() {
+            flags.push(format!("-L{}", self.lib_dir_str()));
+        }
+        flags.extend(self.lib_flags(mode));
+        append_missing(&mut flags, self.extra_ldflags());
+        flags
+    }
+}
+
+/// Appends the flags of `extra` that are not in `flags` yet
+fn append_missing(flags: &mut Vec<String>, extra: &str) {
+    for flag in extra.split_whitespace() {
+        if !flags.iter().any(|x| x == flag) {
+            flags.push(flag.to_string());
+        }
+    }
+}
+
+impl BuildTimeVars {
+    /// Expands `$(EXTRA_CFLAGS)`-style references to the `EXTRA_*` vendor
+    /// keys left in `CFLAGS`, `LDFLAGS` and `LDSHARED`, missing keys expand
+    /// to nothing
+    pub(crate) fn expand_extra_flags(&mut self) {
+        self.cflags = expand_extra(&self.cflags, self);
+        self.ldflags = expand_extra(&self.ldflags, self);
+        self.ldshared = expand_extra(&self.ldshared, self);
+    }
+}
+
+/// `value` with `$(EXTRA_*)` and `${EXTRA_*}` references replaced and
+/// whitespace runs they leave collapsed
+fn expand_extra(value: &str, vars: &BuildTimeVars) -> String {
+    if !value.contains("$(EXTRA_") && !value.contains("${EXTRA_") {
+        return value.to_string();
+    }
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let close = match rest.as_bytes().get(1) {
+            Some(b'(') => ')',
+            Some(b'{') => '}',
+            _ => {
+                out.push('$');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        match rest.find(close) {
+            Some(end) if rest[2..end].starts_with("EXTRA_") => {
+                out.push_str(vars.get_str(&rest[2..end]));
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                out.push('$');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, LinkMode, PythonConfig};
+
+    #[test]
+    fn python3_config_flags() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(
+            config.include_flags(),
+            vec!["-I/usr/local/include/python3.11"]
+        );
+        assert_eq!(config.compile_flags()[1], "-Wsign-compare");
+        assert_eq!(config.thread_link_flag(), Some("-pthread"));
+        assert_eq!(
+            config.lib_flags(LinkMode::ExtensionModule),
+            vec!["-ldl", "-lm", "-lm", "-pthread"]
+        );
+        assert_eq!(
+            config.link_flags(LinkMode::Embed),
+            vec![
+                "-L/usr/local/lib",
+                "-lpython3.11",
+                "-ldl",
+                "-lm",
+                "-lm",
+                "-pthread"
+            ]
+        );
+
+        let static_library =
+            "/usr/local/lib/python3.11/config-3.11-x86_64-linux-gnu/libpython3.11.a";
+        assert_eq!(
+            config.static_library_path().unwrap().to_str(),
+            Some(static_library)
+        );
+        assert_eq!(config.link_flags(LinkMode::EmbedStatic)[1], static_library);
+
+        let config = fixtures::get("cpython313t-linux").unwrap().config();
+        assert_eq!(config.lib_flags(LinkMode::Embed)[0], "-lpython3.13t");
+        assert!(config.py_cflags_nodist().contains("-I./Include/internal"));
+        assert!(config.py_cflags().starts_with("-Wsign-compare"));
+
+        let config = fixtures::get("cpython39-linux-aarch64").unwrap().config();
+        assert_eq!(config.thread_link_flag(), Some("-lpthread"));
+        let lib_flags = config.lib_flags(LinkMode::ExtensionModule);
+        assert_eq!(lib_flags.iter().filter(|x| *x == "-lpthread").count(), 1);
+        assert_eq!(
+            config.link_flags(LinkMode::ExtensionModule)[0],
+            "-L/usr/local/lib/python3.9/config-3.9-aarch64-linux-gnu"
+        );
+
+        let config = fixtures::get("cpython38-darwin").unwrap().config();
+        assert_eq!(config.thread_link_flag(), None);
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'CFLAGS': '-O3 -flto -fPIC', \
+             'PY_CFLAGS': '-O3 -fPIC', 'PY_CFLAGS_NODIST': '-flto -fPIC'}",
+        )
+        .unwrap();
+        assert_eq!(config.compile_flags(), vec!["-O3", "-fPIC"]);
+    }
+
+    #[test]
+    fn extra_flags() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', \
+             'CFLAGS': '-O2 $(EXTRA_CFLAGS) -fPIC', 'EXTRA_CFLAGS': '-fcf-protection', \
+             'LDFLAGS': '${EXTRA_LDFLAGS} -L/usr/lib $(LDLAST)', \
+             'EXTRA_LDFLAGS': '-Wl,-z,relro', 'LDSHARED': 'gcc -shared $(EXTRA_LINK)'}",
+        )
+        .unwrap();
+        assert_eq!(config.cflags(), "-O2 -fcf-protection -fPIC");
+        assert_eq!(config.ldflags(), "-Wl,-z,relro -L/usr/lib $(LDLAST)");
+        assert_eq!(config.ldshared(), "gcc -shared");
+        assert_eq!(config.extra_cflags(), "-fcf-protection");
+        assert_eq!(
+            config.compile_flags(),
+            vec!["-O2", "-fcf-protection", "-fPIC"]
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'CFLAGS': '-O2', \
+             'EXTRA_CFLAGS': '-g', 'EXTRA_LDFLAGS': '-Wl,-z,now'}",
+        )
+        .unwrap();
+        assert_eq!(config.compile_flags(), vec!["-O2", "-g"]);
+        assert_eq!(
+            config.link_flags(LinkMode::ExtensionModule),
+            vec!["-Wl,-z,now"]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::{LinkModel, PythonConfig};
+
+/// A macOS framework to link, see [`PythonConfig::frameworks`] and
+/// [`PythonConfig::python_framework`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Framework {
+    /// Name of the framework, `CoreFoundation`
+    pub name: String,
+    /// Version in `Versions/` of the bundle, the Python version for the
+    /// Python framework, `None` for the current version
+    pub version: Option<String>,
+    /// Directory holding the bundle, passed to the linker with `-F`, `None`
+    /// for the system locations
+    pub search_path: Option<PathBuf>,
+}
+
+impl Framework {
+    /// Arguments for a compiler driver or `ld`, `["-framework", "CoreFoundation"]`
+    pub fn linker_args(&self) -> Vec<String> {
+        vec!["-framework".to_string(), self.name.clone()]
+    }
+}
+
+/// The flags linking the framework, `-framework CoreFoundation`
+impl fmt::Display for Framework {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "-framework {}", self.name)
+    }
+}
+
+/// Keys with the link flags frameworks are read from
+const FRAMEWORK_KEYS: &[&str] = &["LDFLAGS", "LIBS", "SYSLIBS"];
+
+/// Framework names and `-F` directories of `flags`, along with the other
+/// flags. Handles `-framework X`, `-Wl,-framework,X`, `-F X` and `-FX`.
+pub(crate) fn split_framework_flags<S: AsRef<str>>(
+    flags: &[S],
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut names = Vec::new();
+    let mut dirs = Vec::new();
+    let mut other = Vec::new();
+    let mut flags = flags.iter().map(AsRef::as_ref);
+    while let Some(flag) = flags.next() {
+        if flag == "-framework" {
+            names.extend(flags.next().map(str::to_string));
+        } else if let Some(name) = flag.strip_prefix("-Wl,-framework,") {
+            names.push(name.to_string());
+        } else if flag == "-F" {
+            dirs.extend(flags.next().map(str::to_string));
+        } else if let Some(dir) = flag.strip_prefix("-F") {
+            dirs.push(dir.to_string());
+        } else {
+            other.push(flag.to_string());
+        }
+    }
+    (names, dirs, other)
+}
+
+impl PythonConfig {
+    /// Returns the Python framework of macOS framework builds, with the
+    /// Python version and `PYTHONFRAMEWORKPREFIX` as search path, `None`
+    /// for other builds
+    pub fn python_framework(&self) -> Option<Framework> {
+        if self.link_model() != LinkModel::Framework {
+            return None;
+        }
+        let vars = &self.sys_config_data.build_time_vars;
+        let prefix = vars.get_str("PYTHONFRAMEWORKPREFIX");
+        Some(Framework {
+            name: vars.get_str("PYTHONFRAMEWORK").to_string(),
+            version: Some(self.version_str().to_string()),
+            search_path: (!prefix.is_empty()).then(|| PathBuf::from(prefix)),
+        })
+    }
+
+    /// Returns the frameworks libpython depends on, from the `-framework`
+    /// flags of `LDFLAGS`, `LIBS` and `SYSLIBS`, each once. They get the
+    /// first `-F` directory of the same keys as search path.
+    pub fn frameworks(&self) -> Vec<Framework> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let flags: Vec<&str> = FRAMEWORK_KEYS
+            .iter()
+            .flat_map(|key| vars.get_str(key).split_whitespace())
+            .collect();
+        let (names, dirs, _) = split_framework_flags(&flags);
+        let search_path = dirs.first().map(PathBuf::from);
+        let mut frameworks: Vec<Framework> = Vec::new();
+        for name in names {
+            if frameworks.iter().all(|framework| framework.name != name) {
+                frameworks.push(Framework {
+                    name,
+                    version: None,
+                    search_path: search_path.clone(),
+                });
+            }
+        }
+        frameworks
+    }
+
+    /// Returns the distinct search paths of
+    /// [`PythonConfig::python_framework`] and [`PythonConfig::frameworks`],
+    /// the `FRAMEWORK_SEARCH_PATHS` of Xcode
+    pub fn framework_search_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for framework in self.python_framework().into_iter().chain(self.frameworks()) {
+            if let Some(path) = framework.search_path {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_framework_flags, Framework};
+    use crate::{fixtures, LinkMode, PythonConfig};
+    use std::path::PathBuf;
+
+    #[test]
+    fn frameworks() {
+        let config = fixtures::get("cpython38-darwin").unwrap().config();
+        let prefix = PathBuf::from("/Applications/Xcode.app/Contents/Developer/Library/Frameworks");
+        assert_eq!(
+            config.python_framework(),
+            Some(Framework {
+                name: "Python3".to_string(),
+                version: Some("3.8".to_string()),
+                search_path: Some(prefix.clone()),
+            })
+        );
+        assert_eq!(
+            config.frameworks(),
+            vec![Framework {
+                name: "CoreFoundation".to_string(),
+                version: None,
+                search_path: None,
+            }]
+        );
+        assert_eq!(config.framework_search_paths(), vec![prefix]);
+        assert_eq!(
+            config.frameworks()[0].to_string(),
+            "-framework CoreFoundation"
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'MACHDEP': 'darwin', \
+             'LDFLAGS': '-F/Library/Frameworks -Wl,-framework,Tcl', \
+             'LIBS': '-ldl -framework CoreFoundation -framework Tcl'}",
+        )
+        .unwrap();
+        let frameworks = config.frameworks();
+        let names: Vec<&str> = frameworks.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["Tcl", "CoreFoundation"]);
+        assert_eq!(
+            frameworks[0].search_path,
+            Some(PathBuf::from("/Library/Frameworks"))
+        );
+
+        let directives = config.cargo_link_directives(LinkMode::Embed);
+        assert!(directives
+            .contains(&"cargo:rustc-link-search=framework=/Library/Frameworks".to_string()));
+        assert!(directives.contains(&"cargo:rustc-link-lib=framework=Tcl".to_string()));
+        assert!(!directives
+            .iter()
+            .any(|directive| directive.ends_with("=-framework")));
+
+        let (names, dirs, other) =
+            split_framework_flags(&["-F", "/opt/fw", "-lz", "-framework", "Carbon"]);
+        assert_eq!(names, vec!["Carbon"]);
+        assert_eq!(dirs, vec!["/opt/fw"]);
+        assert_eq!(other, vec!["-lz"]);
+
+        assert_eq!(config.python_framework(), None);
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert!(config.frameworks().is_empty());
+    }
+}
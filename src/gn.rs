@@ -0,0 +1,107 @@
+use crate::paths::windows_join;
+use crate::{LinkMode, PythonConfig};
+use std::fmt::Write;
+
+impl PythonConfig {
+    /// Returns GN args for embedding this distribution in GN-based projects,
+    /// like Chromium's: `python_include_dirs`, `python_lib_dirs`,
+    /// `python_libs` and `python_ldflags` translated from
+    /// [`PythonConfig::include_flags`] and [`PythonConfig::link_flags`] with
+    /// libpython included.
+    ///
+    /// Libraries are named the way GN `libs` expects them, `python3.11` on
+    /// Unix, `python311.lib` on Windows and `CoreFoundation.framework` for
+    /// macOS frameworks.
+    pub fn to_gn_args(&self) -> String {
+        let include_dirs: Vec<String> = self
+            .include_flags()
+            .iter()
+            .map(|flag| flag[2..].to_string())
+            .collect();
+        let mut lib_dirs = Vec::new();
+        let mut libs = Vec::new();
+        let mut ldflags = Vec::new();
+        if self.sys_platform() == "win32" && !self.is_mingw() {
+            lib_dirs.push(
+                windows_join(self.prefix_str(), &["libs"])
+                    .display()
+                    .to_string(),
+            );
+            libs.push(format!(
+                "python{}{}.lib",
+                self.major_minor().0,
+                self.major_minor().1
+            ));
+        } else {
+            let flags = self.link_flags(LinkMode::Embed);
+            let mut flags = flags.iter();
+            while let Some(flag) = flags.next() {
+                let (list, value) = if let Some(dir) = flag.strip_prefix("-L") {
+                    (&mut lib_dirs, dir.to_string())
+                } else if let Some(lib) = flag.strip_prefix("-l") {
+                    (&mut libs, lib.to_string())
+                } else if flag == "-framework" {
+                    match flags.next() {
+                        Some(framework) => (&mut libs, format!("{}.framework", framework)),
+                        None => continue,
+                    }
+                } else {
+                    (&mut ldflags, flag.to_string())
+                };
+                if !list.contains(&value) {
+                    list.push(value);
+                }
+            }
+        }
+
+        let mut out = self.generated_comment("#");
+        for (name, values) in [
+            ("python_include_dirs", include_dirs),
+            ("python_lib_dirs", lib_dirs),
+            ("python_libs", libs),
+            ("python_ldflags", ldflags),
+        ] {
+            let values: Vec<String> = values.iter().map(|value| gn_str(value)).collect();
+            writeln!(out, "{} = [ {} ]", name, values.join(", ")).unwrap();
+        }
+        out
+    }
+}
+
+/// GN string literal of `value`
+fn gn_str(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '$' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gn_str;
+    use crate::fixtures;
+
+    #[test]
+    fn gn_args() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(
+            config.to_gn_args(),
+            "# Generated by python3-config for CPython 3.11 (linux-x86_64)\n\n\
+             python_include_dirs = [ \"/usr/local/include/python3.11\" ]\n\
+             python_lib_dirs = [ \"/usr/local/lib\" ]\n\
+             python_libs = [ \"python3.11\", \"dl\", \"m\" ]\n\
+             python_ldflags = [ \"-pthread\" ]\n"
+        );
+
+        let config = fixtures::get("cpython38-darwin").unwrap().config();
+        assert!(config.to_gn_args().contains("\"CoreFoundation.framework\""));
+
+        assert_eq!(gn_str("C:\\Python311 $x"), "\"C:\\\\Python311 \\$x\"");
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt;
+
+use crate::transform::FLAG_KEYS;
+use crate::{ConfigValue, PythonConfig, SanitizeFlags, Source};
+
+/// Flags taking a path, either attached or as the next argument
+const PATH_FLAGS: &[&str] = &["-I", "-L", "-F", "-isystem", "-iquote", "-idirafter"];
+
+/// Linker flags followed by an attached path
+const LINKER_PATH_FLAGS: &[&str] = &["-Wl,-rpath,", "-Wl,-rpath=", "-Wl,-R"];
+
+/// Why [`PythonConfig::hermetic_sanitize`] removed a flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalReason {
+    /// Absolute path outside the installation prefix, such as the build
+    /// directory or the home directory of the builder
+    HostPath,
+    /// Source path remapping like `-fdebug-prefix-map`
+    PrefixMap,
+    /// Macro holding a build date or time
+    Timestamp,
+    /// Macro holding the name of the build user or machine
+    UserName,
+}
+
+impl fmt::Display for RemovalReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemovalReason::HostPath => write!(f, "host path"),
+            RemovalReason::PrefixMap => write!(f, "prefix map"),
+            RemovalReason::Timestamp => write!(f, "timestamp"),
+            RemovalReason::UserName => write!(f, "user name"),
+        }
+    }
+}
+
+/// A flag removed by [`PythonConfig::hermetic_sanitize`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedFlag {
+    /// Key of `build_time_vars` the flag was removed from
+    pub key: String,
+    /// The flag, with its separate argument if any
+    pub flag: String,
+    /// Why it was removed
+    pub reason: RemovalReason,
+}
+
+/// The flags removed by [`PythonConfig::hermetic_sanitize`], in key order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HermeticReport {
+    /// Removed flags
+    pub removed: Vec<RemovedFlag>,
+}
+
+/// One `KEY: flag (reason)` line per removed flag
+impl fmt::Display for HermeticReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for removed in &self.removed {
+            writeln!(f, "{}: {} ({})", removed.key, removed.flag, removed.reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl PythonConfig {
+    /// Returns a copy of this configuration suitable for hermetic build
+    /// systems like Bazel or Buck, with flags tied to the machine that built
+    /// the interpreter removed from the compiler and linker command lines:
+    /// absolute paths outside `prefix` and `exec_prefix` such as
+    /// `-I/home/builder/src`, prefix maps, and `-D` macros holding build
+    /// timestamps or user and host names.
+    ///
+    /// The report lists every removed flag.
+    pub fn hermetic_sanitize(&self) -> (Self, HermeticReport) {
+        let roots: Vec<&str> = [self.prefix_str(), self.exec_prefix_str()]
+            .iter()
+            .map(|root| root.trim_end_matches('/'))
+            .filter(|root| !root.is_empty())
+            .collect();
+        let prefix_maps = SanitizeFlags::new([
+            "-fdebug-prefix-map=*",
+            "-ffile-prefix-map=*",
+            "-fmacro-prefix-map=*",
+        ]);
+        let mut report = HermeticReport::default();
+        let mut updates = Vec::new();
+        for key in FLAG_KEYS {
+            let value = match self.get(key).and_then(ConfigValue::as_str) {
+                Some(value) => value,
+                None => continue,
+            };
+            let mut kept = Vec::new();
+            let mut flags = value.split_whitespace();
+            while let Some(flag) = flags.next() {
+                let mut flag = flag.to_string();
+                let path = if PATH_FLAGS.contains(&flag.as_str()) {
+                    let arg = flags.next().unwrap_or_default();
+                    flag = format!("{} {}", flag, arg);
+                    Some(arg)
+                } else {
+                    flag_path(&flag)
+                };
+                let reason = if prefix_maps.matches(&flag) {
+                    Some(RemovalReason::PrefixMap)
+                } else if path.is_some_and(|path| is_host_path(path, &roots)) {
+                    Some(RemovalReason::HostPath)
+                } else {
+                    macro_reason(&flag)
+                };
+                match reason {
+                    Some(reason) => report.removed.push(RemovedFlag {
+                        key: key.to_string(),
+                        flag,
+                        reason,
+                    }),
+                    None => kept.push(flag),
+                }
+            }
+            if report.removed.last().is_some_and(|x| x.key == *key) {
+                updates.push((*key, kept.join(" ")));
+            }
+        }
+        let mut config = self.clone();
+        config.with_source(
+            Source::Transform("hermetic_sanitize".to_string()),
+            |config| {
+                for (key, value) in updates {
+                    config
+                        .set(key, ConfigValue::String(value.into()))
+                        .expect("string values are valid for flag keys");
+                }
+            },
+        );
+        (config, report)
+    }
+}
+
+/// Path attached to `flag`, `/x` of `-I/x`, `--sysroot=/x` or `-Wl,-rpath,/x`
+fn flag_path(flag: &str) -> Option<&str> {
+    PATH_FLAGS
+        .iter()
+        .chain(LINKER_PATH_FLAGS)
+        .find_map(|prefix| flag.strip_prefix(prefix))
+        .or_else(|| Some(flag.split_once('=')?.1).filter(|_| flag.starts_with('-')))
+}
+
+/// Whether `path` is absolute and outside all of `roots`
+fn is_host_path(path: &str, roots: &[&str]) -> bool {
+    let bytes = path.as_bytes();
+    let absolute = path.starts_with('/')
+        || (bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':');
+    absolute
+        && !roots.iter().any(|root| {
+            path.strip_prefix(root)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+}
+
+/// Why the `-D` macro `flag` depends on the build machine, if it does
+fn macro_reason(flag: &str) -> Option<RemovalReason> {
+    let (name, value) = flag.strip_prefix("-D")?.split_once('=')?;
+    let value = value.trim_matches(|c| c == '"' || c == '\'' || c == '\\');
+    let name = name.to_ascii_uppercase();
+    if name.contains("USER") || name.contains("HOST") {
+        Some(RemovalReason::UserName)
+    } else if name.contains("DATE") || name.contains("TIME") || is_timestamp(value) {
+        Some(RemovalReason::Timestamp)
+    } else {
+        None
+    }
+}
+
+/// Whether `value` looks like an epoch, `2024-01-31` or `12:30:00`
+fn is_timestamp(value: &str) -> bool {
+    let pattern = |pattern: &str| {
+        value.as_bytes().windows(pattern.len()).any(|window| {
+            window.iter().zip(pattern.bytes()).all(|(c, p)| {
+                if p == b'0' {
+                    c.is_ascii_digit()
+                } else {
+                    *c == p
+                }
+            })
+        })
+    };
+    (value.len() >= 9 && value.bytes().all(|c| c.is_ascii_digit()))
+        || pattern("0000-00-00")
+        || pattern("00:00:00")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RemovalReason;
+    use crate::{fixtures, PythonConfig};
+
+    #[test]
+    fn hermetic_sanitize() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': '/opt/python', \
+             'CFLAGS': '-O2 -I/home/builder/src/Include -I /opt/python/include \
+             -fdebug-prefix-map=/build=. -DBUILD_DATE=\"2024-01-31\" -DBUILD_USER=builder \
+             -DNDEBUG -DSOURCE_EPOCH=1706659200 -I./Include', \
+             'LDFLAGS': '-L/opt/python/lib -Wl,-rpath,/tmp/build/lib -L /usr/lib \
+             --sysroot=/home/builder/sysroot -Wl,-O1'}",
+        )
+        .unwrap();
+        let (sanitized, report) = config.hermetic_sanitize();
+        assert_eq!(
+            sanitized.cflags(),
+            "-O2 -I /opt/python/include -DNDEBUG -I./Include"
+        );
+        assert_eq!(sanitized.ldflags(), "-L/opt/python/lib -Wl,-O1");
+        let reasons: Vec<(&str, RemovalReason)> = report
+            .removed
+            .iter()
+            .map(|x| (x.flag.as_str(), x.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("-I/home/builder/src/Include", RemovalReason::HostPath),
+                ("-fdebug-prefix-map=/build=.", RemovalReason::PrefixMap),
+                ("-DBUILD_DATE=\"2024-01-31\"", RemovalReason::Timestamp),
+                ("-DBUILD_USER=builder", RemovalReason::UserName),
+                ("-DSOURCE_EPOCH=1706659200", RemovalReason::Timestamp),
+                ("-Wl,-rpath,/tmp/build/lib", RemovalReason::HostPath),
+                ("-L /usr/lib", RemovalReason::HostPath),
+                ("--sysroot=/home/builder/sysroot", RemovalReason::HostPath),
+            ]
+        );
+        assert!(report
+            .to_string()
+            .starts_with("CFLAGS: -I/home/builder/src/Include (host path)\n"));
+
+        let config = fixtures::get("cpython311-freebsd").unwrap().config();
+        let (sanitized, report) = config.hermetic_sanitize();
+        assert!(report.removed.is_empty());
+        assert_eq!(sanitized.ldflags(), config.ldflags());
+    }
+}
@@ -0,0 +1,207 @@
+use std::io::Read;
+
+use crate::{sha256_hex, ConfigValue, Error, ParseOptions, PythonConfig};
+
+/// Largest file [`PythonConfig::from_url`] downloads
+const MAX_SIZE: usize = 16 * 1024 * 1024;
+
+impl PythonConfig {
+    /// Downloads and parses a `_sysconfigdata.py` file or the `PYTHON.json`
+    /// metadata of a python-build-standalone distribution over HTTPS.
+    ///
+    /// The expected SHA-256 of the file can be given in the URL fragment,
+    /// like pip does: `https://example.com/_sysconfigdata.py#sha256=<hex>`.
+    /// Download fails if the content doesn't match.
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        Self::from_url_with_options(url, &ParseOptions::new())
+    }
+
+    /// Like [`PythonConfig::from_url`] with options. The download is
+    /// bounded by the [`Limits`](crate::Limits) too: it fails when the file
+    /// is larger than the maximum file size, and the timeout also applies
+    /// to each step of the request.
+    pub fn from_url_with_options(url: &str, options: &ParseOptions) -> Result<Self, Error> {
+        let (location, sha256) = split_checksum(url)?;
+        if !location.starts_with("https://") {
+            return Err(Error::Unsupported(format!(
+                "{} is not an https URL",
+                location
+            )));
+        }
+        let mut agent = ureq::AgentBuilder::new();
+        if let Some(timeout) = options.limits.timeout {
+            agent = agent.timeout(timeout);
+        }
+        let response = agent
+            .build()
+            .get(location)
+            .call()
+            .map_err(|err| Error::Download(err.to_string()))?;
+        let max = options.limits.max_file_size.min(MAX_SIZE);
+        let body = read_body(response.into_reader(), max)?;
+        Self::from_downloaded(location, &body, sha256, options)
+    }
+
+    /// Verifies and parses the content downloaded from `location`
+    fn from_downloaded(
+        location: &str,
+        body: &[u8],
+        sha256: Option<&str>,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        if let Some(expected) = sha256 {
+            let actual = sha256_hex(body);
+            if !expected.eq_ignore_ascii_case(&actual) {
+                return Err(Error::HashMismatch {
+                    path: location.into(),
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+        }
+        let src = std::str::from_utf8(body)
+            .map_err(|err| Error::Download(format!("{}: {}", location, err)))?;
+        if src.trim_start().starts_with('{') {
+            from_standalone_metadata(src)
+        } else {
+            PythonConfig::parse_with_options(src, options)
+        }
+    }
+}
+
+/// Reads a response body of at most `max` bytes, without reading the rest
+/// of a larger one
+fn read_body(reader: impl Read, max: usize) -> Result<Vec<u8>, Error> {
+    let mut body = Vec::new();
+    reader
+        .take(max.saturating_add(1) as u64)
+        .read_to_end(&mut body)?;
+    if body.len() > max {
+        return Err(Error::LimitExceeded {
+            limit: "file size",
+            max,
+        });
+    }
+    Ok(body)
+}
+
+/// Splits the `#sha256=` fragment from `url`
+fn split_checksum(url: &str) -> Result<(&str, Option<&str>), Error> {
+    match url.split_once('#') {
+        None => Ok((url, None)),
+        Some((location, fragment)) => match fragment.strip_prefix("sha256=") {
+            Some(sha256) => Ok((location, Some(sha256))),
+            None => Err(Error::Unsupported(format!(
+                "unknown URL fragment {}, expected sha256=<hex>",
+                fragment
+            ))),
+        },
+    }
+}
+
+/// Builds a configuration from the `python_config_vars` of a
+/// python-build-standalone `PYTHON.json`, which stores every value as a
+/// string. Values of integer flags such as `Py_ENABLE_SHARED` and
+/// `SIZEOF_VOID_P` are converted back to integers.
+fn from_standalone_metadata(src: &str) -> Result<PythonConfig, Error> {
+    let metadata: serde_json::Value =
+        serde_json::from_str(src).map_err(|err| Error::Download(err.to_string()))?;
+    let config_vars = metadata
+        .get("python_config_vars")
+        .and_then(|vars| vars.as_object())
+        .ok_or(Error::MissingBuildTimeVars)?;
+    let mut vars: Vec<(String, ConfigValue)> = config_vars
+        .iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(value) if is_integer_key(key) => value
+                    .parse()
+                    .map(ConfigValue::Int)
+                    .unwrap_or_else(|_| ConfigValue::String(value.as_str().into())),
+                serde_json::Value::String(value) => ConfigValue::String(value.as_str().into()),
+                serde_json::Value::Number(value) => ConfigValue::Int(value.as_i64()?),
+                serde_json::Value::Null => ConfigValue::None,
+                _ => return None,
+            };
+            Some((key.clone(), value))
+        })
+        .collect();
+    if !config_vars.contains_key("VERSION") {
+        if let Some(version) = metadata
+            .get("python_major_minor_version")
+            .and_then(|version| version.as_str())
+        {
+            vars.push(("VERSION".to_string(), ConfigValue::String(version.into())));
+        }
+    }
+    PythonConfig::from_vars(vars)
+}
+
+fn is_integer_key(key: &str) -> bool {
+    ["SIZEOF_", "ALIGNOF_", "HAVE_", "Py_", "WITH_", "USE_"]
+        .iter()
+        .any(|prefix| key.starts_with(prefix))
+        || key == "COUNT_ALLOCS"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_body, split_checksum};
+    use crate::{Error, ParseOptions, PythonConfig};
+
+    #[test]
+    fn standalone_metadata() {
+        let src = r#"{
+            "version": "7",
+            "target_triple": "aarch64-unknown-linux-gnu",
+            "python_major_minor_version": "3.12",
+            "python_config_vars": {
+                "SOABI": "cpython-312-aarch64-linux-gnu",
+                "EXT_SUFFIX": ".cpython-312-aarch64-linux-gnu.so",
+                "Py_ENABLE_SHARED": "1",
+                "SIZEOF_VOID_P": "8",
+                "LDVERSION": "3.12"
+            }
+        }"#;
+        let config = PythonConfig::from_downloaded(
+            "PYTHON.json",
+            src.as_bytes(),
+            None,
+            &ParseOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(config.version_str(), "3.12");
+        assert_eq!(config.pointer_size(), 8);
+        assert!(config.enable_shared());
+        assert_eq!(config.ld_version(), "3.12");
+    }
+
+    #[test]
+    fn checksum_verification() {
+        let src = "build_time_vars = {'VERSION': '3.11'}";
+        let (_, sha256) = split_checksum(
+            "https://example.com/_sysconfigdata.py#sha256=\
+             0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        let options = ParseOptions::new();
+        let err = PythonConfig::from_downloaded("x", src.as_bytes(), sha256, &options).unwrap_err();
+        assert!(matches!(err, Error::HashMismatch { .. }));
+
+        let sha256 = crate::sha256_hex(src.as_bytes());
+        let config =
+            PythonConfig::from_downloaded("x", src.as_bytes(), Some(&sha256), &options).unwrap();
+        assert_eq!(config.version_str(), "3.11");
+
+        assert!(split_checksum("https://example.com/x.py#md5=00").is_err());
+        let err = PythonConfig::from_url("http://example.com/x.py").unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn body_size_limit() {
+        assert_eq!(read_body(&b"0123456789"[..], 10).unwrap().len(), 10);
+        let err = read_body(&b"0123456789"[..], 9).unwrap_err();
+        assert!(matches!(err, Error::LimitExceeded { max: 9, .. }));
+    }
+}
@@ -0,0 +1,136 @@
+use std::path::{Path, PathBuf};
+
+use crate::transform::FLAG_KEYS;
+use crate::{ConfigValue, Error, PythonConfig, Transform};
+
+/// Points a configuration of an uninstalled CPython build at its source and
+/// build directories, so extension modules can be built against a working
+/// tree the way `sysconfig` does when running from it:
+///
+/// * headers come from `Include` in the source directory and `pyconfig.h`
+///   from the build directory
+/// * libpython is linked from the build directory
+/// * relative `-I` and `-L` flags, `-I./Include` and `-L.`, which `make`
+///   resolves in the build directory, become absolute
+/// * `srcdir`, `abs_srcdir` and `abs_builddir` are updated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InTreeBuild {
+    srcdir: PathBuf,
+    builddir: PathBuf,
+}
+
+impl InTreeBuild {
+    /// Sources checked out in `srcdir` and built in `builddir`, the same
+    /// directory for in-tree builds
+    pub fn new(srcdir: impl Into<PathBuf>, builddir: impl Into<PathBuf>) -> Self {
+        Self {
+            srcdir: srcdir.into(),
+            builddir: builddir.into(),
+        }
+    }
+
+    /// `flag` with a relative path made absolute in the build directory
+    fn resolve_flag(&self, flag: &str) -> Option<String> {
+        let (option, path) = ["-I", "-L"]
+            .iter()
+            .find_map(|option| Some((*option, flag.strip_prefix(option)?)))?;
+        if path.is_empty() || Path::new(path).is_absolute() {
+            return None;
+        }
+        let path = path.strip_prefix("./").unwrap_or(path);
+        let resolved = match path {
+            "." => self.builddir.clone(),
+            path => self.builddir.join(path),
+        };
+        Some(format!("{}{}", option, resolved.display()))
+    }
+}
+
+impl Transform for InTreeBuild {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        let mut updates: Vec<(String, String)> = Vec::new();
+        for key in FLAG_KEYS {
+            let value = match config.get(key).and_then(ConfigValue::as_str) {
+                Some(value) => value,
+                None => continue,
+            };
+            let mut changed = false;
+            let flags: Vec<String> = value
+                .split_whitespace()
+                .map(|flag| match self.resolve_flag(flag) {
+                    Some(resolved) => {
+                        changed = true;
+                        resolved
+                    }
+                    None => flag.to_string(),
+                })
+                .collect();
+            if changed {
+                updates.push((key.to_string(), flags.join(" ")));
+            }
+        }
+        let srcdir = self.srcdir.display().to_string();
+        let builddir = self.builddir.display().to_string();
+        let include = self.srcdir.join("Include").display().to_string();
+        for (key, value) in [
+            ("srcdir", &srcdir),
+            ("abs_srcdir", &srcdir),
+            ("abs_builddir", &builddir),
+            ("INCLUDEPY", &include),
+            ("CONFINCLUDEPY", &builddir),
+            ("LIBDIR", &builddir),
+            ("LIBPL", &builddir),
+        ] {
+            updates.push((key.to_string(), value.to_string()));
+        }
+        for (key, value) in updates {
+            config.set(&key, ConfigValue::String(value.into()))?;
+        }
+        Ok(())
+    }
+}
+
+impl PythonConfig {
+    /// Returns a copy of this configuration of an uninstalled build resolved
+    /// against its source and build directories, see [`InTreeBuild`]
+    pub fn in_tree_build(
+        &self,
+        srcdir: impl Into<PathBuf>,
+        builddir: impl Into<PathBuf>,
+    ) -> Result<Self, Error> {
+        self.apply(&[Box::new(InTreeBuild::new(srcdir, builddir))])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, ConfigValue, LinkMode};
+
+    #[test]
+    fn in_tree_build() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let config = config
+            .in_tree_build("/src/cpython", "/src/cpython/build")
+            .unwrap();
+        assert_eq!(
+            config.include_flags(),
+            vec!["-I/src/cpython/Include", "-I/src/cpython/build"]
+        );
+        assert_eq!(
+            config.get("PY_CPPFLAGS").and_then(ConfigValue::as_str),
+            Some("-I/src/cpython/build -I/src/cpython/build/Include")
+        );
+        assert!(config
+            .py_cflags_nodist()
+            .contains("-I/src/cpython/build/Include/internal"));
+        assert_eq!(config.lib_dir_str(), "/src/cpython/build");
+        assert_eq!(
+            config.link_flags(LinkMode::Embed)[..2],
+            ["-L/src/cpython/build", "-lpython3.11"]
+        );
+        assert_eq!(
+            config.get("abs_srcdir").and_then(ConfigValue::as_str),
+            Some("/src/cpython")
+        );
+    }
+}
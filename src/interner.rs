@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// Shared pool of strings deduplicating keys and values across many parsed
+/// configurations, see [`ParseOptions::interner`]
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    strings: Arc<Mutex<HashSet<Arc<str>>>>,
+}
+
+impl Interner {
+    /// Create an empty interner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared copy of `s`
+    pub fn intern(&self, s: &str) -> Arc<str> {
+        let mut strings = self.strings.lock().unwrap();
+        if let Some(interned) = strings.get(s) {
+            return interned.clone();
+        }
+        let interned: Arc<str> = Arc::from(s);
+        strings.insert(interned.clone());
+        interned
+    }
+
+    /// Number of distinct strings
+    pub fn len(&self) -> usize {
+        self.strings.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no string has been interned yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interner;
+    use crate::{ConfigValue, ParseOptions, PythonConfig};
+    use std::fs;
+    use std::sync::Arc;
+
+    #[test]
+    fn interned_values_are_shared() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let interner = Interner::new();
+        let options = ParseOptions::new().interner(interner.clone());
+        let a = PythonConfig::parse_with_options(&src, &options).unwrap();
+        let b = PythonConfig::parse_with_options(&src, &options).unwrap();
+        let value =
+            |config: &PythonConfig| match &config.sys_config_data.build_time_vars.raw["CFLAGS"] {
+                ConfigValue::String(value) => value.clone(),
+                _ => unreachable!(),
+            };
+        assert!(Arc::ptr_eq(&value(&a), &value(&b)));
+        assert!(!interner.is_empty());
+    }
+}
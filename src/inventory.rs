@@ -0,0 +1,78 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::PythonConfig;
+
+/// Columns of [`export_csv`]
+const HEADER: &str = "path,version,implementation,abi,platform,prefix,link_model\n";
+
+/// Serializes the configurations found by
+/// [`discover_all`](crate::discover_all) to CSV with a header line, one row
+/// per file with its path, Python version, implementation, ABI tag,
+/// platform, installation prefix and [`LinkModel`](crate::LinkModel), to
+/// audit the interpreters of images and hosts
+pub fn export_csv(configs: &[(PathBuf, PythonConfig)]) -> String {
+    let mut out = String::from(HEADER);
+    for (path, config) in configs {
+        let fields = [
+            path.display().to_string(),
+            config.version_str().to_string(),
+            config.implementation().to_string(),
+            config.abi_tag().to_string(),
+            config.get_platform().to_string(),
+            config.prefix_str().to_string(),
+            config.link_model().to_string(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        writeln!(out, "{}", fields.join(",")).unwrap();
+    }
+    out
+}
+
+/// `field` quoted when it holds a comma, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::export_csv;
+    use crate::{fixtures, PythonConfig};
+    use std::path::PathBuf;
+
+    #[test]
+    fn export_inventory() {
+        let configs = vec![
+            (
+                PathBuf::from(
+                    "/usr/local/lib/python3.11/_sysconfigdata__linux_x86_64-linux-gnu.py",
+                ),
+                fixtures::get("cpython311-linux").unwrap().config(),
+            ),
+            (
+                PathBuf::from("/opt/a,b/_sysconfigdata.py"),
+                PythonConfig::parse(
+                    "build_time_vars = {'VERSION': '3.12', 'prefix': '/opt/\"a,b\"'}",
+                )
+                .unwrap(),
+            ),
+        ];
+        let csv = export_csv(&configs);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "path,version,implementation,abi,platform,prefix,link_model"
+        );
+        assert_eq!(
+            lines[1],
+            "/usr/local/lib/python3.11/_sysconfigdata__linux_x86_64-linux-gnu.py,3.11,CPython,\
+             cp311,linux-x86_64,/usr/local,Shared"
+        );
+        assert!(lines[2].starts_with("\"/opt/a,b/_sysconfigdata.py\",3.12,"));
+        assert!(lines[2].ends_with(",\"/opt/\"\"a,b\"\"\",Static"));
+    }
+}
@@ -0,0 +1,262 @@
+use crate::ValueKind;
+
+/// Documentation of a well-known `build_time_vars` key, see [`key_info`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyInfo {
+    /// The key, `EXT_SUFFIX`
+    pub key: &'static str,
+    /// Short description
+    pub description: &'static str,
+    /// Type of the value
+    pub kind: ValueKind,
+    /// Python version that introduced the key, `None` for keys every
+    /// Python 3 has
+    pub since: Option<(u32, u32)>,
+}
+
+const fn string(
+    key: &'static str,
+    since: Option<(u32, u32)>,
+    description: &'static str,
+) -> KeyInfo {
+    KeyInfo {
+        key,
+        description,
+        kind: ValueKind::String,
+        since,
+    }
+}
+
+const fn int(key: &'static str, since: Option<(u32, u32)>, description: &'static str) -> KeyInfo {
+    KeyInfo {
+        key,
+        description,
+        kind: ValueKind::Int,
+        since,
+    }
+}
+
+/// Known keys, sorted like `build_time_vars`
+const KEYS: &[KeyInfo] = &[
+    string(
+        "ABIFLAGS",
+        Some((3, 2)),
+        "ABI flags of the build, d for debug and t for free-threaded",
+    ),
+    string("AR", None, "archiver command"),
+    string("BINDIR", None, "directory of the interpreter executable"),
+    string(
+        "BLDLIBRARY",
+        None,
+        "linker flags for libpython inside the build tree",
+    ),
+    string(
+        "BLDSHARED",
+        None,
+        "command linking extension modules inside the build tree",
+    ),
+    string("CC", None, "C compiler command"),
+    string(
+        "CCSHARED",
+        None,
+        "compiler flags for position independent code",
+    ),
+    string("CFLAGS", None, "C compiler flags of the build"),
+    string(
+        "CFLAGSFORSHARED",
+        None,
+        "extra compiler flags for objects of a shared libpython",
+    ),
+    string("CONFIG_ARGS", None, "arguments passed to configure"),
+    string(
+        "CONFINCLUDEPY",
+        None,
+        "directory of the platform-specific headers, pyconfig.h",
+    ),
+    string("CXX", None, "C++ compiler command"),
+    string(
+        "DLLLIBRARY",
+        None,
+        "file name of the libpython DLL on Cygwin and mingw",
+    ),
+    string("EXE", None, "suffix of executables, .exe on Windows"),
+    string(
+        "EXT_SUFFIX",
+        Some((3, 2)),
+        "file name suffix of extension modules",
+    ),
+    string(
+        "HOST_GNU_TYPE",
+        None,
+        "GNU triple of the host the interpreter runs on",
+    ),
+    string(
+        "INCLUDEDIR",
+        None,
+        "include directory of the installation prefix",
+    ),
+    string("INCLUDEPY", None, "directory of the Python headers"),
+    string(
+        "INSTSONAME",
+        None,
+        "file name of the installed shared libpython",
+    ),
+    string("LDFLAGS", None, "linker flags of the build"),
+    string(
+        "LDLIBRARY",
+        None,
+        "file name of the libpython to link against",
+    ),
+    string("LDSHARED", None, "command linking extension modules"),
+    string(
+        "LDVERSION",
+        Some((3, 3)),
+        "version and ABI flags in the libpython name, 3.13t",
+    ),
+    string("LIBDIR", None, "directory of libpython"),
+    string("LIBPC", None, "directory of the pkg-config files"),
+    string(
+        "LIBPL",
+        None,
+        "directory of the static libpython, Makefile and config.c",
+    ),
+    string(
+        "LIBPYTHON",
+        Some((3, 8)),
+        "libpython linker flag of extension modules, empty where they don't link it",
+    ),
+    string("LIBRARY", None, "file name of the static libpython"),
+    string("LIBS", None, "libraries libpython depends on"),
+    string(
+        "LINKFORSHARED",
+        None,
+        "linker flags for executables embedding Python",
+    ),
+    string(
+        "MACHDEP",
+        None,
+        "platform identifier, the value of sys.platform",
+    ),
+    string(
+        "MACOSX_DEPLOYMENT_TARGET",
+        None,
+        "minimum macOS version of the build",
+    ),
+    string(
+        "MULTIARCH",
+        Some((3, 6)),
+        "Debian multiarch tuple of the platform",
+    ),
+    string("OPT", None, "optimization and debugging compiler flags"),
+    string(
+        "PLATLIBDIR",
+        Some((3, 9)),
+        "name of the platform-specific library directory, lib or lib64",
+    ),
+    string(
+        "PYTHONFRAMEWORK",
+        None,
+        "name of the macOS framework, empty for non-framework builds",
+    ),
+    string(
+        "PYTHONFRAMEWORKPREFIX",
+        None,
+        "directory containing the macOS framework",
+    ),
+    string(
+        "PY_CFLAGS",
+        None,
+        "C compiler flags extension modules inherit",
+    ),
+    string(
+        "PY_CFLAGS_NODIST",
+        Some((3, 5)),
+        "C compiler flags only used to build CPython itself",
+    ),
+    int(
+        "PY_ENABLE_SHARED",
+        None,
+        "1 if libpython is a shared library",
+    ),
+    int("Py_DEBUG", None, "1 for debug builds"),
+    int(
+        "Py_ENABLE_SHARED",
+        None,
+        "1 if libpython is a shared library",
+    ),
+    int(
+        "Py_GIL_DISABLED",
+        Some((3, 13)),
+        "1 for free-threaded builds",
+    ),
+    int(
+        "Py_TRACE_REFS",
+        None,
+        "1 for builds tracing object references",
+    ),
+    string(
+        "SHLIBS",
+        None,
+        "libraries shared modules of the standard library link",
+    ),
+    string("SHLIB_SUFFIX", None, "file name suffix of shared libraries"),
+    int("SIZEOF_LONG", None, "size of a C long in bytes"),
+    int("SIZEOF_SIZE_T", None, "size of a C size_t in bytes"),
+    int("SIZEOF_VOID_P", None, "size of a pointer in bytes"),
+    int("SIZEOF_WCHAR_T", None, "size of a C wchar_t in bytes"),
+    string(
+        "SOABI",
+        Some((3, 2)),
+        "ABI tag of extension module file names, cpython-311-x86_64-linux-gnu",
+    ),
+    string("TZPATH", Some((3, 9)), "search path of the zoneinfo module"),
+    string("VERSION", None, "major and minor Python version, 3.11"),
+    int("WITH_DOC_STRINGS", None, "1 if docstrings are compiled in"),
+    int(
+        "WITH_PYMALLOC",
+        None,
+        "1 if the pymalloc allocator is enabled",
+    ),
+    int(
+        "WITH_THREAD",
+        None,
+        "1 if thread support is enabled, always on since Python 3.7",
+    ),
+    string(
+        "exec_prefix",
+        None,
+        "installation prefix of platform-specific files",
+    ),
+    string("prefix", None, "installation prefix"),
+];
+
+/// Returns the documentation of `key`, `None` for keys this crate doesn't know
+pub fn key_info(key: &str) -> Option<&'static KeyInfo> {
+    KEYS.iter().find(|info| info.key == key)
+}
+
+/// Returns the documentation of every key known to [`key_info`]
+pub fn known_keys() -> &'static [KeyInfo] {
+    KEYS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{key_info, known_keys};
+    use crate::{fixtures, ValueKind};
+
+    #[test]
+    fn key_infos() {
+        let info = key_info("Py_GIL_DISABLED").unwrap();
+        assert_eq!(info.kind, ValueKind::Int);
+        assert_eq!(info.since, Some((3, 13)));
+        assert!(key_info("NOT_A_KEY").is_none());
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        for info in known_keys() {
+            if let Some(value) = config.get(info.key) {
+                assert_eq!(value.kind(), info.kind, "{}", info.key);
+            }
+        }
+    }
+}
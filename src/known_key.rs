@@ -0,0 +1,252 @@
+use std::fmt;
+
+use crate::{Error, PythonConfig};
+
+/// Value of a [`KnownKey`] as this crate interprets it, see
+/// [`PythonConfig::get_known`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownValue<'a> {
+    /// String, empty when the key is missing
+    Str(&'a str),
+    /// Switch, `true` when the key is `1`
+    Bool(bool),
+    /// Integer
+    Int(i64),
+}
+
+impl<'a> KnownValue<'a> {
+    /// Returns the string value
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self {
+            KnownValue::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the switch value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            KnownValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer value
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            KnownValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KnownValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KnownValue::Str(value) => f.write_str(value),
+            KnownValue::Bool(value) => write!(f, "{}", *value as u8),
+            KnownValue::Int(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+macro_rules! known_keys {
+    ($vars:ident; $($variant:ident => $name:literal, $value:expr;)*) => {
+        /// A `build_time_vars` key this crate reads into a field while
+        /// parsing, for lookups without hashing or comparing strings, see
+        /// [`PythonConfig::get_known`]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum KnownKey {
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant,
+            )*
+        }
+
+        impl KnownKey {
+            /// Every known key
+            pub const ALL: &'static [KnownKey] = &[$(KnownKey::$variant,)*];
+
+            /// Name of the key in `build_time_vars`
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(KnownKey::$variant => $name,)*
+                }
+            }
+
+            /// The known key named `name`
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(KnownKey::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl PythonConfig {
+            /// Returns the value of a known key from the field it was parsed
+            /// into, the fast path of [`PythonConfig::get`] for tools polling
+            /// many configurations. Switches like `Py_DEBUG` are booleans and
+            /// missing strings are empty.
+            pub fn get_known(&self, key: KnownKey) -> KnownValue<'_> {
+                let $vars = &self.sys_config_data.build_time_vars;
+                match key {
+                    $(KnownKey::$variant => $value,)*
+                }
+            }
+        }
+    };
+}
+
+impl PythonConfig {
+    /// Like [`PythonConfig::get_known`] but `None` when the source didn't
+    /// provide the key and it couldn't be derived from others, as is common
+    /// for a bare `pyconfig.h`. `Some(KnownValue::Str(""))` is a key known
+    /// to be empty.
+    pub fn known_value(&self, key: KnownKey) -> Option<KnownValue<'_>> {
+        let value = self.get_known(key);
+        let derived = match value {
+            KnownValue::Str(value) => !value.is_empty(),
+            KnownValue::Bool(_) => false,
+            KnownValue::Int(value) => value != 0,
+        };
+        let provided = self
+            .sys_config_data
+            .build_time_vars
+            .raw
+            .contains_key(key.name());
+        if provided || derived {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Known keys whose value is unknown, see [`PythonConfig::known_value`]
+    pub fn unknown_keys(&self) -> Vec<KnownKey> {
+        KnownKey::ALL
+            .iter()
+            .copied()
+            .filter(|key| self.known_value(*key).is_none())
+            .collect()
+    }
+
+    /// Fails with [`Error::KeyError`] naming the first of `keys` whose value
+    /// is unknown, for consumers that can't fall back to defaults
+    pub fn require_known(&self, keys: &[KnownKey]) -> Result<(), Error> {
+        match keys.iter().find(|key| self.known_value(**key).is_none()) {
+            Some(key) => Err(Error::KeyError(key.name())),
+            None => Ok(()),
+        }
+    }
+}
+
+known_keys! {
+    vars;
+    Abiflags => "ABIFLAGS", KnownValue::Str(&vars.abiflags);
+    Bindir => "BINDIR", KnownValue::Str(&vars.bindir);
+    Cc => "CC", KnownValue::Str(&vars.cc);
+    Cflags => "CFLAGS", KnownValue::Str(&vars.cflags);
+    CountAllocs => "COUNT_ALLOCS", KnownValue::Bool(vars.count_allocs);
+    Cxx => "CXX", KnownValue::Str(&vars.cxx);
+    Exe => "EXE", KnownValue::Str(&vars.exe);
+    ExtSuffix => "EXT_SUFFIX", KnownValue::Str(&vars.ext_suffix);
+    HostGnuType => "HOST_GNU_TYPE", KnownValue::Str(&vars.host_gnu_type);
+    Includedir => "INCLUDEDIR", KnownValue::Str(&vars.include_dir);
+    Ldflags => "LDFLAGS", KnownValue::Str(&vars.ldflags);
+    Ldshared => "LDSHARED", KnownValue::Str(&vars.ldshared);
+    Ldversion => "LDVERSION", KnownValue::Str(&vars.ld_version);
+    Libdir => "LIBDIR", KnownValue::Str(&vars.lib_dir);
+    Libpl => "LIBPL", KnownValue::Str(&vars.config_dir);
+    Libs => "LIBS", KnownValue::Str(&vars.libs);
+    MacosxDeploymentTarget => "MACOSX_DEPLOYMENT_TARGET",
+        KnownValue::Str(&vars.macosx_deployment_target);
+    Machdep => "MACHDEP", KnownValue::Str(&vars.machdep);
+    Multiarch => "MULTIARCH", KnownValue::Str(&vars.multiarch);
+    PyDebug => "Py_DEBUG", KnownValue::Bool(vars.py_debug);
+    PyEnableShared => "Py_ENABLE_SHARED", KnownValue::Bool(vars.py_enable_shared);
+    PyRefDebug => "Py_REF_DEBUG", KnownValue::Bool(vars.py_ref_debug);
+    PyTraceRefs => "Py_TRACE_REFS", KnownValue::Bool(vars.py_trace_refs);
+    ShlibSuffix => "SHLIB_SUFFIX", KnownValue::Str(&vars.shlib_suffix);
+    SizeofVoidP => "SIZEOF_VOID_P", KnownValue::Int(i64::from(vars.size_of_void_p));
+    Soabi => "SOABI", KnownValue::Str(&vars.soabi);
+    Version => "VERSION", KnownValue::Str(&vars.version);
+    ExecPrefix => "exec_prefix", KnownValue::Str(&vars.exec_prefix);
+    ImplementationVersion => "implementation_version",
+        KnownValue::Str(vars.implementation_version.as_deref().unwrap_or_default());
+    Prefix => "prefix", KnownValue::Str(&vars.prefix);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KnownKey, KnownValue};
+    use crate::{fixtures, BuildTimeVars, ConfigValue, Error, PythonConfig};
+
+    #[test]
+    fn known_keys() {
+        for key in KnownKey::ALL {
+            assert_eq!(KnownKey::from_name(key.name()), Some(*key));
+            let mut vars = BuildTimeVars::default();
+            let value = ConfigValue::String("x".into());
+            let recognized = vars.set(key.name(), Some(&value)).unwrap_or(true);
+            assert!(recognized, "{}", key.name());
+        }
+        assert_eq!(KnownKey::from_name("AR"), None);
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(config.get_known(KnownKey::Version), KnownValue::Str("3.11"));
+        assert_eq!(config.get_known(KnownKey::SizeofVoidP).as_int(), Some(8));
+        assert_eq!(
+            config.get_known(KnownKey::PyEnableShared).as_bool(),
+            Some(true)
+        );
+        for key in KnownKey::ALL {
+            if let (Some(ConfigValue::String(value)), KnownValue::Str(known)) =
+                (config.get(key.name()), config.get_known(*key))
+            {
+                assert_eq!(&**value, known, "{}", key.name());
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_values() {
+        // loaded from PC/pyconfig.h
+        let config = PythonConfig::from_vars(vec![
+            ("VERSION".to_string(), ConfigValue::String("3.11".into())),
+            ("Py_DEBUG".to_string(), ConfigValue::Int(0)),
+            ("ABIFLAGS".to_string(), ConfigValue::String("".into())),
+        ])
+        .unwrap();
+        assert_eq!(
+            config.known_value(KnownKey::Version),
+            Some(KnownValue::Str("3.11"))
+        );
+        assert_eq!(
+            config.known_value(KnownKey::PyDebug),
+            Some(KnownValue::Bool(false))
+        );
+        assert_eq!(
+            config.known_value(KnownKey::Abiflags),
+            Some(KnownValue::Str(""))
+        );
+        assert_eq!(config.known_value(KnownKey::PyEnableShared), None);
+        assert_eq!(config.known_value(KnownKey::Prefix), None);
+        assert_eq!(config.get_known(KnownKey::Prefix), KnownValue::Str(""));
+        assert!(config.unknown_keys().contains(&KnownKey::SizeofVoidP));
+        assert!(config.require_known(&[KnownKey::Version]).is_ok());
+        assert!(matches!(
+            config.require_known(&[KnownKey::Version, KnownKey::Libdir, KnownKey::Prefix]),
+            Err(Error::KeyError("LIBDIR"))
+        ));
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(
+            config.known_value(KnownKey::Prefix),
+            Some(KnownValue::Str("/usr/local"))
+        );
+        assert!(config
+            .require_known(&[KnownKey::Libdir, KnownKey::Ldversion])
+            .is_ok());
+    }
+}
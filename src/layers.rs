@@ -0,0 +1,46 @@
+use crate::{Error, ParseOptions, PythonConfig, RequiredKeys, Source, SysConfigData};
+
+impl PythonConfig {
+    /// Parses a configuration split across several `_sysconfigdata*.py`
+    /// sources, as some distributions ship local overrides in an additional
+    /// file. Keys of later layers replace the ones of earlier layers.
+    ///
+    /// The first layer must be a complete sysconfigdata, keys set by layer
+    /// `n > 0` have [`Source::Layer`] provenance.
+    pub fn parse_layers(layers: &[&str]) -> Result<Self, Error> {
+        let (first, rest) = layers.split_first().ok_or(Error::MissingBuildTimeVars)?;
+        let mut config = Self::parse(first)?;
+        let options = ParseOptions::new().required_keys(RequiredKeys::Keys(Vec::new()));
+        for (index, src) in rest.iter().enumerate() {
+            let (layer, _) = SysConfigData::parse(src, &options)?;
+            config.with_source(Source::Layer(index + 1), |config| {
+                for (key, value) in layer.build_time_vars.raw {
+                    config.set(&key, value)?;
+                }
+                Ok::<_, Error>(())
+            })?;
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PythonConfig, Source};
+
+    #[test]
+    fn parse_layers() {
+        let vendor = "build_time_vars = {'VERSION': '3.12', 'CC': 'gcc', 'LIBDIR': '/usr/lib64'}";
+        let local = "# local overrides\nbuild_time_vars = {'CC': 'clang', 'OPT': '-O2'}";
+        let config = PythonConfig::parse_layers(&[vendor, local]).unwrap();
+        assert_eq!(config.cc(), "clang");
+        assert_eq!(config.lib_dir_str(), "/usr/lib64");
+        assert_eq!(config.provenance("CC"), Some(Source::Layer(1)));
+        assert_eq!(config.provenance("OPT"), Some(Source::Layer(1)));
+        assert_eq!(config.provenance("LIBDIR"), Some(Source::SysConfigData));
+
+        assert!(PythonConfig::parse_layers(&[]).is_err());
+        assert!(PythonConfig::parse_layers(&[local]).is_err());
+        assert!(PythonConfig::parse_layers(&[vendor, "x = 1"]).is_err());
+    }
+}
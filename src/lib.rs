@@ -1,28 +1,371 @@
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryFrom;
 use std::error;
 use std::fmt;
+use std::io::{self, Read};
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 
-use rustpython_parser::ast::{Expression, ExpressionType, Number, StatementType, StringGroup};
+use provenance::Provenance;
+#[cfg(feature = "rustpython")]
+use rustpython_parser::ast::{
+    Expression, ExpressionType, Number, Statement, StatementType, StringGroup, UnaryOperator,
+};
+#[cfg(feature = "rustpython")]
 use rustpython_parser::error::ParseError;
+#[cfg(feature = "rustpython")]
 use rustpython_parser::parser;
 
+// lets `#[derive(FromPythonConfig)]` name this crate in its own tests
+extern crate self as python3_config;
+
+mod abiflags;
+mod arch;
+#[cfg(feature = "emit")]
+mod bazel;
+#[cfg(feature = "serde")]
+mod build_details;
+#[cfg(feature = "emit")]
+mod build_env;
+mod cache;
+mod canonical;
+mod cargo;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod conformance;
+#[cfg(feature = "container")]
+pub mod container;
+mod convert;
+mod cross;
+#[cfg(feature = "emit")]
+mod crossenv;
+mod defines;
+#[cfg(feature = "discovery")]
+mod discovery;
+mod distutils;
+mod embed;
+#[cfg(feature = "emit")]
+mod emit;
+mod env_flags;
+mod error_context;
+mod explain;
+mod extension;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+mod flags;
+mod framework;
+#[cfg(feature = "emit")]
+mod gn;
+mod hermetic;
+#[cfg(feature = "http")]
+mod http;
+mod in_tree;
+mod interner;
+#[cfg(feature = "emit")]
+mod inventory;
+mod key_info;
+mod known_key;
+mod layers;
+mod library;
+mod linker;
+mod lint;
+mod lite;
+mod locale;
+mod lookup;
+#[cfg(feature = "manifest")]
+mod manifest;
+mod matrix;
+#[cfg(feature = "emit")]
+mod msbuild;
+mod optimization;
+mod options;
+mod paths;
+mod platform;
+mod post_process;
+mod provenance;
+#[cfg(feature = "pyo3-build-config")]
+mod pyo3_config;
+mod registry;
+mod release;
+mod report;
+mod reproducibility;
+mod rules;
+mod runner;
+mod scheme;
+mod setup;
+mod sizes;
+mod source;
+mod static_config;
+mod subset;
+mod target;
+mod third_party;
+mod transform;
+mod upgrade;
+mod variant;
+mod verify;
+mod version;
+mod wheel;
+#[cfg(feature = "emit")]
+mod xcconfig;
+
+pub use abiflags::{abiflag_meaning, register_abiflag, AbiFlagMeaning};
+pub use arch::{archs, register_arch, Arch};
+#[cfg(feature = "serde")]
+pub use build_details::BUILD_DETAILS_FILE;
+pub use cache::ConfigCache;
+pub use cargo::{configure_embedded_build, configure_extension_module_build, SYSCONFIGDATA_ENV};
+pub use convert::{FromPythonConfig, ValueKind};
+#[cfg(feature = "emit")]
+pub use crossenv::{make_crossenv_layout, CrossenvLayout};
+pub use defines::Define;
+#[cfg(feature = "discovery")]
+pub use discovery::{discover_all, discover_all_stream, discover_variants, scan, DiscoverStream};
+pub use embed::EmbeddedInitHints;
+pub use error_context::Phase;
+pub use explain::{Decision, Explanation, Input};
+pub use extension::{rename_extension, ExtSuffixParts};
+pub use framework::Framework;
+pub use hermetic::{HermeticReport, RemovalReason, RemovedFlag};
+pub use in_tree::InTreeBuild;
+pub use interner::Interner;
+#[cfg(feature = "emit")]
+pub use inventory::export_csv;
+pub use key_info::{key_info, known_keys, KeyInfo};
+pub use known_key::{KnownKey, KnownValue};
+pub use library::{LinkMode, LinkModel};
+pub use linker::{parse_linker_flags, LinkerArg};
+pub use lint::{LintFinding, LintRule};
+pub use locale::LocaleSupport;
+#[cfg(feature = "manifest")]
+pub use manifest::Manifest;
+pub use matrix::{SupportEntry, SupportMatrix};
+pub use optimization::{OptimizationFlags, OptimizationLevel};
+pub use options::{Backend, Limits, ParseOptions, RequiredKeys, WarningAction, WarningKind};
+pub use paths::{normalize_windows_path, InstallPaths};
+pub use platform::{platforms, register_platform, Platform};
+pub use post_process::{
+    register_post_processor, ConfigPostProcessor, ForceStaticLinking, ResolvePrefixSymlink,
+};
+pub use provenance::Source;
+#[cfg(feature = "derive")]
+pub use python3_config_derive::FromPythonConfig;
+pub use release::ReleaseLevel;
+pub use reproducibility::ReproducibilityRecord;
+pub use rules::{rules_for_version, VersionRules};
+pub use runner::Invocation;
+pub use scheme::{EnsurePip, InstallScheme};
+pub use setup::{parse_module_setup, ModuleBuild};
+pub use sizes::{HashAlgorithm, UnicodeWidth};
+#[cfg(feature = "serde")]
+pub use source::{BuildDetails, JsonDump};
+pub use source::{
+    ConfigSource, InterpreterProbe, Loader, MakefileSource, PyconfigH, SysconfigData,
+    SysconfigReport,
+};
+pub use static_config::{StaticConfig, StaticValue};
+pub use subset::KeysProfile;
+pub use target::{expected_for_target, ExpectedConfig};
+pub use third_party::ThirdPartyLibs;
+pub use transform::{
+    Anonymize, ApplyOverrides, Relocate, SanitizeFlags, SetOptimization, ToAbi3, Transform,
+};
+pub use upgrade::{upgrade_report, Impact, KeyChange, UpgradeReport};
+pub use variant::AbiVariant;
+pub use version::PythonVersion;
+pub use wheel::{macos_platform_tag, Tag};
+
+/// Version of the logic deriving tags, platforms and link settings from
+/// `build_time_vars`, bumped whenever a derivation changes.
+///
+/// It is part of [`PythonConfig::to_json`], [`PythonConfig::to_pyo3_config`]
+/// and [`PythonConfig::reproducibility_record`], so caches keyed on them are
+/// invalidated by crate upgrades even though the input file is unchanged.
+pub const DERIVATION_VERSION: u32 = 3;
+
 /// Represents an error during parsing
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Python source code syntax error
+    #[cfg(feature = "rustpython")]
     SyntaxError(ParseError),
     /// missing build_time_vars variable
     MissingBuildTimeVars,
     /// missing required key in configuration
     KeyError(&'static str),
+    /// syntax not understood by the lightweight parser
+    InvalidSyntax {
+        /// 1-based line number
+        line: usize,
+        /// description of the problem
+        message: String,
+    },
+    /// keys required by [`RequiredKeys`] missing in configuration
+    MissingKeys(Vec<String>),
+    /// unsupported Rust target triple
+    UnknownTarget(String),
+    /// unknown [`Decision`] name
+    UnknownDecision(String),
+    /// unknown [`AbiVariant`] name
+    UnknownVariant(String),
+    /// configuration of a Python version older than 3, holds the version
+    UnsupportedPython(String),
+    /// text that isn't a [`PythonVersion`]
+    InvalidVersion(String),
+    /// input exceeds one of the [`Limits`]
+    LimitExceeded {
+        /// the exceeded limit
+        limit: &'static str,
+        /// its configured value
+        max: usize,
+    },
+    /// value of a known key outside the range it can take
+    InvalidValue {
+        /// the key
+        key: String,
+        /// description of the problem
+        message: String,
+    },
+    /// key missing from `build_time_vars`
+    UnknownKey {
+        /// the requested key
+        key: String,
+        /// an existing key with a similar name
+        suggestion: Option<String>,
+    },
+    /// value of a type that can't be converted to the requested one
+    TypeMismatch {
+        /// the type the conversion accepts
+        expected: ValueKind,
+        /// the type of the value
+        found: ValueKind,
+    },
+    /// warning denied by [`ParseOptions::deny_warnings`] or [`ParseOptions::warning`]
+    Warning(ParseWarning),
+    /// operation not supported by this configuration
+    Unsupported(String),
+    /// no source of a [`Loader`] could be loaded, holds the description and
+    /// error of each
+    NoSource(Vec<(String, Error)>),
+    /// I/O error while reading a file
+    Io(io::Error),
+    /// downloading a remote file failed
+    Download(String),
+    /// invalid or unsupported container image
+    InvalidImage(String),
+    /// invalid `python-target.toml` manifest
+    InvalidManifest(String),
+    /// invalid or unsupported PEP 739 `build-details.json`
+    InvalidBuildDetails(String),
+    /// file content does not match the expected SHA-256 hash
+    HashMismatch {
+        /// path of the file
+        path: PathBuf,
+        /// expected hash
+        expected: String,
+        /// actual hash
+        actual: String,
+    },
+    /// configuration does not match the requested target
+    TargetMismatch {
+        /// Rust target triple
+        target: String,
+        /// description of the mismatch
+        reason: String,
+    },
+    /// `error` with the file, phase and key it happened in, see
+    /// [`Error::root_cause`] to match on its kind
+    Context {
+        /// file being processed
+        path: Option<PathBuf>,
+        /// what was being done
+        phase: Option<Phase>,
+        /// `build_time_vars` key being processed
+        key: Option<String>,
+        /// the underlying error
+        error: Box<Error>,
+    },
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "rustpython")]
             Error::SyntaxError(err) => err.fmt(f),
             Error::MissingBuildTimeVars => write!(f, "missing build_time_vars variable"),
             Error::KeyError(key) => write!(f, "missing required key {}", key),
+            Error::InvalidSyntax { line, message } => write!(f, "line {}: {}", line, message),
+            Error::MissingKeys(keys) => write!(f, "missing required keys {}", keys.join(", ")),
+            Error::UnknownTarget(target) => write!(f, "unsupported target {}", target),
+            Error::UnknownDecision(name) => write!(f, "unknown decision {}", name),
+            Error::UnknownVariant(name) => write!(f, "unknown ABI variant {}", name),
+            Error::LimitExceeded { limit, max } => {
+                write!(f, "input exceeds the {} limit of {}", limit, max)
+            }
+            Error::UnsupportedPython(version) => write!(
+                f,
+                "unsupported Python {}, only Python 3 sysconfigdata can be read",
+                version
+            ),
+            Error::InvalidVersion(version) => write!(f, "invalid Python version {}", version),
+            Error::InvalidValue { key, message } => {
+                write!(f, "invalid value of {}: {}", key, message)
+            }
+            Error::UnknownKey {
+                key,
+                suggestion: Some(suggestion),
+            } => write!(f, "unknown key {}, did you mean {}?", key, suggestion),
+            Error::UnknownKey { key, .. } => write!(f, "unknown key {}", key),
+            Error::TypeMismatch { expected, found } => {
+                write!(f, "expected {} value, found {}", expected, found)
+            }
+            Error::Warning(warning) => write!(f, "denied warning: {}", warning),
+            Error::Unsupported(reason) => write!(f, "unsupported: {}", reason),
+            Error::NoSource(errors) if errors.is_empty() => {
+                write!(f, "no configuration source given")
+            }
+            Error::NoSource(errors) => {
+                write!(f, "no configuration source could be loaded")?;
+                for (source, err) in errors {
+                    write!(f, "\n  {}: {}", source, err)?;
+                }
+                Ok(())
+            }
+            Error::Io(err) => err.fmt(f),
+            Error::Download(reason) => write!(f, "download failed: {}", reason),
+            Error::InvalidImage(reason) => write!(f, "invalid image: {}", reason),
+            Error::InvalidManifest(reason) => write!(f, "invalid manifest: {}", reason),
+            Error::InvalidBuildDetails(reason) => {
+                write!(f, "invalid build-details.json: {}", reason)
+            }
+            Error::HashMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "sha256 of {} is {}, expected {}",
+                path.display(),
+                actual,
+                expected
+            ),
+            Error::TargetMismatch { target, reason } => write!(
+                f,
+                "sysconfigdata does not match target {}: {}",
+                target, reason
+            ),
+            Error::Context {
+                path, key, error, ..
+            } => {
+                if let Some(path) = path {
+                    write!(f, "{}: ", path.display())?;
+                }
+                if let Some(key) = key {
+                    write!(f, "{}: ", key)?;
+                }
+                error.fmt(f)
+            }
         }
     }
 }
@@ -30,72 +373,301 @@ impl fmt::Display for Error {
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
+            #[cfg(feature = "rustpython")]
             Error::SyntaxError(err) => Some(err),
             Error::MissingBuildTimeVars => None,
             Error::KeyError(_) => None,
+            Error::InvalidSyntax { .. } => None,
+            Error::MissingKeys(_) => None,
+            Error::UnknownTarget(_) => None,
+            Error::UnknownDecision(_) => None,
+            Error::UnknownVariant(_) => None,
+            Error::UnsupportedPython(_) => None,
+            Error::InvalidVersion(_) => None,
+            Error::LimitExceeded { .. } => None,
+            Error::InvalidValue { .. } => None,
+            Error::UnknownKey { .. } => None,
+            Error::TypeMismatch { .. } => None,
+            Error::Warning(_) => None,
+            Error::Unsupported(_) => None,
+            Error::NoSource(_) => None,
+            Error::Io(err) => Some(err),
+            Error::Download(_) => None,
+            Error::InvalidImage(_) => None,
+            Error::InvalidManifest(_) => None,
+            Error::InvalidBuildDetails(_) => None,
+            Error::HashMismatch { .. } => None,
+            Error::TargetMismatch { .. } => None,
+            Error::Context { error, .. } => error.source(),
         }
     }
 }
 
+#[cfg(feature = "rustpython")]
 impl From<ParseError> for Error {
     fn from(err: ParseError) -> Self {
         Self::SyntaxError(err)
     }
 }
 
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Python implementation a configuration belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PythonImplementation {
+    /// CPython
+    CPython,
+    /// PyPy
+    PyPy,
+    /// GraalPy
+    GraalPy,
+}
+
+impl fmt::Display for PythonImplementation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PythonImplementation::CPython => write!(f, "CPython"),
+            PythonImplementation::PyPy => write!(f, "PyPy"),
+            PythonImplementation::GraalPy => write!(f, "GraalPy"),
+        }
+    }
+}
+
 /// Python configuration information
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(transparent))]
 pub struct PythonConfig {
     sys_config_data: SysConfigData,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    derived: Derived,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    provenance: Provenance,
+}
+
+/// Values derived from `build_time_vars`, computed on first use
+#[derive(Debug, Clone, Default)]
+struct Derived {
+    python_tag: OnceLock<String>,
+    abi_tag: OnceLock<String>,
+    sys_platform: OnceLock<String>,
+    platform: OnceLock<String>,
+    type_sizes: OnceLock<BTreeMap<String, u32>>,
 }
 
 impl PythonConfig {
+    fn new(sys_config_data: SysConfigData) -> Self {
+        Self {
+            sys_config_data,
+            derived: Derived::default(),
+            provenance: Provenance::default(),
+        }
+    }
+
+    /// Mutable access to the variables, invalidates derived values
+    fn vars_mut(&mut self) -> &mut BuildTimeVars {
+        self.derived = Derived::default();
+        &mut self.sys_config_data.build_time_vars
+    }
+
     /// Parse from `_sysconfigdata.py` content
     pub fn parse(src: &str) -> Result<Self, Error> {
-        let sys_config_data = SysConfigData::parse(src)?;
-        Ok(Self { sys_config_data })
+        Self::parse_with_report(src).map(|(config, _)| config)
+    }
+
+    /// Parse from `_sysconfigdata.py` content, also returns a [`ParseReport`]
+    /// describing which keys were recognized and which were ignored
+    pub fn parse_with_report(src: &str) -> Result<(Self, ParseReport), Error> {
+        let (sys_config_data, report) = SysConfigData::parse(src, &ParseOptions::default())?;
+        Ok((Self::new(sys_config_data).post_process()?, report))
+    }
+
+    /// Parse from `_sysconfigdata.py` content with custom [`ParseOptions`]
+    pub fn parse_with_options(src: &str, options: &ParseOptions) -> Result<Self, Error> {
+        let (sys_config_data, _) = SysConfigData::parse(src, options)?;
+        Self::new(sys_config_data).post_process()
     }
 
-    /// Returns Python version
+    /// Parse `_sysconfigdata.py` content held in memory, such as a file
+    /// embedded with `include_bytes!`. Doesn't touch the filesystem or
+    /// spawn processes, for sandboxed build environments and WebAssembly.
+    /// A UTF-8 byte order mark is skipped.
+    pub fn parse_bytes(src: &[u8]) -> Result<Self, Error> {
+        Self::parse_bytes_with_options(src, &ParseOptions::default())
+    }
+
+    /// Like [`PythonConfig::parse_bytes`] with custom [`ParseOptions`]
+    pub fn parse_bytes_with_options(src: &[u8], options: &ParseOptions) -> Result<Self, Error> {
+        let src = src.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(src);
+        let src = std::str::from_utf8(src).map_err(|err| Error::InvalidSyntax {
+            line: src[..err.valid_up_to()]
+                .iter()
+                .filter(|&&byte| byte == b'\n')
+                .count()
+                + 1,
+            message: "invalid UTF-8".to_string(),
+        })?;
+        Self::parse_with_options(src, options)
+    }
+
+    /// Parse `_sysconfigdata.py` content from `reader` with custom
+    /// [`ParseOptions`], reading at most one byte past the file size limit
+    /// so oversized input is rejected without buffering all of it
+    pub fn read_with_options<R: Read>(reader: R, options: &ParseOptions) -> Result<Self, Error> {
+        let max = options.limits.max_file_size;
+        let mut src = String::new();
+        reader
+            .take(max.saturating_add(1) as u64)
+            .read_to_string(&mut src)?;
+        if src.len() > max {
+            return Err(Error::LimitExceeded {
+                limit: "file size",
+                max,
+            });
+        }
+        Self::parse_with_options(&src, options)
+    }
+
+    /// Builds a configuration from `build_time_vars` entries, for example ones
+    /// read from another format than `_sysconfigdata.py`
+    pub fn from_vars<I>(vars: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (String, ConfigValue)>,
+    {
+        let mut build_time_vars = BuildTimeVars::default();
+        for (key, value) in vars {
+            build_time_vars.set(&key, Some(&value))?;
+            build_time_vars.raw.insert(Arc::from(key), value);
+        }
+        build_time_vars.apply_implementation_profile();
+        if build_time_vars.version.is_empty() {
+            return Err(Error::KeyError("VERSION"));
+        }
+        build_time_vars.check_supported()?;
+        let mut config = Self::new(SysConfigData { build_time_vars });
+        config.provenance.base = Source::Vars;
+        config.post_process()
+    }
+
+    /// Returns the raw value of `key` in `build_time_vars`. When there is no
+    /// exact match a key differing only in case is used, so `ext_suffix`
+    /// finds `EXT_SUFFIX`.
+    pub fn get(&self, key: &str) -> Option<&ConfigValue> {
+        let raw = &self.sys_config_data.build_time_vars.raw;
+        raw.get(key)
+            .or_else(|| raw.get(self.find_key_ignore_case(key)?))
+    }
+
+    /// Returns every key of `build_time_vars` with a literal value, sorted by key
+    pub fn vars(&self) -> impl Iterator<Item = (&str, &ConfigValue)> {
+        self.sys_config_data
+            .build_time_vars
+            .raw
+            .iter()
+            .map(|(key, value)| (&**key, value))
+    }
+
+    /// Sets `key` in `build_time_vars`, updating the values derived from it.
+    /// Fails if `VERSION` isn't a string or `SIZEOF_VOID_P` isn't an integer.
+    pub fn set(&mut self, key: &str, value: ConfigValue) -> Result<(), Error> {
+        let vars = self.vars_mut();
+        vars.set(key, Some(&value))?;
+        let key: Arc<str> = Arc::from(key);
+        vars.raw.insert(key.clone(), value);
+        vars.expand_extra_flags();
+        vars.expand_path_placeholders();
+        let source = self.provenance.current.clone().unwrap_or(Source::Override);
+        self.provenance.overrides.insert(key, source);
+        Ok(())
+    }
+
+    /// Returns Python version, `VERSION` as written
+    #[deprecated(note = "use python_version()")]
     pub fn version(&self) -> &str {
-        &self.sys_config_data.build_time_vars.version
+        self.version_str()
     }
 
-    /// Returns Python major version
+    /// Returns Python major version, `0` when `VERSION` has none.
+    /// [`PythonConfig::python_version`] tells those apart.
+    #[deprecated(note = "use python_version(), which fails instead of returning 0")]
     pub fn version_major(&self) -> u32 {
-        let version = self.version();
-        version
-            .split('.')
-            .next()
-            .and_then(|x| x.parse::<u32>().ok())
-            .unwrap()
+        self.major_minor().0
     }
 
-    /// Returns Python minor version
+    /// Returns Python minor version, `0` when `VERSION` has none.
+    /// [`PythonConfig::python_version`] tells those apart.
+    #[deprecated(note = "use python_version(), which fails instead of returning 0")]
     pub fn version_minor(&self) -> u32 {
-        let version = self.version();
-        version
-            .split('.')
-            .nth(1)
-            .and_then(|x| x.parse::<u32>().ok())
-            .unwrap()
+        self.major_minor().1
     }
 
     /// Returns the installation prefix of the Python interpreter
+    #[deprecated(note = "use paths()")]
     pub fn prefix(&self) -> &str {
-        &self.sys_config_data.build_time_vars.prefix
+        self.prefix_str()
     }
 
     /// Returns the executable path prefix for the Python interpreter
+    #[deprecated(note = "use paths()")]
     pub fn exec_prefix(&self) -> &str {
+        self.exec_prefix_str()
+    }
+
+    /// `VERSION`, for this crate's own use of [`PythonConfig::version`]
+    pub(crate) fn version_str(&self) -> &str {
+        &self.sys_config_data.build_time_vars.version
+    }
+
+    /// Major and minor version of `VERSION`, `0` for missing parts
+    pub(crate) fn major_minor(&self) -> (u32, u32) {
+        let mut parts = self
+            .version_str()
+            .split('.')
+            .map(|x| x.parse::<u32>().unwrap_or_default());
+        let major = parts.next().unwrap_or_default();
+        let minor = parts.next().unwrap_or_default();
+        (major, minor)
+    }
+
+    /// `prefix`, for this crate's own use of [`PythonConfig::prefix`]
+    pub(crate) fn prefix_str(&self) -> &str {
+        &self.sys_config_data.build_time_vars.prefix
+    }
+
+    /// `exec_prefix`, for this crate's own use of
+    /// [`PythonConfig::exec_prefix`]
+    pub(crate) fn exec_prefix_str(&self) -> &str {
         &self.sys_config_data.build_time_vars.exec_prefix
     }
 
+    /// C compiler command
+    pub fn cc(&self) -> &str {
+        &self.sys_config_data.build_time_vars.cc
+    }
+
+    /// C++ compiler command
+    pub fn cxx(&self) -> &str {
+        &self.sys_config_data.build_time_vars.cxx
+    }
+
     /// C compilation flags
     pub fn cflags(&self) -> &str {
         &self.sys_config_data.build_time_vars.cflags
     }
 
+    /// Command used to link extension modules
+    pub fn ldshared(&self) -> &str {
+        &self.sys_config_data.build_time_vars.ldshared
+    }
+
     /// Returns linker flags required for linking this Python
     /// distribution. All libraries / frameworks have the appropriate `-l`
     /// or `-framework` prefixes.
@@ -121,17 +693,56 @@ impl PythonConfig {
     }
 
     /// The location of the distribution's `python3-config` script
+    #[deprecated(note = "use paths()")]
     pub fn config_dir(&self) -> &str {
-        &self.sys_config_data.build_time_vars.config_dir
+        self.config_dir_str()
+    }
+
+    /// Returns the directory executables are installed to
+    #[deprecated(note = "use paths()")]
+    pub fn bindir(&self) -> &str {
+        self.bindir_str()
     }
 
-    /// Returns the C headers include directory
+    /// Returns the suffix of executables, `.exe` on Windows and empty elsewhere
+    pub fn exe(&self) -> &str {
+        &self.sys_config_data.build_time_vars.exe
+    }
+
+    /// Returns the C headers include directory. References like
+    /// `${exec_prefix}/include` are expanded unless parsed with
+    /// [`ParseOptions::keep_placeholders`], [`PythonConfig::get`] returns
+    /// the value as written.
+    #[deprecated(note = "use paths()")]
     pub fn include_dir(&self) -> &str {
-        &self.sys_config_data.build_time_vars.include_dir
+        self.include_dir_str()
     }
 
-    /// Returns library directory
+    /// Returns library directory, expanded like
+    /// [`include_dir`](Self::include_dir)
+    #[deprecated(note = "use paths()")]
     pub fn lib_dir(&self) -> &str {
+        self.lib_dir_str()
+    }
+
+    /// `LIBPL`, for this crate's own use of [`PythonConfig::config_dir`]
+    pub(crate) fn config_dir_str(&self) -> &str {
+        &self.sys_config_data.build_time_vars.config_dir
+    }
+
+    /// `BINDIR`, for this crate's own use of [`PythonConfig::bindir`]
+    pub(crate) fn bindir_str(&self) -> &str {
+        &self.sys_config_data.build_time_vars.bindir
+    }
+
+    /// `INCLUDEDIR`, for this crate's own use of
+    /// [`PythonConfig::include_dir`]
+    pub(crate) fn include_dir_str(&self) -> &str {
+        &self.sys_config_data.build_time_vars.include_dir
+    }
+
+    /// `LIBDIR`, for this crate's own use of [`PythonConfig::lib_dir`]
+    pub(crate) fn lib_dir_str(&self) -> &str {
         &self.sys_config_data.build_time_vars.lib_dir
     }
 
@@ -170,30 +781,281 @@ impl PythonConfig {
         self.sys_config_data.build_time_vars.with_thread
     }
 
+    /// Returns the Debian style multiarch tuple, for example `x86_64-linux-gnu`
+    pub fn multiarch(&self) -> &str {
+        &self.sys_config_data.build_time_vars.multiarch
+    }
+
     /// Returns pointer size (size of C `void*`) of this distribution
     pub fn pointer_size(&self) -> u32 {
         self.sys_config_data.build_time_vars.size_of_void_p
     }
+
+    /// Returns the version of the implementation when it differs from the Python
+    /// language version, for example `7.3.17` for PyPy
+    pub fn implementation_version(&self) -> Option<&str> {
+        self.sys_config_data
+            .build_time_vars
+            .implementation_version
+            .as_deref()
+    }
+
+    /// Returns the Python implementation, detected from `SOABI` or `EXT_SUFFIX`
+    pub fn implementation(&self) -> PythonImplementation {
+        self.sys_config_data.build_time_vars.implementation()
+    }
+
+    /// Returns the wheel interpreter tag, for example `cp38` or `graalpy310`
+    pub fn python_tag(&self) -> &str {
+        self.derived.python_tag.get_or_init(|| {
+            let prefix = match self.implementation() {
+                PythonImplementation::CPython => "cp",
+                PythonImplementation::PyPy => "pp",
+                PythonImplementation::GraalPy => "graalpy",
+            };
+            format!("{}{}{}", prefix, self.major_minor().0, self.major_minor().1)
+        })
+    }
+
+    /// Returns the wheel ABI tag, for example `cp38`, `abi3` or `graalpy240_310_native`.
+    /// `ABIFLAGS` letters of vendor spins are handled as described by
+    /// [`register_abiflag`].
+    pub fn abi_tag(&self) -> &str {
+        self.derived
+            .abi_tag
+            .get_or_init(|| match self.implementation() {
+                PythonImplementation::CPython if self.ext_suffix().starts_with(".abi3.") => {
+                    "abi3".to_string()
+                }
+                PythonImplementation::CPython => format!(
+                    "cp{}{}{}",
+                    self.major_minor().0,
+                    self.major_minor().1,
+                    abiflags::tag_abiflags(self.abiflags())
+                ),
+                PythonImplementation::PyPy | PythonImplementation::GraalPy => {
+                    normalize_tag(&self.sys_config_data.build_time_vars.soabi_abi_parts())
+                }
+            })
+    }
+
+    /// Checks that this configuration is suitable for the Rust target `triple`,
+    /// see [`expected_for_target`]
+    pub fn validate_target(&self, triple: &str) -> Result<(), Error> {
+        let expected = expected_for_target(triple)?;
+        let vars = &self.sys_config_data.build_time_vars;
+        let mismatch = |reason: String| Error::TargetMismatch {
+            target: triple.to_string(),
+            reason,
+        };
+        if vars.size_of_void_p != 0 && vars.size_of_void_p != expected.pointer_size {
+            return Err(mismatch(format!(
+                "pointer size is {}, expected {}",
+                vars.size_of_void_p, expected.pointer_size
+            )));
+        }
+        let platform = vars.platform_chunk();
+        // mingw_x86_64_ucrt and mingw_x86_64_clang are variants of mingw_x86_64
+        let variant = platform.starts_with("mingw")
+            && platform
+                .strip_prefix(expected.platform.as_str())
+                .is_some_and(|rest| rest.starts_with('_'));
+        if !platform.is_empty() && platform != expected.platform && !variant {
+            return Err(mismatch(format!(
+                "platform is {}, expected {}",
+                platform, expected.platform
+            )));
+        }
+        // BSD extension suffixes have no platform part, compare sys.platform
+        if let Some(os) = platform::bsd_os(self.sys_platform()) {
+            if !triple.contains(&format!("-{}", os)) {
+                return Err(mismatch(format!(
+                    "sys.platform is {}, expected a {} target",
+                    self.sys_platform(),
+                    os
+                )));
+            }
+        }
+        if !vars.ext_suffix.is_empty() && !vars.ext_suffix.ends_with(expected.shlib_suffix) {
+            return Err(mismatch(format!(
+                "extension suffix is {}, expected {}",
+                vars.ext_suffix, expected.shlib_suffix
+            )));
+        }
+        Ok(())
+    }
+
+    /// Serialize to a JSON string, fields and `build_time_vars` keys are
+    /// always emitted in the same order
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// [`DERIVATION_VERSION`] of the crate that wrote the JSON this
+    /// configuration was read from with [`PythonConfig::from_json`], `None`
+    /// for other sources
+    pub fn derivation_version(&self) -> Option<u32> {
+        Some(self.sys_config_data.build_time_vars.derivation_version).filter(|x| *x != 0)
+    }
+
+    /// Deserialize from a JSON string produced by [`PythonConfig::to_json`]
+    #[cfg(feature = "serde")]
+    pub fn from_json(src: &str) -> Result<Self, serde_json::Error> {
+        let mut config: Self = serde_json::from_str(src)?;
+        config.provenance.base = Source::Vars;
+        Ok(config)
+    }
+
+    /// Returns the JSON Schema of the serialized form produced by [`PythonConfig::to_json`]
+    #[cfg(feature = "schemars")]
+    pub fn json_schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(PythonConfig)
+    }
+}
+
+/// Statistics collected while parsing `build_time_vars`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    /// Total number of keys in `build_time_vars`
+    pub total_keys: usize,
+    /// Number of keys understood by this crate
+    pub recognized_keys: usize,
+    /// Names of keys that were skipped, in source order
+    pub ignored_keys: Vec<String>,
+    /// Number of string values
+    pub string_values: usize,
+    /// Number of integer values
+    pub int_values: usize,
+    /// Number of float values
+    pub float_values: usize,
+    /// Number of `None` values
+    pub none_values: usize,
+    /// Warnings reported with [`WarningAction::Warn`], in source order
+    pub warnings: Vec<ParseWarning>,
+}
+
+/// Suspicious construct found while parsing `build_time_vars`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Category of the warning
+    pub kind: WarningKind,
+    /// Key the warning is about
+    pub key: String,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            WarningKind::UnknownValueType => write!(
+                f,
+                "value of {} is not a string, integer, float or None literal",
+                self.key
+            ),
+            WarningKind::DuplicateKey => write!(f, "duplicate key {}", self.key),
+            WarningKind::Coercion => write!(f, "value of {} has an unexpected type", self.key),
+            WarningKind::StaticLto => write!(
+                f,
+                "{} enables LTO, the static libpython only links with the compiler that built it",
+                self.key
+            ),
+            WarningKind::UnsupportedLinkModel => write!(
+                f,
+                "{} links extension modules with a file of the CPython build tree",
+                self.key
+            ),
+            WarningKind::MultipleAssignments => write!(
+                f,
+                "{} is assigned more than once, the last assignment wins",
+                self.key
+            ),
+            WarningKind::UnknownAbiFlags => write!(
+                f,
+                "{} has letters of unknown meaning, passed through to the ABI tag",
+                self.key
+            ),
+            WarningKind::IntegerOutOfRange => {
+                write!(f, "integer value of {} does not fit in 64 bits", self.key)
+            }
+        }
+    }
+}
+
+impl ParseReport {
+    fn record_key(&mut self, key: String, recognized: bool) {
+        self.total_keys += 1;
+        if recognized {
+            self.recognized_keys += 1;
+        } else {
+            self.ignored_keys.push(key);
+        }
+    }
+
+    /// Handles a warning the way `options` asks for
+    fn warn(&mut self, options: &ParseOptions, kind: WarningKind, key: &str) -> Result<(), Error> {
+        let warning = ParseWarning {
+            kind,
+            key: key.to_string(),
+        };
+        match options.warning_action(kind) {
+            WarningAction::Ignore => {}
+            WarningAction::Warn => self.warnings.push(warning),
+            WarningAction::Deny => return Err(Error::Warning(warning)),
+        }
+        Ok(())
+    }
+
+    fn record_value(&mut self, value: Option<&ConfigValue>) {
+        match value {
+            Some(ConfigValue::String(_)) => self.string_values += 1,
+            Some(ConfigValue::Int(_)) => self.int_values += 1,
+            Some(ConfigValue::Float(_)) => self.float_values += 1,
+            Some(ConfigValue::None) => self.none_values += 1,
+            None => {}
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(transparent))]
 struct SysConfigData {
     pub build_time_vars: BuildTimeVars,
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename = "PythonConfig"))]
 struct BuildTimeVars {
+    /// [`DERIVATION_VERSION`] of the serialized form, `0` unless read from JSON
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, serialize_with = "serialize_derivation_version")
+    )]
+    pub derivation_version: u32,
     pub abiflags: String,
+    pub bindir: String,
     pub count_allocs: bool,
+    pub cc: String,
+    pub cxx: String,
     pub cflags: String,
+    pub ldshared: String,
     pub config_dir: String,
     pub ext_suffix: String,
+    pub exe: String,
     pub exec_prefix: String,
     pub include_dir: String,
     pub lib_dir: String,
     pub libs: String,
     pub ldflags: String,
     pub ld_version: String,
+    pub multiarch: String,
+    pub machdep: String,
+    pub host_gnu_type: String,
+    pub macosx_deployment_target: String,
     pub prefix: String,
     pub py_debug: bool,
     pub py_ref_debug: bool,
@@ -204,81 +1066,622 @@ struct BuildTimeVars {
     pub size_of_void_p: u32,
     pub with_thread: bool,
     pub version: String,
+    pub implementation_version: Option<String>,
+    /// Every key of `build_time_vars` with a literal value
+    pub raw: BTreeMap<Arc<str>, ConfigValue>,
+    /// Leave `${exec_prefix}`-style references in the path values, see
+    /// [`ParseOptions::keep_placeholders`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    pub keep_placeholders: bool,
+}
+
+#[cfg(feature = "serde")]
+fn serialize_derivation_version<S>(_: &u32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_u32(DERIVATION_VERSION)
+}
+
+#[cfg(feature = "serde")]
+fn serialize_float<S>(text: &Arc<str>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_f64(text.parse().unwrap_or(f64::NAN))
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_float<'de, D>(deserializer: D) -> Result<Arc<str>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    <f64 as serde::Deserialize>::deserialize(deserializer).map(float_text)
+}
+
+/// Shortest decimal text reading back as `value`, always with a `.` or an
+/// exponent like Python's `repr`
+#[cfg(any(feature = "rustpython", feature = "serde"))]
+fn float_text(value: f64) -> Arc<str> {
+    format!("{:?}", value).into()
+}
+
+/// A literal value in `build_time_vars`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ConfigValue {
+    /// String literal
+    String(Arc<str>),
+    /// Integer literal
+    Int(i64),
+    /// Float literal, kept as its decimal text without `_` separators so
+    /// it is written back unchanged. Serialized as a number, deserialized
+    /// numbers get the shortest text reading back as the same `f64`.
+    Float(
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "serialize_float",
+                deserialize_with = "deserialize_float"
+            )
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "f64"))]
+        Arc<str>,
+    ),
+    /// `None`
+    None,
+}
+
+impl ConfigValue {
+    /// Returns the string value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ConfigValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer value
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            ConfigValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a float literal, or of an integer one
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            ConfigValue::Float(text) => text.parse().ok(),
+            ConfigValue::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for `None`
+    pub fn is_none(&self) -> bool {
+        matches!(self, ConfigValue::None)
+    }
+
+    /// The value of `literal`, or the warning it is skipped with
+    fn from_literal<F>(literal: lite::Literal, intern: F) -> Result<Self, WarningKind>
+    where
+        F: Fn(&str) -> Arc<str>,
+    {
+        Ok(match literal {
+            lite::Literal::String(value) => ConfigValue::String(intern(&value)),
+            lite::Literal::Int(value) => ConfigValue::Int(value),
+            lite::Literal::IntOutOfRange => return Err(WarningKind::IntegerOutOfRange),
+            lite::Literal::Float(text) => ConfigValue::Float(text.into()),
+            lite::Literal::None => ConfigValue::None,
+        })
+    }
+
+    /// The value of `expr`, or the warning it is skipped with
+    #[cfg(feature = "rustpython")]
+    fn from_expr<F>(expr: &Expression, src: &str, intern: F) -> Result<Self, WarningKind>
+    where
+        F: Fn(&str) -> Arc<str>,
+    {
+        let value = match &expr.node {
+            ExpressionType::String { .. } => {
+                get_string(expr).map(|value| ConfigValue::String(intern(&value)))
+            }
+            ExpressionType::Number {
+                value: Number::Integer { value },
+            } => {
+                // the decimal form of the big integer, sysconfigdata only
+                // holds small ones
+                return value
+                    .to_string()
+                    .parse()
+                    .map(ConfigValue::Int)
+                    .map_err(|_| WarningKind::IntegerOutOfRange);
+            }
+            ExpressionType::Number {
+                value: Number::Float { value },
+            } if value.is_finite() => {
+                let location = expr.location;
+                let text = lite::float_at(src, location.row(), location.column())
+                    .filter(|text| text.parse() == Ok(*value))
+                    .map_or_else(|| float_text(*value), Arc::from);
+                Some(ConfigValue::Float(text))
+            }
+            ExpressionType::None => Some(ConfigValue::None),
+            // negative numbers, as the lightweight parser reads them
+            ExpressionType::Unop {
+                op: UnaryOperator::Neg,
+                a,
+            } => match &a.node {
+                ExpressionType::Number {
+                    value: Number::Integer { value },
+                } => {
+                    return format!("-{}", value)
+                        .parse()
+                        .map(ConfigValue::Int)
+                        .map_err(|_| WarningKind::IntegerOutOfRange);
+                }
+                ExpressionType::Number { .. } => match ConfigValue::from_expr(a, src, intern)? {
+                    ConfigValue::Float(text) => {
+                        Some(ConfigValue::Float(format!("-{}", text).into()))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        value.ok_or(WarningKind::UnknownValueType)
+    }
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigValue::String(value) => f.write_str(value),
+            ConfigValue::Int(value) => write!(f, "{}", value),
+            ConfigValue::Float(text) => f.write_str(text),
+            ConfigValue::None => f.write_str("None"),
+        }
+    }
 }
 
 impl SysConfigData {
-    pub fn parse(src: &str) -> Result<Self, Error> {
-        let program = parser::parse_program(src)?;
-        let mut vars = BuildTimeVars::default();
-        for stmt in program.statements {
-            if let StatementType::Assign { targets, value } = stmt.node {
-                let var_name = targets.get(0).ok_or(Error::MissingBuildTimeVars)?;
+    pub fn parse(src: &str, options: &ParseOptions) -> Result<(Self, ParseReport), Error> {
+        let mut vars = BuildTimeVars {
+            keep_placeholders: options.keep_placeholders,
+            ..BuildTimeVars::default()
+        };
+        let mut report = ParseReport::default();
+        let intern = |s: &str| match &options.interner {
+            Some(interner) => interner.intern(s),
+            None => Arc::from(s),
+        };
+        let limits = &options.limits;
+        if src.len() > limits.max_file_size {
+            return Err(Error::LimitExceeded {
+                limit: "file size",
+                max: limits.max_file_size,
+            });
+        }
+        if lite::nesting_depth(src) > limits.max_depth {
+            return Err(Error::LimitExceeded {
+                limit: "nesting depth",
+                max: limits.max_depth,
+            });
+        }
+        let deadline = limits
+            .timeout
+            .map(|timeout| (Instant::now() + timeout, timeout));
+        let check_deadline = || match deadline {
+            Some((deadline, timeout)) if Instant::now() >= deadline => Err(Error::LimitExceeded {
+                limit: "timeout (ms)",
+                max: timeout.as_millis().min(usize::MAX as u128) as usize,
+            }),
+            _ => Ok(()),
+        };
+        let mut seen = HashSet::new();
+        let mut visit = |key: String, value: Result<ConfigValue, WarningKind>| {
+            if report.total_keys >= limits.max_entries {
+                return Err(Error::LimitExceeded {
+                    limit: "entries",
+                    max: limits.max_entries,
+                });
+            }
+            check_deadline()?;
+            let value = match value {
+                Ok(value) => Some(value),
+                Err(kind) => {
+                    report.warn(options, kind, &key)?;
+                    None
+                }
+            };
+            report.record_value(value.as_ref());
+            if !seen.insert(key.clone()) {
+                report.warn(options, WarningKind::DuplicateKey, &key)?;
+            }
+            let recognized = match vars.set(&key, value.as_ref()) {
+                Ok(recognized) => recognized,
+                Err(err) if options.required_keys == RequiredKeys::Default => return Err(err),
+                Err(_) => true,
+            };
+            if recognized && value.as_ref().is_some_and(|value| is_coerced(&key, value)) {
+                report.warn(options, WarningKind::Coercion, &key)?;
+            }
+            if let Some(value) = value {
+                vars.raw.insert(intern(&key), value);
+            }
+            report.record_key(key, recognized);
+            Ok(())
+        };
+        let assignments = match options.backend {
+            #[cfg(feature = "rustpython")]
+            Backend::RustPython => visit_program(src, intern, &mut visit)?,
+            #[cfg(not(feature = "rustpython"))]
+            Backend::RustPython => {
+                return Err(Error::Unsupported(
+                    "the RustPython backend requires the rustpython feature".to_string(),
+                ))
+            }
+            Backend::Lightweight => lite::parse_build_time_vars(
+                src,
+                |_| true,
+                |key, value| {
+                    let value = value.ok_or(WarningKind::UnknownValueType);
+                    visit(
+                        key,
+                        value.and_then(|value| ConfigValue::from_literal(value, intern)),
+                    )
+                },
+            )?,
+        };
+        check_deadline()?;
+        if assignments > 1 {
+            report.warn(options, WarningKind::MultipleAssignments, "build_time_vars")?;
+        }
+        vars.apply_implementation_profile();
+        if let Some(key) = vars.static_lto_key() {
+            report.warn(options, WarningKind::StaticLto, key)?;
+        }
+        if let Some(key) = vars.unsupported_link_key() {
+            report.warn(options, WarningKind::UnsupportedLinkModel, key)?;
+        }
+        if !abiflags::unknown_abiflags(&vars.abiflags).is_empty() {
+            report.warn(options, WarningKind::UnknownAbiFlags, "ABIFLAGS")?;
+        }
+        match &options.required_keys {
+            RequiredKeys::Default => {
+                if vars.version.is_empty() {
+                    // no build_time_vars found
+                    return Err(Error::MissingBuildTimeVars);
+                }
+            }
+            RequiredKeys::Keys(keys) => {
+                if assignments == 0 {
+                    return Err(Error::MissingBuildTimeVars);
+                }
+                let missing: Vec<String> = keys
+                    .iter()
+                    .filter(|key| {
+                        matches!(vars.raw.get(key.as_str()), None | Some(ConfigValue::None))
+                    })
+                    .cloned()
+                    .collect();
+                if !missing.is_empty() {
+                    return Err(Error::MissingKeys(missing));
+                }
+            }
+        }
+        vars.check_supported()?;
+        Ok((
+            SysConfigData {
+                build_time_vars: vars,
+            },
+            report,
+        ))
+    }
+}
+
+/// Reads the `build_time_vars = {...}` assignments of `src` with rustpython,
+/// calling `visit` for each entry with a string key of the last one, see
+/// [`WarningKind::MultipleAssignments`]. Returns the number of assignments.
+#[cfg(feature = "rustpython")]
+fn visit_program<I, V>(src: &str, intern: I, mut visit: V) -> Result<usize, Error>
+where
+    I: Fn(&str) -> Arc<str> + Copy,
+    V: FnMut(String, Result<ConfigValue, WarningKind>) -> Result<(), Error>,
+{
+    let program = parser::parse_program(src)?;
+    let mut dicts = Vec::new();
+    collect_build_time_vars(&program.statements, &mut dicts)?;
+    if let Some(elements) = dicts.last() {
+        for (key, value) in elements.iter() {
+            if let Some(key) = key.as_ref().and_then(get_string) {
+                visit(key, ConfigValue::from_expr(value, src, intern))?;
+            }
+        }
+    }
+    Ok(dicts.len())
+}
+
+/// Elements of the dicts assigned to `build_time_vars` in `statements` and
+/// the branches of their `if` statements, in source order
+#[cfg(feature = "rustpython")]
+fn collect_build_time_vars<'a>(
+    statements: &'a [Statement],
+    dicts: &mut Vec<&'a [(Option<Expression>, Expression)]>,
+) -> Result<(), Error> {
+    for stmt in statements {
+        match &stmt.node {
+            StatementType::Assign { targets, value } => {
+                let var_name = targets.first().ok_or(Error::MissingBuildTimeVars)?;
                 match &var_name.node {
                     ExpressionType::Identifier { name } if name == "build_time_vars" => {}
                     _ => continue,
                 }
-                if let ExpressionType::Dict { elements } = value.node {
-                    for (key, value) in elements {
-                        if let Some(key) = key.and_then(|key| get_string(&key)) {
-                            match key.as_str() {
-                                "ABIFLAGS" => {
-                                    vars.abiflags = get_string(&value).unwrap_or_default()
-                                }
-                                "COUNT_ALLOCS" => vars.count_allocs = get_bool(&value),
-                                "CFLAGS" => vars.cflags = get_string(&value).unwrap_or_default(),
-                                "LIBPL" => vars.config_dir = get_string(&value).unwrap_or_default(),
-                                "EXT_SUFFIX" => {
-                                    vars.ext_suffix = get_string(&value).unwrap_or_default()
-                                }
-                                "exec_prefix" => {
-                                    vars.exec_prefix = get_string(&value).unwrap_or_default()
-                                }
-                                "INCLUDEDIR" => {
-                                    vars.include_dir = get_string(&value).unwrap_or_default()
-                                }
-                                "LIBDIR" => vars.lib_dir = get_string(&value).unwrap_or_default(),
-                                "LIBS" => vars.libs = get_string(&value).unwrap_or_default(),
-                                "LDFLAGS" => vars.ldflags = get_string(&value).unwrap_or_default(),
-                                "LDVERSION" => {
-                                    vars.ld_version = get_string(&value).unwrap_or_default()
-                                }
-                                "prefix" => vars.prefix = get_string(&value).unwrap_or_default(),
-                                "Py_DEBUG" => vars.py_debug = get_bool(&value),
-                                "Py_ENABLE_SHARED" => vars.py_enable_shared = get_bool(&value),
-                                "Py_REF_DEBUG" => vars.py_ref_debug = get_bool(&value),
-                                "Py_TRACE_REFS" => vars.py_trace_refs = get_bool(&value),
-                                "SOABI" => vars.soabi = get_string(&value).unwrap_or_default(),
-                                "SHLIB_SUFFIX" => {
-                                    vars.shlib_suffix = get_string(&value).unwrap_or_default()
-                                }
-                                "SIZEOF_VOID_P" => {
-                                    vars.size_of_void_p = get_number(&value)
-                                        .ok_or(Error::KeyError("SIZEOF_VOID_P"))?
-                                        as u32
-                                }
-                                "VERSION" => {
-                                    vars.version =
-                                        get_string(&value).ok_or(Error::KeyError("VERSION"))?
-                                }
-                                _ => continue,
-                            }
-                        } else {
-                            continue;
-                        }
-                    }
+                if let ExpressionType::Dict { elements } = &value.node {
+                    dicts.push(elements);
                 }
             }
+            StatementType::If { body, orelse, .. } => {
+                collect_build_time_vars(body, dicts)?;
+                if let Some(orelse) = orelse {
+                    collect_build_time_vars(orelse, dicts)?;
+                }
+            }
+            _ => {}
         }
-        if vars.version.is_empty() {
-            // no build_time_vars found
-            return Err(Error::MissingBuildTimeVars);
+    }
+    Ok(())
+}
+
+impl BuildTimeVars {
+    /// Raw string value of `key`, empty if missing or not a string
+    fn get_str(&self, key: &str) -> &str {
+        match self.raw.get(key) {
+            Some(ConfigValue::String(value)) => value,
+            _ => "",
         }
-        Ok(SysConfigData {
-            build_time_vars: vars,
-        })
+    }
+
+    /// Raw integer value of `key`
+    fn get_int(&self, key: &str) -> Option<i64> {
+        match self.raw.get(key) {
+            Some(ConfigValue::Int(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// `SOABI`, falling back to the tag part of `EXT_SUFFIX`
+    fn soabi_or_ext_suffix(&self) -> &str {
+        if !self.soabi.is_empty() {
+            return &self.soabi;
+        }
+        self.ext_suffix.split('.').nth(1).unwrap_or_default()
+    }
+
+    fn implementation(&self) -> PythonImplementation {
+        let soabi = self.soabi_or_ext_suffix();
+        if soabi.starts_with("pypy") {
+            PythonImplementation::PyPy
+        } else if soabi.starts_with("graalpy") {
+            PythonImplementation::GraalPy
+        } else {
+            PythonImplementation::CPython
+        }
+    }
+
+    /// The ABI relevant leading parts of `SOABI`, the remaining parts describe the platform
+    fn soabi_abi_parts(&self) -> String {
+        let parts = match self.implementation() {
+            // cpython-38-darwin, cp38-win_amd64
+            PythonImplementation::CPython if self.soabi_or_ext_suffix().starts_with("cpython") => 2,
+            PythonImplementation::CPython => 1,
+            PythonImplementation::PyPy => 2,
+            PythonImplementation::GraalPy => 3,
+        };
+        self.soabi_or_ext_suffix()
+            .split('-')
+            .take(parts)
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// The platform part of `SOABI`, for example `x86_64-linux-gnu`
+    fn platform_chunk(&self) -> &str {
+        let soabi = self.soabi_or_ext_suffix();
+        let abi_len = self.soabi_abi_parts().len();
+        soabi[abi_len..].trim_start_matches('-')
+    }
+
+    /// Rejects Python 2 configurations, most derived values would be wrong
+    fn check_supported(&self) -> Result<(), Error> {
+        let major = self.version.split('.').next().unwrap_or_default();
+        match major.parse::<u32>() {
+            Ok(major) if major < 3 => Err(Error::UnsupportedPython(self.version.clone())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fill in keys that alternative implementations and minimal sources
+    /// don't ship
+    fn apply_implementation_profile(&mut self) {
+        self.expand_extra_flags();
+        self.expand_path_placeholders();
+        if self.size_of_void_p == 0 && !self.platform_chunk().is_empty() {
+            self.size_of_void_p = platform_pointer_size(self.platform_chunk());
+        }
+        if self.implementation() == PythonImplementation::CPython {
+            // cpython-37m-x86_64-linux-gnu, sources without ABIFLAGS
+            if !self.raw.contains_key("ABIFLAGS") {
+                if let Some(tag) = self.soabi_or_ext_suffix().strip_prefix("cpython-") {
+                    let tag = tag.split('-').next().unwrap_or_default();
+                    self.abiflags = tag
+                        .trim_start_matches(|c: char| c.is_ascii_digit())
+                        .to_string();
+                }
+            }
+            return;
+        }
+        // pypy310-pp73-x86_64-linux-gnu, graalpy240-310-native-x86_64-linux
+        let soabi = self.soabi_or_ext_suffix().to_string();
+        if soabi.is_empty() {
+            return;
+        }
+        let abi_parts = self.soabi_abi_parts();
+        if self.version.is_empty() {
+            let version = match self.implementation() {
+                PythonImplementation::PyPy => abi_parts.split('-').next().map(|x| &x[4..]),
+                _ => abi_parts.split('-').nth(1),
+            };
+            // the major version is the first digit, `310` is 3.10
+            if let Some(version) = version.filter(|v| v.len() >= 2 && v.is_ascii()) {
+                self.version = format!("{}.{}", &version[..1], &version[1..]);
+            }
+        }
+        if self.ext_suffix.is_empty() {
+            let shlib_suffix = if self.shlib_suffix.is_empty() {
+                ".so"
+            } else {
+                &self.shlib_suffix
+            };
+            self.ext_suffix = format!(".{}{}", soabi, shlib_suffix);
+        }
+    }
+
+    /// Store a recognized key, returns `false` if the key is ignored
+    fn set(&mut self, key: &str, value: Option<&ConfigValue>) -> Result<bool, Error> {
+        let get_string = || value.and_then(ConfigValue::as_str).map(str::to_string);
+        let get_bool = || value.and_then(ConfigValue::as_int) == Some(1);
+        match key {
+            "ABIFLAGS" => self.abiflags = get_string().unwrap_or_default(),
+            "BINDIR" => self.bindir = get_string().unwrap_or_default(),
+            "COUNT_ALLOCS" => self.count_allocs = get_bool(),
+            "CC" => self.cc = get_string().unwrap_or_default(),
+            "CXX" => self.cxx = get_string().unwrap_or_default(),
+            "CFLAGS" => self.cflags = get_string().unwrap_or_default(),
+            "LDSHARED" => self.ldshared = get_string().unwrap_or_default(),
+            "LIBPL" => self.config_dir = get_string().unwrap_or_default(),
+            "EXT_SUFFIX" => self.ext_suffix = get_string().unwrap_or_default(),
+            "EXE" => self.exe = get_string().unwrap_or_default(),
+            "exec_prefix" => self.exec_prefix = get_string().unwrap_or_default(),
+            "INCLUDEDIR" => self.include_dir = get_string().unwrap_or_default(),
+            "LIBDIR" => self.lib_dir = get_string().unwrap_or_default(),
+            "LIBS" => self.libs = get_string().unwrap_or_default(),
+            "LDFLAGS" => self.ldflags = get_string().unwrap_or_default(),
+            "LDVERSION" => self.ld_version = get_string().unwrap_or_default(),
+            "MULTIARCH" => self.multiarch = get_string().unwrap_or_default(),
+            "MACHDEP" => self.machdep = get_string().unwrap_or_default(),
+            "HOST_GNU_TYPE" => self.host_gnu_type = get_string().unwrap_or_default(),
+            "MACOSX_DEPLOYMENT_TARGET" => {
+                self.macosx_deployment_target = get_string().unwrap_or_default()
+            }
+            "prefix" => self.prefix = get_string().unwrap_or_default(),
+            "Py_DEBUG" => self.py_debug = get_bool(),
+            "Py_ENABLE_SHARED" => self.py_enable_shared = get_bool(),
+            "Py_REF_DEBUG" => self.py_ref_debug = get_bool(),
+            "Py_TRACE_REFS" => self.py_trace_refs = get_bool(),
+            "SOABI" => self.soabi = get_string().unwrap_or_default(),
+            "SHLIB_SUFFIX" => self.shlib_suffix = get_string().unwrap_or_default(),
+            "SIZEOF_VOID_P" => {
+                let size = value
+                    .and_then(ConfigValue::as_int)
+                    .ok_or(Error::KeyError("SIZEOF_VOID_P"))?;
+                self.size_of_void_p = u32::try_from(size).map_err(|_| Error::InvalidValue {
+                    key: "SIZEOF_VOID_P".to_string(),
+                    message: format!("pointer size {} is out of range", size),
+                })?
+            }
+            "VERSION" => self.version = get_string().ok_or(Error::KeyError("VERSION"))?,
+            "implementation_version" => self.implementation_version = get_string(),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+/// Whether `value` has another type than the one [`BuildTimeVars`] reads
+/// the recognized `key` as, `None` values aside
+fn is_coerced(key: &str, value: &ConfigValue) -> bool {
+    let expects_int = matches!(
+        key,
+        "COUNT_ALLOCS"
+            | "Py_DEBUG"
+            | "Py_ENABLE_SHARED"
+            | "Py_REF_DEBUG"
+            | "Py_TRACE_REFS"
+            | "SIZEOF_VOID_P"
+    );
+    match value {
+        ConfigValue::String(_) => expects_int,
+        ConfigValue::Int(_) => !expects_int,
+        ConfigValue::Float(_) => true,
+        ConfigValue::None => false,
+    }
+}
+
+/// Pointer size of a platform as spelled in `SOABI`/`MULTIARCH`, 4 for the
+/// ILP32 ABIs of 64-bit architectures such as `x86_64-linux-gnux32`
+fn platform_pointer_size(platform: &str) -> u32 {
+    if platform.ends_with("-gnux32") || platform.ends_with("_ilp32") {
+        return 4;
+    }
+    arch_pointer_size(platform.split('-').next().unwrap_or_default())
+}
+
+/// Pointer size of a CPU architecture as spelled in `SOABI`/`MULTIARCH`,
+/// guessed from the name for architectures missing from [`archs`]
+fn arch_pointer_size(name: &str) -> u32 {
+    match arch::find_by_name(name) {
+        Some(arch) => arch.pointer_size,
+        None if name.contains("64") => 8,
+        None => 4,
     }
 }
 
+/// Reads only `keys` from `_sysconfigdata.py` content, skipping the values of
+/// every other key without decoding them. Keys missing from the file or with
+/// values other than string, integer, float or `None` literals are left out.
+pub fn parse_keys(src: &str, keys: &[&str]) -> Result<BTreeMap<String, ConfigValue>, Error> {
+    let mut values = BTreeMap::new();
+    let assignments = lite::parse_build_time_vars(
+        src,
+        |key| keys.contains(&key),
+        |key, value| {
+            if let Some(Ok(value)) =
+                value.map(|value| ConfigValue::from_literal(value, |s: &str| Arc::from(s)))
+            {
+                values.insert(key, value);
+            }
+            Ok(())
+        },
+    )?;
+    if assignments == 0 {
+        return Err(Error::MissingBuildTimeVars);
+    }
+    Ok(values)
+}
+
+/// Lowercase hex SHA-256 of `data`
+#[cfg(any(feature = "http", feature = "manifest"))]
+fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Normalize a tag the same way `packaging.tags` does
+fn normalize_tag(tag: &str) -> String {
+    tag.replace(['.', '-'], "_")
+}
+
+#[cfg(feature = "rustpython")]
 fn get_string(expr: &Expression) -> Option<String> {
     match &expr.node {
         ExpressionType::String { value: sg } => match sg {
@@ -287,7 +1690,7 @@ fn get_string(expr: &Expression) -> Option<String> {
                 let mut s = String::new();
                 for value in values {
                     if let StringGroup::Constant { value: cs } = value {
-                        s.push_str(&cs)
+                        s.push_str(cs)
                     }
                 }
                 Some(s)
@@ -298,25 +1701,6 @@ fn get_string(expr: &Expression) -> Option<String> {
     }
 }
 
-fn get_number(expr: &Expression) -> Option<i32> {
-    use num_traits::cast::ToPrimitive;
-
-    match &expr.node {
-        ExpressionType::Number { value } => {
-            if let Number::Integer { value } = value {
-                value.to_i32()
-            } else {
-                None
-            }
-        }
-        _ => None,
-    }
-}
-
-fn get_bool(expr: &Expression) -> bool {
-    get_number(expr).map(|x| x == 1).unwrap_or(false)
-}
-
 impl FromStr for PythonConfig {
     type Err = Error;
 
@@ -327,10 +1711,16 @@ impl FromStr for PythonConfig {
 
 #[cfg(test)]
 mod tests {
-    use super::{Error, PythonConfig};
+    use super::{
+        parse_keys, Backend, ConfigValue, Error, Limits, ParseOptions, PythonConfig,
+        PythonImplementation, PythonVersion, RequiredKeys, SysConfigData, WarningAction,
+        WarningKind,
+    };
     use std::fs;
+    use std::time::Duration;
 
     #[test]
+    #[allow(deprecated)]
     fn read_python_sysconfig_data() {
         let src =
             fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
@@ -340,12 +1730,32 @@ mod tests {
         assert_eq!(config.version(), "3.8");
         assert_eq!(config.version_major(), 3);
         assert_eq!(config.version_minor(), 8);
+        assert_eq!(config.python_version(), Some(PythonVersion::new(3, 8)));
+        assert_eq!(config.implementation(), PythonImplementation::CPython);
+        assert_eq!(config.python_tag(), "cp38");
+        assert_eq!(config.abi_tag(), "cp38");
 
         // Test FromStr impl
         let config: PythonConfig = src.parse().unwrap();
         assert_eq!(config.abiflags(), "");
     }
 
+    #[test]
+    fn parse_bytes() {
+        let src = include_bytes!("../tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py");
+        let config = PythonConfig::parse_bytes(src).unwrap();
+        assert_eq!(config.soabi(), "cpython-38-darwin");
+
+        let config =
+            PythonConfig::parse_bytes(b"\xEF\xBB\xBFbuild_time_vars = {'VERSION': '3.12'}")
+                .unwrap();
+        assert_eq!(config.version_str(), "3.12");
+
+        let err = PythonConfig::parse_bytes(b"# x\nbuild_time_vars = {'CC': '\xFF'}").unwrap_err();
+        assert!(matches!(err, Error::InvalidSyntax { line: 2, .. }));
+    }
+
+    #[cfg(feature = "rustpython")]
     #[test]
     fn read_invalid_python_sysconfig_data() {
         let config = PythonConfig::parse("i++").unwrap_err();
@@ -362,4 +1772,346 @@ mod tests {
                 .unwrap_err();
         assert!(matches!(config, Error::KeyError("SIZEOF_VOID_P")));
     }
+
+    #[test]
+    fn read_graalpy_sysconfig_data() {
+        let src =
+            fs::read_to_string("tests/fixtures/graalpy240_310_sysconfigdata_linux.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.implementation(), PythonImplementation::GraalPy);
+        assert_eq!(config.version_str(), "3.10");
+        assert_eq!(config.pointer_size(), 8);
+        assert_eq!(config.python_tag(), "graalpy310");
+        assert_eq!(config.abi_tag(), "graalpy240_310_native");
+
+        // non-ASCII tags don't give a version
+        let err = PythonConfig::parse(
+            "build_time_vars = {'SOABI': 'graalpy24-é1-native-x86_64-linux', \
+             'SIZEOF_VOID_P': 8}",
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::MissingBuildTimeVars));
+    }
+
+    #[test]
+    fn read_pypy_sysconfig_data() {
+        let src = fs::read_to_string("tests/fixtures/pypy310_pp73_sysconfigdata_linux.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.implementation(), PythonImplementation::PyPy);
+        assert_eq!(config.implementation_version(), Some("7.3.17"));
+        assert_eq!(config.version_str(), "3.10");
+        assert_eq!(config.pointer_size(), 8);
+        assert_eq!(config.python_tag(), "pp310");
+        assert_eq!(config.abi_tag(), "pypy310_pp73");
+        assert_eq!(config.ext_suffix(), ".pypy310-pp73-x86_64-linux-gnu.so");
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn json_schema_matches_serialized_form() {
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&config.to_json().unwrap()).unwrap();
+        let schema = serde_json::to_value(PythonConfig::json_schema()).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        for key in json.as_object().unwrap().keys() {
+            assert!(properties.contains_key(key), "{} missing from schema", key);
+        }
+        assert_eq!(json["derivation_version"], super::DERIVATION_VERSION);
+        assert_eq!(config.derivation_version(), None);
+        let config = PythonConfig::from_json(&config.to_json().unwrap()).unwrap();
+        assert_eq!(config.soabi(), "cpython-38-darwin");
+        assert_eq!(config.derivation_version(), Some(super::DERIVATION_VERSION));
+    }
+
+    #[test]
+    fn multiple_assignments() {
+        let src = "import sys\n\
+                   build_time_vars = {'VERSION': '3.6', 'OLD': 1}\n\
+                   if sys.version_info >= (3, 8):\n    \
+                       build_time_vars = {'VERSION': '3.8', 'LIBS': '-ldl'}\n\
+                   else:\n    \
+                       build_time_vars = {'VERSION': '3.7', 'LIBS': '-lpthread'}\n";
+        for backend in [
+            #[cfg(feature = "rustpython")]
+            Backend::RustPython,
+            Backend::Lightweight,
+        ] {
+            let options = ParseOptions::new().backend(backend);
+            let (sys_config_data, report) = SysConfigData::parse(src, &options).unwrap();
+            let config = PythonConfig::new(sys_config_data);
+            assert_eq!(config.version_str(), "3.7");
+            assert_eq!(config.libs(), "-lpthread");
+            assert_eq!(config.get("OLD"), None);
+            assert_eq!(report.total_keys, 2);
+            assert_eq!(report.warnings.len(), 1);
+            assert_eq!(report.warnings[0].kind, WarningKind::MultipleAssignments);
+            assert_eq!(
+                report.warnings[0].to_string(),
+                "build_time_vars is assigned more than once, the last assignment wins"
+            );
+
+            let options = options.warning(WarningKind::MultipleAssignments, WarningAction::Deny);
+            assert!(matches!(
+                SysConfigData::parse(src, &options),
+                Err(Error::Warning(_))
+            ));
+        }
+        let values = parse_keys(src, &["LIBS"]).unwrap();
+        assert_eq!(values["LIBS"], ConfigValue::String("-lpthread".into()));
+    }
+
+    #[test]
+    fn float_values() {
+        let src = "build_time_vars = {'VERSION': '3.12',\n 'RATIO': 1.50, 'TINY': 1_0e-3}";
+        for backend in [
+            #[cfg(feature = "rustpython")]
+            Backend::RustPython,
+            Backend::Lightweight,
+        ] {
+            let options = ParseOptions::new().backend(backend);
+            let config = PythonConfig::parse_with_options(src, &options).unwrap();
+            assert_eq!(
+                config.get("RATIO"),
+                Some(&ConfigValue::Float("1.50".into()))
+            );
+            assert_eq!(
+                config.get("TINY"),
+                Some(&ConfigValue::Float("10e-3".into()))
+            );
+            assert_eq!(config.get("TINY").unwrap().as_float(), Some(0.01));
+            #[cfg(feature = "emit")]
+            assert!(config.to_sysconfigdata().contains("'RATIO': 1.50,"));
+        }
+        #[cfg(feature = "serde")]
+        {
+            let config = PythonConfig::parse(src).unwrap();
+            let json = config.to_json().unwrap();
+            assert!(json.contains("\"RATIO\": 1.5,"));
+            let config = PythonConfig::from_json(&json).unwrap();
+            assert_eq!(config.get("RATIO"), Some(&ConfigValue::Float("1.5".into())));
+        }
+    }
+
+    #[test]
+    fn parse_only_requested_keys() {
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let values = parse_keys(&src, &["LIBS", "SIZEOF_VOID_P", "MISSING"]).unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(
+            values["LIBS"].as_str(),
+            Some("-ldl -lSystem  -framework CoreFoundation")
+        );
+        assert_eq!(values["SIZEOF_VOID_P"], ConfigValue::Int(8));
+
+        let err = parse_keys("i = 0", &["LIBS"]).unwrap_err();
+        assert!(matches!(err, Error::MissingBuildTimeVars));
+    }
+
+    #[test]
+    fn required_keys() {
+        let options = ParseOptions::new().required_keys(RequiredKeys::keys(vec!["CFLAGS"]));
+        let config = PythonConfig::parse_with_options(
+            "build_time_vars = {'CFLAGS': '-O2', 'SIZEOF_VOID_P': ''}",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(config.cflags(), "-O2");
+        assert_eq!(config.version_str(), "");
+
+        let options = ParseOptions::new()
+            .required_keys(RequiredKeys::keys(vec!["CFLAGS", "LIBS", "LDFLAGS"]));
+        let err = PythonConfig::parse_with_options(
+            "build_time_vars = {'CFLAGS': '-O2', 'LIBS': None}",
+            &options,
+        )
+        .unwrap_err();
+        assert!(matches!(&err, Error::MissingKeys(keys) if keys == &["LIBS", "LDFLAGS"]));
+        assert_eq!(err.to_string(), "missing required keys LIBS, LDFLAGS");
+
+        let err = PythonConfig::parse_with_options("i = 0", &options).unwrap_err();
+        assert!(matches!(err, Error::MissingBuildTimeVars));
+    }
+
+    #[test]
+    fn limits() {
+        let src = "build_time_vars = {'VERSION': '3.11', 'A': ((('(((',), 1), 2), 'B': 1}";
+        assert!(PythonConfig::parse(src).is_ok());
+        for (limits, name) in [
+            (Limits::new().max_file_size(10), "file size"),
+            (Limits::new().max_entries(2), "entries"),
+            (Limits::new().max_depth(2), "nesting depth"),
+        ] {
+            for backend in [
+                #[cfg(feature = "rustpython")]
+                Backend::RustPython,
+                Backend::Lightweight,
+            ] {
+                let options = ParseOptions::new().limits(limits).backend(backend);
+                let err = PythonConfig::parse_with_options(src, &options).unwrap_err();
+                assert!(matches!(err, Error::LimitExceeded { limit, .. } if limit == name));
+            }
+        }
+        let options = ParseOptions::new().limits(Limits::new().timeout(Duration::ZERO));
+        let err = PythonConfig::parse_with_options(src, &options).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::LimitExceeded {
+                limit: "timeout (ms)",
+                max: 0
+            }
+        ));
+        let options = ParseOptions::new().limits(Limits::new().timeout(Duration::from_secs(60)));
+        assert!(PythonConfig::parse_with_options(src, &options).is_ok());
+
+        let options = ParseOptions::new().limits(Limits::new().max_file_size(10));
+        let err = PythonConfig::read_with_options(src.as_bytes(), &options).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::LimitExceeded {
+                limit: "file size",
+                max: 10
+            }
+        ));
+        let options = ParseOptions::new().limits(Limits::new().max_file_size(src.len()));
+        assert!(PythonConfig::read_with_options(src.as_bytes(), &options).is_ok());
+
+        let nested = format!(
+            "build_time_vars = {{'VERSION': {}'3.11'{}}}",
+            "(".repeat(500),
+            ")".repeat(500)
+        );
+        let err = PythonConfig::parse(&nested).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "input exceeds the nesting depth limit of 200"
+        );
+    }
+
+    #[test]
+    fn reject_python2() {
+        let src = "build_time_vars = {'VERSION': '2.7', 'SIZEOF_VOID_P': 8}";
+        let err = PythonConfig::parse(src).unwrap_err();
+        assert!(matches!(&err, Error::UnsupportedPython(version) if version == "2.7"));
+        assert_eq!(
+            err.to_string(),
+            "unsupported Python 2.7, only Python 3 sysconfigdata can be read"
+        );
+        let err = PythonConfig::from_vars(vec![(
+            "VERSION".to_string(),
+            ConfigValue::String("2.7".into()),
+        )])
+        .unwrap_err();
+        assert!(matches!(err, Error::UnsupportedPython(_)));
+    }
+
+    #[test]
+    fn warnings() {
+        let src = "build_time_vars = {'VERSION': '3.8', 'Py_DEBUG': '1', 'AR': f(), 'AR': 'ar'}";
+        let (_, report) = PythonConfig::parse_with_report(src).unwrap();
+        let warnings: Vec<_> = report
+            .warnings
+            .iter()
+            .map(|warning| (warning.kind, warning.key.as_str()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (WarningKind::Coercion, "Py_DEBUG"),
+                (WarningKind::UnknownValueType, "AR"),
+                (WarningKind::DuplicateKey, "AR"),
+            ]
+        );
+
+        let strict = ParseOptions::new().deny_warnings(true);
+        let err = PythonConfig::parse_with_options(src, &strict).unwrap_err();
+        assert!(matches!(&err, Error::Warning(warning) if warning.kind == WarningKind::Coercion));
+        assert_eq!(
+            err.to_string(),
+            "denied warning: value of Py_DEBUG has an unexpected type"
+        );
+
+        let lenient = strict
+            .warning(WarningKind::Coercion, WarningAction::Ignore)
+            .warning(WarningKind::UnknownValueType, WarningAction::Warn);
+        let err = PythonConfig::parse_with_options(src, &lenient).unwrap_err();
+        assert!(
+            matches!(&err, Error::Warning(warning) if warning.kind == WarningKind::DuplicateKey)
+        );
+        let lenient = lenient.warning(WarningKind::DuplicateKey, WarningAction::Ignore);
+        assert!(PythonConfig::parse_with_options(src, &lenient).is_ok());
+    }
+
+    #[test]
+    fn parse_report() {
+        let (_, report) = PythonConfig::parse_with_report(
+            "build_time_vars = {'VERSION': '3.8', 'SIZEOF_VOID_P': 8, 'AR': 'ar', 'TZPATH': None, \
+             'LTO_RATIO': 0.5}",
+        )
+        .unwrap();
+        assert_eq!(report.total_keys, 5);
+        assert_eq!(report.recognized_keys, 2);
+        assert_eq!(report.ignored_keys, vec!["AR", "TZPATH", "LTO_RATIO"]);
+        assert_eq!(report.string_values, 2);
+        assert_eq!(report.int_values, 1);
+        assert_eq!(report.float_values, 1);
+        assert_eq!(report.none_values, 1);
+    }
+
+    #[test]
+    fn backends_agree() {
+        let lightweight = ParseOptions::new().backend(Backend::Lightweight);
+        for entry in fs::read_dir("tests/fixtures").unwrap() {
+            let src = fs::read_to_string(entry.unwrap().path()).unwrap();
+            let (config, report) = PythonConfig::parse_with_report(&src).unwrap();
+            let (lite_config, lite_report) = SysConfigData::parse(&src, &lightweight).unwrap();
+            assert_eq!(
+                config.sys_config_data.build_time_vars.raw,
+                lite_config.build_time_vars.raw
+            );
+            assert_eq!(report, lite_report);
+        }
+
+        let src = "build_time_vars = {'VERSION': '3.12', 'SIZEOF_LONG': 8, \
+                   'HUGE': 99999999999999999999, 'MAX': 9223372036854775807, \
+                   'MIN': -9223372036854775808, 'NEG': -1.50}";
+        let (config, report) = PythonConfig::parse_with_report(src).unwrap();
+        let (lite_config, lite_report) = SysConfigData::parse(src, &lightweight).unwrap();
+        assert_eq!(
+            config.sys_config_data.build_time_vars.raw,
+            lite_config.build_time_vars.raw
+        );
+        assert_eq!(report, lite_report);
+        assert_eq!(config.get("MAX"), Some(&ConfigValue::Int(i64::MAX)));
+        assert_eq!(config.get("MIN"), Some(&ConfigValue::Int(i64::MIN)));
+        assert_eq!(config.get("NEG").unwrap().to_string(), "-1.50");
+        assert_eq!(config.get("HUGE"), None);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].kind, WarningKind::IntegerOutOfRange);
+        assert_eq!(
+            report.warnings[0].to_string(),
+            "integer value of HUGE does not fit in 64 bits"
+        );
+
+        for size in ["-8", "4294967304"].iter() {
+            let src = format!(
+                "build_time_vars = {{'VERSION': '3.12', 'SIZEOF_VOID_P': {}}}",
+                size
+            );
+            for options in [ParseOptions::new(), lightweight.clone()].iter() {
+                let err = PythonConfig::parse_with_options(&src, options).unwrap_err();
+                assert!(matches!(err.root_cause(), Error::InvalidValue { .. }));
+                assert_eq!(err.key(), Some("SIZEOF_VOID_P"));
+                assert_eq!(
+                    err.to_string(),
+                    format!(
+                        "invalid value of SIZEOF_VOID_P: pointer size {} is out of range",
+                        size
+                    )
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,709 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::convert::shell_split;
+use crate::paths::windows_join;
+use crate::{BuildTimeVars, ConfigValue, PythonConfig, PythonImplementation};
+
+/// How libpython is provided by a distribution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LinkModel {
+    /// Shared library, `libpython3.X.so` or `libpython3.X.dylib`
+    Shared,
+    /// Static library linked into the interpreter, `libpython3.X.a`
+    Static,
+    /// macOS framework build
+    Framework,
+}
+
+impl fmt::Display for LinkModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkModel::Shared => write!(f, "Shared"),
+            LinkModel::Static => write!(f, "Static"),
+            LinkModel::Framework => write!(f, "Framework"),
+        }
+    }
+}
+
+/// What is linked against libpython, taken by the flag and build script
+/// helpers such as [`PythonConfig::link_flags`] and
+/// [`PythonConfig::cargo_link_directives`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkMode {
+    /// An extension module loaded by the interpreter, linking libpython
+    /// only where [`PythonConfig::should_link_libpython`] requires it
+    ExtensionModule,
+    /// A program embedding the interpreter, as for `--embed`
+    Embed,
+    /// A program embedding the interpreter with libpython linked
+    /// statically from [`PythonConfig::static_library_path`], falling back
+    /// to [`LinkMode::Embed`] for distributions without a static library
+    EmbedStatic,
+}
+
+impl LinkMode {
+    /// Whether libpython is always linked, for [`LinkMode::Embed`] and
+    /// [`LinkMode::EmbedStatic`]
+    pub fn is_embed(self) -> bool {
+        self != LinkMode::ExtensionModule
+    }
+}
+
+impl PythonConfig {
+    /// Returns how libpython is provided, see [`LinkModel`]
+    pub fn link_model(&self) -> LinkModel {
+        self.link_model_with_reason().0
+    }
+
+    /// The link model along with the rule that selected it
+    pub(crate) fn link_model_with_reason(&self) -> (LinkModel, String) {
+        let vars = &self.sys_config_data.build_time_vars;
+        let framework = vars.get_str("PYTHONFRAMEWORK");
+        if !framework.is_empty() {
+            return (
+                LinkModel::Framework,
+                format!("PYTHONFRAMEWORK={}", framework),
+            );
+        }
+        let ldlibrary = vars.get_str("LDLIBRARY");
+        let dll = vars.get_str("DLLLIBRARY");
+        if !dll.is_empty() && !vars.py_enable_shared {
+            // older mingw builds ship the DLL without setting Py_ENABLE_SHARED
+            return (LinkModel::Shared, format!("DLLLIBRARY={} is a DLL", dll));
+        }
+        if !vars.py_enable_shared {
+            let reason = if vars.raw.contains_key("Py_ENABLE_SHARED") {
+                "Py_ENABLE_SHARED=0".to_string()
+            } else {
+                "Py_ENABLE_SHARED is not set".to_string()
+            };
+            return (LinkModel::Static, reason);
+        }
+        if ldlibrary.ends_with(".dll.a") {
+            (
+                LinkModel::Shared,
+                format!("LDLIBRARY={} is the import library of a DLL", ldlibrary),
+            )
+        } else if ldlibrary.ends_with(".a") {
+            (
+                LinkModel::Static,
+                format!(
+                    "LDLIBRARY={} is a static library despite Py_ENABLE_SHARED=1",
+                    ldlibrary
+                ),
+            )
+        } else if ldlibrary.is_empty() {
+            (LinkModel::Shared, "Py_ENABLE_SHARED=1".to_string())
+        } else {
+            (
+                LinkModel::Shared,
+                format!(
+                    "Py_ENABLE_SHARED=1 and LDLIBRARY ends with {}",
+                    library_suffix(ldlibrary)
+                ),
+            )
+        }
+    }
+
+    /// Returns whether libpython was built with link time optimization, from
+    /// `--with-lto` in `CONFIG_ARGS` or `-flto` in `PY_CFLAGS_NODIST`. The
+    /// objects of its static library then hold compiler specific LTO bytecode,
+    /// linking them with another compiler or version fails with cryptic errors
+    /// unless they were built with `-ffat-lto-objects`.
+    pub fn static_lib_uses_lto(&self) -> bool {
+        self.sys_config_data.build_time_vars.lto_key().is_some()
+    }
+
+    /// Returns whether the interpreter was built with profile guided
+    /// optimization, from `--enable-optimizations` in `CONFIG_ARGS` or a
+    /// `-fprofile-use` or `-fprofile-instr-use` flag in the compiler flags.
+    /// `PGO_PROF_USE_FLAG` is ignored, configure sets it whenever the
+    /// compiler supports PGO.
+    pub fn built_with_pgo(&self) -> bool {
+        let vars = &self.sys_config_data.build_time_vars;
+        vars.has_configure_arg("--enable-optimizations")
+            || ["PY_CFLAGS", "PY_CFLAGS_NODIST"].iter().any(|key| {
+                vars.get_str(key).split_whitespace().any(|flag| {
+                    flag == "-fprofile-use"
+                        || flag.starts_with("-fprofile-use=")
+                        || flag.starts_with("-fprofile-instr-use")
+                })
+            })
+    }
+
+    /// Returns whether the interpreter binary was optimized with LLVM BOLT,
+    /// from `--enable-bolt` in `CONFIG_ARGS`, available since Python 3.12
+    pub fn built_with_bolt(&self) -> bool {
+        self.sys_config_data
+            .build_time_vars
+            .has_configure_arg("--enable-bolt")
+    }
+
+    /// Returns the absolute path of the shared libpython, as recorded in the
+    /// install name of macOS builds. Resolves `@rpath`, `@loader_path` and
+    /// `@executable_path` references and framework layouts, unversioned
+    /// framework symlinks are mapped to the `Versions/X.Y` library they point to.
+    ///
+    /// The DLL of mingw and Cygwin builds is in `BINDIR`.
+    ///
+    /// Returns `None` for static builds.
+    pub fn dylib_install_name(&self) -> Option<PathBuf> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let framework = vars.get_str("PYTHONFRAMEWORK");
+        if self.link_model() == LinkModel::Static {
+            return None;
+        }
+        let dll = vars.get_str("DLLLIBRARY");
+        if self.is_mingw() && !dll.is_empty() {
+            return Some(windows_join(&vars.bindir, &[dll]));
+        }
+        if self.is_cygwin() && !dll.is_empty() {
+            return Some(Path::new(&vars.bindir).join(dll));
+        }
+        let name = [vars.get_str("INSTSONAME"), vars.get_str("LDLIBRARY")]
+            .iter()
+            .copied()
+            .find(|name| !name.is_empty())?;
+        if name.ends_with(".a") {
+            return None;
+        }
+        if !framework.is_empty() {
+            let unversioned = format!("{0}.framework/{0}", framework);
+            let name = if name == unversioned {
+                format!("{0}.framework/Versions/{1}/{0}", framework, vars.version)
+            } else {
+                name.to_string()
+            };
+            return Some(Path::new(vars.get_str("PYTHONFRAMEWORKPREFIX")).join(name));
+        }
+        let lib_dir = Path::new(&vars.lib_dir);
+        let path = if let Some(name) = name.strip_prefix("@rpath/") {
+            lib_dir.join(name)
+        } else if let Some(name) = name.strip_prefix("@loader_path/") {
+            lib_dir.join(name)
+        } else if let Some(name) = name.strip_prefix("@executable_path/") {
+            Path::new(vars.get_str("BINDIR")).join(name)
+        } else {
+            lib_dir.join(name)
+        };
+        Some(path)
+    }
+
+    /// Returns the file name the dynamic loader looks up at runtime for the
+    /// shared libpython, e.g. `libpython3.11.so.1.0`, taken from `INSTSONAME`,
+    /// or `DLLLIBRARY` for mingw and Cygwin builds, `cygpython3.9.dll`.
+    ///
+    /// Returns `None` for static builds.
+    pub fn runtime_library(&self) -> Option<&str> {
+        let vars = &self.sys_config_data.build_time_vars;
+        if self.link_model() != LinkModel::Shared {
+            return None;
+        }
+        [
+            vars.get_str("DLLLIBRARY"),
+            vars.get_str("INSTSONAME"),
+            vars.get_str("LDLIBRARY"),
+        ]
+        .iter()
+        .copied()
+        .find(|name| !name.is_empty() && !name.ends_with(".a"))
+    }
+
+    /// Returns the name of the libpython DLL of mingw and Cygwin builds,
+    /// `DLLLIBRARY`, `cygpython3.9.dll`. Linkers use its import library, see
+    /// [`PythonConfig::python_lib_name`].
+    pub fn dll_library(&self) -> Option<&str> {
+        Some(self.sys_config_data.build_time_vars.get_str("DLLLIBRARY")).filter(|x| !x.is_empty())
+    }
+
+    /// Returns the libraries shared modules of the standard library link,
+    /// `SHLIBS`, usually the same as [`PythonConfig::libs`]
+    pub fn shlibs(&self) -> &str {
+        self.sys_config_data.build_time_vars.get_str("SHLIBS")
+    }
+
+    /// Returns whether a binary linked in `mode` has to link libpython.
+    ///
+    /// Embedding programs always do. Extension modules resolve the symbols
+    /// of the interpreter that loads them, linking libpython into them
+    /// breaks statically linked interpreters and loads a second copy, except
+    /// where the platform requires it: Windows, mingw and Cygwin DLLs can't
+    /// have undefined symbols and the Android loader doesn't resolve them
+    /// against the executable. Elsewhere `LIBPYTHON`, the flag of
+    /// extension modules since Python 3.8, decides, it's empty when they
+    /// don't link libpython.
+    pub fn should_link_libpython(&self, mode: LinkMode) -> bool {
+        if mode.is_embed()
+            || self.sys_platform() == "win32"
+            || self.is_cygwin()
+            || self.is_android()
+        {
+            return true;
+        }
+        self.get("LIBPYTHON")
+            .and_then(ConfigValue::as_str)
+            .is_some_and(|flag| !flag.trim().is_empty())
+    }
+
+    /// Returns the name to link libpython with, the argument of `-l`:
+    /// `python3.11` from `LDLIBRARY`, which is the import library
+    /// `libpython3.11.dll.a` rather than the DLL for mingw and Cygwin builds,
+    /// and `python311`, `python313t` or `python311_d` for MSVC builds.
+    ///
+    /// Falls back to `python` followed by `LDVERSION`, or `VERSION` and the
+    /// ABI flags.
+    pub fn python_lib_name(&self) -> String {
+        let vars = &self.sys_config_data.build_time_vars;
+        if self.sys_platform() == "win32" && !self.is_mingw() {
+            let free_threaded =
+                self.abiflags().contains('t') || vars.get_int("Py_GIL_DISABLED") == Some(1);
+            return format!(
+                "python{}{}{}{}",
+                self.major_minor().0,
+                self.major_minor().1,
+                if free_threaded { "t" } else { "" },
+                if self.debug() { "_d" } else { "" }
+            );
+        }
+        if let Some(name) = link_name(vars.get_str("LDLIBRARY")) {
+            return name.to_string();
+        }
+        if vars.ld_version.is_empty() {
+            format!("python{}{}", vars.version, vars.abiflags)
+        } else {
+            format!("python{}", vars.ld_version)
+        }
+    }
+
+    /// Returns the static libpython, `LIBRARY` in `LIBPL`, which shared
+    /// builds usually ship too. `None` when `LIBRARY` isn't a `.a` archive,
+    /// as for MSVC builds.
+    pub fn static_library_path(&self) -> Option<PathBuf> {
+        let library = self.sys_config_data.build_time_vars.get_str("LIBRARY");
+        if library.ends_with(".a") && !self.config_dir_str().is_empty() {
+            Some(Path::new(self.config_dir_str()).join(library))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the AIX import file extension modules resolve libpython
+    /// symbols with, `python.exp` in `LIBPL`, from the `-bI:` flag of
+    /// `LDSHARED` or `BLDSHARED`
+    pub fn aix_import_file(&self) -> Option<&str> {
+        self.sys_config_data.build_time_vars.aix_import_file()
+    }
+
+    /// Returns directories suitable for `-Wl,-rpath` when linking against
+    /// libpython: `LIBDIR` and `LIBPL`
+    pub fn rpath_suggestions(&self) -> Vec<PathBuf> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for dir in [&vars.lib_dir, &vars.config_dir].iter() {
+            let dir = PathBuf::from(dir);
+            if !dir.as_os_str().is_empty() && !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        dirs
+    }
+
+    /// Best-effort path of the interpreter executable an installation of this
+    /// distribution provides, `BINDIR/python3.X` (`python3.13t` for
+    /// free-threaded builds), `pypy3.X` or `graalpy` for alternative
+    /// implementations and `python.exe` in `prefix` on Windows
+    ///
+    /// Returns `None` when neither `BINDIR` nor `prefix` is known.
+    pub fn python_executable_path(&self) -> Option<PathBuf> {
+        let windows = self.sys_platform() == "win32";
+        let free_threaded = if self.abiflags().contains('t') {
+            "t"
+        } else {
+            ""
+        };
+        let name = match self.implementation() {
+            PythonImplementation::CPython if windows => format!("python{}", free_threaded),
+            PythonImplementation::CPython => {
+                format!("python{}{}", self.version_str(), free_threaded)
+            }
+            PythonImplementation::PyPy => format!("pypy{}", self.version_str()),
+            PythonImplementation::GraalPy => "graalpy".to_string(),
+        };
+        let exe = if self.exe().is_empty() && windows {
+            ".exe"
+        } else {
+            self.exe()
+        };
+        let file = format!("{}{}", name, exe);
+        if !self.bindir_str().is_empty() && windows {
+            Some(windows_join(self.bindir_str(), &[&file]))
+        } else if !self.bindir_str().is_empty() {
+            Some(Path::new(self.bindir_str()).join(file))
+        } else if self.prefix_str().is_empty() {
+            None
+        } else if windows {
+            Some(windows_join(self.prefix_str(), &[&file]))
+        } else {
+            Some(Path::new(self.prefix_str()).join("bin").join(file))
+        }
+    }
+}
+
+impl BuildTimeVars {
+    fn aix_import_file(&self) -> Option<&str> {
+        ["LDSHARED", "BLDSHARED"].iter().find_map(|key| {
+            self.get_str(key)
+                .split_whitespace()
+                .find_map(|flag| flag.trim_start_matches("-Wl,").strip_prefix("-bI:"))
+        })
+    }
+
+    /// Key describing a way of linking extension modules that can't be
+    /// reproduced outside the CPython build tree, if any: AIX import files
+    /// given relative to it such as `-bI:Modules/python.exp`
+    pub(crate) fn unsupported_link_key(&self) -> Option<&'static str> {
+        let relative = self
+            .aix_import_file()
+            .is_some_and(|file| !file.starts_with('/'));
+        if relative {
+            Some("LDSHARED")
+        } else {
+            None
+        }
+    }
+
+    /// Whether `arg` is one of the words of `CONFIG_ARGS`
+    fn has_configure_arg(&self, arg: &str) -> bool {
+        shell_split(self.get_str("CONFIG_ARGS"))
+            .iter()
+            .any(|word| word == arg)
+    }
+
+    /// Key enabling link time optimization of libpython, if any
+    pub(crate) fn lto_key(&self) -> Option<&'static str> {
+        // --with-lto takes yes, full or thin since 3.11, and no to turn it off
+        let configured = shell_split(self.get_str("CONFIG_ARGS")).iter().any(|word| {
+            match word.strip_prefix("--with-lto") {
+                Some(value) => value.is_empty() || (value.starts_with('=') && value != "=no"),
+                None => false,
+            }
+        });
+        if configured {
+            Some("CONFIG_ARGS")
+        } else if self
+            .get_str("PY_CFLAGS_NODIST")
+            .split_whitespace()
+            .any(|flag| flag == "-flto" || flag.starts_with("-flto="))
+        {
+            Some("PY_CFLAGS_NODIST")
+        } else {
+            None
+        }
+    }
+
+    /// Key enabling link time optimization when libpython is only available
+    /// as a static library of LTO objects without machine code, see
+    /// [`PythonConfig::static_lib_uses_lto`]
+    pub(crate) fn static_lto_key(&self) -> Option<&'static str> {
+        let fat = self
+            .get_str("PY_CFLAGS_NODIST")
+            .split_whitespace()
+            .any(|flag| flag == "-ffat-lto-objects");
+        if self.py_enable_shared || !self.get_str("PYTHONFRAMEWORK").is_empty() || fat {
+            return None;
+        }
+        self.lto_key()
+    }
+}
+
+/// Name to pass to `-l` for the library file `file_name`, `python3.11` for
+/// `libpython3.11.so.1.0` or `libpython3.11.dll.a`
+fn link_name(file_name: &str) -> Option<&str> {
+    let stem = file_name.strip_prefix("lib")?;
+    let stem = match stem.strip_suffix(".dll.a") {
+        Some(stem) => stem,
+        None => &stem[..stem.len() - library_suffix(stem).len()],
+    };
+    Some(stem).filter(|x| !x.is_empty() && !x.contains('/'))
+}
+
+/// Extension of a library file name, including versions after `.so`
+fn library_suffix(name: &str) -> &str {
+    let start = name
+        .find(".so")
+        .or_else(|| name.rfind('.'))
+        .unwrap_or(name.len());
+    &name[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LinkMode, LinkModel, PythonConfig, WarningKind};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn dylib_install_name() {
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(
+            config.dylib_install_name().unwrap(),
+            Path::new("/Applications/Xcode.app/Contents/Developer/Library/Frameworks/Python3.framework/Versions/3.8/Python3")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'Py_ENABLE_SHARED': 1, 'LIBDIR': '/opt/python/lib', \
+             'INSTSONAME': '@rpath/libpython3.11.dylib'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.dylib_install_name().unwrap(),
+            Path::new("/opt/python/lib/libpython3.11.dylib")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'PYTHONFRAMEWORK': 'Python', \
+             'PYTHONFRAMEWORKPREFIX': '/Library/Frameworks', 'INSTSONAME': 'Python.framework/Python'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.dylib_install_name().unwrap(),
+            Path::new("/Library/Frameworks/Python.framework/Versions/3.11/Python")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'LDLIBRARY': 'libpython3.11.a'}",
+        )
+        .unwrap();
+        assert_eq!(config.dylib_install_name(), None);
+    }
+
+    #[test]
+    fn runtime_library() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.runtime_library(), Some("libpython3.11.so.1.0"));
+        assert_eq!(
+            config.rpath_suggestions(),
+            vec![
+                Path::new("/usr/local/lib"),
+                Path::new("/usr/local/lib/python3.11/config-3.11-x86_64-linux-gnu")
+            ]
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'INSTSONAME': 'libpython3.11.a'}",
+        )
+        .unwrap();
+        assert_eq!(config.runtime_library(), None);
+    }
+
+    #[test]
+    fn link_model() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.link_model(), LinkModel::Shared);
+
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.link_model(), LinkModel::Framework);
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'Py_ENABLE_SHARED': 1, 'LDLIBRARY': 'libpython3.11.a'}",
+        )
+        .unwrap();
+        assert_eq!(config.link_model(), LinkModel::Static);
+
+        let config = crate::fixtures::get("cpython311-mingw").unwrap().config();
+        assert_eq!(config.link_model(), LinkModel::Shared);
+        assert_eq!(config.runtime_library(), Some("libpython3.11.dll"));
+        assert_eq!(
+            config.dylib_install_name().unwrap().to_str(),
+            Some("C:\\msys64\\mingw64\\bin\\libpython3.11.dll")
+        );
+
+        assert_eq!(config.dll_library(), Some("libpython3.11.dll"));
+        assert_eq!(config.python_lib_name(), "python3.11");
+
+        let config = crate::fixtures::get("cpython39-cygwin").unwrap().config();
+        assert_eq!(config.link_model(), LinkModel::Shared);
+        assert_eq!(config.python_lib_name(), "python3.9");
+        assert_eq!(config.runtime_library(), Some("cygpython3.9.dll"));
+        assert_eq!(
+            config.dylib_install_name().unwrap(),
+            Path::new("/usr/bin/cygpython3.9.dll")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.8', 'ABIFLAGS': '', 'SHLIBS': '-lm', \
+             'DLLLIBRARY': 'libpython3.8.dll', 'LDLIBRARY': 'libpython3.8.dll.a', \
+             'BINDIR': '/mingw64/bin', 'EXT_SUFFIX': '.cp38-mingw_x86_64.pyd'}",
+        )
+        .unwrap();
+        assert_eq!(config.link_model(), LinkModel::Shared);
+        assert_eq!(config.runtime_library(), Some("libpython3.8.dll"));
+        assert_eq!(config.shlibs(), "-lm");
+
+        let linux = crate::fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(linux.python_lib_name(), "python3.11");
+        assert_eq!(linux.dll_library(), None);
+        let config = crate::fixtures::get("cpython38-darwin").unwrap().config();
+        assert_eq!(config.python_lib_name(), "python3.8");
+    }
+
+    #[test]
+    fn static_lib_uses_lto() {
+        let lto = |name: &str| {
+            let config = crate::fixtures::get(name).unwrap().config();
+            config.static_lib_uses_lto()
+        };
+        assert!(lto("cpython311-linux"));
+        assert!(lto("cpython310-conda"));
+        assert!(!lto("cpython39-linux-aarch64"));
+
+        // fat LTO objects still hold machine code
+        let src =
+            fs::read_to_string("tests/fixtures/cpython310_sysconfigdata_x86_64_conda_linux_gnu.py")
+                .unwrap();
+        let (_, report) = PythonConfig::parse_with_report(&src).unwrap();
+        assert!(report.warnings.is_empty());
+
+        let src = "build_time_vars = {'VERSION': '3.12', 'Py_ENABLE_SHARED': 0, \
+                   'PY_CFLAGS_NODIST': '-flto=thin'}";
+        let (config, report) = PythonConfig::parse_with_report(src).unwrap();
+        assert!(config.static_lib_uses_lto());
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].kind, WarningKind::StaticLto);
+        assert_eq!(report.warnings[0].key, "PY_CFLAGS_NODIST");
+
+        let lto = |args: &str| {
+            PythonConfig::parse(&format!(
+                "build_time_vars = {{'VERSION': '3.12', 'CONFIG_ARGS': \"{}\"}}",
+                args
+            ))
+            .unwrap()
+            .static_lib_uses_lto()
+        };
+        assert!(lto("'--with-lto'"));
+        assert!(lto("'--enable-optimizations' '--with-lto=thin'"));
+        assert!(!lto("'--with-lto=no'"));
+        assert!(!lto("'--with-lto-flags'"));
+        assert!(!lto("'CFLAGS=-DNO--with-lto'"));
+    }
+
+    #[test]
+    fn pgo_and_bolt() {
+        let config = |name: &str| crate::fixtures::get(name).unwrap().config();
+        assert!(config("cpython311-linux").built_with_pgo());
+        assert!(config("cpython311-freebsd").built_with_pgo());
+        assert!(!config("cpython38-darwin").built_with_pgo());
+        assert!(!config("cpython311-linux").built_with_bolt());
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', \
+             'CONFIG_ARGS': \"'--enable-shared' '--enable-bolt'\", \
+             'PY_CFLAGS_NODIST': '-fprofile-instr-use=code.profclangd'}",
+        )
+        .unwrap();
+        assert!(config.built_with_pgo());
+        assert!(config.built_with_bolt());
+    }
+
+    #[test]
+    fn aix_import_file() {
+        let src = "build_time_vars = {'VERSION': '3.11', 'MACHDEP': 'aix', \
+                   'LDSHARED': '/usr/lib/python3.11/config/ld_so_aix gcc \
+                   -bI:/usr/lib/python3.11/config/python.exp'}";
+        let (config, report) = PythonConfig::parse_with_report(src).unwrap();
+        assert_eq!(
+            config.aix_import_file(),
+            Some("/usr/lib/python3.11/config/python.exp")
+        );
+        assert!(report.warnings.is_empty());
+
+        let src = "build_time_vars = {'VERSION': '3.11', 'MACHDEP': 'aix', \
+                   'LDSHARED': 'Modules/ld_so_aix gcc -bI:Modules/python.exp'}";
+        let (config, report) = PythonConfig::parse_with_report(src).unwrap();
+        assert_eq!(config.aix_import_file(), Some("Modules/python.exp"));
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].kind, WarningKind::UnsupportedLinkModel);
+        assert_eq!(
+            report.warnings[0].to_string(),
+            "LDSHARED links extension modules with a file of the CPython build tree"
+        );
+    }
+
+    #[test]
+    fn python_executable_path() {
+        let python = |name: &str| {
+            crate::fixtures::get(name)
+                .unwrap()
+                .config()
+                .python_executable_path()
+        };
+        assert_eq!(
+            python("cpython311-linux").unwrap(),
+            Path::new("/usr/local/bin/python3.11")
+        );
+        assert_eq!(
+            python("cpython313t-linux").unwrap(),
+            Path::new("/usr/local/bin/python3.13t")
+        );
+        assert_eq!(
+            python("pypy310-linux").unwrap(),
+            Path::new("/opt/pypy3.10-v7.3.17-linux64/bin/pypy3.10")
+        );
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': 'C:\\\\Python312', \
+             'EXT_SUFFIX': '.cp312-win_amd64.pyd'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.python_executable_path().unwrap(),
+            Path::new("C:\\Python312\\python.exe")
+        );
+        let config = PythonConfig::parse("build_time_vars = {'VERSION': '3.12'}").unwrap();
+        assert_eq!(config.python_executable_path(), None);
+    }
+
+    #[test]
+    fn should_link_libpython() {
+        let links = |name: &str| {
+            crate::fixtures::get(name)
+                .unwrap()
+                .config()
+                .should_link_libpython(LinkMode::ExtensionModule)
+        };
+        assert!(!links("cpython311-linux"));
+        assert!(!links("cpython38-darwin"));
+        assert!(links("cpython39-cygwin"));
+        assert!(links("cpython311-mingw"));
+        let config = crate::fixtures::get("cpython311-linux").unwrap().config();
+        assert!(config.should_link_libpython(LinkMode::Embed));
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'MACHDEP': 'android', \
+             'MULTIARCH': 'aarch64-linux-android', 'LIBPYTHON': '-lpython3.13', \
+             'LIBDIR': '/data/python/lib', 'LDVERSION': '3.13'}",
+        )
+        .unwrap();
+        assert!(config.should_link_libpython(LinkMode::ExtensionModule));
+        assert_eq!(
+            config.cargo_link_directives(LinkMode::ExtensionModule),
+            vec![
+                "cargo:rustc-link-search=native=/data/python/lib",
+                "cargo:rustc-link-lib=python3.13",
+            ]
+        );
+    }
+}
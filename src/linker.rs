@@ -0,0 +1,160 @@
+use std::fmt;
+
+use crate::PythonConfig;
+
+/// Linker options taking their value as the next argument, as in
+/// `-Wl,-rpath,/usr/lib`
+const OPTIONS_WITH_VALUE: &[&str] = &[
+    "-rpath",
+    "-rpath-link",
+    "-soname",
+    "-h",
+    "-R",
+    "-z",
+    "--version-script",
+    "-install_name",
+    "-headerpad",
+    "-framework",
+    "-undefined",
+    "-exported_symbols_list",
+    "-compatibility_version",
+    "-current_version",
+];
+
+/// Linker directive a compiler driver passes through, decoded from
+/// `-Wl,a,b` or `-Xlinker a` compiler flags
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkerArg {
+    /// The linker option, for example `-rpath` or `--as-needed`
+    pub option: String,
+    /// Value of options followed by a separate argument, `/x` for `-rpath,/x`
+    pub value: Option<String>,
+}
+
+impl LinkerArg {
+    /// Arguments for invoking the linker directly, for example with lld:
+    /// `["-rpath", "/x"]`
+    pub fn linker_args(&self) -> Vec<&str> {
+        let mut args = vec![self.option.as_str()];
+        args.extend(self.value.as_deref());
+        args
+    }
+
+    /// The compiler driver flag, `-Wl,-rpath,/x`
+    pub fn to_cc_flag(&self) -> String {
+        format!("-Wl,{}", self.linker_args().join(","))
+    }
+
+    /// `rustc` arguments passing this directive to a `cc`-like linker:
+    /// `-Clink-arg=-Wl,-rpath,/x`
+    pub fn to_rustc_args(&self) -> Vec<String> {
+        vec![format!("-Clink-arg={}", self.to_cc_flag())]
+    }
+}
+
+impl fmt::Display for LinkerArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_cc_flag())
+    }
+}
+
+/// Splits compiler driver `flags` into linker directives, decoded from
+/// `-Wl,` and `-Xlinker` flags, and the remaining flags
+pub fn parse_linker_flags(flags: &str) -> (Vec<LinkerArg>, Vec<String>) {
+    let mut raw = Vec::new();
+    let mut other = Vec::new();
+    let mut words = flags.split_whitespace();
+    while let Some(word) = words.next() {
+        if let Some(args) = word.strip_prefix("-Wl,") {
+            raw.extend(args.split(',').filter(|arg| !arg.is_empty()));
+        } else if word == "-Xlinker" {
+            raw.extend(words.next());
+        } else {
+            other.push(word.to_string());
+        }
+    }
+    let mut directives = Vec::new();
+    let mut raw = raw.into_iter();
+    while let Some(option) = raw.next() {
+        let value = if OPTIONS_WITH_VALUE.contains(&option) {
+            raw.next().map(str::to_string)
+        } else {
+            None
+        };
+        directives.push(LinkerArg {
+            option: option.to_string(),
+            value,
+        });
+    }
+    (directives, other)
+}
+
+impl PythonConfig {
+    /// Returns the linker directives of `LDFLAGS`, see [`parse_linker_flags`]
+    pub fn linker_args(&self) -> Vec<LinkerArg> {
+        parse_linker_flags(self.ldflags()).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_linker_flags, LinkerArg};
+    use crate::fixtures;
+
+    #[test]
+    fn linker_args() {
+        let (args, other) =
+            parse_linker_flags("-Wl,-rpath,/x,--as-needed -L/lib -Xlinker -z -Xlinker now");
+        assert_eq!(
+            args,
+            vec![
+                LinkerArg {
+                    option: "-rpath".to_string(),
+                    value: Some("/x".to_string())
+                },
+                LinkerArg {
+                    option: "--as-needed".to_string(),
+                    value: None
+                },
+                LinkerArg {
+                    option: "-z".to_string(),
+                    value: Some("now".to_string())
+                },
+            ]
+        );
+        assert_eq!(other, vec!["-L/lib"]);
+        assert_eq!(args[0].linker_args(), vec!["-rpath", "/x"]);
+        assert_eq!(args[0].to_rustc_args(), vec!["-Clink-arg=-Wl,-rpath,/x"]);
+        assert_eq!(args[2].to_string(), "-Wl,-z,now");
+
+        // AIX export and import files and Solaris options pass through unchanged
+        let (args, other) = parse_linker_flags(
+            "-Wl,-bE:Modules/python.exp -lld -bI:/usr/lib/python3.11/config/python.exp \
+             -Wl,-R,/usr/lib/64 -Wl,-h,libpython3.11.so.1.0",
+        );
+        let flags: Vec<String> = args.iter().map(LinkerArg::to_cc_flag).collect();
+        assert_eq!(
+            flags,
+            [
+                "-Wl,-bE:Modules/python.exp",
+                "-Wl,-R,/usr/lib/64",
+                "-Wl,-h,libpython3.11.so.1.0"
+            ]
+        );
+        assert_eq!(other, ["-lld", "-bI:/usr/lib/python3.11/config/python.exp"]);
+
+        let args = fixtures::get("cpython310-conda")
+            .unwrap()
+            .config()
+            .linker_args();
+        let flags: Vec<String> = args.iter().map(LinkerArg::to_cc_flag).collect();
+        assert_eq!(flags[3..5], ["-Wl,-z,relro", "-Wl,-z,now"]);
+        assert_eq!(
+            flags[flags.len() - 2..],
+            [
+                "-Wl,-rpath,/opt/conda/lib",
+                "-Wl,-rpath-link,/opt/conda/lib"
+            ]
+        );
+    }
+}
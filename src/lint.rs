@@ -0,0 +1,297 @@
+use std::fmt;
+
+use crate::{
+    ApplyOverrides, ConfigValue, Error, PythonConfig, PythonImplementation, SanitizeFlags,
+    Transform,
+};
+
+/// A check of [`PythonConfig::lint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// An installation directory outside `prefix` and `exec_prefix`, left
+    /// behind when a configuration was moved without relocating it
+    StalePrefix,
+    /// `-march=native` and similar flags tuning code for the CPU of the build
+    /// machine, which break binaries redistributed to other machines
+    NativeArch,
+    /// `LDVERSION` is missing, so the name of libpython has to be guessed
+    MissingLdversion,
+    /// `EXT_SUFFIX` doesn't match `SOABI` and `SHLIB_SUFFIX`
+    InconsistentExtSuffix,
+}
+
+impl LintRule {
+    /// Every rule
+    pub const ALL: &'static [LintRule] = &[
+        LintRule::StalePrefix,
+        LintRule::NativeArch,
+        LintRule::MissingLdversion,
+        LintRule::InconsistentExtSuffix,
+    ];
+
+    /// Name of the rule, `stale-prefix`
+    pub fn name(self) -> &'static str {
+        match self {
+            LintRule::StalePrefix => "stale-prefix",
+            LintRule::NativeArch => "native-arch",
+            LintRule::MissingLdversion => "missing-ldversion",
+            LintRule::InconsistentExtSuffix => "inconsistent-ext-suffix",
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A problem found by [`PythonConfig::lint`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintFinding {
+    /// Rule finding the problem
+    pub rule: LintRule,
+    /// Key of `build_time_vars` with the problem
+    pub key: String,
+    /// Description of the problem
+    pub message: String,
+    /// Value of `key` fixing the problem, `None` when it can't be fixed
+    /// automatically
+    pub fix: Option<ConfigValue>,
+}
+
+/// `KEY: message [rule]`, with the fix if any
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} [{}]", self.key, self.message, self.rule)?;
+        if let Some(fix) = &self.fix {
+            write!(f, ", fix: '{}'", fix)?;
+        }
+        Ok(())
+    }
+}
+
+/// Installation directories and whether they live under `exec_prefix`
+/// rather than `prefix`
+const INSTALL_DIRS: &[(&str, bool)] = &[
+    ("BINDIR", true),
+    ("BINLIBDEST", true),
+    ("CONFINCLUDEPY", true),
+    ("INCLUDEDIR", false),
+    ("INCLUDEPY", false),
+    ("LIBDEST", false),
+    ("LIBDIR", true),
+    ("LIBPL", false),
+];
+
+/// First directories below the installation prefix
+const PREFIX_CHILDREN: &[&str] = &["bin", "include", "lib", "lib64"];
+
+/// Flags tuning code for the CPU of the build machine
+const NATIVE_FLAGS: &[&str] = &["-march=native", "-mtune=native", "-mcpu=native"];
+
+impl PythonConfig {
+    /// Checks this configuration for problems that break builds against it
+    /// or binaries built with it, see [`LintRule`]. Findings are sorted by
+    /// rule, then key.
+    pub fn lint(&self) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        self.lint_stale_prefix(&mut findings);
+        self.lint_native_arch(&mut findings);
+        self.lint_ldversion(&mut findings);
+        self.lint_ext_suffix(&mut findings);
+        findings
+    }
+
+    /// Returns a copy of this configuration with the fixes of [`lint`]
+    /// findings applied, with every finding, fixed or not
+    ///
+    /// [`lint`]: PythonConfig::lint
+    pub fn lint_fix(&self) -> Result<(Self, Vec<LintFinding>), Error> {
+        let findings = self.lint();
+        let fixes: ApplyOverrides = findings
+            .iter()
+            .filter_map(|finding| Some((finding.key.clone(), finding.fix.clone()?)))
+            .collect();
+        let fixes: Box<dyn Transform> = Box::new(fixes);
+        Ok((self.apply(&[fixes])?, findings))
+    }
+
+    fn lint_stale_prefix(&self, findings: &mut Vec<LintFinding>) {
+        let prefix = self.prefix_str().trim_end_matches('/');
+        let exec_prefix = match self.exec_prefix_str().trim_end_matches('/') {
+            "" => prefix,
+            exec_prefix => exec_prefix,
+        };
+        if !self.prefix_str().starts_with('/') {
+            return;
+        }
+        for (key, under_exec_prefix) in INSTALL_DIRS {
+            let path = match self.get(key).and_then(ConfigValue::as_str) {
+                Some(path) if path.starts_with('/') => path,
+                _ => continue,
+            };
+            if is_under(path, prefix) || is_under(path, exec_prefix) {
+                continue;
+            }
+            let root = if *under_exec_prefix {
+                exec_prefix
+            } else {
+                prefix
+            };
+            let fix = path
+                .match_indices('/')
+                .find(|(start, _)| {
+                    let child = path[start + 1..].split('/').next().unwrap_or_default();
+                    PREFIX_CHILDREN.contains(&child)
+                })
+                .map(|(start, _)| {
+                    ConfigValue::String(format!("{}{}", root, &path[start..]).into())
+                });
+            findings.push(LintFinding {
+                rule: LintRule::StalePrefix,
+                key: key.to_string(),
+                message: format!("{} is outside the prefix {}", path, root),
+                fix,
+            });
+        }
+    }
+
+    fn lint_native_arch(&self, findings: &mut Vec<LintFinding>) {
+        let native = SanitizeFlags::new(NATIVE_FLAGS.iter().copied());
+        for key in crate::transform::FLAG_KEYS {
+            let value = match self.get(key).and_then(ConfigValue::as_str) {
+                Some(value) => value,
+                None => continue,
+            };
+            let (found, kept): (Vec<&str>, Vec<&str>) = value
+                .split_whitespace()
+                .partition(|flag| native.matches(flag));
+            if found.is_empty() {
+                continue;
+            }
+            findings.push(LintFinding {
+                rule: LintRule::NativeArch,
+                key: key.to_string(),
+                message: format!(
+                    "{} tunes code for the build machine, binaries may crash on other CPUs",
+                    found.join(" ")
+                ),
+                fix: Some(ConfigValue::String(kept.join(" ").into())),
+            });
+        }
+    }
+
+    fn lint_ldversion(&self, findings: &mut Vec<LintFinding>) {
+        // Windows builds and alternative implementations have no LDVERSION
+        if self.get("LDVERSION").is_some()
+            || self.implementation() != PythonImplementation::CPython
+            || self.version_str().is_empty()
+            || self.ext_suffix().ends_with(".pyd")
+        {
+            return;
+        }
+        let ldversion = format!("{}{}", self.version_str(), self.abiflags());
+        findings.push(LintFinding {
+            rule: LintRule::MissingLdversion,
+            key: "LDVERSION".to_string(),
+            message: "LDVERSION is missing".to_string(),
+            fix: Some(ConfigValue::String(ldversion.into())),
+        });
+    }
+
+    fn lint_ext_suffix(&self, findings: &mut Vec<LintFinding>) {
+        let soabi = match self.get("SOABI").and_then(ConfigValue::as_str) {
+            Some(soabi) if !soabi.is_empty() => soabi,
+            _ => return,
+        };
+        let ext_suffix = self.ext_suffix();
+        // Windows tags differ from SOABI, stable ABI suffixes are set on purpose
+        if ext_suffix.is_empty() || ext_suffix.ends_with(".pyd") || ext_suffix.starts_with(".abi3")
+        {
+            return;
+        }
+        let shlib_suffix = match self.shlib_suffix() {
+            "" => ".so",
+            suffix => suffix,
+        };
+        let expected = format!(".{}{}", soabi, shlib_suffix);
+        if ext_suffix != expected {
+            findings.push(LintFinding {
+                rule: LintRule::InconsistentExtSuffix,
+                key: "EXT_SUFFIX".to_string(),
+                message: format!(
+                    "{} doesn't match SOABI {} and SHLIB_SUFFIX {}",
+                    ext_suffix, soabi, shlib_suffix
+                ),
+                fix: Some(ConfigValue::String(expected.into())),
+            });
+        }
+    }
+}
+
+/// Whether `path` is `root` or inside it, by whole path components
+fn is_under(path: &str, root: &str) -> bool {
+    match path.strip_prefix(root) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || root.is_empty(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LintRule;
+    use crate::{fixtures, ConfigValue, PythonConfig};
+
+    #[test]
+    fn lint_fixtures() {
+        for fixture in fixtures::all() {
+            let findings = fixture.config().lint();
+            assert!(findings.is_empty(), "{}: {:?}", fixture.name, findings);
+        }
+    }
+
+    #[test]
+    fn lint_fix() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'ABIFLAGS': '', \
+             'prefix': '/opt/python', 'exec_prefix': '/opt/python', \
+             'INCLUDEPY': '/build/stage/include/python3.12', \
+             'LIBDIR': '/opt/python/lib', 'BINDIR': '/somewhere', \
+             'CFLAGS': '-O2 -march=native -g', \
+             'SOABI': 'cpython-312-x86_64-linux-gnu', 'SHLIB_SUFFIX': '.so', \
+             'EXT_SUFFIX': '.cpython-311-x86_64-linux-gnu.so'}",
+        )
+        .unwrap();
+        let findings = config.lint();
+        let rules: Vec<(LintRule, &str)> = findings
+            .iter()
+            .map(|finding| (finding.rule, finding.key.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (LintRule::StalePrefix, "BINDIR"),
+                (LintRule::StalePrefix, "INCLUDEPY"),
+                (LintRule::NativeArch, "CFLAGS"),
+                (LintRule::MissingLdversion, "LDVERSION"),
+                (LintRule::InconsistentExtSuffix, "EXT_SUFFIX"),
+            ]
+        );
+        assert_eq!(findings[0].fix, None);
+        assert_eq!(
+            findings[2].to_string(),
+            "CFLAGS: -march=native tunes code for the build machine, binaries may crash \
+             on other CPUs [native-arch], fix: '-O2 -g'"
+        );
+
+        let (fixed, _) = config.lint_fix().unwrap();
+        let get = |key| fixed.get(key).and_then(ConfigValue::as_str);
+        assert_eq!(get("INCLUDEPY"), Some("/opt/python/include/python3.12"));
+        assert_eq!(get("BINDIR"), Some("/somewhere"));
+        assert_eq!(fixed.cflags(), "-O2 -g");
+        assert_eq!(fixed.ld_version(), "3.12");
+        assert_eq!(fixed.ext_suffix(), ".cpython-312-x86_64-linux-gnu.so");
+        assert_eq!(fixed.lint().len(), 1);
+    }
+}
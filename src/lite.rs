@@ -0,0 +1,544 @@
+//! Lightweight parser for the subset of Python found in `_sysconfigdata*.py`:
+//! a `build_time_vars` dict of string, integer, float and `None` literals.
+
+use std::ops::Range;
+
+use crate::Error;
+
+/// A literal value read by the lightweight parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Literal {
+    String(String),
+    Int(i64),
+    /// Integer literal that doesn't fit in an `i64`
+    IntOutOfRange,
+    /// Decimal text of a float literal, without `_` separators
+    Float(String),
+    None,
+}
+
+/// Reads the `build_time_vars = {...}` assignments in `src`, calling `visit`
+/// for each entry of the last one. Values of entries for which `wanted` returns `false` are
+/// skipped without being decoded and reported as `None`, as are values that
+/// aren't simple literals.
+///
+/// Returns the number of `build_time_vars` assignments.
+pub(crate) fn parse_build_time_vars<W, V>(
+    src: &str,
+    wanted: W,
+    mut visit: V,
+) -> Result<usize, Error>
+where
+    W: Fn(&str) -> bool,
+    V: FnMut(String, Option<Literal>) -> Result<(), Error>,
+{
+    let mut entries = Vec::new();
+    let opens = parse_entries(src, wanted, |key, value, span| {
+        entries.push((key, value, span.start));
+        Ok(())
+    })?;
+    if let Some(&open) = opens.last() {
+        for (key, value, start) in entries {
+            if start >= open {
+                visit(key, value)?;
+            }
+        }
+    }
+    Ok(opens.len())
+}
+
+/// Location of entries in the last `build_time_vars` dict of a source file
+#[cfg(feature = "emit")]
+pub(crate) struct Spans {
+    /// Byte range of each value by key
+    pub values: Vec<(String, Range<usize>)>,
+    /// Offset just after the opening brace
+    pub open: usize,
+}
+
+/// Returns the byte ranges of the values of the last `build_time_vars`
+/// assignment in `src` without decoding them, `None` if there is no such
+/// assignment
+#[cfg(feature = "emit")]
+pub(crate) fn build_time_vars_spans(src: &str) -> Result<Option<Spans>, Error> {
+    let mut values = Vec::new();
+    let opens = parse_entries(
+        src,
+        |_| false,
+        |key, _, span| {
+            values.push((key, span));
+            Ok(())
+        },
+    )?;
+    Ok(opens.last().map(|&open| {
+        // only keep entries of the last assignment
+        values.retain(|(_, span)| span.start >= open);
+        Spans { values, open }
+    }))
+}
+
+/// Shared implementation of [`parse_build_time_vars`] and
+/// [`build_time_vars_spans`], passing the byte range of each value to
+/// `visit` and returning the offsets after the opening brace of each dict
+fn parse_entries<W, V>(src: &str, wanted: W, mut visit: V) -> Result<Vec<usize>, Error>
+where
+    W: Fn(&str) -> bool,
+    V: FnMut(String, Option<Literal>, Range<usize>) -> Result<(), Error>,
+{
+    let mut parser = Parser::new(src);
+    let mut opens = Vec::new();
+    while parser.skip_to_assignment("build_time_vars") {
+        parser.skip_trivia();
+        parser.expect(b'=')?;
+        parser.skip_trivia();
+        parser.expect(b'{')?;
+        opens.push(parser.pos);
+        loop {
+            parser.skip_trivia();
+            if parser.eat(b'}') {
+                break;
+            }
+            let key = match parser.parse_value()? {
+                Some(Literal::String(key)) => Some(key),
+                _ => None,
+            };
+            parser.skip_trivia();
+            parser.expect(b':')?;
+            parser.skip_trivia();
+            let start = parser.pos;
+            match key {
+                Some(key) if wanted(&key) => {
+                    let value = parser.parse_value()?;
+                    visit(key, value, start..parser.pos)?;
+                }
+                Some(key) => {
+                    let end = parser.skip_value()?;
+                    visit(key, None, start..end)?;
+                }
+                None => {
+                    parser.skip_value()?;
+                }
+            }
+            parser.skip_trivia();
+            if !parser.eat(b',') {
+                parser.skip_trivia();
+                parser.expect(b'}')?;
+                break;
+            }
+        }
+    }
+    Ok(opens)
+}
+
+/// Text of the float literal at the 1-based `row` and `column`, counted in
+/// characters, of `src`, the way the RustPython parser locates expressions
+#[cfg(feature = "rustpython")]
+pub(crate) fn float_at(src: &str, row: usize, column: usize) -> Option<String> {
+    let line = src.split('\n').nth(row.checked_sub(1)?)?;
+    let (offset, _) = line.char_indices().nth(column.checked_sub(1)?)?;
+    match Parser::new(&line[offset..]).parse_value() {
+        Ok(Some(Literal::Float(text))) => Some(text),
+        _ => None,
+    }
+}
+
+/// Deepest nesting of brackets in `src`, ignoring strings and comments.
+/// Scanning stops at an unterminated string, leaving it to the parser to
+/// report.
+pub(crate) fn nesting_depth(src: &str) -> usize {
+    let mut parser = Parser::new(src);
+    let mut depth = 0usize;
+    let mut max_depth = 0;
+    loop {
+        parser.skip_trivia();
+        match parser.peek() {
+            None => return max_depth,
+            Some(b'\'') | Some(b'"') => {
+                let mut ignored = String::new();
+                if parser.parse_string(true, &mut ignored).is_err() {
+                    return max_depth;
+                }
+            }
+            Some(b'(') | Some(b'[') | Some(b'{') => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+                parser.pos += 1;
+            }
+            Some(b')') | Some(b']') | Some(b'}') => {
+                depth = depth.saturating_sub(1);
+                parser.pos += 1;
+            }
+            Some(_) => {
+                let c = parser.rest().chars().next().unwrap();
+                parser.pos += c.len_utf8();
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.src.as_bytes().get(self.pos + offset).copied()
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn line(&self) -> usize {
+        self.src[..self.pos].matches('\n').count() + 1
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::InvalidSyntax {
+            line: self.line(),
+            message: message.to_string(),
+        }
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), Error> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c as char)))
+        }
+    }
+
+    /// Skips whitespace, newlines, comments and line continuations
+    fn skip_trivia(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                b' ' | b'\t' | b'\r' | b'\n' | b'\x0c' => self.pos += 1,
+                b'\\' if matches!(self.peek_at(1), Some(b'\n') | Some(b'\r')) => self.pos += 2,
+                b'#' => match self.rest().find('\n') {
+                    Some(end) => self.pos += end,
+                    None => self.pos = self.src.len(),
+                },
+                _ => break,
+            }
+        }
+    }
+
+    /// Moves to just after the next line starting with `name` as an assignment
+    /// target, possibly indented
+    fn skip_to_assignment(&mut self, name: &str) -> bool {
+        loop {
+            let line_start = self.pos == 0 || self.src.as_bytes()[self.pos - 1] == b'\n';
+            let rest = self.rest();
+            // assignments may be indented in the branches of an if
+            let indented = rest.trim_start_matches([' ', '\t']);
+            if line_start && indented.starts_with(name) {
+                let after = indented[name.len()..].trim_start_matches([' ', '\t']);
+                if after.starts_with('=') && !after.starts_with("==") {
+                    self.pos += rest.len() - indented.len() + name.len();
+                    return true;
+                }
+            }
+            match rest.find('\n') {
+                Some(end) => self.pos += end + 1,
+                None => {
+                    self.pos = self.src.len();
+                    return false;
+                }
+            }
+        }
+    }
+
+    /// Parses a literal, returns `None` for other expressions which are skipped
+    fn parse_value(&mut self) -> Result<Option<Literal>, Error> {
+        match self.peek() {
+            Some(b'\'') | Some(b'"') => self.parse_strings(),
+            Some(b'r') | Some(b'R') | Some(b'u') | Some(b'U')
+                if matches!(self.peek_at(1), Some(b'\'') | Some(b'"')) =>
+            {
+                self.parse_strings()
+            }
+            Some(b'-') | Some(b'0'..=b'9') => {
+                let start = self.pos;
+                self.pos += 1;
+                self.skip_digits();
+                let mut float = false;
+                if self.peek() == Some(b'.') {
+                    float = true;
+                    self.pos += 1;
+                    self.skip_digits();
+                }
+                if matches!(self.peek(), Some(b'e') | Some(b'E')) {
+                    let sign = matches!(self.peek_at(1), Some(b'+') | Some(b'-')) as usize;
+                    if matches!(self.peek_at(1 + sign), Some(b'0'..=b'9')) {
+                        float = true;
+                        self.pos += 1 + sign;
+                        self.skip_digits();
+                    }
+                }
+                if matches!(self.peek(), Some(c) if c == b'.' || c.is_ascii_alphanumeric()) {
+                    // hex and complex literals aren't supported
+                    self.pos = start;
+                    self.skip_value()?;
+                    return Ok(None);
+                }
+                let digits = self.src[start..self.pos].replace('_', "");
+                if float {
+                    return Ok(Some(Literal::Float(digits)));
+                }
+                // only digits are left, parsing fails on overflow alone
+                match digits.parse::<i64>() {
+                    Ok(value) => Ok(Some(Literal::Int(value))),
+                    Err(_) => Ok(Some(Literal::IntOutOfRange)),
+                }
+            }
+            _ if self.rest().starts_with("None") && !self.is_identifier_at(4) => {
+                self.pos += 4;
+                Ok(Some(Literal::None))
+            }
+            _ => {
+                self.skip_value()?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') | Some(b'_') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn is_identifier_at(&self, offset: usize) -> bool {
+        matches!(self.peek_at(offset), Some(c) if c == b'_' || c.is_ascii_alphanumeric())
+    }
+
+    /// Parses adjacent string literals, concatenating them
+    fn parse_strings(&mut self) -> Result<Option<Literal>, Error> {
+        let mut value = String::new();
+        loop {
+            let raw = match self.peek() {
+                Some(b'r') | Some(b'R') => {
+                    self.pos += 1;
+                    true
+                }
+                Some(b'u') | Some(b'U') => {
+                    self.pos += 1;
+                    false
+                }
+                _ => false,
+            };
+            self.parse_string(raw, &mut value)?;
+            let before = self.pos;
+            self.skip_trivia();
+            let next_is_string = match self.peek() {
+                Some(b'\'') | Some(b'"') => true,
+                Some(b'r') | Some(b'R') | Some(b'u') | Some(b'U') => {
+                    matches!(self.peek_at(1), Some(b'\'') | Some(b'"'))
+                }
+                _ => false,
+            };
+            if !next_is_string {
+                self.pos = before;
+                return Ok(Some(Literal::String(value)));
+            }
+        }
+    }
+
+    fn parse_string(&mut self, raw: bool, out: &mut String) -> Result<(), Error> {
+        let quote = self.peek().ok_or_else(|| self.error("expected string"))?;
+        let triple = self.peek_at(1) == Some(quote) && self.peek_at(2) == Some(quote);
+        self.pos += if triple { 3 } else { 1 };
+        loop {
+            let rest = self.rest();
+            let c = rest
+                .chars()
+                .next()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            if c as u32 == quote as u32 {
+                if !triple {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                if rest.as_bytes().get(1) == Some(&quote) && rest.as_bytes().get(2) == Some(&quote)
+                {
+                    self.pos += 3;
+                    return Ok(());
+                }
+            }
+            if c == '\n' && !triple {
+                return Err(self.error("unterminated string"));
+            }
+            if c == '\\' {
+                let escaped = rest[1..]
+                    .chars()
+                    .next()
+                    .ok_or_else(|| self.error("unterminated string"))?;
+                if raw {
+                    out.push('\\');
+                    out.push(escaped);
+                    self.pos += 1 + escaped.len_utf8();
+                    continue;
+                }
+                self.pos += 1 + escaped.len_utf8();
+                match escaped {
+                    '\n' => {}
+                    '\\' | '\'' | '"' => out.push(escaped),
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    'a' => out.push('\x07'),
+                    'b' => out.push('\x08'),
+                    'f' => out.push('\x0c'),
+                    'v' => out.push('\x0b'),
+                    '0'..='7' => {
+                        let mut digits = escaped.to_string();
+                        while digits.len() < 3 {
+                            match self.peek() {
+                                Some(d @ b'0'..=b'7') => {
+                                    digits.push(d as char);
+                                    self.pos += 1;
+                                }
+                                _ => break,
+                            }
+                        }
+                        let code = u32::from_str_radix(&digits, 8).unwrap();
+                        out.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                    }
+                    'x' => out.push(self.parse_hex_escape(2)?),
+                    'u' => out.push(self.parse_hex_escape(4)?),
+                    'U' => out.push(self.parse_hex_escape(8)?),
+                    other => {
+                        // unknown escapes are kept verbatim
+                        out.push('\\');
+                        out.push(other);
+                    }
+                }
+                continue;
+            }
+            out.push(c);
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn parse_hex_escape(&mut self, len: usize) -> Result<char, Error> {
+        let digits = self
+            .rest()
+            .get(..len)
+            .ok_or_else(|| self.error("truncated escape sequence"))?;
+        let code =
+            u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid escape sequence"))?;
+        self.pos += len;
+        char::from_u32(code).ok_or_else(|| self.error("invalid escape sequence"))
+    }
+
+    /// Skips an arbitrary expression up to the next `,` or closing bracket at
+    /// the same nesting level, returns the offset the expression ends at
+    fn skip_value(&mut self) -> Result<usize, Error> {
+        let mut depth = 0usize;
+        let mut end = self.pos;
+        loop {
+            if depth == 0 {
+                end = self.pos;
+            }
+            self.skip_trivia();
+            match self.peek() {
+                None => return Err(self.error("unexpected end of file")),
+                Some(b'\'') | Some(b'"') => {
+                    let mut ignored = String::new();
+                    self.parse_string(true, &mut ignored)?;
+                }
+                Some(b'(') | Some(b'[') | Some(b'{') => {
+                    depth += 1;
+                    self.pos += 1;
+                }
+                Some(b')') | Some(b']') | Some(b'}') => {
+                    if depth == 0 {
+                        return Ok(end);
+                    }
+                    depth -= 1;
+                    self.pos += 1;
+                }
+                Some(b',') | Some(b':') if depth == 0 => return Ok(end),
+                Some(_) => {
+                    let c = self.rest().chars().next().unwrap();
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_build_time_vars, Literal};
+    use crate::Error;
+
+    fn parse(src: &str) -> Result<Vec<(String, Option<Literal>)>, Error> {
+        let mut entries = Vec::new();
+        parse_build_time_vars(
+            src,
+            |_| true,
+            |key, value| {
+                entries.push((key, value));
+                Ok(())
+            },
+        )?;
+        Ok(entries)
+    }
+
+    #[test]
+    fn literals() {
+        let entries = parse(
+            "# comment\nbuild_time_vars = {'A': 'x'\n 'y',\n \"B\": -1, 'C': None, \
+             'D': r'\\d', 'E': 'a\\tb\\x41\\u00e9', 'F': 1.50, 'G': (1, 2), 'H': 'it''s', \
+             'I': -1_0.25e-3, 'J': 1e5, 'K': 0x10, 'L': 1j}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                ("A".to_string(), Some(Literal::String("xy".to_string()))),
+                ("B".to_string(), Some(Literal::Int(-1))),
+                ("C".to_string(), Some(Literal::None)),
+                ("D".to_string(), Some(Literal::String("\\d".to_string()))),
+                ("E".to_string(), Some(Literal::String("a\tbAé".to_string()))),
+                ("F".to_string(), Some(Literal::Float("1.50".to_string()))),
+                ("G".to_string(), None),
+                ("H".to_string(), Some(Literal::String("its".to_string()))),
+                (
+                    "I".to_string(),
+                    Some(Literal::Float("-10.25e-3".to_string()))
+                ),
+                ("J".to_string(), Some(Literal::Float("1e5".to_string()))),
+                ("K".to_string(), None),
+                ("L".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let err = parse("build_time_vars = {'A': 'x'\n 'y' 'B': 1}").unwrap_err();
+        assert!(matches!(err, Error::InvalidSyntax { line: 2, .. }));
+        let err = parse("build_time_vars = {'A': 'x").unwrap_err();
+        assert!(matches!(err, Error::InvalidSyntax { .. }));
+    }
+}
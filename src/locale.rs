@@ -0,0 +1,74 @@
+use crate::PythonConfig;
+
+/// Locale and encoding behavior compiled into the runtime, which embedders
+/// have to mirror in `PyPreConfig` when initializing Python themselves, see
+/// [`PythonConfig::locale_support`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocaleSupport {
+    /// `PY_COERCE_C_LOCALE`: the C locale is coerced to a UTF-8 one at startup
+    /// (PEP 538), `PyPreConfig.coerce_c_locale` enables it for embedded
+    /// interpreters. `None` when the key is missing.
+    pub coerce_c_locale: Option<bool>,
+    /// `_Py_FORCE_UTF8_LOCALE`, defined on Android and VxWorks: the locale
+    /// encoding is always UTF-8, whatever `setlocale()` reports
+    pub force_utf8_locale: bool,
+    /// `_Py_FORCE_UTF8_FS_ENCODING`, defined on macOS and Android: the
+    /// filesystem encoding is always UTF-8
+    pub force_utf8_fs_encoding: bool,
+    /// `HAVE_LANGINFO_H`: the locale encoding comes from
+    /// `nl_langinfo(CODESET)`, `None` when the key is missing
+    pub langinfo_codeset: Option<bool>,
+    /// `HAVE_SETLOCALE`: the runtime can set the locale from the environment,
+    /// `None` when the key is missing
+    pub setlocale: Option<bool>,
+}
+
+impl PythonConfig {
+    /// Returns the locale and encoding behavior of this build, from the
+    /// configure results and the `pyport.h` platform defines
+    pub fn locale_support(&self) -> LocaleSupport {
+        let vars = &self.sys_config_data.build_time_vars;
+        let flag = |key| vars.get_int(key).map(|x| x != 0);
+        let android = self.is_android();
+        let vxworks = self.sys_platform().starts_with("vxworks");
+        LocaleSupport {
+            coerce_c_locale: flag("PY_COERCE_C_LOCALE"),
+            force_utf8_locale: android || vxworks,
+            force_utf8_fs_encoding: android || self.sys_platform() == "darwin",
+            langinfo_codeset: flag("HAVE_LANGINFO_H"),
+            setlocale: flag("HAVE_SETLOCALE"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, PythonConfig};
+
+    #[test]
+    fn locale_support() {
+        let locale = fixtures::get("cpython38-darwin")
+            .unwrap()
+            .config()
+            .locale_support();
+        assert_eq!(locale.coerce_c_locale, Some(true));
+        assert!(locale.force_utf8_fs_encoding && !locale.force_utf8_locale);
+        assert_eq!(locale.langinfo_codeset, Some(true));
+
+        let locale = fixtures::get("cpython311-linux")
+            .unwrap()
+            .config()
+            .locale_support();
+        assert_eq!(locale.coerce_c_locale, None);
+        assert!(!locale.force_utf8_fs_encoding && !locale.force_utf8_locale);
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'MULTIARCH': 'aarch64-linux-android', \
+             'PY_COERCE_C_LOCALE': 0}",
+        )
+        .unwrap();
+        let locale = config.locale_support();
+        assert_eq!(locale.coerce_c_locale, Some(false));
+        assert!(locale.force_utf8_locale && locale.force_utf8_fs_encoding);
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+
+use crate::source::expand;
+use crate::{ConfigValue, Error, PythonConfig};
+
+impl PythonConfig {
+    /// Like [`PythonConfig::get`] but fails with [`Error::UnknownKey`]
+    /// suggesting the closest existing key when `key` is missing
+    pub fn try_get(&self, key: &str) -> Result<&ConfigValue, Error> {
+        self.get(key).ok_or_else(|| Error::UnknownKey {
+            key: key.to_string(),
+            suggestion: self.suggest_key(key).map(str::to_string),
+        })
+    }
+
+    /// Returns the keys, sorted, whose values contain `needle`, a flag like
+    /// `-march=native` or a path like a stale prefix. `$(VAR)` references in
+    /// string values are expanded first, other values are searched in their
+    /// Python spelling.
+    pub fn find_references(&self, needle: &str) -> Vec<&str> {
+        let strings: BTreeMap<String, String> = self
+            .vars()
+            .filter_map(|(key, value)| Some((key.to_string(), value.as_str()?.to_string())))
+            .collect();
+        self.vars()
+            .filter(|(_, value)| match value {
+                ConfigValue::String(value) if value.contains('$') => {
+                    expand(value, &strings, 0).contains(needle)
+                }
+                ConfigValue::String(value) => value.contains(needle),
+                value => value.to_string().contains(needle),
+            })
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Key spelled like `key` ignoring case, if only one exists
+    pub(crate) fn find_key_ignore_case(&self, key: &str) -> Option<&str> {
+        let mut matches = self
+            .vars()
+            .map(|(k, _)| k)
+            .filter(|k| k.eq_ignore_ascii_case(key));
+        let found = matches.next()?;
+        if matches.next().is_some() {
+            return None;
+        }
+        Some(found)
+    }
+
+    /// Closest key to `key` by edit distance, ignoring case, if it is close
+    /// enough to be a plausible typo
+    fn suggest_key(&self, key: &str) -> Option<&str> {
+        let wanted = key.to_ascii_uppercase();
+        let threshold = (wanted.len() / 3).max(2);
+        self.vars()
+            .map(|(k, _)| (levenshtein(&wanted, &k.to_ascii_uppercase()), k))
+            .filter(|(distance, _)| *distance <= threshold)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, k)| k)
+    }
+}
+
+/// Number of single character insertions, deletions and substitutions
+/// turning `a` into `b`
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::levenshtein;
+    use crate::{fixtures, Error, PythonConfig};
+
+    #[test]
+    fn fuzzy_lookup() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(config.get("ext_suffix"), config.get("EXT_SUFFIX"));
+        assert!(config.get("ext_suffix").is_some());
+        let err = config.try_get("EXT_SUFIX").unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::UnknownKey { suggestion: Some(key), .. } if key == "EXT_SUFFIX"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "unknown key EXT_SUFIX, did you mean EXT_SUFFIX?"
+        );
+        let err = config.try_get("NOTHING_LIKE_THIS").unwrap_err();
+        assert_eq!(err.to_string(), "unknown key NOTHING_LIKE_THIS");
+
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+    }
+
+    #[test]
+    fn find_references() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'OPT': '-O2 -march=native', \
+             'CFLAGS': '$(OPT) -Wall', 'LDFLAGS': '-L/old/prefix/lib', \
+             'LIBDIR': '/old/prefix/lib', 'SIZEOF_VOID_P': 8}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.find_references("-march=native"),
+            vec!["CFLAGS", "OPT"]
+        );
+        assert_eq!(
+            config.find_references("/old/prefix"),
+            vec!["LDFLAGS", "LIBDIR"]
+        );
+        assert_eq!(config.find_references("8"), vec!["SIZEOF_VOID_P"]);
+        assert!(config.find_references("-flto").is_empty());
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert!(config.find_references("-fwrapv").contains(&"OPT"));
+    }
+}
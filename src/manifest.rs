@@ -0,0 +1,136 @@
+//! `python-target.toml` manifests describing a target Python, meant to be
+//! checked into version control for reproducible cross builds:
+//!
+//! ```toml
+//! version = "3.11"
+//! target = "aarch64-unknown-linux-gnu"
+//! sysconfigdata = "sysroot/usr/lib/python3.11/_sysconfigdata__linux_aarch64-linux-gnu.py"
+//! sha256 = "..."
+//!
+//! [overrides]
+//! CC = "aarch64-linux-gnu-gcc"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{sha256_hex, ApplyOverrides, ConfigValue, Error, PythonConfig, Source, Transform};
+
+/// Contents of a `python-target.toml` manifest
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Expected `VERSION`, for example `3.11`
+    pub version: Option<String>,
+    /// Rust target triple the sysconfigdata must match, see [`PythonConfig::validate_target`]
+    pub target: Option<String>,
+    /// Path of the `_sysconfigdata.py` file, relative to the manifest
+    pub sysconfigdata: PathBuf,
+    /// Expected SHA-256 of the sysconfigdata file, in hex
+    pub sha256: Option<String>,
+    /// Values replacing the ones of the sysconfigdata file
+    #[serde(default)]
+    pub overrides: BTreeMap<String, ConfigValue>,
+}
+
+impl Manifest {
+    /// Parse manifest content
+    pub fn parse(src: &str) -> Result<Self, Error> {
+        toml::from_str(src).map_err(|err| Error::InvalidManifest(err.to_string()))
+    }
+
+    /// Loads the sysconfigdata the manifest points to, with paths relative to
+    /// `base_dir`, verifies its hash, version and target, then applies the
+    /// overrides
+    pub fn load_config(&self, base_dir: &Path) -> Result<PythonConfig, Error> {
+        let path = base_dir.join(&self.sysconfigdata);
+        let src = fs::read(&path)?;
+        if let Some(expected) = &self.sha256 {
+            let actual = sha256_hex(&src);
+            if !expected.eq_ignore_ascii_case(&actual) {
+                return Err(Error::HashMismatch {
+                    path,
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+        let src = String::from_utf8(src)
+            .map_err(|err| Error::InvalidManifest(format!("{}: {}", path.display(), err)))?;
+        let config = PythonConfig::parse(&src)?;
+        if let Some(version) = &self.version {
+            if config.version_str() != version {
+                return Err(Error::InvalidManifest(format!(
+                    "{} is Python {}, manifest expects {}",
+                    path.display(),
+                    config.version_str(),
+                    version
+                )));
+            }
+        }
+        if let Some(target) = &self.target {
+            config.validate_target(target)?;
+        }
+        let mut config = config;
+        let overrides = self
+            .overrides
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<ApplyOverrides>();
+        config.with_source(Source::Transform(overrides.name()), |config| {
+            overrides.transform(config)
+        })?;
+        Ok(config)
+    }
+}
+
+impl PythonConfig {
+    /// Loads the configuration described by a `python-target.toml` manifest,
+    /// see [`Manifest::load_config`]
+    pub fn from_manifest(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let manifest = Manifest::parse(&fs::read_to_string(path)?)?;
+        manifest.load_config(path.parent().unwrap_or_else(|| Path::new("")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Manifest;
+    use crate::{Error, PythonConfig};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn from_manifest() {
+        let config = PythonConfig::from_manifest("tests/manifests/python-target.toml").unwrap();
+        assert_eq!(config.version_str(), "3.11");
+        assert_eq!(config.cc(), "clang --target=x86_64-unknown-linux-gnu");
+        assert_eq!(config.lib_dir_str(), "/opt/sysroot/usr/local/lib");
+    }
+
+    #[test]
+    fn manifest_verification() {
+        let src = fs::read_to_string("tests/manifests/python-target.toml").unwrap();
+        let base_dir = Path::new("tests/manifests");
+
+        let mut manifest = Manifest::parse(&src).unwrap();
+        manifest.sha256 = Some("00".repeat(32));
+        let err = manifest.load_config(base_dir).unwrap_err();
+        assert!(matches!(err, Error::HashMismatch { .. }));
+
+        let mut manifest = Manifest::parse(&src).unwrap();
+        manifest.target = Some("aarch64-unknown-linux-gnu".to_string());
+        let err = manifest.load_config(base_dir).unwrap_err();
+        assert!(matches!(err, Error::TargetMismatch { .. }));
+
+        let mut manifest = Manifest::parse(&src).unwrap();
+        manifest.version = Some("3.12".to_string());
+        let err = manifest.load_config(base_dir).unwrap_err();
+        assert!(matches!(err, Error::InvalidManifest(_)));
+
+        let err = Manifest::parse("sysconfigdata = 1").unwrap_err();
+        assert!(matches!(err, Error::InvalidManifest(_)));
+    }
+}
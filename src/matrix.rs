@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use crate::PythonConfig;
+
+/// Build of a Python version in a [`SupportMatrix`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupportEntry {
+    /// Python version, `3.12`
+    pub version: String,
+    /// Implementation name, `CPython`
+    pub implementation: String,
+    /// Platform, see [`PythonConfig::get_platform`]
+    pub platform: String,
+    /// ABI tag, see [`PythonConfig::abi_tag`]
+    pub abi: String,
+    /// Free-threaded build
+    pub free_threaded: bool,
+    /// Debug build
+    pub debug: bool,
+    /// Size of a pointer in bytes
+    pub pointer_size: u32,
+}
+
+/// Versions and build flavors available across a fleet of configurations,
+/// to decide which wheels can be built
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupportMatrix {
+    /// Distinct builds, sorted by version then platform and ABI
+    pub entries: Vec<SupportEntry>,
+}
+
+impl SupportMatrix {
+    /// Collects the distinct builds of `configs`
+    pub fn build(configs: &[PythonConfig]) -> Self {
+        let mut entries: Vec<((u32, u32), SupportEntry)> = configs
+            .iter()
+            .map(|config| {
+                let entry = SupportEntry {
+                    version: config.version_str().to_string(),
+                    implementation: config.implementation().to_string(),
+                    platform: config.get_platform().to_string(),
+                    abi: config.abi_tag().to_string(),
+                    free_threaded: config.abiflags().contains('t'),
+                    debug: config.debug(),
+                    pointer_size: config.pointer_size(),
+                };
+                ((config.major_minor().0, config.major_minor().1), entry)
+            })
+            .collect();
+        entries.sort_by(|(a_version, a), (b_version, b)| {
+            (a_version, &a.implementation, &a.platform, &a.abi, a.debug).cmp(&(
+                b_version,
+                &b.implementation,
+                &b.platform,
+                &b.abi,
+                b.debug,
+            ))
+        });
+        let mut entries: Vec<SupportEntry> = entries.into_iter().map(|(_, entry)| entry).collect();
+        entries.dedup();
+        SupportMatrix { entries }
+    }
+
+    /// Serialize to CSV with a header line
+    pub fn to_csv(&self) -> String {
+        let mut out =
+            String::from("version,implementation,platform,abi,free_threaded,debug,pointer_size\n");
+        for entry in &self.entries {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                entry.version,
+                entry.implementation,
+                entry.platform,
+                entry.abi,
+                entry.free_threaded,
+                entry.debug,
+                entry.pointer_size
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    /// Serialize to a JSON string
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SupportMatrix;
+    use crate::fixtures;
+
+    #[test]
+    fn support_matrix() {
+        let mut configs: Vec<_> = fixtures::all().iter().map(|x| x.config()).collect();
+        configs.push(fixtures::get("cpython311-linux").unwrap().config());
+        let matrix = SupportMatrix::build(&configs);
+        assert_eq!(matrix.entries.len(), fixtures::all().len());
+        assert_eq!(matrix.entries[0].version, "3.7");
+        assert_eq!(matrix.entries[0].abi, "cp37m");
+        let last = matrix.entries.last().unwrap();
+        assert_eq!(last.version, "3.13");
+        assert!(last.free_threaded);
+        let csv = matrix.to_csv();
+        assert!(csv.starts_with("version,implementation,"));
+        assert!(csv.contains("\n3.11,CPython,linux-x86_64,cp311,false,false,8\n"));
+    }
+}
@@ -0,0 +1,109 @@
+use std::fmt::Write;
+
+use crate::{normalize_windows_path, Error, PythonConfig};
+
+impl PythonConfig {
+    /// Returns an MSBuild property sheet (`.props`) for MSVC projects
+    /// embedding this Windows distribution: the `include` and `libs`
+    /// directories of the installation, the import library, `python311.lib`,
+    /// `python313t.lib` for free-threaded and `python311_d.lib` for debug
+    /// builds, and `Py_GIL_DISABLED` where the headers don't define it.
+    ///
+    /// Paths are relative to the `PythonDir` property, set to `prefix`, so
+    /// configurations loaded from a bare `pyconfig.h` only need it filled in.
+    pub fn to_msbuild_props(&self) -> Result<String, Error> {
+        if self.sys_platform() != "win32" || self.is_mingw() {
+            return Err(Error::Unsupported(format!(
+                "MSBuild property sheets need an MSVC build of Python, not {}",
+                self.get_platform()
+            )));
+        }
+        let vars = &self.sys_config_data.build_time_vars;
+        let free_threaded =
+            self.abiflags().contains('t') || vars.get_int("Py_GIL_DISABLED") == Some(1);
+        let library = format!("{}.lib", self.python_lib_name());
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        writeln!(out, "<!-- {} -->", xml_escape(&self.generated_by())).unwrap();
+        out.push_str(
+            "<Project ToolsVersion=\"4.0\" \
+             xmlns=\"http://schemas.microsoft.com/developer/msbuild/2003\">\n  \
+             <PropertyGroup Label=\"UserMacros\">\n",
+        );
+        writeln!(
+            out,
+            "    <PythonDir>{}</PythonDir>",
+            xml_escape(&normalize_windows_path(self.prefix_str()))
+        )
+        .unwrap();
+        out.push_str(
+            "  </PropertyGroup>\n  <ItemDefinitionGroup>\n    <ClCompile>\n      \
+             <AdditionalIncludeDirectories>$(PythonDir)\\include;\
+             %(AdditionalIncludeDirectories)</AdditionalIncludeDirectories>\n",
+        );
+        if free_threaded && vars.get_int("Py_GIL_DISABLED") != Some(1) {
+            out.push_str(
+                "      <PreprocessorDefinitions>Py_GIL_DISABLED=1;\
+                 %(PreprocessorDefinitions)</PreprocessorDefinitions>\n",
+            );
+        }
+        out.push_str(
+            "    </ClCompile>\n    <Link>\n      \
+             <AdditionalLibraryDirectories>$(PythonDir)\\libs;\
+             %(AdditionalLibraryDirectories)</AdditionalLibraryDirectories>\n",
+        );
+        writeln!(
+            out,
+            "      <AdditionalDependencies>{};%(AdditionalDependencies)</AdditionalDependencies>",
+            library
+        )
+        .unwrap();
+        out.push_str("    </Link>\n  </ItemDefinitionGroup>\n</Project>\n");
+        Ok(out)
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, ConfigValue, Error, PythonConfig};
+
+    #[test]
+    fn msbuild_props() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'prefix': 'C:/Python313', \
+             'EXT_SUFFIX': '.cp313t-win_amd64.pyd', 'ABIFLAGS': 't'}",
+        )
+        .unwrap();
+        let props = config.to_msbuild_props().unwrap();
+        assert!(props.contains("    <PythonDir>C:\\Python313</PythonDir>\n"));
+        assert!(props.contains(
+            "<AdditionalIncludeDirectories>$(PythonDir)\\include;%(AdditionalIncludeDirectories)"
+        ));
+        assert!(props.contains("<PreprocessorDefinitions>Py_GIL_DISABLED=1;"));
+        assert!(props.contains("<AdditionalDependencies>python313t.lib;"));
+
+        // loaded from PC/pyconfig.h
+        let config = PythonConfig::from_vars(vec![
+            ("VERSION".to_string(), ConfigValue::String("3.11".into())),
+            ("MS_WINDOWS".to_string(), ConfigValue::Int(1)),
+            ("Py_DEBUG".to_string(), ConfigValue::Int(1)),
+        ])
+        .unwrap();
+        let props = config.to_msbuild_props().unwrap();
+        assert!(props.contains("    <PythonDir></PythonDir>\n"));
+        assert!(props.contains("<AdditionalDependencies>python311_d.lib;"));
+        assert!(!props.contains("PreprocessorDefinitions"));
+
+        let config = fixtures::get("cpython311-mingw").unwrap().config();
+        assert!(matches!(
+            config.to_msbuild_props(),
+            Err(Error::Unsupported(_))
+        ));
+    }
+}
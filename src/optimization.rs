@@ -0,0 +1,163 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, PythonConfig};
+
+/// Optimization level of a C compiler `-O` flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptimizationLevel {
+    /// `-O0`, no optimization
+    O0,
+    /// `-O1`, also spelled `-O`
+    O1,
+    /// `-O2`
+    O2,
+    /// `-O3`
+    O3,
+    /// `-Os`, optimize for size
+    Os,
+    /// `-Oz`, optimize aggressively for size
+    Oz,
+    /// `-Og`, optimize without hurting debugging
+    Og,
+    /// `-Ofast`
+    Ofast,
+}
+
+impl OptimizationLevel {
+    /// The compiler flag, `-O2`
+    pub fn flag(self) -> &'static str {
+        match self {
+            OptimizationLevel::O0 => "-O0",
+            OptimizationLevel::O1 => "-O1",
+            OptimizationLevel::O2 => "-O2",
+            OptimizationLevel::O3 => "-O3",
+            OptimizationLevel::Os => "-Os",
+            OptimizationLevel::Oz => "-Oz",
+            OptimizationLevel::Og => "-Og",
+            OptimizationLevel::Ofast => "-Ofast",
+        }
+    }
+
+    /// Level of the compiler flag `flag`, `None` if it isn't a `-O` flag
+    pub(crate) fn from_flag(flag: &str) -> Option<Self> {
+        Some(match flag {
+            "-O0" => OptimizationLevel::O0,
+            "-O" | "-O1" => OptimizationLevel::O1,
+            "-O2" => OptimizationLevel::O2,
+            "-O3" => OptimizationLevel::O3,
+            "-Os" => OptimizationLevel::Os,
+            "-Oz" => OptimizationLevel::Oz,
+            "-Og" => OptimizationLevel::Og,
+            "-Ofast" => OptimizationLevel::Ofast,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for OptimizationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.flag())
+    }
+}
+
+/// Parses a flag like `-O2`, or the level alone, `2` or `s`
+impl FromStr for OptimizationLevel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let flag = if s.starts_with("-O") {
+            s.to_string()
+        } else {
+            format!("-O{}", s)
+        };
+        Self::from_flag(&flag)
+            .ok_or_else(|| Error::Unsupported(format!("unknown optimization level {}", s)))
+    }
+}
+
+/// The optimization and debug information flags of the C compiler command
+/// line, see [`PythonConfig::optimization_flags`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptimizationFlags {
+    /// Effective optimization level, that of the last `-O` flag
+    pub level: Option<OptimizationLevel>,
+    /// Effective debug information flag, the last `-g` flag, `None` without
+    /// one or after `-g0`
+    pub debug_info: Option<String>,
+    /// Every `-O` and `-g` flag, in command line order
+    pub flags: Vec<String>,
+}
+
+impl PythonConfig {
+    /// Returns the `-O` and `-g` flags extension modules are compiled with,
+    /// from `CFLAGS`, or `OPT` when it's missing
+    pub fn optimization_flags(&self) -> OptimizationFlags {
+        let cflags = match self.cflags() {
+            "" => self.sys_config_data.build_time_vars.get_str("OPT"),
+            cflags => cflags,
+        };
+        let mut flags = OptimizationFlags::default();
+        for flag in cflags.split_whitespace() {
+            if let Some(level) = OptimizationLevel::from_flag(flag) {
+                flags.level = Some(level);
+            } else if is_debug_info_flag(flag) {
+                flags.debug_info = Some(flag.to_string()).filter(|flag| flag != "-g0");
+            } else {
+                continue;
+            }
+            flags.flags.push(flag.to_string());
+        }
+        flags
+    }
+}
+
+/// Whether `flag` selects debug information, `-g`, `-g3` or `-ggdb`, but not
+/// options like `-gsplit-dwarf`
+pub(crate) fn is_debug_info_flag(flag: &str) -> bool {
+    match flag.strip_prefix("-g") {
+        Some(rest) => {
+            let level = rest
+                .strip_prefix("gdb")
+                .or_else(|| rest.strip_prefix("dwarf"))
+                .unwrap_or(rest);
+            level.chars().all(|c| c.is_ascii_digit()) && level.len() <= 1
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OptimizationLevel;
+    use crate::{fixtures, PythonConfig};
+
+    #[test]
+    fn optimization_flags() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let flags = config.optimization_flags();
+        assert_eq!(flags.level, Some(OptimizationLevel::O3));
+        assert_eq!(flags.debug_info.as_deref(), Some("-g"));
+        assert_eq!(flags.flags, vec!["-g", "-O3"]);
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', \
+             'OPT': '-DNDEBUG -O -g3 -gsplit-dwarf -O2 -g0'}",
+        )
+        .unwrap();
+        let flags = config.optimization_flags();
+        assert_eq!(flags.level, Some(OptimizationLevel::O2));
+        assert_eq!(flags.debug_info, None);
+        assert_eq!(flags.flags, vec!["-O", "-g3", "-O2", "-g0"]);
+
+        assert_eq!(
+            "s".parse::<OptimizationLevel>().unwrap(),
+            OptimizationLevel::Os
+        );
+        assert_eq!(
+            "-Ofast".parse::<OptimizationLevel>().unwrap().to_string(),
+            "-Ofast"
+        );
+        assert!("-O9".parse::<OptimizationLevel>().is_err());
+    }
+}
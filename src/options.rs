@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::Interner;
+
+/// Keys that must be present in `build_time_vars`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RequiredKeys {
+    /// `VERSION` must be present and `SIZEOF_VOID_P` must be an integer when present
+    #[default]
+    Default,
+    /// Only the listed keys must be present with a value other than `None`
+    Keys(Vec<String>),
+}
+
+impl RequiredKeys {
+    /// Require exactly `keys`
+    pub fn keys<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        RequiredKeys::Keys(keys.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Parser used to read `_sysconfigdata.py` content
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Full Python parser from `rustpython-parser`, the default when the
+    /// `rustpython` feature is enabled
+    #[cfg_attr(feature = "rustpython", default)]
+    RustPython,
+    /// Hand written parser for the literal subset of Python used by
+    /// `_sysconfigdata.py`, values other than string, number and `None`
+    /// literals are skipped. The default without the `rustpython` feature.
+    #[cfg_attr(not(feature = "rustpython"), default)]
+    Lightweight,
+}
+
+/// Bounds on the input accepted by the parser, protecting services that
+/// parse untrusted sysconfigdata from crafted files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub(crate) max_file_size: usize,
+    pub(crate) max_entries: usize,
+    pub(crate) max_depth: usize,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl Default for Limits {
+    /// 64 MiB, one million `build_time_vars` entries and, like CPython's own
+    /// parser, 200 nested brackets, without timeout
+    fn default() -> Self {
+        Limits {
+            max_file_size: 64 * 1024 * 1024,
+            max_entries: 1_000_000,
+            max_depth: 200,
+            timeout: None,
+        }
+    }
+}
+
+impl Limits {
+    /// Default limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum size of the source in bytes
+    pub fn max_file_size(mut self, max_file_size: usize) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Set the maximum number of `build_time_vars` entries
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Set the maximum nesting depth of brackets in expressions
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set the maximum time spent parsing, checked between
+    /// `build_time_vars` entries. The error reports the limit in
+    /// milliseconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// Category of a suspicious construct found while parsing, see
+/// [`ParseOptions::warning`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningKind {
+    /// value other than a string, number or `None` literal, which is skipped
+    UnknownValueType,
+    /// key assigned more than once, the last value wins
+    DuplicateKey,
+    /// value of a known key with an unexpected type, for example a string
+    /// `Py_ENABLE_SHARED`, read as its default
+    Coercion,
+    /// libpython is only available as a static library of LTO objects, see
+    /// [`PythonConfig::static_lib_uses_lto`](crate::PythonConfig::static_lib_uses_lto)
+    StaticLto,
+    /// extension modules are linked with files of the CPython build tree,
+    /// such as an AIX import file relative to it
+    UnsupportedLinkModel,
+    /// `build_time_vars` assigned more than once, for example in the
+    /// branches of an `if` on the interpreter version. Only the entries of
+    /// the last complete assignment in source order are read.
+    MultipleAssignments,
+    /// `ABIFLAGS` letters of a vendor spin without a meaning, passed through
+    /// to the ABI tag, see [`register_abiflag`](crate::register_abiflag)
+    UnknownAbiFlags,
+    /// integer value that doesn't fit in 64 bits, which is skipped
+    IntegerOutOfRange,
+}
+
+/// What to do with a [`WarningKind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningAction {
+    /// do not report it
+    Ignore,
+    /// record it in [`ParseReport::warnings`](crate::ParseReport::warnings)
+    Warn,
+    /// fail parsing with [`Error::Warning`](crate::Error::Warning)
+    Deny,
+}
+
+/// Options controlling how `_sysconfigdata.py` content is parsed
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    pub(crate) required_keys: RequiredKeys,
+    pub(crate) interner: Option<Interner>,
+    pub(crate) backend: Backend,
+    pub(crate) deny_warnings: bool,
+    pub(crate) warnings: BTreeMap<WarningKind, WarningAction>,
+    pub(crate) limits: Limits,
+    pub(crate) keep_placeholders: bool,
+}
+
+impl ParseOptions {
+    /// Default parse options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the keys that must be present, see [`RequiredKeys`]
+    pub fn required_keys(mut self, required_keys: RequiredKeys) -> Self {
+        self.required_keys = required_keys;
+        self
+    }
+
+    /// Store keys and string values in a shared [`Interner`], reducing memory
+    /// use when many configurations are held at once
+    pub fn interner(mut self, interner: Interner) -> Self {
+        self.interner = Some(interner);
+        self
+    }
+
+    /// Set the parser backend, see [`Backend`]
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Set the bounds on the accepted input, see [`Limits`]
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Keep `${exec_prefix}`-style references in the installation paths,
+    /// such as [`PythonConfig::include_dir`](crate::PythonConfig::include_dir),
+    /// instead of expanding them against the other values
+    pub fn keep_placeholders(mut self, keep_placeholders: bool) -> Self {
+        self.keep_placeholders = keep_placeholders;
+        self
+    }
+
+    /// Fail parsing on every warning category not configured with
+    /// [`ParseOptions::warning`], for strict CI environments
+    pub fn deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+
+    /// Set what to do with warnings of category `kind`, overriding
+    /// [`ParseOptions::deny_warnings`]
+    pub fn warning(mut self, kind: WarningKind, action: WarningAction) -> Self {
+        self.warnings.insert(kind, action);
+        self
+    }
+
+    /// Action configured for warnings of category `kind`
+    pub(crate) fn warning_action(&self, kind: WarningKind) -> WarningAction {
+        match self.warnings.get(&kind) {
+            Some(action) => *action,
+            None if self.deny_warnings => WarningAction::Deny,
+            None => WarningAction::Warn,
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::source::expand;
+use crate::{BuildTimeVars, PythonConfig};
+
+/// Keys of the installation paths, some distributions write them as
+/// `${exec_prefix}/include` like the Makefile does
+const PATH_KEYS: &[&str] = &[
+    "prefix",
+    "exec_prefix",
+    "BINDIR",
+    "INCLUDEDIR",
+    "LIBDIR",
+    "LIBPL",
+];
+
+/// Directories of an installation, see [`PythonConfig::paths`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstallPaths {
+    /// `prefix`
+    pub prefix: Option<PathBuf>,
+    /// `exec_prefix`
+    pub exec_prefix: Option<PathBuf>,
+    /// `BINDIR`
+    pub bindir: Option<PathBuf>,
+    /// `INCLUDEDIR`
+    pub include_dir: Option<PathBuf>,
+    /// `LIBDIR`
+    pub lib_dir: Option<PathBuf>,
+    /// `LIBPL`
+    pub config_dir: Option<PathBuf>,
+}
+
+impl PythonConfig {
+    /// Returns every directory of the installation as a normalized path,
+    /// `None` for those unset. Prefer it to the string getters like
+    /// [`prefix`](Self::prefix), which remain for compatibility.
+    pub fn paths(&self) -> InstallPaths {
+        InstallPaths {
+            prefix: self.prefix_path(),
+            exec_prefix: self.exec_prefix_path(),
+            bindir: self.bindir_path(),
+            include_dir: self.include_dir_path(),
+            lib_dir: self.lib_dir_path(),
+            config_dir: self.config_dir_path(),
+        }
+    }
+
+    /// Returns [`prefix`](Self::prefix) as a normalized path, `None` when unset.
+    ///
+    /// Paths of Windows distributions use `\` separators and drive letters,
+    /// see [`normalize_windows_path`], whatever the host platform.
+    pub fn prefix_path(&self) -> Option<PathBuf> {
+        self.normalize(self.prefix_str())
+    }
+
+    /// Returns [`exec_prefix`](Self::exec_prefix) as a normalized path, `None` when unset
+    pub fn exec_prefix_path(&self) -> Option<PathBuf> {
+        self.normalize(self.exec_prefix_str())
+    }
+
+    /// Returns [`bindir`](Self::bindir) as a normalized path, `None` when unset
+    pub fn bindir_path(&self) -> Option<PathBuf> {
+        self.normalize(self.bindir_str())
+    }
+
+    /// Returns [`include_dir`](Self::include_dir) as a normalized path, `None` when unset
+    pub fn include_dir_path(&self) -> Option<PathBuf> {
+        self.normalize(self.include_dir_str())
+    }
+
+    /// Returns [`lib_dir`](Self::lib_dir) as a normalized path, `None` when unset
+    pub fn lib_dir_path(&self) -> Option<PathBuf> {
+        self.normalize(self.lib_dir_str())
+    }
+
+    /// Returns [`config_dir`](Self::config_dir) as a normalized path, `None` when unset
+    pub fn config_dir_path(&self) -> Option<PathBuf> {
+        self.normalize(self.config_dir_str())
+    }
+
+    /// `path` without repeated separators, `.` components and trailing
+    /// separators, `None` if empty
+    fn normalize(&self, path: &str) -> Option<PathBuf> {
+        if path.is_empty() {
+            None
+        } else if self.sys_platform() == "win32" {
+            Some(PathBuf::from(normalize_windows_path(path)))
+        } else {
+            Some(Path::new(path).components().collect())
+        }
+    }
+}
+
+impl BuildTimeVars {
+    /// Expands `${var}` and `$(var)` references in the installation paths
+    /// against the other string values of `build_time_vars`, unknown
+    /// variables expand to nothing. The raw values are left untouched.
+    pub(crate) fn expand_path_placeholders(&mut self) {
+        if self.keep_placeholders {
+            return;
+        }
+        let templates: Vec<(&str, String)> = PATH_KEYS
+            .iter()
+            .filter_map(|key| {
+                let value = self.raw.get(*key)?.as_str()?;
+                let placeholder = value.contains("${") || value.contains("$(");
+                placeholder.then(|| (*key, value.to_string()))
+            })
+            .collect();
+        if templates.is_empty() {
+            return;
+        }
+        let strings: BTreeMap<String, String> = self
+            .raw
+            .iter()
+            .filter_map(|(key, value)| Some((key.to_string(), value.as_str()?.to_string())))
+            .collect();
+        for (key, template) in templates {
+            let path = expand(&template, &strings, 0);
+            match key {
+                "prefix" => self.prefix = path,
+                "exec_prefix" => self.exec_prefix = path,
+                "BINDIR" => self.bindir = path,
+                "INCLUDEDIR" => self.include_dir = path,
+                "LIBDIR" => self.lib_dir = path,
+                _ => self.config_dir = path,
+            }
+        }
+    }
+}
+
+/// Normalizes a Windows path as written by native, MSYS2 or mingw Pythons:
+/// `C:/msys64/mingw64`, `c:\msys64\mingw64\` and `/c/msys64/mingw64` all
+/// become `C:\msys64\mingw64`.
+///
+/// Separators are `\`, the drive letter is uppercase and repeated or
+/// trailing separators are dropped. UNC paths keep their leading `\\`.
+pub fn normalize_windows_path(path: &str) -> String {
+    let path = path.replace('/', "\\");
+    let bytes = path.as_bytes();
+    let (root, rest) = if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        (drive(bytes[0]), &path[2..])
+    } else if let Some(rest) = path.strip_prefix("\\\\") {
+        ("\\\\".to_string(), rest)
+    } else if let Some(rest) = path.strip_prefix('\\') {
+        match rest.as_bytes() {
+            // MSYS2 mount of drive C: at /c
+            [letter, ..]
+                if letter.is_ascii_alphabetic()
+                    && matches!(rest.as_bytes().get(1), None | Some(b'\\')) =>
+            {
+                (drive(*letter), &rest[1..])
+            }
+            _ => ("\\".to_string(), rest),
+        }
+    } else {
+        (String::new(), path.as_str())
+    };
+    let parts: Vec<&str> = rest
+        .split('\\')
+        .filter(|x| !x.is_empty() && *x != ".")
+        .collect();
+    root + &parts.join("\\")
+}
+
+/// Root of drive `letter`, `C:\`
+fn drive(letter: u8) -> String {
+    format!("{}:\\", letter.to_ascii_uppercase() as char)
+}
+
+/// `parts` appended to the Windows path `base` with `\` separators
+pub(crate) fn windows_join(base: &str, parts: &[&str]) -> PathBuf {
+    let mut path = normalize_windows_path(base);
+    for part in parts {
+        if !path.is_empty() && !path.ends_with('\\') {
+            path.push('\\');
+        }
+        path.push_str(part);
+    }
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_windows_path, windows_join};
+    use crate::{fixtures, ConfigValue, ParseOptions, PythonConfig};
+    use std::path::Path;
+
+    #[test]
+    fn typed_paths() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(config.prefix_path().unwrap(), Path::new("/usr/local"));
+        assert_eq!(
+            config.config_dir_path().unwrap(),
+            Path::new("/usr/local/lib/python3.11/config-3.11-x86_64-linux-gnu")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': '/opt//python/', \
+             'LIBDIR': '/opt/python/./lib/'}",
+        )
+        .unwrap();
+        let prefix = config.prefix_path().unwrap();
+        assert_eq!(prefix.to_str(), Some("/opt/python"));
+        let lib_dir = config.lib_dir_path().unwrap();
+        assert_eq!(lib_dir.to_str(), Some("/opt/python/lib"));
+        assert_eq!(config.bindir_path(), None);
+        let paths = config.paths();
+        assert_eq!(paths.prefix, Some(prefix));
+        assert_eq!(paths.lib_dir, Some(lib_dir));
+        assert_eq!(paths.include_dir, None);
+    }
+
+    #[test]
+    fn path_placeholders() {
+        let src = "build_time_vars = {'VERSION': '3.12', 'prefix': '/opt/py', \
+                   'exec_prefix': '${prefix}', 'INCLUDEDIR': '${exec_prefix}/include', \
+                   'LIBDIR': '$(exec_prefix)/lib', 'LIBPL': '${LIBDIR}/python3.12/${MISSING}'}";
+        let mut config = PythonConfig::parse(src).unwrap();
+        assert_eq!(config.exec_prefix_str(), "/opt/py");
+        assert_eq!(config.include_dir_str(), "/opt/py/include");
+        assert_eq!(config.lib_dir_str(), "/opt/py/lib");
+        assert_eq!(config.config_dir_str(), "/opt/py/lib/python3.12/");
+        assert_eq!(
+            config.get("INCLUDEDIR").and_then(ConfigValue::as_str),
+            Some("${exec_prefix}/include")
+        );
+        config
+            .set("prefix", ConfigValue::String("/usr".into()))
+            .unwrap();
+        assert_eq!(
+            config.include_dir_path().unwrap(),
+            Path::new("/usr/include")
+        );
+
+        let options = ParseOptions::new().keep_placeholders(true);
+        let config = PythonConfig::parse_with_options(src, &options).unwrap();
+        assert_eq!(config.include_dir_str(), "${exec_prefix}/include");
+    }
+
+    #[test]
+    fn windows_paths() {
+        assert_eq!(
+            normalize_windows_path("C:/msys64/mingw64"),
+            "C:\\msys64\\mingw64"
+        );
+        assert_eq!(normalize_windows_path("c:\\Python311\\"), "C:\\Python311");
+        assert_eq!(
+            normalize_windows_path("/c/msys64//mingw64/"),
+            "C:\\msys64\\mingw64"
+        );
+        assert_eq!(normalize_windows_path("/c"), "C:\\");
+        assert_eq!(
+            normalize_windows_path("//server/share/py"),
+            "\\\\server\\share\\py"
+        );
+        assert_eq!(normalize_windows_path("/mingw64/lib"), "\\mingw64\\lib");
+        assert_eq!(
+            normalize_windows_path("Lib/site-packages"),
+            "Lib\\site-packages"
+        );
+        assert_eq!(
+            windows_join("C:/Python311/", &["Lib", "site-packages"]),
+            Path::new("C:\\Python311\\Lib\\site-packages")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'prefix': '/c/msys64/mingw64', \
+             'LIBDIR': 'C:/msys64/mingw64/lib', 'EXT_SUFFIX': '.cp311-mingw_x86_64.pyd'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.prefix_path().unwrap().to_str(),
+            Some("C:\\msys64\\mingw64")
+        );
+        assert_eq!(
+            config.lib_dir_path().unwrap().to_str(),
+            Some("C:\\msys64\\mingw64\\lib")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'prefix': 'c:/Python311/', \
+             'EXT_SUFFIX': '.cp311-win_amd64.pyd'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.install_scheme().purelib.to_str(),
+            Some("C:\\Python311\\Lib\\site-packages")
+        );
+    }
+}
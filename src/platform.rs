@@ -0,0 +1,537 @@
+use crate::registry::Registry;
+use crate::{arch, PythonConfig};
+
+/// Operating system as spelled by Rust target triples, cargo and
+/// `sys.platform`, one row of the table behind
+/// [`expected_for_target`](crate::expected_for_target) and the operating
+/// system check of
+/// [`PythonConfig::assert_matches_cargo_target`](crate::PythonConfig::assert_matches_cargo_target).
+///
+/// Windows isn't part of the table, its tags depend on the toolchain and
+/// the architecture. [`register_platform`] adds other systems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Platform {
+    /// Operating system part of Rust target triples, `linux`
+    pub rust_os: &'static str,
+    /// `CARGO_CFG_TARGET_OS`, `macos`
+    pub cargo_os: &'static str,
+    /// `sys.platform` without version digits, `freebsd` for `freebsd14`
+    pub sys_platform: &'static str,
+    /// Suffix of extension modules, `.so`
+    pub shlib_suffix: &'static str,
+    /// Platform part of `EXT_SUFFIX`, `{arch}` standing for the multiarch
+    /// name of the architecture and `{env}` for the environment of the
+    /// triple, empty when extension suffixes have none
+    pub ext_platform: &'static str,
+    /// `MULTIARCH`, with the same placeholders
+    pub multiarch: Option<&'static str>,
+    /// Environment of triples without one, `gnu` for Linux
+    pub default_env: &'static str,
+}
+
+const fn platform(
+    rust_os: &'static str,
+    cargo_os: &'static str,
+    sys_platform: &'static str,
+    shlib_suffix: &'static str,
+    ext_platform: &'static str,
+    multiarch: Option<&'static str>,
+) -> Platform {
+    Platform {
+        rust_os,
+        cargo_os,
+        sys_platform,
+        shlib_suffix,
+        ext_platform,
+        multiarch,
+        default_env: "",
+    }
+}
+
+const PLATFORMS: &[Platform] = &[
+    Platform {
+        default_env: "gnu",
+        ..platform(
+            "linux",
+            "linux",
+            "linux",
+            ".so",
+            "{arch}-linux-{env}",
+            Some("{arch}-linux-{env}"),
+        )
+    },
+    platform("darwin", "macos", "darwin", ".so", "darwin", Some("darwin")),
+    platform("ios", "ios", "darwin", ".so", "darwin", Some("darwin")),
+    platform("cygwin", "cygwin", "cygwin", ".dll", "{arch}-cygwin", None),
+    platform("freebsd", "freebsd", "freebsd", ".so", "", None),
+    platform("openbsd", "openbsd", "openbsd", ".so", "", None),
+    platform("netbsd", "netbsd", "netbsd", ".so", "", None),
+    platform("dragonfly", "dragonfly", "dragonfly", ".so", "", None),
+    platform(
+        "emscripten",
+        "emscripten",
+        "emscripten",
+        ".so",
+        "wasm32-emscripten",
+        Some("wasm32-emscripten"),
+    ),
+    platform(
+        "wasi",
+        "wasi",
+        "wasi",
+        ".so",
+        "wasm32-wasi",
+        Some("wasm32-wasi"),
+    ),
+];
+
+/// The built-in platforms and the ones added with [`register_platform`]
+static REGISTRY: Registry<Platform> = Registry::new(PLATFORMS);
+
+/// Adds an operating system to the table, for example a new port or a
+/// corporate fork with its own `sys.platform`.
+///
+/// Registered platforms take precedence over the built-in ones with the
+/// same names.
+pub fn register_platform(platform: Platform) {
+    REGISTRY.register(platform);
+}
+
+/// Returns the table of known operating systems, registered ones first
+pub fn platforms() -> Vec<Platform> {
+    REGISTRY.entries()
+}
+
+/// The platform of the operating system part of a Rust target triple
+pub(crate) fn find_by_rust_os(os: &str) -> Option<Platform> {
+    REGISTRY.find(|platform| platform.rust_os == os)
+}
+
+/// The platform of `sys.platform`, ignoring version digits
+pub(crate) fn find_by_sys_platform(sys_platform: &str) -> Option<Platform> {
+    let name = sys_platform.trim_end_matches(|c: char| c.is_ascii_digit());
+    REGISTRY.find(|platform| platform.sys_platform == name)
+}
+
+impl PythonConfig {
+    /// Returns the value of `sys.platform` for this distribution,
+    /// for example `linux`, `darwin` or `win32`
+    pub fn sys_platform(&self) -> &str {
+        self.derived
+            .sys_platform
+            .get_or_init(|| self.compute_sys_platform())
+    }
+
+    fn compute_sys_platform(&self) -> String {
+        let vars = &self.sys_config_data.build_time_vars;
+        if !vars.machdep.is_empty() {
+            return vars.machdep.clone();
+        }
+        let platform = vars.platform_chunk();
+        // pyconfig.h of Windows installations defines MS_WINDOWS
+        if platform.starts_with("win")
+            || platform.starts_with("mingw")
+            || vars.get_int("MS_WINDOWS") == Some(1)
+        {
+            "win32".to_string()
+        } else if platform.contains("-linux") {
+            "linux".to_string()
+        } else {
+            platform.rsplit('-').next().unwrap_or_default().to_string()
+        }
+    }
+
+    /// Returns whether this is a mingw-w64 build such as the Pythons of MSYS2,
+    /// `sys.platform` is `win32` but the toolchain and layout are GNU ones
+    pub fn is_mingw(&self) -> bool {
+        let vars = &self.sys_config_data.build_time_vars;
+        vars.platform_chunk().starts_with("mingw") || vars.host_gnu_type.contains("-mingw")
+    }
+
+    /// Returns whether this is a Cygwin build, with a posix layout but a
+    /// libpython DLL extension modules have to link against
+    pub fn is_cygwin(&self) -> bool {
+        self.sys_platform().starts_with("cygwin")
+    }
+
+    /// Returns whether this is an Android build, `sys.platform` `android`
+    /// since Python 3.13 and `linux` with an Android target triple before
+    pub fn is_android(&self) -> bool {
+        let vars = &self.sys_config_data.build_time_vars;
+        self.sys_platform() == "android"
+            || [vars.host_gnu_type.as_str(), vars.multiarch.as_str()]
+                .iter()
+                .any(|x| x.contains("android"))
+    }
+
+    /// Returns whether this is an x32 build, the ILP32 ABI of x86_64 with
+    /// 4 byte pointers, `x86_64-linux-gnux32`
+    pub fn is_x32(&self) -> bool {
+        let vars = &self.sys_config_data.build_time_vars;
+        [
+            vars.multiarch.as_str(),
+            vars.host_gnu_type.as_str(),
+            vars.platform_chunk(),
+        ]
+        .iter()
+        .any(|x| x.ends_with("-gnux32"))
+    }
+
+    /// Returns whether this is an AIX build, `sys.platform` `aix7` for example
+    pub fn is_aix(&self) -> bool {
+        self.sys_platform().starts_with("aix")
+    }
+
+    /// Returns whether this is a Solaris or illumos build, `sys.platform` `sunos5`
+    pub fn is_solaris(&self) -> bool {
+        self.sys_platform().starts_with("sunos")
+    }
+
+    /// Returns the value of `sysconfig.get_platform()` for this distribution,
+    /// for example `linux-x86_64`, `macosx-11.0-arm64` or `mingw_x86_64`
+    pub fn get_platform(&self) -> &str {
+        self.derived
+            .platform
+            .get_or_init(|| self.compute_platform())
+    }
+
+    fn compute_platform(&self) -> String {
+        let vars = &self.sys_config_data.build_time_vars;
+        let platform = vars.platform_chunk();
+        match platform {
+            "win_amd64" => return "win-amd64".to_string(),
+            "win_arm64" => return "win-arm64".to_string(),
+            "win32" => return "win32".to_string(),
+            _ if platform.starts_with("mingw") => return platform.to_string(),
+            _ => {}
+        }
+        let sys_platform = self.sys_platform();
+        let machine = self.machine();
+        if self.is_aix() {
+            if let Some(platform) = self.aix_platform() {
+                return platform;
+            }
+        }
+        if self.is_solaris() {
+            // x86_64-pc-solaris2.11
+            if let Some(release) = vars.host_gnu_type.split("-solaris").nth(1) {
+                let machine = if machine.starts_with("sparc") {
+                    "sun4v"
+                } else {
+                    "i86pc"
+                };
+                let bitness = if self.pointer_size() == 4 {
+                    "32bit"
+                } else {
+                    "64bit"
+                };
+                return format!("solaris-{}-{}.{}", release, machine, bitness);
+            }
+        }
+        if let Some(os) = bsd_os(sys_platform) {
+            return bsd_platform(os, &vars.host_gnu_type, &machine);
+        }
+        if sys_platform == "darwin" {
+            let release = &vars.macosx_deployment_target;
+            return format!(
+                "macosx-{}-{}",
+                release,
+                macos_machine(&vars.cflags, &machine)
+            );
+        }
+        let osname = sys_platform.trim_end_matches(|c: char| c.is_ascii_digit());
+        let machine = match arch::find_by_name(&machine) {
+            Some(arch) if osname == "linux" => arch.machine,
+            _ => &machine,
+        };
+        format!("{}-{}", osname, machine)
+    }
+
+    /// `_aix_support.aix_platform()`, `aix-7205-2015-64` from the AIX version
+    /// in `BUILD_GNU_TYPE` and `AIX_BUILDDATE`
+    fn aix_platform(&self) -> Option<String> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let gnu_type = match vars.get_str("BUILD_GNU_TYPE") {
+            "" => &vars.host_gnu_type,
+            gnu_type => gnu_type,
+        };
+        // powerpc-ibm-aix7.2.5.0
+        let vrmf = gnu_type.rsplit('-').next()?.strip_prefix("aix")?;
+        let mut parts = vrmf.split('.').map(|x| x.parse::<u32>().ok());
+        let version = parts.next()??;
+        let release = parts.next().flatten().unwrap_or(0);
+        let technology_level = parts.next().flatten().unwrap_or(0);
+        // _MISSING_BD of _aix_support, for builds predating AIX_BUILDDATE
+        let build_date = vars.get_int("AIX_BUILDDATE").unwrap_or(9988);
+        Some(format!(
+            "aix-{}{}{:02}-{:04}-{}",
+            version,
+            release,
+            technology_level,
+            build_date,
+            self.pointer_size() * 8
+        ))
+    }
+
+    /// Returns the name of the `_sysconfigdata` module this distribution
+    /// imports, for example `_sysconfigdata__linux_x86_64-linux-gnu` or
+    /// `_sysconfigdata_t_linux_x86_64-linux-gnu` for free-threaded builds.
+    ///
+    /// Cross compilation tools set `_PYTHON_SYSCONFIGDATA_NAME` to it so a host
+    /// interpreter loads the target's data. Returns `None` on Windows, which
+    /// has no sysconfigdata, except for mingw builds.
+    pub fn sysconfigdata_name(&self) -> Option<String> {
+        if self.sys_platform() == "win32" && !self.is_mingw() {
+            return None;
+        }
+        if !self.version_rules().sysconfigdata_suffixed {
+            return Some("_sysconfigdata".to_string());
+        }
+        Some(format!(
+            "_sysconfigdata_{}_{}_{}",
+            self.abiflags(),
+            self.sys_platform(),
+            self.multiarch()
+        ))
+    }
+
+    /// Machine name as reported by `uname -m`, derived from `HOST_GNU_TYPE`
+    /// or `MULTIARCH`
+    pub(crate) fn machine(&self) -> String {
+        let vars = &self.sys_config_data.build_time_vars;
+        [
+            vars.host_gnu_type.as_str(),
+            vars.multiarch.as_str(),
+            vars.platform_chunk(),
+        ]
+        .iter()
+        .filter(|x| x.contains('-'))
+        .filter_map(|x| x.split('-').next())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+    }
+}
+
+/// BSD operating system of `sys.platform`, `freebsd` for `freebsd14`
+pub(crate) fn bsd_os(sys_platform: &str) -> Option<&'static str> {
+    ["freebsd", "openbsd", "netbsd", "dragonfly"]
+        .iter()
+        .copied()
+        .find(|os| sys_platform.starts_with(os))
+}
+
+/// `get_platform()` of BSDs, `{os}-{release}-{machine}` with the release of
+/// `HOST_GNU_TYPE` (`amd64-portbld-freebsd14.0`) and the `uname -m` name of
+/// the architecture. The branch suffix of the release, `-RELEASE` for
+/// example, isn't recorded at build time and is left out.
+fn bsd_platform(os: &str, host_gnu_type: &str, machine: &str) -> String {
+    let machine = match (os, machine) {
+        (_, "x86_64") => "amd64",
+        ("netbsd", "aarch64") => "evbarm",
+        (_, "aarch64") => "arm64",
+        (_, "i386") | (_, "i486") | (_, "i586") | (_, "i686") => "i386",
+        (_, machine) => machine,
+    };
+    let release = host_gnu_type
+        .rsplit('-')
+        .next()
+        .and_then(|x| x.strip_prefix(os))
+        .filter(|x| !x.is_empty());
+    match release {
+        Some(release) => format!("{}-{}-{}", os, release, machine),
+        None => format!("{}-{}", os, machine),
+    }
+}
+
+/// Mirrors the universal binary handling of `_osx_support.get_platform_osx`
+fn macos_machine(cflags: &str, machine: &str) -> String {
+    let mut archs: Vec<&str> = Vec::new();
+    let mut flags = cflags.split_whitespace();
+    while let Some(flag) = flags.next() {
+        if flag == "-arch" {
+            if let Some(arch) = flags.next() {
+                archs.push(arch);
+            }
+        }
+    }
+    archs.sort_unstable();
+    archs.dedup();
+    match archs.as_slice() {
+        [] if machine == "aarch64" => "arm64".to_string(),
+        [] => machine.to_string(),
+        [arch] => arch.to_string(),
+        ["arm64", "x86_64"] => "universal2".to_string(),
+        ["i386", "ppc"] => "fat".to_string(),
+        ["i386", "x86_64"] => "intel".to_string(),
+        ["i386", "ppc", "x86_64"] => "fat3".to_string(),
+        ["ppc64", "x86_64"] => "fat64".to_string(),
+        _ => "universal".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_by_rust_os, find_by_sys_platform, platforms};
+    use crate::PythonConfig;
+    use std::fs;
+
+    #[test]
+    fn platform_table() {
+        assert_eq!(
+            find_by_sys_platform("freebsd14").unwrap().rust_os,
+            "freebsd"
+        );
+        assert_eq!(find_by_sys_platform("darwin").unwrap().cargo_os, "macos");
+        assert_eq!(find_by_rust_os("ios").unwrap().sys_platform, "darwin");
+        assert!(find_by_rust_os("haiku").is_none());
+
+        assert!(find_by_sys_platform("haiku1").is_none());
+        assert_eq!(find_by_rust_os("cygwin").unwrap().shlib_suffix, ".dll");
+        assert!(platforms()
+            .iter()
+            .any(|platform| platform.rust_os == "dragonfly"));
+    }
+
+    #[test]
+    fn get_platform() {
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.sys_platform(), "darwin");
+        assert_eq!(config.get_platform(), "macosx-10.14.6-universal2");
+
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.sys_platform(), "linux");
+        assert_eq!(config.get_platform(), "linux-x86_64");
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'EXT_SUFFIX': '.cp311-mingw_x86_64.pyd'}",
+        )
+        .unwrap();
+        assert_eq!(config.sys_platform(), "win32");
+        assert_eq!(config.get_platform(), "mingw_x86_64");
+        assert!(config.is_mingw());
+        assert_eq!(
+            config.sysconfigdata_name().as_deref(),
+            Some("_sysconfigdata__win32_")
+        );
+    }
+
+    #[test]
+    fn linux_machines() {
+        let platform = |multiarch: &str| {
+            let config = PythonConfig::parse(&format!(
+                "build_time_vars = {{'VERSION': '3.12', 'MULTIARCH': '{0}', \
+                 'SOABI': 'cpython-312-{0}'}}",
+                multiarch
+            ))
+            .unwrap();
+            (config.get_platform().to_string(), config.platform_tag())
+        };
+        let expected = |platform: &str, tag: &str| (platform.to_string(), tag.to_string());
+        assert_eq!(
+            platform("riscv64-linux-gnu"),
+            expected("linux-riscv64", "manylinux_2_31_riscv64")
+        );
+        assert_eq!(
+            platform("powerpc64le-linux-gnu"),
+            expected("linux-ppc64le", "manylinux_2_17_ppc64le")
+        );
+        assert_eq!(
+            platform("s390x-linux-gnu"),
+            expected("linux-s390x", "manylinux_2_17_s390x")
+        );
+        assert_eq!(
+            platform("loongarch64-linux-gnu"),
+            expected("linux-loongarch64", "manylinux_2_36_loongarch64")
+        );
+        assert_eq!(
+            platform("arm-linux-gnueabihf"),
+            expected("linux-armv7l", "manylinux_2_17_armv7l")
+        );
+        assert_eq!(
+            platform("x86_64-linux-gnux32"),
+            expected("linux-x86_64", "manylinux_2_17_i686")
+        );
+        assert_eq!(crate::arch_pointer_size("hppa"), 4);
+        assert_eq!(crate::arch_pointer_size("s390x"), 8);
+    }
+
+    #[test]
+    fn bsd() {
+        let config = crate::fixtures::get("cpython311-freebsd").unwrap().config();
+        assert_eq!(config.sys_platform(), "freebsd14");
+        assert_eq!(config.get_platform(), "freebsd-14.0-amd64");
+        assert_eq!(config.platform_tag(), "freebsd_14_0_amd64");
+        assert_eq!(
+            config.sysconfigdata_name().as_deref(),
+            Some("_sysconfigdata__freebsd14_")
+        );
+
+        let platform = |src: &str| PythonConfig::parse(src).unwrap().get_platform().to_string();
+        assert_eq!(
+            platform(
+                "build_time_vars = {'VERSION': '3.11', 'MACHDEP': 'openbsd7', \
+                 'HOST_GNU_TYPE': 'aarch64-unknown-openbsd7.4'}"
+            ),
+            "openbsd-7.4-arm64"
+        );
+        assert_eq!(
+            platform(
+                "build_time_vars = {'VERSION': '3.11', 'MACHDEP': 'netbsd10', \
+                 'HOST_GNU_TYPE': 'x86_64--netbsd'}"
+            ),
+            "netbsd-amd64"
+        );
+    }
+
+    #[test]
+    fn aix_and_solaris() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'MACHDEP': 'aix', 'SIZEOF_VOID_P': 8, \
+             'BUILD_GNU_TYPE': 'powerpc-ibm-aix7.2.5.0', 'AIX_BUILDDATE': 2015}",
+        )
+        .unwrap();
+        assert!(config.is_aix());
+        assert_eq!(config.get_platform(), "aix-7205-2015-64");
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'MACHDEP': 'sunos5', 'SIZEOF_VOID_P': 8, \
+             'HOST_GNU_TYPE': 'x86_64-pc-solaris2.11'}",
+        )
+        .unwrap();
+        assert!(config.is_solaris());
+        assert_eq!(config.get_platform(), "solaris-2.11-i86pc.64bit");
+        assert_eq!(config.platform_tag(), "solaris_2_11_i86pc_64bit");
+    }
+
+    #[test]
+    fn sysconfigdata_name() {
+        let name = |src: &str| PythonConfig::parse(src).unwrap().sysconfigdata_name();
+        assert_eq!(
+            name(
+                "build_time_vars = {'VERSION': '3.13', 'ABIFLAGS': 't', \
+                  'MACHDEP': 'linux', 'MULTIARCH': 'aarch64-linux-gnu'}"
+            )
+            .as_deref(),
+            Some("_sysconfigdata_t_linux_aarch64-linux-gnu")
+        );
+        assert_eq!(
+            name("build_time_vars = {'VERSION': '3.12', 'MACHDEP': 'freebsd14'}").as_deref(),
+            Some("_sysconfigdata__freebsd14_")
+        );
+        assert_eq!(
+            name("build_time_vars = {'VERSION': '3.5', 'MACHDEP': 'linux'}").as_deref(),
+            Some("_sysconfigdata")
+        );
+        assert_eq!(
+            name("build_time_vars = {'VERSION': '3.11', 'EXT_SUFFIX': '.cp311-win_amd64.pyd'}"),
+            None
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use crate::{ConfigValue, Error, Phase, PythonConfig, Relocate, Source, Transform};
+
+/// A hook run on every configuration after parsing, see
+/// [`register_post_processor`]. Every [`Transform`] that can be shared
+/// between threads is one.
+pub trait ConfigPostProcessor: Send + Sync {
+    /// Modifies the freshly parsed `config` in place
+    fn process(&self, config: &mut PythonConfig) -> Result<(), Error>;
+
+    /// Name recorded as [`Source::Transform`] for the keys this processor
+    /// sets
+    fn name(&self) -> String;
+}
+
+impl<T: Transform + Send + Sync> ConfigPostProcessor for T {
+    fn process(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        self.transform(config)
+    }
+
+    fn name(&self) -> String {
+        Transform::name(self)
+    }
+}
+
+/// Processors added with [`register_post_processor`]
+static REGISTERED: RwLock<Vec<Arc<dyn ConfigPostProcessor>>> = RwLock::new(Vec::new());
+
+/// Runs `processor` on every configuration parsed from now on, after the
+/// ones registered before, so an organization can apply its conventions,
+/// such as [`ResolvePrefixSymlink`] or [`ForceStaticLinking`], once at
+/// startup instead of wrapping every accessor.
+///
+/// Applies to [`PythonConfig::parse`] and the functions built on it, and to
+/// [`PythonConfig::from_vars`]. A failing processor fails the parse.
+pub fn register_post_processor(processor: impl ConfigPostProcessor + 'static) {
+    REGISTERED.write().unwrap().push(Arc::new(processor));
+}
+
+impl PythonConfig {
+    /// Runs the registered post-processors on this configuration
+    pub(crate) fn post_process(self) -> Result<Self, Error> {
+        let processors = REGISTERED.read().unwrap().clone();
+        self.run_post_processors(&processors)
+    }
+
+    /// Runs `processors` in order, recording the keys they set
+    fn run_post_processors(
+        mut self,
+        processors: &[Arc<dyn ConfigPostProcessor>],
+    ) -> Result<Self, Error> {
+        for processor in processors {
+            self.with_source(Source::Transform(processor.name()), |config| {
+                processor.process(config)
+            })
+            .map_err(|err| err.with_phase(Phase::PostProcess))?;
+        }
+        Ok(self)
+    }
+}
+
+/// Replaces a prefix reached through a symbolic link, such as
+/// `/opt/toolchains/current`, with the directory it points to, so builds
+/// keep using the toolchain they started with when the link is switched.
+/// Does nothing when the link can't be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvePrefixSymlink {
+    link: PathBuf,
+}
+
+impl ResolvePrefixSymlink {
+    /// Resolve paths under the symbolic link `link`
+    pub fn new(link: impl Into<PathBuf>) -> Self {
+        Self { link: link.into() }
+    }
+}
+
+impl Transform for ResolvePrefixSymlink {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        let target = match fs::canonicalize(&self.link) {
+            Ok(target) if target != self.link => target,
+            _ => return Ok(()),
+        };
+        let (link, target) = match (self.link.to_str(), target.to_str()) {
+            (Some(link), Some(target)) => (link, target),
+            _ => return Ok(()),
+        };
+        Relocate::new(link, target).transform(config)
+    }
+}
+
+/// Links libpython statically, for policies shipping self-contained
+/// binaries: `Py_ENABLE_SHARED` is cleared and `LDLIBRARY` becomes the
+/// static library of `LIBRARY`. Fails for distributions without one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForceStaticLinking {}
+
+impl ForceStaticLinking {
+    /// Force static linking
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Transform for ForceStaticLinking {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        let library = match config.get("LIBRARY").and_then(ConfigValue::as_str) {
+            Some(library) if library.ends_with(".a") => library.to_string(),
+            _ => {
+                return Err(Error::Unsupported(
+                    "the distribution has no static libpython".to_string(),
+                ))
+            }
+        };
+        config.set("Py_ENABLE_SHARED", ConfigValue::Int(0))?;
+        config.set("LDLIBRARY", ConfigValue::String(library.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{ConfigPostProcessor, ForceStaticLinking};
+    use crate::{fixtures, ConfigValue, Error, LinkModel, Phase, PythonConfig, Source};
+
+    /// Tags configurations of the prefix `/opt/post-process`
+    struct TagPrefix;
+
+    impl ConfigPostProcessor for TagPrefix {
+        fn process(&self, config: &mut PythonConfig) -> Result<(), Error> {
+            if config.prefix_str() != "/opt/post-process" {
+                return Err(Error::Unsupported("unexpected prefix".to_string()));
+            }
+            config.set("ORG_POLICY", ConfigValue::String("checked".into()))
+        }
+
+        fn name(&self) -> String {
+            "TagPrefix".to_string()
+        }
+    }
+
+    #[test]
+    fn post_processors() {
+        // run on a list of their own, the registered processors apply to
+        // every test parsing a configuration
+        let processors: Vec<Arc<dyn ConfigPostProcessor>> = vec![Arc::new(TagPrefix)];
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': '/opt/post-process'}",
+        )
+        .unwrap()
+        .run_post_processors(&processors)
+        .unwrap();
+        assert_eq!(
+            config.get("ORG_POLICY").and_then(ConfigValue::as_str),
+            Some("checked")
+        );
+        assert_eq!(
+            config.provenance("ORG_POLICY"),
+            Some(Source::Transform("TagPrefix".to_string()))
+        );
+        let err = PythonConfig::parse("build_time_vars = {'VERSION': '3.12'}")
+            .unwrap()
+            .run_post_processors(&processors)
+            .unwrap_err();
+        assert_eq!(err.phase(), Some(Phase::PostProcess));
+
+        let mut config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(config.link_model(), LinkModel::Shared);
+        ForceStaticLinking::new().process(&mut config).unwrap();
+        assert_eq!(config.link_model(), LinkModel::Static);
+        assert_eq!(
+            config.get("LDLIBRARY").and_then(ConfigValue::as_str),
+            Some("libpython3.11.a")
+        );
+
+        let mut config = PythonConfig::parse("build_time_vars = {'VERSION': '3.12'}").unwrap();
+        assert!(ForceStaticLinking::new().process(&mut config).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolve_prefix_symlink() {
+        use super::ResolvePrefixSymlink;
+        use std::fs;
+
+        let dir = std::env::temp_dir().join(format!(
+            "python3-config-post-process-{}",
+            std::process::id()
+        ));
+        let release = dir.join("python-3.12.1");
+        fs::create_dir_all(&release).unwrap();
+        let current = dir.join("current");
+        std::os::unix::fs::symlink(&release, &current).unwrap();
+        let release = fs::canonicalize(&release).unwrap();
+
+        let mut config = PythonConfig::parse(&format!(
+            "build_time_vars = {{'VERSION': '3.12', 'prefix': '{}', 'LIBDIR': '{}/lib'}}",
+            current.display(),
+            current.display()
+        ))
+        .unwrap();
+        ResolvePrefixSymlink::new(&current)
+            .process(&mut config)
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(config.prefix_str(), release.to_str().unwrap());
+        assert_eq!(config.lib_dir_str(), format!("{}/lib", release.display()));
+
+        let before = config.clone();
+        ResolvePrefixSymlink::new(&current)
+            .process(&mut config)
+            .unwrap();
+        assert_eq!(config.prefix_str(), before.prefix_str());
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::PythonConfig;
+
+/// Where the value of a `build_time_vars` key came from, see
+/// [`PythonConfig::provenance`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Source {
+    /// Read from a `_sysconfigdata.py` file
+    SysConfigData,
+    /// Read from the additional `_sysconfigdata.py` layer at this index of
+    /// [`PythonConfig::parse_layers`]
+    Layer(usize),
+    /// Passed to [`PythonConfig::from_vars`], for example from
+    /// python-build-standalone metadata, or deserialized from JSON
+    Vars,
+    /// Compiler variables of the environment, see [`PythonConfig::with_env_flags`]
+    Environment,
+    /// Set with [`PythonConfig::set`]
+    Override,
+    /// Set by the named [`Transform`](crate::Transform)
+    Transform(String),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::SysConfigData => write!(f, "sysconfigdata"),
+            Source::Layer(index) => write!(f, "sysconfigdata layer {}", index),
+            Source::Vars => write!(f, "vars"),
+            Source::Environment => write!(f, "environment"),
+            Source::Override => write!(f, "override"),
+            Source::Transform(name) => write!(f, "transform {}", name),
+        }
+    }
+}
+
+/// Sources of the keys of a configuration
+#[derive(Debug, Clone)]
+pub(crate) struct Provenance {
+    /// Source of every key not in `overrides`
+    pub base: Source,
+    /// Keys set after loading
+    pub overrides: BTreeMap<Arc<str>, Source>,
+    /// Source recorded by [`PythonConfig::set`], `None` for [`Source::Override`]
+    pub current: Option<Source>,
+}
+
+impl Default for Provenance {
+    fn default() -> Self {
+        Self {
+            base: Source::SysConfigData,
+            overrides: BTreeMap::new(),
+            current: None,
+        }
+    }
+}
+
+impl PythonConfig {
+    /// Returns where the value of `key` came from, `None` if `key` has no
+    /// literal value
+    pub fn provenance(&self, key: &str) -> Option<Source> {
+        self.get(key)?;
+        let provenance = &self.provenance;
+        Some(
+            provenance
+                .overrides
+                .get(key)
+                .unwrap_or(&provenance.base)
+                .clone(),
+        )
+    }
+
+    /// Runs `f` with [`PythonConfig::set`] recording `source` as provenance
+    pub(crate) fn with_source<T>(&mut self, source: Source, f: impl FnOnce(&mut Self) -> T) -> T {
+        let previous = self.provenance.current.replace(source);
+        let result = f(self);
+        self.provenance.current = previous;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Source;
+    use crate::{fixtures, ApplyOverrides, ConfigValue, Transform};
+
+    #[test]
+    fn provenance() {
+        let mut config = fixtures::get("cpython311-linux").unwrap().config();
+        config.set("Py_DEBUG", ConfigValue::Int(1)).unwrap();
+        let transforms: Vec<Box<dyn Transform>> = vec![Box::new(
+            ApplyOverrides::new().set("SOABI", ConfigValue::None),
+        )];
+        let config = config
+            .apply(&transforms)
+            .unwrap()
+            .with_env_flags_from(|name| match name {
+                "CC" => Some("clang".to_string()),
+                _ => None,
+            });
+        assert_eq!(config.provenance("VERSION"), Some(Source::SysConfigData));
+        assert_eq!(config.provenance("Py_DEBUG"), Some(Source::Override));
+        assert_eq!(
+            config.provenance("SOABI"),
+            Some(Source::Transform("ApplyOverrides".to_string()))
+        );
+        assert_eq!(config.provenance("CC"), Some(Source::Environment));
+        assert_eq!(config.provenance("NO_SUCH_KEY"), None);
+    }
+}
@@ -0,0 +1,203 @@
+use std::path::Path;
+
+use pyo3_build_config::{
+    BuildFlag, BuildFlags, InterpreterConfig, PythonImplementation as Pyo3Implementation,
+    PythonVersion,
+};
+
+use crate::{
+    rules_for_version, ConfigValue, Error, LinkModel, PythonConfig, PythonImplementation,
+    DERIVATION_VERSION,
+};
+
+impl PythonConfig {
+    /// Converts into the configuration PyO3's build scripts consume, the
+    /// same `InterpreterConfig` `pyo3-build-config` derives from a
+    /// sysconfigdata file. `abi3` is left disabled.
+    ///
+    /// Its build script lines set `PYTHON3_CONFIG_DERIVATION_VERSION` to
+    /// [`DERIVATION_VERSION`], so changes of the derivation logic change the
+    /// serialized configuration.
+    ///
+    /// `build_flags` is a hash set, sort it before hashing its contents.
+    pub fn to_pyo3_config(&self) -> InterpreterConfig {
+        let version = PythonVersion {
+            major: self.major_minor().0 as u8,
+            minor: self.major_minor().1 as u8,
+        };
+        let implementation = match self.implementation() {
+            PythonImplementation::CPython => Pyo3Implementation::CPython,
+            PythonImplementation::PyPy => Pyo3Implementation::PyPy,
+            PythonImplementation::GraalPy => Pyo3Implementation::GraalPy,
+        };
+        let vars = &self.sys_config_data.build_time_vars;
+        let gil_disabled = vars.get_int("Py_GIL_DISABLED") == Some(1);
+        let mut build_flags = BuildFlags::new();
+        for (flag, enabled) in [
+            (BuildFlag::Py_DEBUG, self.debug()),
+            (BuildFlag::Py_REF_DEBUG, self.debug() || self.ref_debug()),
+            (BuildFlag::Py_TRACE_REFS, vars.py_trace_refs),
+            (BuildFlag::Py_GIL_DISABLED, gil_disabled),
+            (BuildFlag::COUNT_ALLOCS, vars.count_allocs),
+        ] {
+            if enabled {
+                build_flags.0.insert(flag);
+            }
+        }
+        let non_empty = |value: &str| Some(value.to_string()).filter(|x| !x.is_empty());
+        InterpreterConfig {
+            implementation,
+            version,
+            shared: self.enable_shared() || self.link_model() == LinkModel::Framework,
+            abi3: false,
+            lib_name: Some(self.pyo3_lib_name(version)),
+            lib_dir: non_empty(self.lib_dir_str()),
+            executable: None,
+            pointer_width: Some(self.pointer_size() * 8).filter(|width| *width != 0),
+            build_flags,
+            suppress_build_script_link_lines: false,
+            extra_build_script_lines: vec![format!(
+                "cargo:rustc-env=PYTHON3_CONFIG_DERIVATION_VERSION={}",
+                DERIVATION_VERSION
+            )],
+            python_framework_prefix: non_empty(vars.get_str("PYTHONFRAMEWORKPREFIX")),
+        }
+    }
+
+    /// Builds a configuration from a PyO3 `InterpreterConfig`, for example
+    /// one read from a `PYO3_CONFIG_FILE`. Only the keys PyO3 knows about
+    /// are set, platform details such as `EXT_SUFFIX` are missing.
+    pub fn from_pyo3_config(config: &InterpreterConfig) -> Result<Self, Error> {
+        let version = format!("{}.{}", config.version.major, config.version.minor);
+        let flag = |flag: BuildFlag| ConfigValue::Int(config.build_flags.0.contains(&flag) as i64);
+        let gil_disabled = config.build_flags.0.contains(&BuildFlag::Py_GIL_DISABLED);
+        let debug = config.build_flags.0.contains(&BuildFlag::Py_DEBUG);
+        let ld_version = config
+            .lib_name
+            .as_deref()
+            .and_then(|name| name.strip_prefix("python"))
+            .filter(|_| config.implementation == Pyo3Implementation::CPython);
+        // PyO3 has no pymalloc flag, python3.7m tells
+        let pymalloc = rules_for_version(config.version.major.into(), config.version.minor.into())
+            .pymalloc_abiflag
+            && ld_version.is_some_and(|x| x.ends_with('m'));
+        let abiflags = format!(
+            "{}{}{}",
+            if gil_disabled { "t" } else { "" },
+            if debug { "d" } else { "" },
+            if pymalloc { "m" } else { "" }
+        );
+        let nodot = format!("{}{}", config.version.major, config.version.minor);
+        let soabi = match config.implementation {
+            Pyo3Implementation::CPython => format!("cpython-{}{}", nodot, abiflags),
+            Pyo3Implementation::PyPy => format!("pypy{}", nodot),
+            Pyo3Implementation::GraalPy => "graalpy".to_string(),
+        };
+        let string = |value: &str| ConfigValue::String(value.into());
+        let mut vars = vec![
+            ("VERSION", string(&version)),
+            ("ABIFLAGS", string(&abiflags)),
+            ("SOABI", string(&soabi)),
+            ("Py_ENABLE_SHARED", ConfigValue::Int(config.shared as i64)),
+            ("Py_DEBUG", flag(BuildFlag::Py_DEBUG)),
+            ("Py_REF_DEBUG", flag(BuildFlag::Py_REF_DEBUG)),
+            ("Py_TRACE_REFS", flag(BuildFlag::Py_TRACE_REFS)),
+            ("Py_GIL_DISABLED", flag(BuildFlag::Py_GIL_DISABLED)),
+            ("COUNT_ALLOCS", flag(BuildFlag::COUNT_ALLOCS)),
+        ];
+        if let Some(lib_dir) = &config.lib_dir {
+            vars.push(("LIBDIR", string(lib_dir)));
+        }
+        if let Some(ld_version) = ld_version {
+            vars.push(("LDVERSION", string(ld_version)));
+        }
+        if let Some(width) = config.pointer_width {
+            vars.push(("SIZEOF_VOID_P", ConfigValue::Int(i64::from(width / 8))));
+        }
+        if let Some(bindir) = config
+            .executable
+            .as_deref()
+            .and_then(|executable| Path::new(executable).parent())
+        {
+            vars.push(("BINDIR", string(&bindir.to_string_lossy())));
+        }
+        if let Some(prefix) = &config.python_framework_prefix {
+            vars.push(("PYTHONFRAMEWORKPREFIX", string(prefix)));
+        }
+        PythonConfig::from_vars(
+            vars.into_iter()
+                .map(|(key, value)| (key.to_string(), value)),
+        )
+    }
+
+    /// Library name PyO3 links, `python3.11` or `pypy3.10-c` on Unix and mingw
+    fn pyo3_lib_name(&self, version: PythonVersion) -> String {
+        let free_threaded = if self.abiflags().contains('t') {
+            "t"
+        } else {
+            ""
+        };
+        match self.implementation() {
+            PythonImplementation::CPython if self.sys_platform() == "win32" && !self.is_mingw() => {
+                format!("python{}{}{}", version.major, version.minor, free_threaded)
+            }
+            PythonImplementation::CPython if !self.ld_version().is_empty() => {
+                format!("python{}", self.ld_version())
+            }
+            PythonImplementation::CPython => {
+                let abiflags = if self.version_rules().libpython_abiflags {
+                    self.abiflags()
+                } else {
+                    free_threaded
+                };
+                format!("python{}.{}{}", version.major, version.minor, abiflags)
+            }
+            PythonImplementation::PyPy => format!("pypy{}.{}-c", version.major, version.minor),
+            PythonImplementation::GraalPy => "python-native".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, PythonConfig};
+    use pyo3_build_config::{BuildFlag, PythonImplementation};
+
+    #[test]
+    fn pyo3_config() {
+        let config = fixtures::get("cpython313t-linux").unwrap().config();
+        let pyo3 = config.to_pyo3_config();
+        assert_eq!(pyo3.implementation, PythonImplementation::CPython);
+        assert_eq!((pyo3.version.major, pyo3.version.minor), (3, 13));
+        assert_eq!(pyo3.lib_name.as_deref(), Some("python3.13t"));
+        assert_eq!(pyo3.lib_dir.as_deref(), Some("/usr/local/lib"));
+        assert_eq!(pyo3.pointer_width, Some(64));
+        assert!(pyo3.shared);
+        assert!(pyo3.build_flags.0.contains(&BuildFlag::Py_GIL_DISABLED));
+        assert_eq!(
+            pyo3.extra_build_script_lines,
+            vec!["cargo:rustc-env=PYTHON3_CONFIG_DERIVATION_VERSION=3"]
+        );
+
+        let back = PythonConfig::from_pyo3_config(&pyo3).unwrap();
+        assert_eq!(back.version_str(), "3.13");
+        assert_eq!(back.abiflags(), "t");
+        assert_eq!(back.lib_dir_str(), "/usr/local/lib");
+        assert_eq!(back.ld_version(), "3.13t");
+        assert_eq!(back.pointer_size(), 8);
+        assert!(back.enable_shared());
+        assert_eq!(back.to_pyo3_config().lib_name, pyo3.lib_name);
+
+        let pyo3 = fixtures::get("pypy310-linux")
+            .unwrap()
+            .config()
+            .to_pyo3_config();
+        assert_eq!(pyo3.implementation, PythonImplementation::PyPy);
+        assert_eq!(pyo3.lib_name.as_deref(), Some("pypy3.10-c"));
+
+        let config = fixtures::get("cpython37m-linux").unwrap().config();
+        let back = PythonConfig::from_pyo3_config(&config.to_pyo3_config()).unwrap();
+        assert_eq!(back.abiflags(), "m");
+        assert_eq!(back.abi_tag(), "cp37m");
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::RwLock;
+
+/// A built-in table with entries added at runtime, behind
+/// [`register_arch`](crate::register_arch),
+/// [`register_platform`](crate::register_platform) and
+/// [`register_abiflag`](crate::register_abiflag).
+///
+/// Registered entries take precedence over the built-in ones, the last
+/// registered first.
+pub(crate) struct Registry<T: 'static> {
+    builtin: &'static [T],
+    registered: RwLock<Vec<T>>,
+}
+
+impl<T: Clone> Registry<T> {
+    pub(crate) const fn new(builtin: &'static [T]) -> Self {
+        Self {
+            builtin,
+            registered: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn register(&self, entry: T) {
+        self.registered.write().unwrap().push(entry);
+    }
+
+    /// Every entry, in lookup order
+    pub(crate) fn entries(&self) -> Vec<T> {
+        let mut entries = self.registered.read().unwrap().clone();
+        entries.reverse();
+        entries.extend_from_slice(self.builtin);
+        entries
+    }
+
+    /// The first entry matching `predicate` in lookup order
+    pub(crate) fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<T> {
+        let registered = self.registered.read().unwrap();
+        registered
+            .iter()
+            .rev()
+            .chain(self.builtin)
+            .find(|entry| predicate(entry))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+
+    #[test]
+    fn registry() {
+        let registry = Registry::new(&[(1, "one"), (2, "two")]);
+        registry.register((2, "deux"));
+        registry.register((3, "trois"));
+        assert_eq!(
+            registry.entries(),
+            vec![(3, "trois"), (2, "deux"), (1, "one"), (2, "two")]
+        );
+        assert_eq!(registry.find(|(n, _)| *n == 2), Some((2, "deux")));
+        assert_eq!(registry.find(|(n, _)| *n == 4), None);
+
+        // registries sharing a built-in table don't see each other's entries
+        static BUILTIN: &[(i32, &str)] = &[(1, "one")];
+        let first = Registry::new(BUILTIN);
+        let second = Registry::new(BUILTIN);
+        first.register((5, "cinq"));
+        assert_eq!(second.entries(), vec![(1, "one")]);
+        assert_eq!(second.find(|(n, _)| *n == 5), None);
+    }
+}
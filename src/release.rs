@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::convert::shell_split;
+use crate::{ConfigValue, PythonConfig};
+
+/// Release level of a CPython version, as in `sys.version_info`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReleaseLevel {
+    /// Alpha release with its serial, `3.14.0a4`
+    Alpha(u32),
+    /// Beta release with its serial, `3.14.0b1`
+    Beta(u32),
+    /// Release candidate with its serial, `3.14.0rc2`
+    Candidate(u32),
+    /// Final release, `3.14.0`
+    Final,
+}
+
+impl ReleaseLevel {
+    /// Whether this is an alpha, beta or release candidate
+    pub fn is_prerelease(self) -> bool {
+        self != ReleaseLevel::Final
+    }
+}
+
+/// Version suffix, `a4`, `b1`, `rc2` or nothing for final releases
+impl fmt::Display for ReleaseLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReleaseLevel::Alpha(serial) => write!(f, "a{}", serial),
+            ReleaseLevel::Beta(serial) => write!(f, "b{}", serial),
+            ReleaseLevel::Candidate(serial) => write!(f, "rc{}", serial),
+            ReleaseLevel::Final => Ok(()),
+        }
+    }
+}
+
+impl PythonConfig {
+    /// Returns the release level of the interpreter, from the full version
+    /// in `py_version`, `PY_VERSION` of `patchlevel.h` or a path in
+    /// `CONFIG_ARGS` like `--prefix=/opt/python/3.14.0a4`. `None` when
+    /// neither has the full version.
+    pub fn release_level(&self) -> Option<ReleaseLevel> {
+        self.full_version().map(|(level, _)| level)
+    }
+
+    /// Returns whether the interpreter was built from a development checkout
+    /// past a release, which CPython marks with a `+` after the version,
+    /// `3.14.0a4+`
+    pub fn is_dev_build(&self) -> bool {
+        self.full_version().is_some_and(|(_, dev)| dev)
+    }
+
+    /// Returns whether the interpreter is an alpha, beta, release candidate
+    /// or development build, which CI systems may want to reject
+    pub fn is_prerelease(&self) -> bool {
+        match self.full_version() {
+            Some((level, dev)) => level.is_prerelease() || dev,
+            None => false,
+        }
+    }
+
+    /// Release level of the full version and whether it is a dev build
+    fn full_version(&self) -> Option<(ReleaseLevel, bool)> {
+        let get = |key| {
+            self.get(key)
+                .and_then(ConfigValue::as_str)
+                .map(|value| value.trim_matches('"'))
+        };
+        if let Some(version) = get("py_version").or_else(|| get("PY_VERSION")) {
+            return parse_full_version(self.version_str(), version);
+        }
+        if self.version_str().is_empty() {
+            return None;
+        }
+        shell_split(get("CONFIG_ARGS").unwrap_or_default())
+            .iter()
+            .flat_map(|word| word.split(|c: char| "/=_-~".contains(c)))
+            .find_map(|chunk| parse_full_version(self.version_str(), chunk))
+    }
+}
+
+/// Release level of `full`, a version like `3.14.0a4+` starting with
+/// `version`, `3.14`, and whether it is a dev build
+fn parse_full_version(version: &str, full: &str) -> Option<(ReleaseLevel, bool)> {
+    let rest = full.strip_prefix(version)?.strip_prefix('.')?;
+    let (rest, dev) = match rest.strip_suffix('+') {
+        Some(rest) => (rest, true),
+        None => (rest, false),
+    };
+    let suffix = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    if suffix.len() == rest.len() {
+        return None;
+    }
+    let parse_serial = |serial: &str| serial.parse().ok();
+    let level = if suffix.is_empty() {
+        ReleaseLevel::Final
+    } else if let Some(serial) = suffix.strip_prefix("rc") {
+        ReleaseLevel::Candidate(parse_serial(serial)?)
+    } else if let Some(serial) = suffix.strip_prefix('a') {
+        ReleaseLevel::Alpha(parse_serial(serial)?)
+    } else if let Some(serial) = suffix.strip_prefix('b') {
+        ReleaseLevel::Beta(parse_serial(serial)?)
+    } else {
+        return None;
+    };
+    Some((level, dev))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReleaseLevel;
+    use crate::{fixtures, PythonConfig};
+
+    #[test]
+    fn release_level() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert_eq!(config.release_level(), Some(ReleaseLevel::Final));
+        assert!(!config.is_prerelease());
+
+        let parse = |vars: &str| {
+            PythonConfig::parse(&format!(
+                "build_time_vars = {{'VERSION': '3.14', {}}}",
+                vars
+            ))
+            .unwrap()
+        };
+        let config = parse("'py_version': '3.14.0a4+'");
+        assert_eq!(config.release_level(), Some(ReleaseLevel::Alpha(4)));
+        assert!(config.is_dev_build() && config.is_prerelease());
+
+        let config = parse("'CONFIG_ARGS': \"'--prefix=/opt/python/3.14.0rc2' '--enable-shared'\"");
+        assert_eq!(config.release_level(), Some(ReleaseLevel::Candidate(2)));
+        assert_eq!(ReleaseLevel::Candidate(2).to_string(), "rc2");
+
+        let config = parse("'py_version': '3.14.1+'");
+        assert_eq!(config.release_level(), Some(ReleaseLevel::Final));
+        assert!(config.is_prerelease());
+
+        let config = parse("'CONFIG_ARGS': \"'--prefix=/usr/local'\"");
+        assert_eq!(config.release_level(), None);
+        assert!(!config.is_prerelease());
+    }
+}
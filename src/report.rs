@@ -0,0 +1,204 @@
+use std::fmt::Write;
+
+use crate::{LinkMode, PythonConfig, ReleaseLevel};
+
+/// A titled group of `(label, value)` rows of a report
+struct Section {
+    title: &'static str,
+    rows: Vec<(&'static str, String)>,
+}
+
+impl PythonConfig {
+    /// Returns a Markdown document describing this configuration for people
+    /// qualifying a new target Python, to attach to CI artifacts or paste
+    /// in a wiki: version and ABI, installation layout, toolchain and flags
+    /// in tables, followed by the [`lint`](PythonConfig::lint) findings
+    pub fn to_markdown_report(&self) -> String {
+        let mut out = format!("# {}\n", self.report_title());
+        for section in self.report_sections() {
+            write!(out, "\n## {}\n\n| | |\n|---|---|\n", section.title).unwrap();
+            for (label, value) in section.rows {
+                writeln!(out, "| {} | `{}` |", label, value.replace('|', "\\|")).unwrap();
+            }
+        }
+        out.push_str("\n## Findings\n\n");
+        let findings = self.lint();
+        if findings.is_empty() {
+            out.push_str("No problems found.\n");
+        }
+        for finding in findings {
+            writeln!(out, "- {}", finding).unwrap();
+        }
+        out
+    }
+
+    /// Returns [`to_markdown_report`](PythonConfig::to_markdown_report) as
+    /// a standalone HTML page
+    pub fn to_html_report(&self) -> String {
+        let title = escape_html(&self.report_title());
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n",
+            title, title
+        );
+        for section in self.report_sections() {
+            writeln!(out, "<h2>{}</h2>\n<table>", section.title).unwrap();
+            for (label, value) in section.rows {
+                writeln!(
+                    out,
+                    "<tr><th>{}</th><td><code>{}</code></td></tr>",
+                    label,
+                    escape_html(&value)
+                )
+                .unwrap();
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("<h2>Findings</h2>\n");
+        let findings = self.lint();
+        if findings.is_empty() {
+            out.push_str("<p>No problems found.</p>\n");
+        } else {
+            out.push_str("<ul>\n");
+            for finding in findings {
+                writeln!(out, "<li>{}</li>", escape_html(&finding.to_string())).unwrap();
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// `CPython 3.11 (linux-x86_64)`
+    fn report_title(&self) -> String {
+        format!(
+            "{} {} ({})",
+            self.implementation(),
+            self.version_str(),
+            self.get_platform()
+        )
+    }
+
+    /// The sections of the reports, leaving out empty values
+    fn report_sections(&self) -> Vec<Section> {
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+        let mut version = vec![
+            ("Implementation", self.implementation().to_string()),
+            ("Version", self.version_str().to_string()),
+        ];
+        if let Some(level) = self.release_level() {
+            let level = match level {
+                ReleaseLevel::Final => "final".to_string(),
+                level => level.to_string(),
+            };
+            let dev = if self.is_dev_build() { "+" } else { "" };
+            version.push(("Release level", format!("{}{}", level, dev)));
+        }
+        version.extend([
+            ("Python tag", self.python_tag().to_string()),
+            ("ABI tag", self.abi_tag().to_string()),
+            ("ABI flags", self.abiflags().to_string()),
+            ("SOABI", self.soabi().to_string()),
+            ("Extension suffix", self.ext_suffix().to_string()),
+            ("Platform", self.get_platform().to_string()),
+            ("Pointer size", self.pointer_size().to_string()),
+            ("Link model", self.link_model().to_string()),
+            ("Stable ABI", yes_no(self.supports_abi3())),
+        ]);
+
+        let static_library = self
+            .static_library_path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        let layout = vec![
+            ("prefix", self.prefix_str().to_string()),
+            ("exec_prefix", self.exec_prefix_str().to_string()),
+            ("BINDIR", self.bindir_str().to_string()),
+            ("INCLUDEDIR", self.include_dir_str().to_string()),
+            ("LIBDIR", self.lib_dir_str().to_string()),
+            ("LIBPL", self.config_dir_str().to_string()),
+            ("Static library", static_library),
+        ];
+
+        let vars = &self.sys_config_data.build_time_vars;
+        let toolchain = vec![
+            ("CC", self.cc().to_string()),
+            ("CXX", self.cxx().to_string()),
+            ("LDSHARED", self.ldshared().to_string()),
+            ("Host", vars.get_str("HOST_GNU_TYPE").to_string()),
+        ];
+
+        let flags = vec![
+            ("Compile", self.compile_flags().join(" ")),
+            (
+                "Link extension modules",
+                self.link_flags(LinkMode::ExtensionModule).join(" "),
+            ),
+            ("Link embedding", self.link_flags(LinkMode::Embed).join(" ")),
+        ];
+
+        vec![
+            ("Version and ABI", version),
+            ("Layout", layout),
+            ("Toolchain", toolchain),
+            ("Flags", flags),
+        ]
+        .into_iter()
+        .map(|(title, rows)| Section {
+            title,
+            rows: rows
+                .into_iter()
+                .filter(|(_, value)| !value.is_empty())
+                .collect(),
+        })
+        .collect()
+    }
+}
+
+/// `text` with the HTML special characters escaped
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, PythonConfig};
+
+    #[test]
+    fn reports() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let markdown = config.to_markdown_report();
+        assert!(markdown.starts_with("# CPython 3.11 (linux-x86_64)\n\n## Version and ABI\n"));
+        assert!(markdown.contains("| ABI tag | `cp311` |\n"));
+        assert!(markdown.contains("| LIBDIR | `/usr/local/lib` |\n"));
+        assert!(markdown.contains("| Stable ABI | `yes` |\n"));
+        assert!(markdown.ends_with("## Findings\n\nNo problems found.\n"));
+
+        let html = config.to_html_report();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<tr><th>ABI tag</th><td><code>cp311</code></td></tr>"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': '/opt/py', \
+             'CFLAGS': '-march=native -DA=\"<b>|\"'}",
+        )
+        .unwrap();
+        let markdown = config.to_markdown_report();
+        assert!(markdown.contains("\\|"));
+        assert!(markdown.contains("\n- CFLAGS: "));
+        let html = config.to_html_report();
+        assert!(html.contains("&lt;b&gt;|"));
+        assert!(html.contains("<ul>\n<li>CFLAGS: "));
+    }
+}
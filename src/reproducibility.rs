@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::{PythonConfig, SanitizeFlags, DERIVATION_VERSION};
+
+/// Flags naming paths of the build machine, left out of the record with
+/// their value
+const PATH_FLAGS: &[&str] = &["-I", "-L", "-isystem", "-iquote", "-idirafter", "--sysroot"];
+
+/// The variables determining binary compatibility of extension modules built
+/// for a configuration, see [`PythonConfig::reproducibility_record`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ReproducibilityRecord {
+    /// Normalized values by name
+    pub entries: BTreeMap<&'static str, String>,
+}
+
+/// One `name=value` line per entry, sorted by name, the canonical form to
+/// hash
+impl fmt::Display for ReproducibilityRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.entries {
+            writeln!(f, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl PythonConfig {
+    /// Returns the minimal normalized set of variables determining binary
+    /// compatibility: implementation, version, ABI, platform, extension
+    /// suffix, pointer size, build flavor, link model and compile flags, plus
+    /// the [`DERIVATION_VERSION`] of the derived values.
+    ///
+    /// Paths and flags that vary between machines building the same
+    /// distribution, like include directories, profile data and prefix maps,
+    /// are left out, so the record and a digest of its [`Display`](fmt::Display)
+    /// form stay stable for embedding into wheel build provenance
+    /// attestations.
+    pub fn reproducibility_record(&self) -> ReproducibilityRecord {
+        let sanitize = SanitizeFlags::default();
+        let mut flags = Vec::new();
+        let mut cflags = self.cflags().split_whitespace();
+        while let Some(flag) = cflags.next() {
+            if PATH_FLAGS.contains(&flag) {
+                cflags.next();
+            } else if !PATH_FLAGS.iter().any(|x| flag.starts_with(x)) && !sanitize.matches(flag) {
+                flags.push(flag);
+            }
+        }
+        let bool_str = |value: bool| if value { "1" } else { "0" }.to_string();
+        let entries = vec![
+            ("implementation", self.implementation().to_string()),
+            ("version", self.version_str().to_string()),
+            ("abi_tag", self.abi_tag().to_string()),
+            ("abiflags", self.abiflags().to_string()),
+            ("platform", self.get_platform().to_string()),
+            ("ext_suffix", self.extension_suffix()),
+            ("pointer_size", self.pointer_size().to_string()),
+            ("debug", bool_str(self.debug())),
+            ("free_threaded", bool_str(self.abiflags().contains('t'))),
+            ("link_model", self.link_model().to_string()),
+            ("compile_flags", flags.join(" ")),
+            ("derivation_version", DERIVATION_VERSION.to_string()),
+        ];
+        ReproducibilityRecord {
+            entries: entries.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, PythonConfig, Relocate, Transform};
+
+    #[test]
+    fn reproducibility_record() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let record = config.reproducibility_record();
+        assert_eq!(record.entries["abi_tag"], "cp311");
+        assert_eq!(record.entries["free_threaded"], "0");
+        assert_eq!(record.entries["derivation_version"], "3");
+        assert!(!record.entries["compile_flags"].contains("-flto"));
+        assert!(record
+            .to_string()
+            .starts_with("abi_tag=cp311\nabiflags=\ncompile_flags="));
+
+        let transforms: Vec<Box<dyn Transform>> =
+            vec![Box::new(Relocate::new("/usr/local", "/opt/python"))];
+        let relocated = config.apply(&transforms).unwrap();
+        assert_eq!(relocated.reproducibility_record(), record);
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', \
+             'CFLAGS': '-O2 -I /build/include -I/x -fdebug-prefix-map=/build=. -DNDEBUG'}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.reproducibility_record().entries["compile_flags"],
+            "-O2 -DNDEBUG"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::PythonConfig;
+
+/// Derivation rules that changed between Python versions, see
+/// [`rules_for_version`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionRules {
+    /// First Python version the rules apply to
+    pub since: (u32, u32),
+    /// Whether extension modules can target the stable ABI, with the
+    /// `.abi3.so` suffix
+    pub stable_abi: bool,
+    /// Whether the name of libpython carries the ABI flags,
+    /// `python3.7m` rather than `python3.7`, the value of `LDVERSION`
+    pub libpython_abiflags: bool,
+    /// Whether pymalloc builds have the `m` ABI flag
+    pub pymalloc_abiflag: bool,
+    /// Whether extension modules link libpython on Unix, so
+    /// `python3-config --libs` includes it without `--embed`
+    pub extensions_link_libpython: bool,
+    /// Whether Windows extension suffixes carry a tag, `.cp311-win_amd64.pyd`
+    /// rather than `.pyd`
+    pub windows_ext_tag: bool,
+    /// Whether the `_sysconfigdata` module name carries the ABI flags,
+    /// platform and multiarch tuple
+    pub sysconfigdata_suffixed: bool,
+}
+
+const PY30: VersionRules = VersionRules {
+    since: (3, 0),
+    stable_abi: false,
+    libpython_abiflags: false,
+    pymalloc_abiflag: false,
+    extensions_link_libpython: true,
+    windows_ext_tag: false,
+    sysconfigdata_suffixed: false,
+};
+
+/// PEP 3149 and PEP 384
+const PY32: VersionRules = VersionRules {
+    since: (3, 2),
+    stable_abi: true,
+    libpython_abiflags: true,
+    pymalloc_abiflag: true,
+    ..PY30
+};
+
+const PY35: VersionRules = VersionRules {
+    since: (3, 5),
+    windows_ext_tag: true,
+    ..PY32
+};
+
+const PY36: VersionRules = VersionRules {
+    since: (3, 6),
+    sysconfigdata_suffixed: true,
+    ..PY35
+};
+
+/// Debug and release builds share an ABI and `--embed` is introduced
+const PY38: VersionRules = VersionRules {
+    since: (3, 8),
+    pymalloc_abiflag: false,
+    extensions_link_libpython: false,
+    ..PY36
+};
+
+/// Rule sets, sorted by the version introducing them
+const RULES: &[VersionRules] = &[PY30, PY32, PY35, PY36, PY38];
+
+/// Returns the derivation rules of Python `major.minor`, those of the
+/// newest rule set introduced at or before it
+pub fn rules_for_version(major: u32, minor: u32) -> VersionRules {
+    RULES
+        .iter()
+        .rev()
+        .find(|rules| rules.since <= (major, minor))
+        .copied()
+        .unwrap_or(PY30)
+}
+
+impl PythonConfig {
+    /// Returns the derivation rules of this distribution's Python version,
+    /// see [`rules_for_version`]
+    pub fn version_rules(&self) -> VersionRules {
+        rules_for_version(self.major_minor().0, self.major_minor().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rules_for_version, RULES};
+    use crate::{fixtures, LinkMode};
+
+    #[test]
+    fn rules_per_version() {
+        for pair in RULES.windows(2) {
+            assert!(pair[0].since < pair[1].since);
+        }
+        assert_eq!(rules_for_version(3, 1).since, (3, 0));
+        assert!(!rules_for_version(3, 1).stable_abi);
+        assert!(rules_for_version(3, 2).stable_abi);
+        assert!(!rules_for_version(3, 4).windows_ext_tag);
+        assert!(rules_for_version(3, 5).windows_ext_tag);
+        assert!(!rules_for_version(3, 5).sysconfigdata_suffixed);
+        assert!(rules_for_version(3, 6).sysconfigdata_suffixed);
+        let py37 = rules_for_version(3, 7);
+        assert!(py37.pymalloc_abiflag && py37.extensions_link_libpython);
+        let py38 = rules_for_version(3, 8);
+        assert!(!py38.pymalloc_abiflag && !py38.extensions_link_libpython);
+        assert!(py38.libpython_abiflags);
+        assert_eq!(rules_for_version(3, 13), py38);
+        assert_eq!(rules_for_version(4, 0), py38);
+
+        let config = fixtures::get("cpython37m-linux").unwrap().config();
+        assert_eq!(config.version_rules(), py37);
+        assert_eq!(
+            config.lib_flags(LinkMode::ExtensionModule)[0],
+            "-lpython3.7m"
+        );
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert!(!config
+            .lib_flags(LinkMode::ExtensionModule)
+            .contains(&"-lpython3.11".to_string()));
+    }
+}
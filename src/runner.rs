@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{arch, Error, PythonConfig};
+
+/// A command line running the target interpreter on the build machine, see
+/// [`PythonConfig::interpreter_invocation`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    /// Program to run, the qemu-user emulator of the target architecture
+    pub program: PathBuf,
+    /// Arguments, the path of the interpreter inside the sysroot first
+    pub args: Vec<String>,
+    /// Environment variables to set
+    pub env: BTreeMap<String, String>,
+}
+
+impl Invocation {
+    /// A [`Command`] running this invocation, more arguments for the
+    /// interpreter can be appended
+    pub fn to_command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args).envs(&self.env);
+        command
+    }
+}
+
+/// POSIX shell command line, `QEMU_LD_PREFIX=/sysroot qemu-aarch64 /sysroot/usr/bin/python3`
+impl fmt::Display for Invocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in &self.env {
+            write!(f, "{}={} ", key, shell_quote(value))?;
+        }
+        write!(f, "{}", self.program.display())?;
+        for arg in &self.args {
+            write!(f, " {}", shell_quote(arg))?;
+        }
+        Ok(())
+    }
+}
+
+impl PythonConfig {
+    /// Suggests how to run the interpreter of this Linux distribution,
+    /// installed in the target root file system `sysroot`, under qemu-user
+    /// during a cross build: `qemu-aarch64` with `QEMU_LD_PREFIX` pointing
+    /// the emulated dynamic loader at `sysroot`, and `PYTHONHOME` set to the
+    /// installation prefix. qemu resolves the paths the interpreter opens
+    /// inside `sysroot` first, so `PYTHONHOME` and `LD_LIBRARY_PATH`, passed
+    /// through `QEMU_SET_ENV` for shared builds, are target paths.
+    ///
+    /// With binfmt_misc handlers registered the interpreter also runs
+    /// directly, given the same environment.
+    pub fn interpreter_invocation(&self, sysroot: impl AsRef<Path>) -> Result<Invocation, Error> {
+        let sysroot = sysroot.as_ref();
+        if self.sys_platform() != "linux" {
+            return Err(Error::Unsupported(format!(
+                "qemu-user only runs Linux binaries, not {}",
+                self.get_platform()
+            )));
+        }
+        let machine = self.machine();
+        let qemu = arch::find_by_name(&machine)
+            .and_then(|arch| qemu_arch(arch.multiarch))
+            .ok_or_else(|| Error::Unsupported(format!("no qemu-user emulator for {}", machine)))?;
+        let python = self.python_executable_path().ok_or_else(|| {
+            Error::Unsupported("the interpreter path is unknown without BINDIR".to_string())
+        })?;
+        let python = sysroot.join(python.strip_prefix("/").unwrap_or(&python));
+
+        let mut env = BTreeMap::new();
+        env.insert("QEMU_LD_PREFIX".to_string(), sysroot.display().to_string());
+        if !self.prefix_str().is_empty() {
+            let home = if self.exec_prefix_str().is_empty()
+                || self.exec_prefix_str() == self.prefix_str()
+            {
+                self.prefix_str().to_string()
+            } else {
+                format!("{}:{}", self.prefix_str(), self.exec_prefix_str())
+            };
+            env.insert("PYTHONHOME".to_string(), home);
+        }
+        if self.enable_shared() && !self.lib_dir_str().is_empty() {
+            // set for the emulated process only, not for qemu itself
+            env.insert(
+                "QEMU_SET_ENV".to_string(),
+                format!("LD_LIBRARY_PATH={}", self.lib_dir_str()),
+            );
+        }
+        Ok(Invocation {
+            program: PathBuf::from(format!("qemu-{}", qemu)),
+            args: vec![python.display().to_string()],
+            env,
+        })
+    }
+}
+
+/// Architecture suffix of the qemu-user emulator for the multiarch name
+/// `multiarch`, `ppc64le` for `powerpc64le`
+fn qemu_arch(multiarch: &str) -> Option<&'static str> {
+    Some(match multiarch {
+        "x86_64" => "x86_64",
+        "i386" => "i386",
+        "aarch64" => "aarch64",
+        "arm" => "arm",
+        "powerpc" => "ppc",
+        "powerpc64" => "ppc64",
+        "powerpc64le" => "ppc64le",
+        "s390x" => "s390x",
+        "riscv64" => "riscv64",
+        "loongarch64" => "loongarch64",
+        "mips" => "mips",
+        "mipsel" => "mipsel",
+        "mips64" => "mips64",
+        "mips64el" => "mips64el",
+        "sparc64" => "sparc64",
+        "m68k" => "m68k",
+        "alpha" => "alpha",
+        "hppa" => "hppa",
+        "sh4" => "sh4",
+        _ => return None,
+    })
+}
+
+/// `value` in single quotes unless it only has safe characters
+pub(crate) fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-=:+,@%".contains(c));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, Error};
+    use std::path::Path;
+
+    #[test]
+    fn interpreter_invocation() {
+        let config = fixtures::get("cpython39-linux-aarch64").unwrap().config();
+        let invocation = config.interpreter_invocation("/srv/sysroot").unwrap();
+        assert_eq!(invocation.program, Path::new("qemu-aarch64"));
+        assert_eq!(
+            invocation.args,
+            vec!["/srv/sysroot/usr/local/bin/python3.9"]
+        );
+        assert_eq!(invocation.env["QEMU_LD_PREFIX"], "/srv/sysroot");
+        assert_eq!(invocation.env["PYTHONHOME"], "/usr/local");
+        assert_eq!(
+            invocation.to_string(),
+            "PYTHONHOME=/usr/local QEMU_LD_PREFIX=/srv/sysroot qemu-aarch64 \
+             /srv/sysroot/usr/local/bin/python3.9"
+        );
+        let command = invocation.to_command();
+        assert_eq!(command.get_program(), "qemu-aarch64");
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let invocation = config.interpreter_invocation("/sysroot").unwrap();
+        assert_eq!(invocation.program, Path::new("qemu-x86_64"));
+        assert_eq!(
+            invocation.env["QEMU_SET_ENV"],
+            "LD_LIBRARY_PATH=/usr/local/lib"
+        );
+
+        let config = fixtures::get("cpython38-darwin").unwrap().config();
+        assert!(matches!(
+            config.interpreter_invocation("/sysroot"),
+            Err(Error::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,481 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::paths::windows_join;
+use crate::{ConfigValue, Error, PythonConfig, PythonImplementation};
+
+/// Keys of an [`InstallScheme`], in field order
+const SCHEME_KEYS: [&str; 7] = [
+    "stdlib",
+    "platstdlib",
+    "purelib",
+    "platlib",
+    "include",
+    "scripts",
+    "data",
+];
+
+/// `posix_user` scheme of `sysconfig`
+const POSIX_USER: [&str; 7] = [
+    "{userbase}/{platlibdir}/{implementation_lower}{py_version_short}{abi_thread}",
+    "{userbase}/{platlibdir}/{implementation_lower}{py_version_short}{abi_thread}",
+    "{userbase}/lib/{implementation_lower}{py_version_short}{abi_thread}/site-packages",
+    "{userbase}/lib/{implementation_lower}{py_version_short}{abi_thread}/site-packages",
+    "{userbase}/include/{implementation_lower}{py_version_short}{abi_thread}",
+    "{userbase}/bin",
+    "{userbase}",
+];
+
+/// `osx_framework_user` scheme of `sysconfig`
+const OSX_FRAMEWORK_USER: [&str; 7] = [
+    "{userbase}/lib/{implementation_lower}",
+    "{userbase}/lib/{implementation_lower}",
+    "{userbase}/lib/{implementation_lower}/site-packages",
+    "{userbase}/lib/{implementation_lower}/site-packages",
+    "{userbase}/include/{implementation_lower}{py_version_short}",
+    "{userbase}/bin",
+    "{userbase}",
+];
+
+/// `nt_user` scheme of `sysconfig`
+const NT_USER: [&str; 7] = [
+    "{userbase}/{implementation}{py_version_nodot_plat}",
+    "{userbase}/{implementation}{py_version_nodot_plat}",
+    "{userbase}/{implementation}{py_version_nodot_plat}/site-packages",
+    "{userbase}/{implementation}{py_version_nodot_plat}/site-packages",
+    "{userbase}/{implementation}{py_version_nodot_plat}/Include",
+    "{userbase}/{implementation}{py_version_nodot_plat}/Scripts",
+    "{userbase}",
+];
+
+/// What `make install` does with pip, the `--with-ensurepip` configure option
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnsurePip {
+    /// pip isn't installed, `--without-ensurepip`
+    No,
+    /// the bundled pip is installed, `--with-ensurepip=install`
+    Install,
+    /// the bundled pip is installed or upgraded, `--with-ensurepip=upgrade`
+    Upgrade,
+}
+
+/// Installation directories of a distribution, the `posix_prefix` or `nt`
+/// scheme of `sysconfig.get_paths()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallScheme {
+    /// Standard library, `{prefix}/lib/python3.X`
+    pub stdlib: PathBuf,
+    /// Platform specific standard library, `{exec_prefix}/{PLATLIBDIR}/python3.X`
+    pub platstdlib: PathBuf,
+    /// Pure Python packages, `{prefix}/lib/python3.X/site-packages`
+    pub purelib: PathBuf,
+    /// Packages with extension modules, `{exec_prefix}/{PLATLIBDIR}/python3.X/site-packages`
+    pub platlib: PathBuf,
+    /// C headers, `{prefix}/include/python3.X`
+    pub include: PathBuf,
+    /// Scripts of installed packages, `{prefix}/bin`
+    pub scripts: PathBuf,
+    /// Data files, `{prefix}`
+    pub data: PathBuf,
+}
+
+impl PythonConfig {
+    /// Returns the directory name of libraries for the platform, `lib64` on
+    /// Fedora and derivatives, taken from `PLATLIBDIR` and `lib` when missing
+    pub fn platlibdir(&self) -> &str {
+        match self.sys_config_data.build_time_vars.get_str("PLATLIBDIR") {
+            "" => "lib",
+            platlibdir => platlibdir,
+        }
+    }
+
+    /// Returns the directory of the bundled `ensurepip` wheels, `WHEEL_PKG_DIR`,
+    /// set by distributions that install them separately such as Fedora
+    pub fn wheel_pkg_dir(&self) -> Option<&str> {
+        Some(
+            self.sys_config_data
+                .build_time_vars
+                .get_str("WHEEL_PKG_DIR"),
+        )
+        .filter(|x| !x.is_empty())
+    }
+
+    /// Returns how the installation of this build sets up pip, from
+    /// `ENSUREPIP` or a `WITH_ENSUREPIP` switch, `None` when unknown
+    pub fn ensurepip(&self) -> Option<EnsurePip> {
+        let value = self
+            .get("ENSUREPIP")
+            .or_else(|| self.get("WITH_ENSUREPIP"))?;
+        match value {
+            ConfigValue::String(value) => match &**value {
+                "no" => Some(EnsurePip::No),
+                "install" | "yes" => Some(EnsurePip::Install),
+                "upgrade" => Some(EnsurePip::Upgrade),
+                _ => None,
+            },
+            ConfigValue::Int(0) => Some(EnsurePip::No),
+            ConfigValue::Int(_) => Some(EnsurePip::Install),
+            ConfigValue::Float(_) | ConfigValue::None => None,
+        }
+    }
+
+    /// Returns whether installations of this build come with pip, `false`
+    /// meaning environments created for the target must bootstrap it, `None`
+    /// when the configuration doesn't tell
+    pub fn bundles_pip(&self) -> Option<bool> {
+        self.ensurepip().map(|x| x != EnsurePip::No)
+    }
+
+    /// Returns the directories `zoneinfo` searches for time zone data, `TZPATH`
+    pub fn tzpath(&self) -> Vec<&str> {
+        let separator = if self.sys_platform() == "win32" {
+            ';'
+        } else {
+            ':'
+        };
+        self.sys_config_data
+            .build_time_vars
+            .get_str("TZPATH")
+            .split(separator)
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+
+    /// Returns `DESTSHARED`, the directory of the standard library's
+    /// extension modules, usually `{BINLIBDEST}/lib-dynload`
+    pub fn dest_shared(&self) -> Option<&str> {
+        Some(self.sys_config_data.build_time_vars.get_str("DESTSHARED")).filter(|x| !x.is_empty())
+    }
+
+    /// Returns `DESTLIB`, the directory of the pure Python standard library
+    pub fn dest_lib(&self) -> Option<&str> {
+        Some(self.sys_config_data.build_time_vars.get_str("DESTLIB")).filter(|x| !x.is_empty())
+    }
+
+    /// Returns the directory stdlib-style extension modules are installed
+    /// to: `DESTSHARED` when present, otherwise `lib-dynload` in the
+    /// platform specific standard library, or `DLLs` on Windows
+    pub fn extension_install_dir(&self) -> PathBuf {
+        if let Some(dest_shared) = self.dest_shared() {
+            return PathBuf::from(dest_shared);
+        }
+        if self.sys_platform() == "win32" {
+            return windows_join(self.prefix_str(), &["DLLs"]);
+        }
+        self.install_scheme().platstdlib.join("lib-dynload")
+    }
+
+    /// Returns the installation directories of this distribution.
+    ///
+    /// The standard library locations come from `LIBDEST` and `BINLIBDEST`
+    /// when present, so layouts patched by distributions are honored, and
+    /// `platlib` uses [`PythonConfig::platlibdir`].
+    pub fn install_scheme(&self) -> InstallScheme {
+        let vars = &self.sys_config_data.build_time_vars;
+        let base = Path::new(self.prefix_str());
+        let platbase = if self.exec_prefix_str().is_empty() {
+            base
+        } else {
+            Path::new(self.exec_prefix_str())
+        };
+        // MSYS2 patches mingw builds to the posix_prefix scheme
+        if self.sys_platform() == "win32" && !self.is_mingw() {
+            let prefix = self.prefix_str();
+            return InstallScheme {
+                stdlib: windows_join(prefix, &["Lib"]),
+                platstdlib: windows_join(prefix, &["Lib"]),
+                purelib: windows_join(prefix, &["Lib", "site-packages"]),
+                platlib: windows_join(prefix, &["Lib", "site-packages"]),
+                include: windows_join(prefix, &["Include"]),
+                scripts: windows_join(prefix, &["Scripts"]),
+                data: windows_join(prefix, &[]),
+            };
+        }
+        let name = match self.implementation() {
+            PythonImplementation::PyPy => "pypy",
+            _ => "python",
+        };
+        let free_threaded = if self.abiflags().contains('t') {
+            "t"
+        } else {
+            ""
+        };
+        let lib_name = format!("{}{}{}", name, self.version_str(), free_threaded);
+        let dir_or = |key: &str, default: PathBuf| match vars.get_str(key) {
+            "" => default,
+            dir => PathBuf::from(dir),
+        };
+        InstallScheme {
+            stdlib: dir_or("LIBDEST", base.join("lib").join(&lib_name)),
+            platstdlib: dir_or(
+                "BINLIBDEST",
+                platbase.join(self.platlibdir()).join(&lib_name),
+            ),
+            purelib: base.join("lib").join(&lib_name).join("site-packages"),
+            platlib: platbase
+                .join(self.platlibdir())
+                .join(&lib_name)
+                .join("site-packages"),
+            include: dir_or(
+                "INCLUDEPY",
+                base.join("include").join(format!(
+                    "python{}{}",
+                    self.version_str(),
+                    self.abiflags()
+                )),
+            ),
+            scripts: base.join("bin"),
+            data: base.to_path_buf(),
+        }
+    }
+
+    /// Returns the directories of `pip install --user` below `userbase`,
+    /// `~/.local` by default: the `osx_framework_user` scheme of `sysconfig`
+    /// for macOS framework builds, `nt_user` on Windows and `posix_user`
+    /// otherwise
+    pub fn user_scheme(&self, userbase: impl AsRef<Path>) -> InstallScheme {
+        let templates = if self.sys_platform() == "win32" && !self.is_mingw() {
+            NT_USER
+        } else if self.sys_platform() == "darwin"
+            && !self
+                .sys_config_data
+                .build_time_vars
+                .get_str("PYTHONFRAMEWORK")
+                .is_empty()
+        {
+            OSX_FRAMEWORK_USER
+        } else {
+            POSIX_USER
+        };
+        let userbase = userbase.as_ref().to_string_lossy();
+        let mut paths = self
+            .scheme_paths(
+                SCHEME_KEYS.iter().copied().zip(templates.iter().copied()),
+                &[("userbase", &userbase)],
+            )
+            .expect("user schemes only use known variables");
+        let mut take = |key: &str| paths.remove(key).unwrap_or_default();
+        InstallScheme {
+            stdlib: take("stdlib"),
+            platstdlib: take("platstdlib"),
+            purelib: take("purelib"),
+            platlib: take("platlib"),
+            include: take("include"),
+            scripts: take("scripts"),
+            data: take("data"),
+        }
+    }
+
+    /// Returns the variables [`PythonConfig::scheme_paths`] substitutes:
+    /// the string values of `build_time_vars` and the ones `sysconfig`
+    /// derives for its schemes, such as `base`, `platbase`,
+    /// `py_version_short`, `abi_thread` and `implementation_lower`
+    pub fn scheme_vars(&self) -> BTreeMap<String, String> {
+        let mut vars: BTreeMap<String, String> = self
+            .vars()
+            .filter_map(|(key, value)| Some((key.to_string(), value.as_str()?.to_string())))
+            .collect();
+        let exec_prefix = match self.exec_prefix_str() {
+            "" => self.prefix_str(),
+            exec_prefix => exec_prefix,
+        };
+        let implementation = match self.implementation() {
+            PythonImplementation::CPython => "Python",
+            PythonImplementation::PyPy => "PyPy",
+            PythonImplementation::GraalPy => "GraalPy",
+        };
+        let nodot = format!("{}{}", self.major_minor().0, self.major_minor().1);
+        let win32 = self.sys_platform() == "win32" && !self.is_mingw();
+        let derived = [
+            ("base", self.prefix_str()),
+            ("installed_base", self.prefix_str()),
+            ("platbase", exec_prefix),
+            ("installed_platbase", exec_prefix),
+            ("prefix", self.prefix_str()),
+            ("exec_prefix", exec_prefix),
+            ("platlibdir", self.platlibdir()),
+            ("abiflags", self.abiflags()),
+            (
+                "abi_thread",
+                if self.abiflags().contains('t') {
+                    "t"
+                } else {
+                    ""
+                },
+            ),
+            ("implementation", implementation),
+            ("py_version_short", self.version_str()),
+            ("py_version_nodot", &nodot),
+            ("py_version_nodot_plat", if win32 { &nodot } else { "" }),
+        ];
+        for (key, value) in derived {
+            vars.insert(key.to_string(), value.to_string());
+        }
+        vars.insert(
+            "implementation_lower".to_string(),
+            implementation.to_ascii_lowercase(),
+        );
+        vars
+    }
+
+    /// Expands the path `templates` of a custom scheme like
+    /// `sysconfig.get_paths(scheme, vars)` does: `{name}` is replaced by
+    /// the entry of `vars` or else of [`PythonConfig::scheme_vars`], `{{`
+    /// and `}}` stand for literal braces, and `.` components and repeated
+    /// separators are removed from the result.
+    ///
+    /// Fails with [`Error::UnknownKey`] on a variable defined by neither.
+    pub fn scheme_paths<'a, I>(
+        &self,
+        templates: I,
+        vars: &[(&str, &str)],
+    ) -> Result<BTreeMap<String, PathBuf>, Error>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut scheme_vars = self.scheme_vars();
+        for (key, value) in vars {
+            scheme_vars.insert(key.to_string(), value.to_string());
+        }
+        templates
+            .into_iter()
+            .map(|(key, template)| {
+                let path = expand_template(template, &scheme_vars)?;
+                Ok((key.to_string(), Path::new(&path).components().collect()))
+            })
+            .collect()
+    }
+}
+
+/// `template.format(**vars)` for templates of plain `{name}` fields
+fn expand_template(template: &str, vars: &BTreeMap<String, String>) -> Result<String, Error> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(at) = rest.find(['{', '}']) {
+        out.push_str(&rest[..at]);
+        let (brace, after) = rest[at..].split_at(1);
+        if let Some(after) = after.strip_prefix(brace) {
+            out.push_str(brace);
+            rest = after;
+            continue;
+        }
+        let field = match after.split_once('}') {
+            Some((name, after)) if brace == "{" => vars.get(name).map(|value| (value, after)),
+            _ => None,
+        };
+        let (value, after) = field.ok_or_else(|| Error::UnknownKey {
+            key: after.split('}').next().unwrap_or_default().to_string(),
+            suggestion: None,
+        })?;
+        out.push_str(value);
+        rest = after;
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, EnsurePip, Error, PythonConfig};
+    use std::path::Path;
+
+    #[test]
+    fn install_scheme() {
+        let config = fixtures::get("cpython313t-linux").unwrap().config();
+        let scheme = config.install_scheme();
+        assert_eq!(scheme.stdlib, Path::new("/usr/local/lib/python3.13t"));
+        assert_eq!(
+            scheme.purelib,
+            Path::new("/usr/local/lib/python3.13t/site-packages")
+        );
+        assert_eq!(scheme.scripts, Path::new("/usr/local/bin"));
+        assert_eq!(config.tzpath().len(), 4);
+        assert_eq!(config.wheel_pkg_dir(), None);
+        assert_eq!(config.ensurepip(), Some(EnsurePip::Upgrade));
+        assert_eq!(config.bundles_pip(), Some(true));
+        assert_eq!(config.dest_lib(), Some("/usr/local/lib/python3.13t"));
+        assert_eq!(
+            config.extension_install_dir(),
+            Path::new("/usr/local/lib/python3.13t/lib-dynload")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': '/usr', 'exec_prefix': '/usr', \
+             'PLATLIBDIR': 'lib64', 'WHEEL_PKG_DIR': '/usr/share/python-wheels', \
+             'TZPATH': '/usr/share/zoneinfo'}",
+        )
+        .unwrap();
+        let scheme = config.install_scheme();
+        assert_eq!(scheme.platstdlib, Path::new("/usr/lib64/python3.12"));
+        assert_eq!(
+            scheme.platlib,
+            Path::new("/usr/lib64/python3.12/site-packages")
+        );
+        assert_eq!(
+            scheme.purelib,
+            Path::new("/usr/lib/python3.12/site-packages")
+        );
+        assert_eq!(config.wheel_pkg_dir(), Some("/usr/share/python-wheels"));
+        assert_eq!(config.tzpath(), vec!["/usr/share/zoneinfo"]);
+        assert_eq!(config.dest_shared(), None);
+        assert_eq!(config.bundles_pip(), None);
+        assert_eq!(
+            config.extension_install_dir(),
+            Path::new("/usr/lib64/python3.12/lib-dynload")
+        );
+
+        let config = fixtures::get("cpython312-pyodide").unwrap().config();
+        assert_eq!(config.ensurepip(), Some(EnsurePip::No));
+        assert_eq!(config.bundles_pip(), Some(false));
+    }
+
+    #[test]
+    fn user_scheme() {
+        let config = fixtures::get("cpython313t-linux").unwrap().config();
+        let scheme = config.user_scheme("/home/me/.local");
+        assert_eq!(scheme.stdlib, Path::new("/home/me/.local/lib/python3.13t"));
+        assert_eq!(
+            scheme.purelib,
+            Path::new("/home/me/.local/lib/python3.13t/site-packages")
+        );
+        assert_eq!(
+            scheme.include,
+            Path::new("/home/me/.local/include/python3.13t")
+        );
+        assert_eq!(scheme.scripts, Path::new("/home/me/.local/bin"));
+
+        let config = fixtures::get("cpython38-darwin").unwrap().config();
+        let scheme = config.user_scheme("/Users/me/Library/Python/3.8");
+        assert_eq!(
+            scheme.purelib,
+            Path::new("/Users/me/Library/Python/3.8/lib/python/site-packages")
+        );
+        assert_eq!(
+            scheme.include,
+            Path::new("/Users/me/Library/Python/3.8/include/python3.8")
+        );
+    }
+
+    #[test]
+    fn scheme_paths() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let paths = config
+            .scheme_paths(
+                [
+                    (
+                        "purelib",
+                        "{base}/./site/{implementation_lower}{py_version_nodot}",
+                    ),
+                    ("data", "{home}//share/{{x}}"),
+                ],
+                &[("home", "/srv/app")],
+            )
+            .unwrap();
+        assert_eq!(paths["purelib"], Path::new("/usr/local/site/python311"));
+        assert_eq!(paths["data"], Path::new("/srv/app/share/{x}"));
+        for template in ["{missing}/lib", "{base", "base}"] {
+            let err = config.scheme_paths([("data", template)], &[]).unwrap_err();
+            assert!(matches!(err, Error::UnknownKey { .. }), "{}", template);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Error, PythonConfig};
+
+/// `Modules/Setup*` files in the order `makesetup` reads them, the first
+/// line naming a module decides how it is built
+const SETUP_FILES: &[&str] = &["Setup.local", "Setup.stdlib", "Setup.bootstrap", "Setup"];
+
+/// How a standard library extension module is built according to the
+/// `Modules/Setup*` files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleBuild {
+    /// Linked into libpython or the interpreter, `*static*`
+    Static,
+    /// Separate shared library in `lib-dynload`, `*shared*`
+    Shared,
+    /// Not built at all, `*disabled*`
+    Disabled,
+}
+
+impl fmt::Display for ModuleBuild {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleBuild::Static => write!(f, "static"),
+            ModuleBuild::Shared => write!(f, "shared"),
+            ModuleBuild::Disabled => write!(f, "disabled"),
+        }
+    }
+}
+
+/// Parses the contents of `Modules/Setup*` files, given in the order
+/// `makesetup` reads them: `Setup.local`, `Setup.stdlib`, `Setup.bootstrap`
+/// and `Setup`.
+///
+/// Modules listed before any `*shared*`, `*static*` or `*disabled*` line are
+/// static, a module keeps the build of the first line naming it.
+pub fn parse_module_setup(sources: &[&str]) -> BTreeMap<String, ModuleBuild> {
+    let mut modules = BTreeMap::new();
+    for source in sources {
+        let mut build = ModuleBuild::Static;
+        for line in source.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            match line {
+                "" => continue,
+                "*static*" => build = ModuleBuild::Static,
+                "*shared*" => build = ModuleBuild::Shared,
+                "*disabled*" => build = ModuleBuild::Disabled,
+                _ => {
+                    let mut words = line.split_whitespace();
+                    let first = words.next().unwrap_or_default();
+                    // variable definitions like `PYTHONPATH=$(COREPYTHONPATH)`
+                    if first.contains('=') || words.next().is_some_and(|x| x.starts_with('=')) {
+                        continue;
+                    }
+                    if build == ModuleBuild::Disabled {
+                        for name in line.split_whitespace() {
+                            modules.entry(name.to_string()).or_insert(build);
+                        }
+                    } else {
+                        modules.entry(first.to_string()).or_insert(build);
+                    }
+                }
+            }
+        }
+    }
+    modules
+}
+
+impl PythonConfig {
+    /// Returns how the standard library extension modules are built, read
+    /// from the `Setup*` files `make install` copies next to the `Makefile`
+    /// in `LIBPL`, see [`parse_module_setup`].
+    ///
+    /// Modules missing from the files are built by `setup.py` as shared
+    /// libraries with Python before 3.12. Fails when the directory holds
+    /// none of the files, as with most distributions other than CPython.
+    pub fn module_setup(&self) -> Result<BTreeMap<String, ModuleBuild>, Error> {
+        read_module_setup(Path::new(self.config_dir_str()))
+    }
+}
+
+/// Reads and parses the `Setup*` files in `dir`, missing ones are skipped
+fn read_module_setup(dir: &Path) -> Result<BTreeMap<String, ModuleBuild>, Error> {
+    let mut sources = Vec::new();
+    for name in SETUP_FILES {
+        match fs::read_to_string(dir.join(name)) {
+            Ok(source) => sources.push(source),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    if sources.is_empty() {
+        return Err(Error::Unsupported(format!(
+            "no Setup files in {}",
+            dir.display()
+        )));
+    }
+    let sources: Vec<&str> = sources.iter().map(String::as_str).collect();
+    Ok(parse_module_setup(&sources))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_module_setup, read_module_setup, ModuleBuild};
+    use crate::{Error, PythonConfig};
+    use std::env;
+    use std::fs;
+
+    const SETUP_STDLIB: &str = "\
+# -*- makefile -*-
+*shared*
+array arraymodule.c
+_ssl _ssl.c $(OPENSSL_INCLUDES) $(OPENSSL_LDFLAGS) $(OPENSSL_LIBS)
+*static*
+math mathmodule.c
+*disabled*
+_tkinter nis
+";
+
+    #[test]
+    fn parse() {
+        let local = "PYTHONPATH = $(COREPYTHONPATH)\nCUSTOM=1\n_ssl _ssl.c -lssl\n*shared*\nmath\n";
+        let modules = parse_module_setup(&[local, SETUP_STDLIB]);
+        assert_eq!(modules["_ssl"], ModuleBuild::Static);
+        assert_eq!(modules["math"], ModuleBuild::Shared);
+        assert_eq!(modules["array"], ModuleBuild::Shared);
+        assert_eq!(modules["_tkinter"], ModuleBuild::Disabled);
+        assert_eq!(modules["nis"], ModuleBuild::Disabled);
+        assert!(!modules.contains_key("PYTHONPATH") && !modules.contains_key("CUSTOM=1"));
+        assert_eq!(ModuleBuild::Disabled.to_string(), "disabled");
+    }
+
+    #[test]
+    fn read() {
+        let dir = env::temp_dir().join(format!("python3-config-setup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert!(matches!(
+            read_module_setup(&dir),
+            Err(Error::Unsupported(_))
+        ));
+        fs::write(dir.join("Setup.stdlib"), SETUP_STDLIB).unwrap();
+        fs::write(dir.join("Setup.local"), "*disabled*\narray\n").unwrap();
+        let config = PythonConfig::parse(&format!(
+            "build_time_vars = {{'VERSION': '3.12', 'LIBPL': {:?}}}",
+            dir.display().to_string()
+        ))
+        .unwrap();
+        let modules = config.module_setup().unwrap();
+        assert_eq!(modules["array"], ModuleBuild::Disabled);
+        assert_eq!(modules["math"], ModuleBuild::Static);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use crate::{ConfigValue, PythonConfig};
+
+/// String hash algorithm selected by `Py_HASH_ALGORITHM`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// Not configured, the interpreter picks its built-in default
+    Default,
+    /// SipHash-2-4
+    SipHash24,
+    /// FNV
+    Fnv,
+    /// SipHash-1-3
+    SipHash13,
+    /// Unknown value
+    Other(i64),
+}
+
+/// Width of `Py_UNICODE` code units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeWidth {
+    /// 2 byte code units, as on Windows
+    Ucs2,
+    /// 4 byte code units
+    Ucs4,
+}
+
+impl PythonConfig {
+    /// Returns the sizes of C types recorded by configure as `SIZEOF_*` keys,
+    /// keyed by C type name, for example `long`, `size_t`, `void *` or `_Bool`
+    pub fn type_sizes(&self) -> &BTreeMap<String, u32> {
+        self.derived.type_sizes.get_or_init(|| {
+            self.sys_config_data
+                .build_time_vars
+                .raw
+                .iter()
+                .filter_map(|(key, value)| {
+                    let name = key.strip_prefix("SIZEOF_")?;
+                    match value {
+                        ConfigValue::Int(size) if *size > 0 => {
+                            Some((c_type_name(name), *size as u32))
+                        }
+                        _ => None,
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the size of C `time_t`, 8 on 32-bit platforms built for 64-bit
+    /// time such as Debian armhf since the time64 transition
+    pub fn time_t_size(&self) -> Option<u32> {
+        self.type_sizes().get("time_t").copied()
+    }
+
+    /// Returns the size of `Py_ssize_t`, which matches `size_t`
+    pub fn py_ssize_t_size(&self) -> Option<u32> {
+        self.type_sizes().get("size_t").copied()
+    }
+
+    /// Returns the string hash algorithm this distribution was configured with
+    pub fn hash_algorithm(&self) -> Option<HashAlgorithm> {
+        let algorithm = match self
+            .sys_config_data
+            .build_time_vars
+            .get_int("Py_HASH_ALGORITHM")?
+        {
+            0 => HashAlgorithm::Default,
+            1 => HashAlgorithm::SipHash24,
+            2 => HashAlgorithm::Fnv,
+            3 => HashAlgorithm::SipHash13,
+            other => HashAlgorithm::Other(other),
+        };
+        Some(algorithm)
+    }
+}
+
+impl PythonConfig {
+    /// Returns the size of C `wchar_t`
+    pub fn wchar_size(&self) -> Option<u32> {
+        self.type_sizes().get("wchar_t").copied()
+    }
+
+    /// Returns the size of `Py_UNICODE`. `Py_UNICODE_SIZE` is used when present,
+    /// otherwise the size of `wchar_t` which `Py_UNICODE` aliases since Python 3.3
+    pub fn unicode_size(&self) -> Option<u32> {
+        let vars = &self.sys_config_data.build_time_vars;
+        match vars.get_int("Py_UNICODE_SIZE") {
+            Some(size) if size > 0 => Some(size as u32),
+            _ => self.wchar_size(),
+        }
+    }
+
+    /// Returns whether `Py_UNICODE` is stored as UCS2 or UCS4
+    pub fn unicode_width(&self) -> Option<UnicodeWidth> {
+        match self.unicode_size()? {
+            2 => Some(UnicodeWidth::Ucs2),
+            4 => Some(UnicodeWidth::Ucs4),
+            _ => None,
+        }
+    }
+
+    /// Returns whether `wchar_t` can be used directly as `Py_UNICODE`
+    /// (`HAVE_USABLE_WCHAR_T`)
+    pub fn has_usable_wchar_t(&self) -> bool {
+        self.sys_config_data
+            .build_time_vars
+            .get_int("HAVE_USABLE_WCHAR_T")
+            .map(|x| x == 1)
+            .unwrap_or(false)
+    }
+}
+
+/// Map the suffix of a `SIZEOF_*` key to its C type name
+fn c_type_name(name: &str) -> String {
+    match name {
+        "VOID_P" => "void *".to_string(),
+        "_BOOL" => "_Bool".to_string(),
+        _ if name.ends_with("_T") => name.to_ascii_lowercase(),
+        _ => name.replace('_', " ").to_ascii_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HashAlgorithm, UnicodeWidth};
+    use crate::PythonConfig;
+    use std::fs;
+
+    #[test]
+    fn type_sizes() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        let sizes = config.type_sizes();
+        assert_eq!(sizes["long"], 8);
+        assert_eq!(sizes["long long"], 8);
+        assert_eq!(sizes["wchar_t"], 4);
+        assert_eq!(sizes["pthread_key_t"], 4);
+        assert_eq!(sizes["void *"], 8);
+        assert_eq!(sizes["_Bool"], 1);
+        assert_eq!(config.py_ssize_t_size(), Some(8));
+        assert_eq!(config.hash_algorithm(), Some(HashAlgorithm::Default));
+        assert_eq!(config.time_t_size(), Some(8));
+
+        // armhf after the time64 transition
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'MULTIARCH': 'arm-linux-gnueabihf', \
+             'SIZEOF_VOID_P': 4, 'SIZEOF_TIME_T': 8}",
+        )
+        .unwrap();
+        assert_eq!(config.pointer_size(), 4);
+        assert_eq!(config.time_t_size(), Some(8));
+    }
+
+    #[test]
+    fn unicode_width() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.2', 'Py_UNICODE_SIZE': 2, 'SIZEOF_WCHAR_T': 4}",
+        )
+        .unwrap();
+        assert_eq!(config.unicode_size(), Some(2));
+        assert_eq!(config.unicode_width(), Some(UnicodeWidth::Ucs2));
+        assert_eq!(config.wchar_size(), Some(4));
+        assert!(!config.has_usable_wchar_t());
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'SIZEOF_WCHAR_T': 4, 'HAVE_USABLE_WCHAR_T': 1}",
+        )
+        .unwrap();
+        assert_eq!(config.unicode_width(), Some(UnicodeWidth::Ucs4));
+        assert!(config.has_usable_wchar_t());
+    }
+}
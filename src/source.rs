@@ -0,0 +1,559 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::error_context::load_path;
+use crate::{key_info, ConfigValue, Error, PythonConfig, ValueKind};
+
+/// Nesting of `$(VAR)` references [`MakefileSource`] expands before giving up
+const MAX_EXPANSION_DEPTH: usize = 32;
+
+/// A place a [`PythonConfig`] can be loaded from, see [`Loader`]
+pub trait ConfigSource {
+    /// Short description of the source for error messages, such as its path
+    fn describe(&self) -> String;
+
+    /// Loads the configuration
+    fn load(&self) -> Result<PythonConfig, Error>;
+}
+
+/// Tries [`ConfigSource`]s in priority order, the first that loads wins
+#[derive(Default)]
+pub struct Loader {
+    sources: Vec<Box<dyn ConfigSource>>,
+}
+
+impl fmt::Debug for Loader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sources: Vec<String> = self.sources.iter().map(|x| x.describe()).collect();
+        f.debug_struct("Loader").field("sources", &sources).finish()
+    }
+}
+
+impl Loader {
+    /// Create a loader without sources
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `source`, tried after the ones added before
+    pub fn source(mut self, source: impl ConfigSource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Loads the configuration from the first source that succeeds, fails
+    /// with [`Error::NoSource`] holding every error when none does
+    pub fn load(&self) -> Result<PythonConfig, Error> {
+        let mut errors = Vec::new();
+        for source in &self.sources {
+            match source.load() {
+                Ok(config) => return Ok(config),
+                Err(err) => errors.push((source.describe(), err)),
+            }
+        }
+        Err(Error::NoSource(errors))
+    }
+}
+
+/// A `_sysconfigdata*.py` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysconfigData {
+    /// Path of the file
+    pub path: PathBuf,
+}
+
+impl SysconfigData {
+    /// Source reading the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl ConfigSource for SysconfigData {
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn load(&self) -> Result<PythonConfig, Error> {
+        load_path(&self.path, PythonConfig::parse)
+    }
+}
+
+/// A JSON file written by [`PythonConfig::to_json`]
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonDump {
+    /// Path of the file
+    pub path: PathBuf,
+}
+
+#[cfg(feature = "serde")]
+impl JsonDump {
+    /// Source reading the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ConfigSource for JsonDump {
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn load(&self) -> Result<PythonConfig, Error> {
+        load_path(&self.path, |src| {
+            PythonConfig::from_json(src).map_err(|err| Error::Io(err.into()))
+        })
+    }
+}
+
+/// A PEP 739 `build-details.json`, see [`PythonConfig::from_build_details`]
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildDetails {
+    /// Path of the file
+    pub path: PathBuf,
+}
+
+#[cfg(feature = "serde")]
+impl BuildDetails {
+    /// Source reading the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ConfigSource for BuildDetails {
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn load(&self) -> Result<PythonConfig, Error> {
+        load_path(&self.path, PythonConfig::from_build_details)
+    }
+}
+
+/// A `pyconfig.h` header, the only configuration Windows installations
+/// ship. `VERSION` is taken from the `patchlevel.h` next to it, most paths
+/// and tags stay unknown, see [`PythonConfig::unknown_keys`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PyconfigH {
+    /// Path of the header
+    pub path: PathBuf,
+}
+
+impl PyconfigH {
+    /// Source reading the header at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl ConfigSource for PyconfigH {
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn load(&self) -> Result<PythonConfig, Error> {
+        let mut vars = parse_config_h(&fs::read_to_string(&self.path)?);
+        let patchlevel = self.path.with_file_name("patchlevel.h");
+        if !vars.contains_key("VERSION") && patchlevel.exists() {
+            let patchlevel = parse_config_h(&fs::read_to_string(patchlevel)?);
+            if let (Some(ConfigValue::Int(major)), Some(ConfigValue::Int(minor))) = (
+                patchlevel.get("PY_MAJOR_VERSION"),
+                patchlevel.get("PY_MINOR_VERSION"),
+            ) {
+                vars.insert(
+                    "VERSION".to_string(),
+                    ConfigValue::String(format!("{}.{}", major, minor).into()),
+                );
+            }
+        }
+        PythonConfig::from_vars(vars)
+    }
+}
+
+/// Definitions of a C header like `sysconfig.parse_config_h()` reads them:
+/// `#define X 1` and `/* #undef X */`, which becomes `0`
+pub(crate) fn parse_config_h(src: &str) -> BTreeMap<String, ConfigValue> {
+    let mut vars = BTreeMap::new();
+    for line in src.lines() {
+        let line = line.trim();
+        if let Some(define) = line.strip_prefix("#define ") {
+            let mut parts = define.trim().splitn(2, char::is_whitespace);
+            let name = parts.next().unwrap_or_default();
+            if name.is_empty() || name.contains('(') {
+                continue;
+            }
+            let value = parts.next().unwrap_or_default().trim();
+            let value = match value.parse::<i64>() {
+                Ok(value) => ConfigValue::Int(value),
+                Err(_) if value.is_empty() => ConfigValue::Int(1),
+                Err(_) => ConfigValue::String(value.trim_matches('"').into()),
+            };
+            vars.insert(name.to_string(), value);
+        } else if let Some(undef) = line
+            .strip_prefix("/* #undef ")
+            .and_then(|x| x.strip_suffix("*/"))
+        {
+            vars.insert(undef.trim().to_string(), ConfigValue::Int(0));
+        }
+    }
+    vars
+}
+
+/// The `Makefile` installed in `LIBPL`, the `config-3.X` directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MakefileSource {
+    /// Path of the `Makefile`
+    pub path: PathBuf,
+}
+
+impl MakefileSource {
+    /// Source reading the `Makefile` at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl ConfigSource for MakefileSource {
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn load(&self) -> Result<PythonConfig, Error> {
+        load_path(&self.path, |src| {
+            PythonConfig::from_vars(parse_makefile(src))
+        })
+    }
+}
+
+/// Variable assignments of a `Makefile` with `$(VAR)` and `${VAR}`
+/// references expanded, like `sysconfig` reads them
+pub(crate) fn parse_makefile(src: &str) -> BTreeMap<String, ConfigValue> {
+    let mut raw = BTreeMap::new();
+    let mut logical = String::new();
+    for line in src.lines() {
+        if let Some(continued) = line.strip_suffix('\\') {
+            logical.push_str(continued);
+            logical.push(' ');
+            continue;
+        }
+        logical.push_str(line);
+        let line = std::mem::take(&mut logical);
+        if line.starts_with(['\t', '#']) {
+            continue;
+        }
+        let (name, value) = match line.split_once('=') {
+            Some(assignment) => assignment,
+            None => continue,
+        };
+        let name = name.trim_end_matches([':', '?', '+']).trim();
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if valid {
+            raw.insert(name.to_string(), value.trim().to_string());
+        }
+    }
+    raw.iter()
+        .map(|(name, value)| {
+            let value = expand(value, &raw, 0);
+            let value = match value.parse::<i64>() {
+                Ok(value) => ConfigValue::Int(value),
+                Err(_) => ConfigValue::String(value.into()),
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+/// `value` with variable references replaced, unknown variables are empty
+pub(crate) fn expand(value: &str, vars: &BTreeMap<String, String>, depth: usize) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let close = match rest.chars().next() {
+            Some('$') => {
+                out.push('$');
+                rest = &rest[1..];
+                continue;
+            }
+            Some('(') => ')',
+            Some('{') => '}',
+            _ => {
+                out.push('$');
+                continue;
+            }
+        };
+        match rest.find(close) {
+            Some(end) => {
+                let name = &rest[1..end];
+                if depth < MAX_EXPANSION_DEPTH {
+                    if let Some(value) = vars.get(name) {
+                        out.push_str(&expand(value, vars, depth + 1));
+                    }
+                }
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('$');
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The text `python -m sysconfig` prints, as found in bug reports and CI
+/// logs, see [`PythonConfig::from_sysconfig_report`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysconfigReport {
+    /// Path of the captured output
+    pub path: PathBuf,
+}
+
+impl SysconfigReport {
+    /// Source reading the output captured at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl ConfigSource for SysconfigReport {
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn load(&self) -> Result<PythonConfig, Error> {
+        load_path(&self.path, PythonConfig::from_sysconfig_report)
+    }
+}
+
+impl PythonConfig {
+    /// Builds a configuration from the output of `python -m sysconfig`,
+    /// the `KEY = "value"` lines of its `Variables:` section.
+    ///
+    /// The report prints every value with `str()`, so types are restored:
+    /// well-known keys get the type [`key_info`] documents, other values
+    /// made of digits become integers and `None` becomes `None`. Values
+    /// spanning several lines are joined back.
+    pub fn from_sysconfig_report(src: &str) -> Result<Self, Error> {
+        let vars = parse_sysconfig_report(src);
+        if vars.is_empty() {
+            return Err(Error::MissingBuildTimeVars);
+        }
+        Self::from_vars(vars)
+    }
+}
+
+/// Variables of the `Variables:` section of `python -m sysconfig` output
+pub(crate) fn parse_sysconfig_report(src: &str) -> BTreeMap<String, ConfigValue> {
+    let mut vars = BTreeMap::new();
+    let mut lines = src
+        .lines()
+        .skip_while(|line| line.trim_end() != "Variables:")
+        .skip(1);
+    while let Some(line) = lines.next() {
+        let (name, value) = match line
+            .strip_prefix('\t')
+            .and_then(|line| line.split_once(" = \""))
+        {
+            Some(entry) => entry,
+            None if line.trim().is_empty() => continue,
+            None => break,
+        };
+        let mut value = value.to_string();
+        while !value.ends_with('"') {
+            match lines.next() {
+                Some(next) => {
+                    value.push('\n');
+                    value.push_str(next);
+                }
+                None => {
+                    value.push('"');
+                    break;
+                }
+            }
+        }
+        value.pop();
+        let kind = key_info(name).map(|info| info.kind);
+        let value = match value.parse::<i64>() {
+            _ if value == "None" && kind != Some(ValueKind::String) => ConfigValue::None,
+            Ok(int) if kind != Some(ValueKind::String) => ConfigValue::Int(int),
+            _ => ConfigValue::String(value.into()),
+        };
+        vars.insert(name.to_string(), value);
+    }
+    vars
+}
+
+/// A Python interpreter run to print its `sysconfig.get_config_vars()`,
+/// only possible for interpreters of the host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterpreterProbe {
+    /// Interpreter to run, a path or a name looked up in `PATH`
+    pub python: PathBuf,
+}
+
+impl InterpreterProbe {
+    /// Source running `python`
+    pub fn new(python: impl Into<PathBuf>) -> Self {
+        Self {
+            python: python.into(),
+        }
+    }
+}
+
+/// Prints the configuration variables as a `_sysconfigdata.py` file,
+/// leaving out values that aren't `str`, `int` or `None`
+const PROBE_SCRIPT: &str = "import sysconfig; print('build_time_vars = ' + repr({k: v for k, v in \
+                            sysconfig.get_config_vars().items() if v is None or type(v) in (str, int)}))";
+
+impl ConfigSource for InterpreterProbe {
+    fn describe(&self) -> String {
+        self.python.display().to_string()
+    }
+
+    fn load(&self) -> Result<PythonConfig, Error> {
+        let output = Command::new(&self.python)
+            .args(["-c", PROBE_SCRIPT])
+            .output()?;
+        if !output.status.success() {
+            return Err(Error::Unsupported(format!(
+                "{} failed: {}",
+                self.python.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        PythonConfig::parse(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_config_h, parse_makefile, parse_sysconfig_report, Loader, MakefileSource, PyconfigH,
+        SysconfigData,
+    };
+    use crate::{ConfigValue, Error, PythonConfig};
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn config_h() {
+        let vars = parse_config_h(
+            "#define HAVE_FORK 1\n/* #undef HAVE_LIBREADLINE */\n#define SIZEOF_VOID_P 8\n\
+             #define PY_RELEASE_LEVEL_FINAL 0xF\n#define WITH_DOC_STRINGS\n#define Py_LIMITED(x) x\n",
+        );
+        assert_eq!(vars["HAVE_FORK"], ConfigValue::Int(1));
+        assert_eq!(vars["HAVE_LIBREADLINE"], ConfigValue::Int(0));
+        assert_eq!(vars["WITH_DOC_STRINGS"], ConfigValue::Int(1));
+        assert_eq!(
+            vars["PY_RELEASE_LEVEL_FINAL"],
+            ConfigValue::String("0xF".into())
+        );
+        assert!(!vars.contains_key("Py_LIMITED(x)") && !vars.contains_key("Py_LIMITED"));
+    }
+
+    #[test]
+    fn makefile() {
+        let vars = parse_makefile(
+            "# comment\nVERSION=\t3.11\nprefix=\t\t/usr/local\nLIBDIR=\t\t$(prefix)/lib\n\
+             LIBPL=\t\t${LIBDIR}/python$(VERSION)\nCFLAGS= -O2 \\\n\t-Wall\nSIZEOF= 8\n\
+             DOLLAR= $$HOME $(UNKNOWN)x\nall:\tbuild\n\t$(CC) -o x\nLOOP= $(LOOP)\n",
+        );
+        assert_eq!(vars["LIBDIR"], ConfigValue::String("/usr/local/lib".into()));
+        assert_eq!(
+            vars["LIBPL"],
+            ConfigValue::String("/usr/local/lib/python3.11".into())
+        );
+        assert_eq!(vars["CFLAGS"], ConfigValue::String("-O2  \t-Wall".into()));
+        assert_eq!(vars["SIZEOF"], ConfigValue::Int(8));
+        assert_eq!(vars["DOLLAR"], ConfigValue::String("$HOME x".into()));
+        assert_eq!(vars["LOOP"], ConfigValue::String("".into()));
+        assert!(!vars.contains_key("all"));
+    }
+
+    #[test]
+    fn sysconfig_report() {
+        let src = "Platform: \"linux-x86_64\"\n\
+                   Python version: \"3.11\"\n\
+                   Current installation scheme: \"posix_prefix\"\n\n\
+                   Paths: \n\
+                   \tdata = \"/usr/local\"\n\n\
+                   Variables: \n\
+                   \tABIFLAGS = \"\"\n\
+                   \tCONFIG_ARGS = \"'--enable-shared' 'CFLAGS=-O2\n-g'\"\n\
+                   \tLIBDIR = \"/usr/local/lib\"\n\
+                   \tMACOSX_DEPLOYMENT_TARGET = \"11\"\n\
+                   \tPy_ENABLE_SHARED = \"1\"\n\
+                   \tSIZEOF_VOID_P = \"8\"\n\
+                   \tTZPATH = \"None\"\n\
+                   \tVERSION = \"3.11\"\n\
+                   \tprefix = \"/usr/local\"\n";
+        let vars = parse_sysconfig_report(src);
+        assert_eq!(vars.len(), 9);
+        assert_eq!(vars["SIZEOF_VOID_P"], ConfigValue::Int(8));
+        assert_eq!(
+            vars["MACOSX_DEPLOYMENT_TARGET"],
+            ConfigValue::String("11".into())
+        );
+        assert_eq!(vars["TZPATH"], ConfigValue::String("None".into()));
+        assert_eq!(
+            vars["CONFIG_ARGS"],
+            ConfigValue::String("'--enable-shared' 'CFLAGS=-O2\n-g'".into())
+        );
+        assert!(!vars.contains_key("data"));
+
+        let config = PythonConfig::from_sysconfig_report(src).unwrap();
+        assert_eq!(config.version_str(), "3.11");
+        assert!(config.enable_shared());
+        assert_eq!(config.lib_dir_str(), "/usr/local/lib");
+
+        let err = PythonConfig::from_sysconfig_report("Platform: \"linux-x86_64\"\n").unwrap_err();
+        assert!(matches!(err, Error::MissingBuildTimeVars));
+    }
+
+    #[test]
+    fn loader() {
+        let dir = env::temp_dir().join(format!("python3-config-loader-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("pyconfig.h"), "#define Py_ENABLE_SHARED 1\n").unwrap();
+        fs::write(
+            dir.join("patchlevel.h"),
+            "#define PY_MAJOR_VERSION 3\n#define PY_MINOR_VERSION 12\n",
+        )
+        .unwrap();
+        fs::write(dir.join("Makefile"), "VERSION= 3.11\nprefix= /usr\n").unwrap();
+
+        let loader = Loader::new()
+            .source(SysconfigData::new(dir.join("_sysconfigdata.py")))
+            .source(MakefileSource::new(dir.join("Makefile")))
+            .source(PyconfigH::new(dir.join("pyconfig.h")));
+        let config = loader.load().unwrap();
+        assert_eq!(config.version_str(), "3.11");
+        assert_eq!(config.prefix_str(), "/usr");
+
+        let config = Loader::new()
+            .source(PyconfigH::new(dir.join("pyconfig.h")))
+            .load()
+            .unwrap();
+        assert_eq!(config.version_str(), "3.12");
+        assert!(config.enable_shared());
+
+        let err = Loader::new()
+            .source(SysconfigData::new(dir.join("missing.py")))
+            .load()
+            .unwrap_err();
+        assert!(matches!(&err, Error::NoSource(errors) if errors.len() == 1));
+        assert!(err.to_string().contains("missing.py"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt::Write;
+
+use crate::{ConfigValue, PythonConfig};
+
+/// A literal value of a [`StaticConfig`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaticValue {
+    /// String literal
+    String(&'static str),
+    /// Integer literal
+    Int(i64),
+    /// Decimal text of a float literal
+    Float(&'static str),
+    /// `None`
+    None,
+}
+
+impl From<StaticValue> for ConfigValue {
+    fn from(value: StaticValue) -> Self {
+        match value {
+            StaticValue::String(value) => ConfigValue::String(value.into()),
+            StaticValue::Int(value) => ConfigValue::Int(value),
+            StaticValue::Float(text) => ConfigValue::Float(text.into()),
+            StaticValue::None => ConfigValue::None,
+        }
+    }
+}
+
+/// `build_time_vars` compiled into a binary, usually from the Rust source
+/// generated by [`PythonConfig::to_rust_literal`] in a build script:
+///
+/// ```ignore
+/// static PYTHON: StaticConfig = include!(concat!(env!("OUT_DIR"), "/python.rs"));
+///
+/// let config = PYTHON.config();
+/// ```
+///
+/// It holds only `'static` data, so it can live in a `const` or `static`
+/// without lazy initialization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticConfig {
+    /// Keys and values of `build_time_vars`
+    pub vars: &'static [(&'static str, StaticValue)],
+}
+
+impl StaticConfig {
+    /// Creates a configuration of `vars`
+    pub const fn new(vars: &'static [(&'static str, StaticValue)]) -> Self {
+        Self { vars }
+    }
+
+    /// Builds the [`PythonConfig`], without parsing any Python source
+    pub fn config(&self) -> PythonConfig {
+        PythonConfig::from_vars(
+            self.vars
+                .iter()
+                .map(|(key, value)| (key.to_string(), ConfigValue::from(*value))),
+        )
+        .expect("static configurations are generated from valid ones")
+    }
+}
+
+impl PythonConfig {
+    /// Rust expression of type [`StaticConfig`] holding `build_time_vars`,
+    /// for a build script to write into `OUT_DIR` and the crate to
+    /// `include!`, so runtime tools don't carry and re-parse the
+    /// `_sysconfigdata.py` file
+    pub fn to_rust_literal(&self) -> String {
+        let mut out = String::from("::python3_config::StaticConfig::new(&[\n");
+        for (key, value) in self.vars() {
+            write!(out, "    ({:?}, ::python3_config::StaticValue::", key).unwrap();
+            match value {
+                ConfigValue::String(value) => write!(out, "String({:?})", value),
+                ConfigValue::Int(value) => write!(out, "Int({})", value),
+                ConfigValue::Float(text) => write!(out, "Float({:?})", text),
+                ConfigValue::None => write!(out, "None"),
+            }
+            .unwrap();
+            out.push_str("),\n");
+        }
+        out.push_str("])\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StaticConfig, StaticValue};
+    use crate::{fixtures, ConfigValue, PythonConfig};
+
+    const CONFIG: StaticConfig = StaticConfig::new(&[
+        ("LIBPL", StaticValue::None),
+        ("Py_DEBUG", StaticValue::Int(0)),
+        ("SOABI", StaticValue::String("cpython-312-x86_64-linux-gnu")),
+        ("VERSION", StaticValue::String("3.12")),
+    ]);
+
+    #[test]
+    fn rust_literal() {
+        let config = CONFIG.config();
+        assert_eq!(config.version_str(), "3.12");
+        assert_eq!(config.get("LIBPL"), Some(&ConfigValue::None));
+        let literal = config.to_rust_literal();
+        assert!(literal.starts_with("::python3_config::StaticConfig::new(&[\n"));
+        assert!(literal.contains("    (\"LIBPL\", ::python3_config::StaticValue::None),\n"));
+        assert!(literal.contains("    (\"Py_DEBUG\", ::python3_config::StaticValue::Int(0)),\n"));
+        assert!(literal
+            .contains("    (\"VERSION\", ::python3_config::StaticValue::String(\"3.12\")),\n"));
+
+        let config = PythonConfig::from_vars(vec![
+            ("VERSION".to_string(), ConfigValue::String("3.12".into())),
+            (
+                "CONFIG_ARGS".to_string(),
+                ConfigValue::String("'CFLAGS=\"-O2\"'\n\\".into()),
+            ),
+        ])
+        .unwrap();
+        assert!(config
+            .to_rust_literal()
+            .contains("String(\"'CFLAGS=\\\"-O2\\\"'\\n\\\\\")"));
+    }
+
+    #[test]
+    fn static_config_matches_fixtures() {
+        for fixture in fixtures::all() {
+            let config = fixture.config();
+            let vars: Vec<(&'static str, StaticValue)> = config
+                .vars()
+                .map(|(key, value)| {
+                    let key: &'static str = Box::leak(key.to_string().into_boxed_str());
+                    let value = match value {
+                        ConfigValue::String(value) => {
+                            StaticValue::String(Box::leak(value.to_string().into_boxed_str()))
+                        }
+                        ConfigValue::Int(value) => StaticValue::Int(*value),
+                        ConfigValue::Float(text) => {
+                            StaticValue::Float(Box::leak(text.to_string().into_boxed_str()))
+                        }
+                        ConfigValue::None => StaticValue::None,
+                    };
+                    (key, value)
+                })
+                .collect();
+            let embedded = StaticConfig::new(Box::leak(vars.into_boxed_slice())).config();
+            assert_eq!(
+                embedded.to_rust_literal(),
+                config.to_rust_literal(),
+                "{}",
+                fixture.name
+            );
+        }
+    }
+}
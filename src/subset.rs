@@ -0,0 +1,109 @@
+use crate::PythonConfig;
+
+/// Keys a consumer of a configuration needs, see [`PythonConfig::minimal_subset`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeysProfile {
+    /// Keys PyO3's build configuration reads
+    Pyo3,
+    /// Keys needed to compute wheel tags and extension module file names
+    Wheel,
+    /// The listed keys
+    Keys(Vec<String>),
+}
+
+const PYO3_KEYS: &[&str] = &[
+    "ABIFLAGS",
+    "COUNT_ALLOCS",
+    "EXT_SUFFIX",
+    "LDVERSION",
+    "LIBDIR",
+    "LIBPL",
+    "MULTIARCH",
+    "Py_DEBUG",
+    "Py_ENABLE_SHARED",
+    "Py_GIL_DISABLED",
+    "Py_REF_DEBUG",
+    "Py_TRACE_REFS",
+    "SIZEOF_VOID_P",
+    "SOABI",
+    "VERSION",
+    "WITH_THREAD",
+    "prefix",
+];
+
+const WHEEL_KEYS: &[&str] = &[
+    "ABIFLAGS",
+    "CFLAGS",
+    "EXT_SUFFIX",
+    "HOST_GNU_TYPE",
+    "MACHDEP",
+    "MACOSX_DEPLOYMENT_TARGET",
+    "MULTIARCH",
+    "Py_GIL_DISABLED",
+    "SHLIB_SUFFIX",
+    "SIZEOF_VOID_P",
+    "SOABI",
+    "VERSION",
+];
+
+impl KeysProfile {
+    /// Returns whether `key` is part of this profile
+    pub fn contains(&self, key: &str) -> bool {
+        match self {
+            KeysProfile::Pyo3 => PYO3_KEYS.contains(&key),
+            KeysProfile::Wheel => WHEEL_KEYS.contains(&key),
+            KeysProfile::Keys(keys) => keys.iter().any(|k| k == key),
+        }
+    }
+}
+
+impl PythonConfig {
+    /// Returns a copy of this configuration trimmed to the keys of `profile`,
+    /// `VERSION` is always kept. Serialize it with
+    /// `PythonConfig::to_sysconfigdata` of the `emit` feature to vendor a
+    /// small target description instead of the full file.
+    pub fn minimal_subset(&self, profile: &KeysProfile) -> PythonConfig {
+        let vars = self
+            .vars()
+            .filter(|(key, _)| *key == "VERSION" || profile.contains(key))
+            .map(|(key, value)| (key.to_string(), value.clone()));
+        let mut config =
+            PythonConfig::from_vars(vars).expect("VERSION is kept from a valid configuration");
+        config.provenance = self.provenance.clone();
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeysProfile;
+    use crate::fixtures;
+
+    #[test]
+    fn minimal_subset() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let subset = config.minimal_subset(&KeysProfile::Pyo3);
+        assert!(subset.vars().count() <= 17);
+        assert_eq!(subset.soabi(), config.soabi());
+        assert_eq!(subset.lib_dir_str(), config.lib_dir_str());
+        assert_eq!(subset.cflags(), "");
+        #[cfg(feature = "emit")]
+        {
+            let reparsed = crate::PythonConfig::parse(&subset.to_sysconfigdata()).unwrap();
+            assert!(subset.vars().eq(reparsed.vars()));
+        }
+
+        let subset = config.minimal_subset(&KeysProfile::Wheel);
+        assert_eq!(subset.supported_tags(), config.supported_tags());
+        assert_eq!(
+            subset.extension_filename("foo"),
+            config.extension_filename("foo")
+        );
+
+        let subset = config.minimal_subset(&KeysProfile::Keys(vec!["SOABI".to_string()]));
+        assert_eq!(
+            subset.vars().map(|(key, _)| key).collect::<Vec<_>>(),
+            vec!["SOABI", "VERSION"]
+        );
+    }
+}
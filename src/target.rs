@@ -0,0 +1,475 @@
+use std::env;
+
+use crate::{arch, platform, ConfigValue, Error, PythonConfig};
+
+/// Properties a sysconfigdata for a given Rust target triple is expected to have
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedConfig {
+    /// Size of C `void*`
+    pub pointer_size: u32,
+    /// Platform part of `EXT_SUFFIX`, for example `x86_64-linux-gnu` or `win_amd64`
+    pub platform: String,
+    /// Suffix of extension modules, `.so` or `.pyd`
+    pub shlib_suffix: &'static str,
+    /// Typical Debian style `MULTIARCH` value, `None` on platforms without one
+    pub multiarch: Option<String>,
+}
+
+/// Returns the properties expected of a sysconfigdata built for `triple`,
+/// for example `x86_64-unknown-linux-gnu` or `aarch64-apple-darwin`
+pub fn expected_for_target(triple: &str) -> Result<ExpectedConfig, Error> {
+    let unknown = || Error::UnknownTarget(triple.to_string());
+    let mut parts = triple.split('-');
+    let arch = parts.next().filter(|x| !x.is_empty()).ok_or_else(unknown)?;
+    let rest: Vec<&str> = parts.collect();
+    let (os, env) = match rest.as_slice() {
+        // Android triples have no vendor, aarch64-linux-android
+        ["linux", env] => ("linux", *env),
+        [_vendor, os] => (*os, ""),
+        [_vendor, os, env] => (*os, *env),
+        [os] => (*os, ""),
+        _ => return Err(unknown()),
+    };
+    let arch = arch::find_by_rust_triple(arch).ok_or_else(unknown)?;
+    let (mut multiarch_arch, mut pointer_size) = (arch.multiarch, arch.pointer_size);
+    // CPython names 32-bit x86 Android builds after the NDK triple
+    if env.starts_with("android") && multiarch_arch == "i386" {
+        multiarch_arch = "i686";
+    }
+    // x86_64-unknown-linux-gnux32, aarch64-unknown-linux-gnu_ilp32
+    if env.ends_with("x32") || env.ends_with("_ilp32") {
+        pointer_size = 4;
+    }
+    let expected = match (platform::find_by_rust_os(os), os) {
+        (Some(platform), _) => {
+            let env = if env.is_empty() {
+                platform.default_env
+            } else {
+                env
+            };
+            let expand = |template: &str| {
+                template
+                    .replace("{arch}", multiarch_arch)
+                    .replace("{env}", env)
+            };
+            ExpectedConfig {
+                pointer_size,
+                platform: expand(platform.ext_platform),
+                shlib_suffix: platform.shlib_suffix,
+                multiarch: platform.multiarch.map(expand),
+            }
+        }
+        (None, "windows") if env.starts_with("gnu") => {
+            // MSYS2 mingw-w64 builds, mingw_x86_64 or mingw_x86_64_ucrt
+            let arch = match multiarch_arch {
+                "i386" => "i686",
+                arch => arch,
+            };
+            ExpectedConfig {
+                pointer_size,
+                platform: format!("mingw_{}", arch),
+                shlib_suffix: ".pyd",
+                multiarch: None,
+            }
+        }
+        (None, "windows") => {
+            let platform = match multiarch_arch {
+                "x86_64" => "win_amd64",
+                "i386" => "win32",
+                "aarch64" => "win_arm64",
+                _ => return Err(unknown()),
+            };
+            ExpectedConfig {
+                pointer_size,
+                platform: platform.to_string(),
+                shlib_suffix: ".pyd",
+                multiarch: None,
+            }
+        }
+        _ => return Err(unknown()),
+    };
+    Ok(expected)
+}
+
+impl ExpectedConfig {
+    /// `EXT_SUFFIX` of CPython `version` with `abiflags` on this target,
+    /// `.cpython-312-x86_64-linux-gnu.so` or `.cp312-win_amd64.pyd`
+    pub fn ext_suffix(&self, version: &str, abiflags: &str) -> String {
+        let version = version.replace('.', "");
+        if self.shlib_suffix == ".pyd" {
+            return format!(".cp{}{}-{}.pyd", version, abiflags, self.platform);
+        }
+        let mut suffix = format!(".cpython-{}{}", version, abiflags);
+        if !self.platform.is_empty() {
+            suffix.push('-');
+            suffix.push_str(&self.platform);
+        }
+        suffix.push_str(self.shlib_suffix);
+        suffix
+    }
+}
+
+impl PythonConfig {
+    /// Like [`PythonConfig::from_vars`] for a configuration written by hand
+    /// for the Rust target `triple`: `SHLIB_SUFFIX` and `EXT_SUFFIX` default
+    /// to the ones of the target, `.pyd` on Windows, `.dll` on Cygwin and
+    /// `.so` elsewhere, so the derived values agree with
+    /// [`PythonConfig::validate_target`]. Entries in `vars` take precedence.
+    pub fn from_vars_for_target<I>(triple: &str, vars: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (String, ConfigValue)>,
+    {
+        let expected = expected_for_target(triple)?;
+        let mut vars: Vec<(String, ConfigValue)> = vars.into_iter().collect();
+        let get = |vars: &[(String, ConfigValue)], key: &str| {
+            vars.iter()
+                .find(|(name, _)| name == key)
+                .and_then(|(_, value)| value.as_str().map(str::to_string))
+        };
+        let shlib_suffix = match get(&vars, "SHLIB_SUFFIX") {
+            Some(suffix) => suffix,
+            None => {
+                vars.push((
+                    "SHLIB_SUFFIX".to_string(),
+                    ConfigValue::String(expected.shlib_suffix.into()),
+                ));
+                expected.shlib_suffix.to_string()
+            }
+        };
+        if get(&vars, "EXT_SUFFIX").is_none() {
+            let ext_suffix = match (get(&vars, "SOABI"), get(&vars, "VERSION")) {
+                (Some(soabi), _) => Some(format!(".{}{}", soabi, shlib_suffix)),
+                (None, Some(version)) => {
+                    let abiflags = get(&vars, "ABIFLAGS").unwrap_or_default();
+                    let ext_suffix = expected.ext_suffix(&version, &abiflags);
+                    let tag = &ext_suffix[..ext_suffix.len() - expected.shlib_suffix.len()];
+                    Some(format!("{}{}", tag, shlib_suffix))
+                }
+                (None, None) => None,
+            };
+            if let Some(ext_suffix) = ext_suffix {
+                vars.push((
+                    "EXT_SUFFIX".to_string(),
+                    ConfigValue::String(ext_suffix.into()),
+                ));
+            }
+        }
+        Self::from_vars(vars)
+    }
+
+    /// Checks from a build script that this configuration matches the target
+    /// cargo is compiling for, as described by the `CARGO_CFG_TARGET_*`
+    /// environment variables.
+    ///
+    /// Reports every disagreement in pointer width, operating system and
+    /// architecture, which otherwise only surface as link errors.
+    pub fn assert_matches_cargo_target(&self) -> Result<(), Error> {
+        self.assert_matches_cargo_target_from(|name| env::var(name).ok())
+    }
+
+    /// Like [`PythonConfig::assert_matches_cargo_target`] but reads variables
+    /// through `lookup`
+    pub fn assert_matches_cargo_target_from<F>(&self, lookup: F) -> Result<(), Error>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let target_os = lookup("CARGO_CFG_TARGET_OS").unwrap_or_default();
+        let target_arch = lookup("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+        let mut mismatches = Vec::new();
+        if let Some(width) = lookup("CARGO_CFG_TARGET_POINTER_WIDTH") {
+            let bits = self.pointer_size() * 8;
+            if bits != 0 && width != bits.to_string() {
+                mismatches.push(format!(
+                    "pointer width is {} bits, target has {} bits",
+                    bits, width
+                ));
+            }
+        }
+        let os = cargo_os(self.sys_platform());
+        let os_matches = match os {
+            "macos" => target_os == "macos" || target_os == "ios",
+            _ => os == target_os,
+        };
+        if !os.is_empty() && !target_os.is_empty() && !os_matches {
+            mismatches.push(format!(
+                "operating system is {} (sys.platform {}), target has {}",
+                os,
+                self.sys_platform(),
+                target_os
+            ));
+        }
+        let archs = self.cargo_archs();
+        if !archs.is_empty() && !target_arch.is_empty() && !archs.contains(&target_arch.as_str()) {
+            mismatches.push(format!(
+                "architecture is {}, target has {}",
+                archs.join(" + "),
+                target_arch
+            ));
+        }
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+        let target = lookup("TARGET").unwrap_or_else(|| format!("{}-{}", target_arch, target_os));
+        Err(Error::TargetMismatch {
+            target,
+            reason: mismatches.join(", "),
+        })
+    }
+
+    /// Values of `CARGO_CFG_TARGET_ARCH` this configuration can be used for,
+    /// several for macOS universal builds
+    fn cargo_archs(&self) -> Vec<&'static str> {
+        let mut archs = Vec::new();
+        if self.sys_platform() == "darwin" {
+            let mut flags = self.cflags().split_whitespace();
+            while let Some(flag) = flags.next() {
+                if flag == "-arch" {
+                    archs.extend(flags.next().and_then(cargo_arch));
+                }
+            }
+        }
+        if archs.is_empty() {
+            archs.extend(cargo_arch(&self.machine()));
+        }
+        archs
+    }
+}
+
+/// `CARGO_CFG_TARGET_OS` equivalent of `sys.platform`
+fn cargo_os(sys_platform: &str) -> &str {
+    if sys_platform == "win32" {
+        return "windows";
+    }
+    match platform::find_by_sys_platform(sys_platform) {
+        Some(platform) => platform.cargo_os,
+        None => sys_platform.trim_end_matches(|c: char| c.is_ascii_digit()),
+    }
+}
+
+/// `CARGO_CFG_TARGET_ARCH` equivalent of `uname -m`
+fn cargo_arch(machine: &str) -> Option<&'static str> {
+    match arch::find_by_name(machine) {
+        Some(arch) => Some(arch.cargo).filter(|x| !x.is_empty()),
+        None if machine.starts_with("arm") => Some("arm"),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expected_for_target;
+    use crate::{ConfigValue, Error, PythonConfig};
+    use std::fs;
+
+    #[test]
+    fn target_oracle() {
+        let expected = expected_for_target("x86_64-unknown-linux-gnu").unwrap();
+        assert_eq!(expected.pointer_size, 8);
+        assert_eq!(expected.platform, "x86_64-linux-gnu");
+        assert_eq!(expected.multiarch.as_deref(), Some("x86_64-linux-gnu"));
+
+        let expected = expected_for_target("armv7-unknown-linux-gnueabihf").unwrap();
+        assert_eq!(expected.pointer_size, 4);
+        assert_eq!(expected.platform, "arm-linux-gnueabihf");
+
+        let expected = expected_for_target("i686-pc-windows-msvc").unwrap();
+        assert_eq!(expected.platform, "win32");
+        assert_eq!(expected.shlib_suffix, ".pyd");
+        assert_eq!(expected.multiarch, None);
+
+        let expected = expected_for_target("mips64el-unknown-linux-gnuabi64").unwrap();
+        assert_eq!(expected.platform, "mips64el-linux-gnuabi64");
+        assert_eq!(expected.pointer_size, 8);
+        let expected = expected_for_target("x86_64-unknown-linux-gnux32").unwrap();
+        assert_eq!(expected.platform, "x86_64-linux-gnux32");
+        assert_eq!(expected.pointer_size, 4);
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'SIZEOF_VOID_P': 4, \
+             'MULTIARCH': 'x86_64-linux-gnux32', 'SOABI': 'cpython-312-x86_64-linux-gnux32'}",
+        )
+        .unwrap();
+        assert!(config
+            .validate_target("x86_64-unknown-linux-gnux32")
+            .is_ok());
+        assert!(config.validate_target("x86_64-unknown-linux-gnu").is_err());
+
+        let expected = expected_for_target("aarch64-linux-android").unwrap();
+        assert_eq!(expected.platform, "aarch64-linux-android");
+        assert_eq!(expected.pointer_size, 8);
+        let expected = expected_for_target("armv7-linux-androideabi").unwrap();
+        assert_eq!(expected.platform, "arm-linux-androideabi");
+        assert_eq!(expected.pointer_size, 4);
+        let expected = expected_for_target("i686-linux-android").unwrap();
+        assert_eq!(expected.multiarch.as_deref(), Some("i686-linux-android"));
+        let expected = expected_for_target("x86_64-linux-android").unwrap();
+        assert_eq!(expected.shlib_suffix, ".so");
+        assert_eq!(
+            expected.ext_suffix("3.13", ""),
+            ".cpython-313-x86_64-linux-android.so"
+        );
+
+        let expected = expected_for_target("loongarch64-unknown-linux-gnu").unwrap();
+        assert_eq!(expected.platform, "loongarch64-linux-gnu");
+
+        let expected = expected_for_target("x86_64-unknown-freebsd").unwrap();
+        assert_eq!(expected.platform, "");
+        let config = crate::fixtures::get("cpython311-freebsd").unwrap().config();
+        assert!(config.validate_target("x86_64-unknown-freebsd").is_ok());
+        assert!(config.validate_target("x86_64-unknown-netbsd").is_err());
+        assert!(config.validate_target("i686-unknown-freebsd").is_err());
+        let config = crate::fixtures::get("cpython311-linux").unwrap().config();
+        assert!(config.validate_target("x86_64-unknown-freebsd").is_err());
+
+        let expected = expected_for_target("x86_64-pc-cygwin").unwrap();
+        assert_eq!(expected.platform, "x86_64-cygwin");
+        let config = crate::fixtures::get("cpython39-cygwin").unwrap().config();
+        assert!(config.validate_target("x86_64-pc-cygwin").is_ok());
+
+        let expected = expected_for_target("x86_64-pc-windows-gnu").unwrap();
+        assert_eq!(expected.platform, "mingw_x86_64");
+        let config = crate::fixtures::get("cpython311-mingw").unwrap().config();
+        assert!(config.validate_target("x86_64-pc-windows-gnu").is_ok());
+        assert!(config.validate_target("x86_64-pc-windows-msvc").is_err());
+
+        assert!(matches!(
+            expected_for_target("foo"),
+            Err(Error::UnknownTarget(_))
+        ));
+    }
+
+    #[test]
+    fn validate_target() {
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        config.validate_target("aarch64-apple-darwin").unwrap();
+        let err = config
+            .validate_target("x86_64-unknown-linux-gnu")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "sysconfigdata does not match target x86_64-unknown-linux-gnu: \
+             platform is darwin, expected x86_64-linux-gnu"
+        );
+        let err = config.validate_target("i686-apple-darwin").unwrap_err();
+        assert!(matches!(err, Error::TargetMismatch { .. }));
+    }
+
+    #[test]
+    fn assert_matches_cargo_target() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        let cargo_env = |width: &'static str, os: &'static str, arch: &'static str| {
+            move |name: &str| match name {
+                "CARGO_CFG_TARGET_POINTER_WIDTH" => Some(width.to_string()),
+                "CARGO_CFG_TARGET_OS" => Some(os.to_string()),
+                "CARGO_CFG_TARGET_ARCH" => Some(arch.to_string()),
+                _ => None,
+            }
+        };
+        config
+            .assert_matches_cargo_target_from(cargo_env("64", "linux", "x86_64"))
+            .unwrap();
+        let err = config
+            .assert_matches_cargo_target_from(cargo_env("32", "linux", "x86"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "sysconfigdata does not match target x86-linux: \
+             pointer width is 64 bits, target has 32 bits, \
+             architecture is x86_64, target has x86"
+        );
+
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        config
+            .assert_matches_cargo_target_from(cargo_env("64", "macos", "aarch64"))
+            .unwrap();
+        let err = config
+            .assert_matches_cargo_target_from(cargo_env("64", "windows", "x86_64"))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("operating system is macos (sys.platform darwin), target has windows"));
+
+        // x32 has 4 byte pointers on x86_64
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'MULTIARCH': 'x86_64-linux-gnux32', \
+             'SOABI': 'cpython-312-x86_64-linux-gnux32'}",
+        )
+        .unwrap();
+        assert!(config.is_x32());
+        config
+            .assert_matches_cargo_target_from(cargo_env("32", "linux", "x86_64"))
+            .unwrap();
+    }
+
+    #[test]
+    fn target_suffix_defaults() {
+        let vars = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), ConfigValue::String((*value).into())))
+                .collect::<Vec<_>>()
+        };
+        let cases = [
+            (
+                "x86_64-unknown-linux-gnu",
+                ".so",
+                ".cpython-312-x86_64-linux-gnu.so",
+            ),
+            ("x86_64-pc-windows-msvc", ".pyd", ".cp312-win_amd64.pyd"),
+            ("x86_64-pc-windows-gnu", ".pyd", ".cp312-mingw_x86_64.pyd"),
+            ("x86_64-pc-cygwin", ".dll", ".cpython-312-x86_64-cygwin.dll"),
+            (
+                "wasm32-unknown-emscripten",
+                ".so",
+                ".cpython-312-wasm32-emscripten.so",
+            ),
+            ("x86_64-unknown-freebsd", ".so", ".cpython-312.so"),
+        ];
+        for (triple, shlib_suffix, ext_suffix) in cases {
+            let config =
+                PythonConfig::from_vars_for_target(triple, vars(&[("VERSION", "3.12")])).unwrap();
+            assert_eq!(config.shlib_suffix(), shlib_suffix, "{}", triple);
+            assert_eq!(config.ext_suffix(), ext_suffix, "{}", triple);
+            config.validate_target(triple).unwrap();
+        }
+
+        let config = PythonConfig::from_vars_for_target(
+            "x86_64-pc-windows-msvc",
+            vars(&[("VERSION", "3.13"), ("ABIFLAGS", "t")]),
+        )
+        .unwrap();
+        assert_eq!(config.ext_suffix(), ".cp313t-win_amd64.pyd");
+        assert_eq!(config.abi_tag(), "cp313t");
+
+        let config = PythonConfig::from_vars_for_target(
+            "x86_64-unknown-linux-gnu",
+            vars(&[
+                ("VERSION", "3.10"),
+                ("SOABI", "pypy310-pp73-x86_64-linux-gnu"),
+                ("EXT_SUFFIX", ".custom.so"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.ext_suffix(), ".custom.so");
+        let config = PythonConfig::from_vars_for_target(
+            "x86_64-unknown-linux-gnu",
+            vars(&[
+                ("VERSION", "3.10"),
+                ("SOABI", "pypy310-pp73-x86_64-linux-gnu"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.ext_suffix(), ".pypy310-pp73-x86_64-linux-gnu.so");
+
+        assert!(
+            PythonConfig::from_vars_for_target("nonsense", vars(&[("VERSION", "3.12")])).is_err()
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeSet;
+
+use crate::convert::shell_split;
+use crate::PythonConfig;
+
+/// Keys holding link flags of the interpreter and its extension modules,
+/// besides `*_LIBS` and `MODULE_*_LDFLAGS`
+const LINK_KEYS: &[&str] = &["LIBS", "SHLIBS", "MODLIBS", "LOCALMODLIBS", "BASEMODLIBS"];
+
+/// System libraries a distribution links, see [`PythonConfig::third_party_libs`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThirdPartyLibs {
+    /// Names of all `-l` libraries of the interpreter and extension modules
+    pub libs: BTreeSet<String>,
+    /// OpenSSL or LibreSSL, for `_ssl` and `_hashlib`
+    pub openssl: bool,
+    /// Version hint taken from the OpenSSL paths, like `openssl@3` or
+    /// `libressl-2.8`
+    pub openssl_version: Option<String>,
+    /// libffi, for `_ctypes`
+    pub libffi: bool,
+    /// Line editing library of the `readline` module, `readline` or `edit`
+    pub readline: Option<String>,
+    /// zlib
+    pub zlib: bool,
+    /// libbz2
+    pub bzip2: bool,
+    /// liblzma
+    pub lzma: bool,
+    /// SQLite
+    pub sqlite: bool,
+}
+
+impl PythonConfig {
+    /// Returns the system libraries this distribution links, derived from
+    /// `CONFIG_ARGS`, `MODLIBS` and the other link flags and `HAVE_*` keys.
+    ///
+    /// Extension modules built by `setup.py` don't show in the link flags,
+    /// when none are found `HAVE_LIBREADLINE` tells about readline.
+    pub fn third_party_libs(&self) -> ThirdPartyLibs {
+        let vars = &self.sys_config_data.build_time_vars;
+        let config_args = shell_split(vars.get_str("CONFIG_ARGS"));
+        let with = |option: &str| {
+            config_args
+                .iter()
+                .find_map(|arg| arg.strip_prefix(option))
+                .map(|value| value.strip_prefix('=').unwrap_or(value))
+        };
+
+        let mut libs = BTreeSet::new();
+        let link_keys = vars.raw.keys().filter(|key| {
+            LINK_KEYS.contains(&&***key)
+                || key.ends_with("_LIBS")
+                || (key.starts_with("MODULE_") && key.ends_with("_LDFLAGS"))
+        });
+        for key in link_keys {
+            for word in vars.get_str(key).split_whitespace() {
+                if let Some(lib) = word.strip_prefix("-l").filter(|x| !x.is_empty()) {
+                    libs.insert(lib.to_string());
+                }
+            }
+        }
+        let modules_found = !vars.get_str("MODLIBS").trim().is_empty()
+            || vars.raw.keys().any(|key| {
+                key.starts_with("MODULE_") && (key.ends_with("_LDFLAGS") || key.ends_with("_STATE"))
+            });
+        let has = |lib: &str| libs.contains(lib);
+
+        let readline = if has("edit") || with("--with-readline") == Some("editline") {
+            Some("edit")
+        } else if has("readline")
+            || (!modules_found
+                && vars.get_int("HAVE_LIBREADLINE") == Some(1)
+                && with("--without-readline").is_none())
+        {
+            Some("readline")
+        } else {
+            None
+        };
+        let openssl_version = ["OPENSSL_INCLUDES", "OPENSSL_LDFLAGS", "OPENSSL_RPATH"]
+            .iter()
+            .map(|key| vars.get_str(key))
+            .chain(with("--with-openssl"))
+            .find_map(openssl_version_hint);
+        ThirdPartyLibs {
+            openssl: has("ssl") || has("crypto") || with("--with-openssl").is_some(),
+            openssl_version,
+            libffi: has("ffi")
+                || with("--with-system-ffi").is_some()
+                || !vars.get_str("LIBFFI_INCLUDEDIR").is_empty(),
+            readline: readline.map(str::to_string),
+            zlib: has("z"),
+            bzip2: has("bz2"),
+            lzma: has("lzma"),
+            sqlite: has("sqlite3"),
+            libs,
+        }
+    }
+}
+
+/// `openssl-1.1`, `openssl@3` or `libressl-2.8` found in the paths of `s`
+fn openssl_version_hint(s: &str) -> Option<String> {
+    for name in ["openssl", "libressl"] {
+        for (start, _) in s.match_indices(name) {
+            let rest = &s[start + name.len()..];
+            let separator = rest.starts_with(['-', '@']) as usize;
+            let version_len = rest[separator..]
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len() - separator);
+            let version = rest[separator..separator + version_len].trim_end_matches('.');
+            if version.starts_with(|c: char| c.is_ascii_digit()) {
+                return Some(s[start..start + name.len() + separator + version.len()].to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::openssl_version_hint;
+    use crate::{fixtures, PythonConfig};
+
+    #[test]
+    fn third_party_libs() {
+        let libs = fixtures::get("cpython311-linux")
+            .unwrap()
+            .config()
+            .third_party_libs();
+        assert!(libs.openssl && libs.libffi && libs.zlib && libs.bzip2 && libs.lzma);
+        assert_eq!(libs.readline.as_deref(), Some("readline"));
+        assert_eq!(libs.openssl_version, None);
+        assert!(libs.libs.contains("ncursesw"));
+
+        let libs = fixtures::get("cpython38-darwin")
+            .unwrap()
+            .config()
+            .third_party_libs();
+        assert!(libs.openssl && !libs.zlib);
+        assert_eq!(libs.openssl_version.as_deref(), Some("libressl-2.8"));
+        assert_eq!(libs.readline.as_deref(), Some("readline"));
+
+        let libs = fixtures::get("cpython312-pyodide")
+            .unwrap()
+            .config()
+            .third_party_libs();
+        assert!(!libs.openssl && libs.libffi && libs.zlib);
+        assert_eq!(libs.readline, None);
+
+        let libs = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', \
+             'CONFIG_ARGS': \"'--with-openssl=/opt/homebrew/opt/openssl@3' '--with-readline=editline'\"}",
+        )
+        .unwrap()
+        .third_party_libs();
+        assert!(libs.openssl);
+        assert_eq!(libs.openssl_version.as_deref(), Some("openssl@3"));
+        assert_eq!(libs.readline.as_deref(), Some("edit"));
+    }
+
+    #[test]
+    fn openssl_version() {
+        assert_eq!(
+            openssl_version_hint("-I/usr/local/openssl-1.1.1w/include").as_deref(),
+            Some("openssl-1.1.1")
+        );
+        assert_eq!(openssl_version_hint("/usr/include/openssl"), None);
+    }
+}
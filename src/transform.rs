@@ -0,0 +1,599 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use crate::optimization::is_debug_info_flag;
+use crate::{ConfigValue, Error, OptimizationLevel, PythonConfig, PythonVersion, Source};
+
+/// A reusable modification of a [`PythonConfig`], see [`PythonConfig::apply`]
+pub trait Transform {
+    /// Modifies `config` in place
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error>;
+
+    /// Name recorded as [`Source::Transform`] for the keys this transform
+    /// sets, the type name by default
+    fn name(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name).to_string()
+    }
+}
+
+impl PythonConfig {
+    /// Returns a copy of this configuration with `transforms` applied in order
+    pub fn apply(&self, transforms: &[Box<dyn Transform>]) -> Result<Self, Error> {
+        let mut config = self.clone();
+        for transform in transforms {
+            config.with_source(Source::Transform(transform.name()), |config| {
+                transform.transform(config)
+            })?;
+        }
+        Ok(config)
+    }
+}
+
+/// Replaces the installation prefix `from` with `to` in every string value,
+/// for example to point a config copied out of a sysroot at its new location.
+/// Only whole paths are replaced, `/usr` matches neither `/usrlocal` nor
+/// `/opt/usr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relocate {
+    from: String,
+    to: String,
+}
+
+impl Relocate {
+    /// Relocate paths under `from` to `to`
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        let trim = |path: String| {
+            if path.len() > 1 {
+                path.trim_end_matches('/').to_string()
+            } else {
+                path
+            }
+        };
+        Self {
+            from: trim(from.into()),
+            to: trim(to.into()),
+        }
+    }
+
+    fn relocate(&self, value: &str) -> Option<String> {
+        let mut out = String::new();
+        let mut last = 0;
+        for (start, _) in value.match_indices(&self.from) {
+            let end = start + self.from.len();
+            if start < last
+                || !is_path_start(&value[..start])
+                || !is_path_end(value[end..].chars().next())
+            {
+                continue;
+            }
+            out.push_str(&value[last..start]);
+            out.push_str(&self.to);
+            last = end;
+        }
+        if last == 0 {
+            return None;
+        }
+        out.push_str(&value[last..]);
+        Some(out)
+    }
+}
+
+/// Whether a path can start after `before`: at the start of the value,
+/// after whitespace or a separator as in `PATH=/usr:/opt` or
+/// `-Wl,-rpath,/usr`, or after a flag like `-I` or `-L`
+fn is_path_start(before: &str) -> bool {
+    let mut chars = before.chars().rev();
+    match chars.next() {
+        None => true,
+        Some(c) if c.is_whitespace() || matches!(c, '=' | ':' | ',' | '"' | '\'') => true,
+        Some(c) if c.is_ascii_alphabetic() && chars.next() == Some('-') => {
+            is_path_start(&before[..before.len() - 2])
+        }
+        _ => false,
+    }
+}
+
+fn is_path_end(next: Option<char>) -> bool {
+    match next {
+        None => true,
+        Some(c) => !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.'),
+    }
+}
+
+impl Transform for Relocate {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        if self.from.is_empty() {
+            return Ok(());
+        }
+        let updates: Vec<(String, String)> = config
+            .vars()
+            .filter_map(|(key, value)| {
+                let value = self.relocate(value.as_str()?)?;
+                Some((key.to_string(), value))
+            })
+            .collect();
+        for (key, value) in updates {
+            config.set(&key, ConfigValue::String(value.into()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces user names found in home directories, such as `/home/alice` or
+/// `C:\Users\alice`, with `user` in every path component of every string
+/// value, so configurations can be attached to bug reports without leaking
+/// details of the build machine
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Anonymize {}
+
+/// Directories holding home directories
+const HOME_PARENTS: &[&str] = &["/home/", "/Users/", "\\Users\\"];
+
+/// Replacement of user names
+const PLACEHOLDER: &str = "user";
+
+impl Anonymize {
+    /// Anonymize home directories
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// User names of home directories appearing in `value`
+    fn user_names(value: &str) -> Vec<&str> {
+        let mut names = Vec::new();
+        for parent in HOME_PARENTS {
+            for (start, _) in value.match_indices(parent) {
+                let rest = &value[start + parent.len()..];
+                let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+                let name = &rest[..end];
+                if !name.is_empty() && name != "Shared" && name != PLACEHOLDER {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Replaces path components equal to one of `names`
+    fn anonymize(value: &str, names: &[String]) -> Option<String> {
+        let mut out = String::with_capacity(value.len());
+        let mut changed = false;
+        let mut rest = value;
+        while !rest.is_empty() {
+            let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+            let (word, after) = rest.split_at(end);
+            let component = out.ends_with(['/', '\\']);
+            if component && names.iter().any(|name| name == word) {
+                out.push_str(PLACEHOLDER);
+                changed = true;
+            } else {
+                out.push_str(word);
+            }
+            let mut chars = after.chars();
+            out.extend(chars.next());
+            rest = chars.as_str();
+        }
+        Some(out).filter(|_| changed)
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+impl Transform for Anonymize {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        let mut names: Vec<String> = config
+            .vars()
+            .filter_map(|(_, value)| value.as_str())
+            .flat_map(Self::user_names)
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        names.dedup();
+        if names.is_empty() {
+            return Ok(());
+        }
+        let updates: Vec<(String, String)> = config
+            .vars()
+            .filter_map(|(key, value)| {
+                let value = Self::anonymize(value.as_str()?, &names)?;
+                Some((key.to_string(), value))
+            })
+            .collect();
+        for (key, value) in updates {
+            config.set(&key, ConfigValue::String(value.into()))?;
+        }
+        Ok(())
+    }
+}
+
+impl PythonConfig {
+    /// Returns a copy of this configuration with user names replaced, see
+    /// [`Anonymize`]
+    pub fn anonymize(&self) -> Result<Self, Error> {
+        self.apply(&[Box::new(Anonymize::new())])
+    }
+}
+
+/// Keys holding compiler or linker command lines
+pub(crate) const FLAG_KEYS: &[&str] = &[
+    "BASECFLAGS",
+    "BLDSHARED",
+    "CCSHARED",
+    "CFLAGS",
+    "CFLAGSFORSHARED",
+    "CFLAGS_NODIST",
+    "CONFIGURE_CFLAGS",
+    "CONFIGURE_CFLAGS_NODIST",
+    "CONFIGURE_CPPFLAGS",
+    "CONFIGURE_LDFLAGS",
+    "CONFIGURE_LDFLAGS_NODIST",
+    "CPPFLAGS",
+    "EXTRA_CFLAGS",
+    "EXTRA_LDFLAGS",
+    "LDCXXSHARED",
+    "LDFLAGS",
+    "LDFLAGS_NODIST",
+    "LDSHARED",
+    "LINKFORSHARED",
+    "OPT",
+    "PY_CFLAGS",
+    "PY_CFLAGS_NODIST",
+    "PY_CORE_CFLAGS",
+    "PY_CORE_LDFLAGS",
+    "PY_CPPFLAGS",
+    "PY_LDFLAGS",
+    "PY_LDFLAGS_NODIST",
+    "PY_STDMODULE_CFLAGS",
+];
+
+/// Removes flags that only make sense on the build machine of the
+/// interpreter from compiler and linker command lines, such as
+/// `-march=native`, profile guided optimization and LTO flags
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizeFlags {
+    patterns: Vec<String>,
+}
+
+impl Default for SanitizeFlags {
+    fn default() -> Self {
+        Self::new([
+            "-march=native",
+            "-mtune=native",
+            "-mcpu=native",
+            "-fprofile-use*",
+            "-fprofile-instr-use*",
+            "-fprofile-generate*",
+            "-fprofile-correction",
+            "-fprofile-dir=*",
+            "-flto*",
+            "-ffat-lto-objects",
+            "-fuse-linker-plugin",
+            "-fdebug-prefix-map=*",
+            "-ffile-prefix-map=*",
+            "-fmacro-prefix-map=*",
+        ])
+    }
+}
+
+impl SanitizeFlags {
+    /// Remove flags matching `patterns`, a trailing `*` matches any suffix
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub(crate) fn matches(&self, flag: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => flag.starts_with(prefix),
+                None => flag == pattern,
+            })
+    }
+}
+
+impl Transform for SanitizeFlags {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        for key in FLAG_KEYS {
+            let value = match config.get(key).and_then(ConfigValue::as_str) {
+                Some(value) => value,
+                None => continue,
+            };
+            let flags: Vec<&str> = value.split_whitespace().collect();
+            let kept: Vec<&str> = flags
+                .iter()
+                .copied()
+                .filter(|flag| !self.matches(flag))
+                .collect();
+            if kept.len() != flags.len() {
+                let value = kept.join(" ");
+                config.set(key, ConfigValue::String(value.into()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the `-O` flags of compiler command lines with another level,
+/// for example to rebuild C extensions at `-O0` for debugging. Keys without
+/// a `-O` flag are left alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetOptimization {
+    level: OptimizationLevel,
+    debug_info: bool,
+}
+
+impl SetOptimization {
+    /// Compile at `level`
+    pub fn new(level: OptimizationLevel) -> Self {
+        Self {
+            level,
+            debug_info: false,
+        }
+    }
+
+    /// Also ensure `-g` next to the new level, replacing `-g0`
+    pub fn debug_info(mut self, debug_info: bool) -> Self {
+        self.debug_info = debug_info;
+        self
+    }
+}
+
+impl Transform for SetOptimization {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        for key in FLAG_KEYS {
+            let value = match config.get(key).and_then(ConfigValue::as_str) {
+                Some(value) => value,
+                None => continue,
+            };
+            let flags: Vec<&str> = value.split_whitespace().collect();
+            if !flags
+                .iter()
+                .any(|flag| OptimizationLevel::from_flag(flag).is_some())
+            {
+                continue;
+            }
+            let has_debug_info = flags
+                .iter()
+                .any(|flag| is_debug_info_flag(flag) && *flag != "-g0");
+            let mut kept = Vec::new();
+            for flag in flags {
+                if OptimizationLevel::from_flag(flag).is_some() {
+                    if !kept.contains(&self.level.flag()) {
+                        kept.push(self.level.flag());
+                        if self.debug_info && !has_debug_info {
+                            kept.push("-g");
+                        }
+                    }
+                } else if !(self.debug_info && flag == "-g0") {
+                    kept.push(flag);
+                }
+            }
+            let value = kept.join(" ");
+            config.set(key, ConfigValue::String(value.into()))?;
+        }
+        Ok(())
+    }
+}
+
+impl PythonConfig {
+    /// Returns a copy of this configuration compiling at `level`, see
+    /// [`SetOptimization`]
+    pub fn with_optimization(&self, level: OptimizationLevel) -> Result<Self, Error> {
+        self.apply(&[Box::new(SetOptimization::new(level))])
+    }
+}
+
+/// Turns a CPython configuration into one for building stable ABI (`abi3`)
+/// extension modules: `EXT_SUFFIX` becomes `.abi3.so` and `Py_LIMITED_API`
+/// is defined in `CFLAGS`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToAbi3 {
+    minimum: Option<(u32, u32)>,
+}
+
+impl ToAbi3 {
+    /// Target the stable ABI of the configuration's own Python version
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Target the stable ABI of an older Python version
+    pub fn minimum(major: u32, minor: u32) -> Self {
+        Self {
+            minimum: Some((major, minor)),
+        }
+    }
+}
+
+impl Transform for ToAbi3 {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        let (major, minor) = self
+            .minimum
+            .unwrap_or((config.major_minor().0, config.major_minor().1));
+        let limited_api = config.limited_api_hex(PythonVersion::new(major, minor))?;
+        let ext_suffix = if config.ext_suffix().ends_with(".pyd") {
+            ".pyd".to_string()
+        } else {
+            let shlib_suffix = match config.shlib_suffix() {
+                "" => ".so",
+                suffix => suffix,
+            };
+            format!(".abi3{}", shlib_suffix)
+        };
+        let cflags = format!("{} -DPy_LIMITED_API={}", config.cflags(), limited_api);
+        config.set("EXT_SUFFIX", ConfigValue::String(ext_suffix.into()))?;
+        config.set("CFLAGS", ConfigValue::String(cflags.trim_start().into()))?;
+        Ok(())
+    }
+}
+
+/// Sets keys of `build_time_vars` to fixed values
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyOverrides {
+    overrides: BTreeMap<String, ConfigValue>,
+}
+
+impl ApplyOverrides {
+    /// No overrides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override `key` with `value`
+    pub fn set(mut self, key: impl Into<String>, value: ConfigValue) -> Self {
+        self.overrides.insert(key.into(), value);
+        self
+    }
+}
+
+impl FromIterator<(String, ConfigValue)> for ApplyOverrides {
+    fn from_iter<I: IntoIterator<Item = (String, ConfigValue)>>(iter: I) -> Self {
+        Self {
+            overrides: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Transform for ApplyOverrides {
+    fn transform(&self, config: &mut PythonConfig) -> Result<(), Error> {
+        for (key, value) in &self.overrides {
+            config.set(key, value.clone())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Anonymize, ApplyOverrides, Relocate, SanitizeFlags, SetOptimization, ToAbi3, Transform,
+    };
+    use crate::{ConfigValue, Error, OptimizationLevel, PythonConfig};
+    use std::fs;
+
+    fn linux_config() -> PythonConfig {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        PythonConfig::parse(&src).unwrap()
+    }
+
+    #[test]
+    fn apply_transforms() {
+        let transforms: Vec<Box<dyn Transform>> = vec![
+            Box::new(Relocate::new("/usr/local", "/opt/sysroot/usr/local")),
+            Box::new(SanitizeFlags::default()),
+            Box::new(ToAbi3::minimum(3, 8)),
+            Box::new(ApplyOverrides::new().set("CC", ConfigValue::String("clang".into()))),
+        ];
+        let config = linux_config().apply(&transforms).unwrap();
+        assert_eq!(config.lib_dir_str(), "/opt/sysroot/usr/local/lib");
+        assert_eq!(
+            config.config_dir_str(),
+            "/opt/sysroot/usr/local/lib/python3.11/config-3.11-x86_64-linux-gnu"
+        );
+        assert!(!config
+            .get("PY_CFLAGS_NODIST")
+            .and_then(ConfigValue::as_str)
+            .unwrap()
+            .contains("-flto"));
+        assert_eq!(config.ext_suffix(), ".abi3.so");
+        assert_eq!(config.abi_tag(), "abi3");
+        assert!(config.cflags().ends_with(" -DPy_LIMITED_API=0x03080000"));
+        assert_eq!(config.cc(), "clang");
+    }
+
+    #[test]
+    fn relocate_whole_components() {
+        let relocate = Relocate::new("/usr/", "/sysroot/usr");
+        assert_eq!(
+            relocate
+                .relocate("-L/usr/lib -I/usrlocal/include -L/usr")
+                .as_deref(),
+            Some("-L/sysroot/usr/lib -I/usrlocal/include -L/sysroot/usr")
+        );
+        assert_eq!(relocate.relocate("/opt/lib"), None);
+        assert_eq!(relocate.relocate("/opt/usr/lib -Ix/usr/include"), None);
+        assert_eq!(
+            relocate
+                .relocate("/usr/bin:/opt/usr/bin -Wl,-rpath,/usr/lib --prefix=/usr")
+                .as_deref(),
+            Some("/sysroot/usr/bin:/opt/usr/bin -Wl,-rpath,/sysroot/usr/lib --prefix=/sysroot/usr")
+        );
+    }
+
+    #[test]
+    fn anonymize() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'prefix': '/home/alice/.pyenv/versions/3.12', \
+             'CFLAGS': '-I/tmp/alice/include -ffile-prefix-map=/home/alice/src=. -DALICE', \
+             'LIBDIR': 'C:\\\\Users\\\\bob\\\\lib', 'SIZEOF_VOID_P': 8}",
+        )
+        .unwrap()
+        .anonymize()
+        .unwrap();
+        assert_eq!(config.prefix_str(), "/home/user/.pyenv/versions/3.12");
+        assert_eq!(
+            config.cflags(),
+            "-I/tmp/user/include -ffile-prefix-map=/home/user/src=. -DALICE"
+        );
+        assert_eq!(config.lib_dir_str(), "C:\\Users\\user\\lib");
+        assert_eq!(config.pointer_size(), 8);
+
+        let config = linux_config();
+        let transforms: Vec<Box<dyn Transform>> = vec![Box::new(Anonymize::new())];
+        assert!(config.apply(&transforms).unwrap().vars().eq(config.vars()));
+    }
+
+    #[test]
+    fn abi3_unsupported() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'ABIFLAGS': 't', \
+             'EXT_SUFFIX': '.cpython-313t-x86_64-linux-gnu.so'}",
+        )
+        .unwrap();
+        let err = config.apply(&[Box::new(ToAbi3::new())]).unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn set_optimization() {
+        let config = linux_config()
+            .with_optimization(OptimizationLevel::O0)
+            .unwrap();
+        assert_eq!(
+            config.cflags(),
+            "-Wsign-compare -DNDEBUG -g -fwrapv -O0 -Wall"
+        );
+        assert_eq!(
+            config.optimization_flags().level,
+            Some(OptimizationLevel::O0)
+        );
+        assert_eq!(
+            config.get("OPT").and_then(ConfigValue::as_str),
+            Some("-DNDEBUG -g -fwrapv -O0 -Wall")
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'CFLAGS': '-O2 -g0 -pipe -O3', \
+             'LDFLAGS': '-Wl,-O1'}",
+        )
+        .unwrap()
+        .apply(&[Box::new(
+            SetOptimization::new(OptimizationLevel::Og).debug_info(true),
+        )])
+        .unwrap();
+        assert_eq!(config.cflags(), "-Og -g -pipe");
+        assert_eq!(config.ldflags(), "-Wl,-O1");
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::transform::FLAG_KEYS;
+use crate::{ConfigValue, PythonConfig};
+
+/// How much a changed key matters to builds, see [`upgrade_report`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Impact {
+    /// Extension modules built against the old configuration don't load,
+    /// such as a new `SOABI` or pointer size
+    AbiBreaking,
+    /// Headers, libraries or the interpreter moved, search paths and
+    /// sysroot layouts need updating
+    PathLayout,
+    /// Compiler and linker commands or flags changed, builds still produce
+    /// compatible artifacts
+    FlagsOnly,
+    /// Informational keys, like the configure arguments or the build date
+    Cosmetic,
+}
+
+impl Impact {
+    /// Every impact, most severe first
+    pub const ALL: [Impact; 4] = [
+        Impact::AbiBreaking,
+        Impact::PathLayout,
+        Impact::FlagsOnly,
+        Impact::Cosmetic,
+    ];
+
+    /// Impact of a change of the `build_time_vars` key `key`
+    pub fn of_key(key: &str) -> Impact {
+        if ABI_KEYS.contains(&key) || key.starts_with("SIZEOF_") || key.starts_with("ALIGNOF_") {
+            Impact::AbiBreaking
+        } else if PATH_KEYS.contains(&key)
+            || key.ends_with("DIR")
+            || key.ends_with("DEST")
+            || key.ends_with("prefix")
+        {
+            Impact::PathLayout
+        } else if FLAG_KEYS.contains(&key)
+            || COMMAND_KEYS.contains(&key)
+            || key.ends_with("FLAGS")
+            || key.ends_with("LIBS")
+        {
+            Impact::FlagsOnly
+        } else {
+            Impact::Cosmetic
+        }
+    }
+}
+
+impl fmt::Display for Impact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Impact::AbiBreaking => "ABI-breaking",
+            Impact::PathLayout => "path layout",
+            Impact::FlagsOnly => "flags only",
+            Impact::Cosmetic => "cosmetic",
+        })
+    }
+}
+
+/// Keys deciding which extension modules load
+const ABI_KEYS: &[&str] = &[
+    "ABIFLAGS",
+    "EXT_SUFFIX",
+    "HOST_GNU_TYPE",
+    "LDVERSION",
+    "MACHDEP",
+    "MULTIARCH",
+    "Py_DEBUG",
+    "Py_ENABLE_SHARED",
+    "Py_GIL_DISABLED",
+    "Py_REF_DEBUG",
+    "Py_TRACE_REFS",
+    "SOABI",
+    "VERSION",
+    "WITH_PYMALLOC",
+];
+
+/// Directories and files of the installation not caught by their suffix
+const PATH_KEYS: &[&str] = &[
+    "CONFINCLUDEPY",
+    "INCLUDEPY",
+    "LDLIBRARY",
+    "LIBPC",
+    "LIBPL",
+    "LIBRARY",
+    "LIBRARY_DEPS",
+    "SCRIPTDIR",
+];
+
+/// Tool commands, changes of which don't change the ABI
+const COMMAND_KEYS: &[&str] = &[
+    "AR",
+    "CC",
+    "CXX",
+    "LDCXXSHARED",
+    "LDSHARED",
+    "LINKCC",
+    "RANLIB",
+];
+
+/// A key that differs between two configurations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChange {
+    /// The key, `SOABI`
+    pub key: String,
+    /// Value before the upgrade, `None` if added
+    pub old: Option<ConfigValue>,
+    /// Value after the upgrade, `None` if removed
+    pub new: Option<ConfigValue>,
+    /// How much the change matters
+    pub impact: Impact,
+}
+
+/// `KEY: 'old' -> 'new'`, with `(unset)` for a missing side
+impl fmt::Display for KeyChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = |value: &Option<ConfigValue>| match value {
+            Some(value) => format!("'{}'", value),
+            None => "(unset)".to_string(),
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.key,
+            side(&self.old),
+            side(&self.new)
+        )
+    }
+}
+
+/// Differences between a configuration and its upgrade grouped by
+/// [`Impact`], see [`upgrade_report`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpgradeReport {
+    /// Changed keys, most severe first then by key
+    pub changes: Vec<KeyChange>,
+}
+
+impl UpgradeReport {
+    /// The changes of impact `impact`
+    pub fn group(&self, impact: Impact) -> impl Iterator<Item = &KeyChange> {
+        self.changes
+            .iter()
+            .filter(move |change| change.impact == impact)
+    }
+
+    /// Whether extension modules built for the old configuration load with
+    /// the new one
+    pub fn is_abi_compatible(&self) -> bool {
+        self.group(Impact::AbiBreaking).next().is_none()
+    }
+
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A summary line per group with its changed keys below, empty groups are
+/// left out and cosmetic changes are only counted
+impl fmt::Display for UpgradeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for impact in Impact::ALL {
+            let changes: Vec<&KeyChange> = self.group(impact).collect();
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(f, "{}: {} changed", impact, changes.len())?;
+            if impact != Impact::Cosmetic {
+                for change in changes {
+                    writeln!(f, "  {}", change)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compares the `build_time_vars` of `old` and `new`, for example the
+/// Python 3.11 and 3.12 configurations of a sysroot, and groups the keys
+/// that differ by [`Impact`] so the few that need action stand out from
+/// the hundreds that change with every release
+pub fn upgrade_report(old: &PythonConfig, new: &PythonConfig) -> UpgradeReport {
+    let old: BTreeMap<&str, &ConfigValue> = old.vars().collect();
+    let new: BTreeMap<&str, &ConfigValue> = new.vars().collect();
+    let keys: BTreeSet<&str> = old.keys().chain(new.keys()).copied().collect();
+    let mut changes: Vec<KeyChange> = keys
+        .into_iter()
+        .filter_map(|key| {
+            let (old, new) = (old.get(key).copied(), new.get(key).copied());
+            if old == new {
+                return None;
+            }
+            Some(KeyChange {
+                key: key.to_string(),
+                old: old.cloned(),
+                new: new.cloned(),
+                impact: Impact::of_key(key),
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| (a.impact, &a.key).cmp(&(b.impact, &b.key)));
+    UpgradeReport { changes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{upgrade_report, Impact};
+    use crate::{fixtures, ConfigValue, PythonConfig};
+
+    #[test]
+    fn upgrade_groups() {
+        let old = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.11', 'SOABI': 'cpython-311-x86_64-linux-gnu', \
+             'INCLUDEPY': '/usr/include/python3.11', 'CFLAGS': '-O2', \
+             'CONFIG_ARGS': \"'--enable-shared'\", 'HAVE_FORK': 1}",
+        )
+        .unwrap();
+        let new = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'SOABI': 'cpython-312-x86_64-linux-gnu', \
+             'INCLUDEPY': '/usr/include/python3.12', 'CFLAGS': '-O3', \
+             'CONFIG_ARGS': \"'--enable-shared' '--with-lto'\", 'HAVE_FORK': 1}",
+        )
+        .unwrap();
+        let report = upgrade_report(&old, &new);
+        let keys: Vec<(Impact, &str)> = report
+            .changes
+            .iter()
+            .map(|change| (change.impact, change.key.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (Impact::AbiBreaking, "SOABI"),
+                (Impact::AbiBreaking, "VERSION"),
+                (Impact::PathLayout, "INCLUDEPY"),
+                (Impact::FlagsOnly, "CFLAGS"),
+                (Impact::Cosmetic, "CONFIG_ARGS"),
+            ]
+        );
+        assert!(!report.is_abi_compatible());
+        let summary = report.to_string();
+        assert!(summary.starts_with("ABI-breaking: 2 changed\n  SOABI: "));
+        assert!(summary.contains("  CFLAGS: '-O2' -> '-O3'\n"));
+        assert!(summary.ends_with("cosmetic: 1 changed\n"));
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let report = upgrade_report(&config, &config);
+        assert!(report.is_empty() && report.is_abi_compatible());
+        assert_eq!(report.to_string(), "no changes\n");
+
+        let mut new = config.clone();
+        new.set("LIBDIR", ConfigValue::String("/opt/lib".into()))
+            .unwrap();
+        let report = upgrade_report(&config, &new);
+        assert!(report.is_abi_compatible());
+        assert_eq!(report.changes[0].impact, Impact::PathLayout);
+    }
+
+    #[test]
+    fn key_impacts() {
+        assert_eq!(Impact::of_key("SIZEOF_VOID_P"), Impact::AbiBreaking);
+        assert_eq!(Impact::of_key("BINLIBDEST"), Impact::PathLayout);
+        assert_eq!(Impact::of_key("exec_prefix"), Impact::PathLayout);
+        assert_eq!(Impact::of_key("PY_LDFLAGS_NODIST"), Impact::FlagsOnly);
+        assert_eq!(Impact::of_key("SYSLIBS"), Impact::FlagsOnly);
+        assert_eq!(Impact::of_key("HAVE_FORK"), Impact::Cosmetic);
+    }
+}
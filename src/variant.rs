@@ -0,0 +1,77 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, PythonConfig};
+
+/// Build variant of a distribution, sysroots may ship several side by side,
+/// for example `_sysconfigdata__linux_x86_64-linux-gnu.py` and
+/// `_sysconfigdata_d_linux_x86_64-linux-gnu.py`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AbiVariant {
+    /// Optimized build, no ABI flags
+    Default,
+    /// `Py_DEBUG` build, `d` ABI flag
+    Debug,
+    /// Free-threaded build, `t` ABI flag
+    FreeThreaded,
+    /// Free-threaded `Py_DEBUG` build, `td` ABI flags
+    FreeThreadedDebug,
+}
+
+impl fmt::Display for AbiVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiVariant::Default => write!(f, "default"),
+            AbiVariant::Debug => write!(f, "debug"),
+            AbiVariant::FreeThreaded => write!(f, "free-threaded"),
+            AbiVariant::FreeThreadedDebug => write!(f, "free-threaded-debug"),
+        }
+    }
+}
+
+impl FromStr for AbiVariant {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(AbiVariant::Default),
+            "debug" => Ok(AbiVariant::Debug),
+            "free-threaded" => Ok(AbiVariant::FreeThreaded),
+            "free-threaded-debug" => Ok(AbiVariant::FreeThreadedDebug),
+            _ => Err(Error::UnknownVariant(s.to_string())),
+        }
+    }
+}
+
+impl PythonConfig {
+    /// Returns the build variant, from `ABIFLAGS` and `Py_DEBUG`
+    pub fn abi_variant(&self) -> AbiVariant {
+        let debug = self.debug() || self.abiflags().contains('d');
+        match (self.abiflags().contains('t'), debug) {
+            (false, false) => AbiVariant::Default,
+            (false, true) => AbiVariant::Debug,
+            (true, false) => AbiVariant::FreeThreaded,
+            (true, true) => AbiVariant::FreeThreadedDebug,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AbiVariant;
+    use crate::PythonConfig;
+
+    #[test]
+    fn abi_variant() {
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'ABIFLAGS': 'td', 'Py_DEBUG': 1}",
+        )
+        .unwrap();
+        assert_eq!(config.abi_variant(), AbiVariant::FreeThreadedDebug);
+        assert_eq!(
+            "free-threaded-debug".parse::<AbiVariant>().unwrap(),
+            AbiVariant::FreeThreadedDebug
+        );
+        assert!("release".parse::<AbiVariant>().is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::{LinkModel, PythonConfig};
+
+impl PythonConfig {
+    /// Returns the paths recorded in this configuration that builds rely on
+    /// but that don't exist: `Python.h` in `INCLUDEPY`, the `LIBPL`
+    /// directory and the libpython of shared builds in `LIBDIR`.
+    ///
+    /// Absolute paths are looked up under `sysroot` when given, for
+    /// configurations of cross compilation sysroots.
+    pub fn missing_paths(&self, sysroot: Option<&Path>) -> Vec<PathBuf> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let mut paths = Vec::new();
+        paths.push(self.install_scheme().include.join("Python.h"));
+        if !self.config_dir_str().is_empty() {
+            paths.push(PathBuf::from(self.config_dir_str()));
+        }
+        let ldlibrary = vars.get_str("LDLIBRARY");
+        if self.link_model() == LinkModel::Shared
+            && !self.lib_dir_str().is_empty()
+            && !ldlibrary.is_empty()
+        {
+            paths.push(Path::new(self.lib_dir_str()).join(ldlibrary));
+        }
+        paths
+            .into_iter()
+            .filter(|path| !under_sysroot(path, sysroot).exists())
+            .collect()
+    }
+}
+
+/// `path` inside `sysroot`, unchanged without one
+fn under_sysroot(path: &Path, sysroot: Option<&Path>) -> PathBuf {
+    match sysroot {
+        Some(sysroot) => sysroot.join(
+            path.components()
+                .filter(|x| !matches!(x, Component::RootDir | Component::Prefix(_)))
+                .collect::<PathBuf>(),
+        ),
+        None => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn missing_paths() {
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        let sysroot = env::temp_dir().join(format!("python3-config-verify-{}", std::process::id()));
+        let include = sysroot.join("usr/local/include/python3.11");
+        fs::create_dir_all(&include).unwrap();
+        fs::write(include.join("Python.h"), b"").unwrap();
+        fs::create_dir_all(sysroot.join("usr/local/lib/python3.11/config-3.11-x86_64-linux-gnu"))
+            .unwrap();
+        assert_eq!(
+            config.missing_paths(Some(&sysroot)),
+            vec![PathBuf::from("/usr/local/lib/libpython3.11.so")]
+        );
+        fs::write(sysroot.join("usr/local/lib/libpython3.11.so"), b"").unwrap();
+        assert!(config.missing_paths(Some(&sysroot)).is_empty());
+        fs::remove_dir_all(&sysroot).unwrap();
+    }
+}
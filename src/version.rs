@@ -0,0 +1,85 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{rules_for_version, Error, PythonConfig, VersionRules};
+
+/// Major and minor version of the Python language, `3.11`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PythonVersion {
+    /// Major version, `3`
+    pub major: u32,
+    /// Minor version, `11`
+    pub minor: u32,
+}
+
+impl PythonVersion {
+    /// Version `major.minor`
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Derivation rules of this version, see [`rules_for_version`]
+    pub fn rules(&self) -> VersionRules {
+        rules_for_version(self.major, self.minor)
+    }
+}
+
+impl fmt::Display for PythonVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Parses `3.11`, a patch level like `3.11.4` is ignored
+impl FromStr for PythonVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidVersion(s.to_string());
+        let mut parts = s.split('.');
+        let mut part = || -> Result<u32, Error> {
+            parts
+                .next()
+                .and_then(|x| x.parse().ok())
+                .ok_or_else(invalid)
+        };
+        Ok(Self::new(part()?, part()?))
+    }
+}
+
+impl PythonConfig {
+    /// Returns the Python version of `VERSION`, `None` when it isn't one
+    pub fn python_version(&self) -> Option<PythonVersion> {
+        self.version_str().parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PythonVersion;
+    use crate::{fixtures, Error, PythonConfig};
+
+    #[test]
+    fn python_version() {
+        let version: PythonVersion = "3.11.4".parse().unwrap();
+        assert_eq!(version, PythonVersion::new(3, 11));
+        assert_eq!(version.to_string(), "3.11");
+        assert!(PythonVersion::new(3, 9) < PythonVersion::new(3, 10));
+        assert!(matches!(
+            "3".parse::<PythonVersion>(),
+            Err(Error::InvalidVersion(version)) if version == "3"
+        ));
+
+        let config = fixtures::get("cpython37m-linux").unwrap().config();
+        assert_eq!(config.python_version(), Some(PythonVersion::new(3, 7)));
+        assert!(config.python_version().unwrap().rules().pymalloc_abiflag);
+
+        let config = PythonConfig::parse("build_time_vars = {'VERSION': '3'}").unwrap();
+        assert_eq!(config.python_version(), None);
+        #[allow(deprecated)]
+        {
+            assert_eq!(config.version(), "3");
+            assert_eq!((config.version_major(), config.version_minor()), (3, 0));
+        }
+    }
+}
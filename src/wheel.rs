@@ -0,0 +1,431 @@
+use std::env;
+use std::fmt;
+
+use crate::{arch, normalize_tag, rules_for_version, Error, PythonConfig, PythonImplementation};
+
+/// A wheel compatibility tag, `{interpreter}-{abi}-{platform}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tag {
+    /// Interpreter tag, for example `cp311` or `py3`
+    pub interpreter: String,
+    /// ABI tag, for example `cp311`, `abi3` or `none`
+    pub abi: String,
+    /// Platform tag, for example `manylinux_2_17_x86_64` or `any`
+    pub platform: String,
+}
+
+impl Tag {
+    fn new(interpreter: &str, abi: &str, platform: &str) -> Self {
+        Self {
+            interpreter: interpreter.to_string(),
+            abi: abi.to_string(),
+            platform: platform.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.interpreter, self.abi, self.platform)
+    }
+}
+
+impl PythonConfig {
+    /// Returns the tags of wheels installable into this Python, most
+    /// preferred first, following the `packaging.tags.sys_tags` algorithm:
+    /// interpreter specific tags (`cp311-cp311-*`), the stable ABI of this and
+    /// older versions (`cp311-abi3-*`, `cp310-abi3-*`), then pure Python tags
+    /// down to `py3-none-any`
+    pub fn supported_tags(&self) -> Vec<Tag> {
+        let platforms = self.platform_tags();
+        let interpreter = self.python_tag();
+        let mut tags = Vec::new();
+        let mut abis = vec![self.abi_tag()];
+        if self.implementation() == PythonImplementation::CPython {
+            // debug builds can load extension modules of release builds
+            if let Some(release) = self.abi_tag().strip_suffix('d') {
+                abis.push(release);
+            }
+        }
+        for abi in abis {
+            tags.extend(platforms.iter().map(|p| Tag::new(interpreter, abi, p)));
+        }
+        let abi3 = self.supports_abi3();
+        if abi3 {
+            tags.extend(platforms.iter().map(|p| Tag::new(interpreter, "abi3", p)));
+        }
+        tags.extend(platforms.iter().map(|p| Tag::new(interpreter, "none", p)));
+        let major = self.major_minor().0;
+        let minor = self.major_minor().1;
+        if abi3 {
+            for older in (0..minor)
+                .rev()
+                .filter(|older| rules_for_version(major, *older).stable_abi)
+            {
+                let interpreter = format!("cp{}{}", major, older);
+                tags.extend(platforms.iter().map(|p| Tag::new(&interpreter, "abi3", p)));
+            }
+        }
+        let mut py_versions = vec![format!("py{}{}", major, minor), format!("py{}", major)];
+        py_versions.extend(
+            (0..minor)
+                .rev()
+                .map(|older| format!("py{}{}", major, older)),
+        );
+        for version in &py_versions {
+            tags.extend(platforms.iter().map(|p| Tag::new(version, "none", p)));
+        }
+        tags.push(Tag::new(interpreter, "none", "any"));
+        for version in &py_versions {
+            tags.push(Tag::new(version, "none", "any"));
+        }
+        tags
+    }
+
+    /// Returns the wheel platform tag, for example `manylinux_2_17_x86_64`,
+    /// `macosx_11_0_arm64` or `win_amd64`.
+    ///
+    /// sysconfigdata doesn't record the glibc version, Linux tags use the
+    /// oldest glibc supported by the manylinux images of the architecture
+    /// (`manylinux_2_17`, the same as `manylinux2014`, for most of them) and
+    /// `musllinux_1_2` for musl builds.
+    pub fn platform_tag(&self) -> String {
+        self.platform_tags().swap_remove(0)
+    }
+
+    /// Returns every platform tag wheels installable on this platform may
+    /// have, most specific first, following the `packaging.tags` algorithm.
+    /// Linux tags start at the glibc or musl version [`PythonConfig::platform_tag`]
+    /// assumes.
+    ///
+    /// macOS tags start at the `MACOSX_DEPLOYMENT_TARGET` environment variable
+    /// when set, like wheel builders do, and at the release Python was built
+    /// for otherwise.
+    pub fn platform_tags(&self) -> Vec<String> {
+        self.platform_tags_from(|name| env::var(name).ok())
+    }
+
+    fn platform_tags_from<F>(&self, lookup: F) -> Vec<String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let platform = self.get_platform();
+        if let Some(machine) = platform.strip_prefix("linux-") {
+            return self.linux_platform_tags(machine);
+        }
+        if let Some(rest) = platform.strip_prefix("macosx-") {
+            // macosx-10.14.6-universal2
+            if let Some((release, machine)) = rest.rsplit_once('-') {
+                let release = lookup("MACOSX_DEPLOYMENT_TARGET")
+                    .and_then(|target| parse_macos_version(&target))
+                    .or_else(|| parse_macos_version(release))
+                    .unwrap_or((10, 0));
+                let (major, minor) = macos_minimum(release, machine);
+                return mac_platform_tags(major, minor, machine);
+            }
+        }
+        vec![normalize_tag(platform)]
+    }
+
+    fn linux_platform_tags(&self, machine: &str) -> Vec<String> {
+        let vars = &self.sys_config_data.build_time_vars;
+        let machine = arch::find_by_name(machine).map_or(machine, |arch| arch.machine);
+        // 32-bit interpreters on 64-bit kernels, x32 included, as packaging does
+        let machine = match machine {
+            "x86_64" if self.pointer_size() == 4 => "i686",
+            "aarch64" if self.pointer_size() == 4 => "armv8l",
+            machine => machine,
+        };
+        let mut tags = Vec::new();
+        if vars.multiarch.contains("musl") || vars.host_gnu_type.contains("musl") {
+            for minor in (0..=2).rev() {
+                tags.push(format!("musllinux_1_{}_{}", minor, machine));
+            }
+        } else {
+            let glibc_minor = match machine {
+                "riscv64" => 31,
+                "loongarch64" => 36,
+                _ => 17,
+            };
+            // manylinux_2_5 is the oldest tag for x86, the others start at manylinux2014
+            let oldest = match machine {
+                "x86_64" | "i686" => 5,
+                _ => glibc_minor,
+            };
+            for minor in (oldest..=glibc_minor).rev() {
+                tags.push(format!("manylinux_2_{}_{}", minor, machine));
+                let legacy = match minor {
+                    17 => "manylinux2014",
+                    12 => "manylinux2010",
+                    5 => "manylinux1",
+                    _ => continue,
+                };
+                tags.push(format!("{}_{}", legacy, machine));
+            }
+        }
+        tags.push(format!("linux_{}", machine));
+        tags
+    }
+
+    /// Returns the file name of a wheel of `distribution` at `version` built
+    /// for this configuration, for example
+    /// `foo-1.0-cp311-cp311-manylinux_2_17_x86_64.whl`.
+    ///
+    /// With `abi3` the wheel is tagged for the stable ABI of this Python
+    /// version and newer, `foo-1.0-cp311-abi3-manylinux_2_17_x86_64.whl`.
+    /// Implementations and builds without a stable ABI, such as PyPy or
+    /// free-threaded CPython, ignore `abi3`.
+    pub fn wheel_filename(&self, distribution: &str, version: &str, abi3: bool) -> String {
+        let abi3 = abi3 && self.supports_abi3();
+        let abi_tag = if abi3 { "abi3" } else { self.abi_tag() };
+        format!(
+            "{}-{}-{}-{}-{}.whl",
+            escape_distribution(distribution),
+            version.replace('-', "_"),
+            self.python_tag(),
+            abi_tag,
+            self.platform_tag()
+        )
+    }
+
+    /// Whether extension modules of this configuration can target the stable ABI
+    pub(crate) fn supports_abi3(&self) -> bool {
+        self.python_version()
+            .is_some_and(|version| self.limited_api_hex(version).is_ok())
+    }
+}
+
+/// Returns the platform tag of a macOS wheel for `arch` supporting
+/// `deployment_target` and newer, for example `macosx_10_12_x86_64` or
+/// `macosx_11_0_arm64`.
+///
+/// Like wheel builders the minor version is dropped from macOS 11 on, and
+/// targets older than the first release supporting `arch` are raised to it.
+pub fn macos_platform_tag(arch: &str, deployment_target: &str) -> Result<String, Error> {
+    let version = parse_macos_version(deployment_target).ok_or_else(|| {
+        Error::Unsupported(format!("invalid macOS version {}", deployment_target))
+    })?;
+    let arch = match arch {
+        "aarch64" => "arm64",
+        arch => arch,
+    };
+    let (major, minor) = macos_minimum(version, arch);
+    let minor = if major >= 11 { 0 } else { minor };
+    Ok(format!("macosx_{}_{}_{}", major, minor, arch))
+}
+
+/// Parses a macOS version like `10.9` or `11` into major and minor
+fn parse_macos_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = match parts.next() {
+        Some(minor) => minor.parse().ok()?,
+        None => 0,
+    };
+    Some((major, minor))
+}
+
+/// Raises `version` to the first macOS release supporting `arch`
+fn macos_minimum(version: (u32, u32), arch: &str) -> (u32, u32) {
+    let minimum = match arch {
+        "arm64" => (11, 0),
+        "universal2" => (10, 9),
+        _ => (10, 0),
+    };
+    version.max(minimum)
+}
+
+/// macOS platform tags from the deployment target down to 10.0, mirroring
+/// `packaging.tags.mac_platforms`
+fn mac_platform_tags(major: u32, minor: u32, arch: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut push = |version: (u32, u32), formats: Vec<&str>| {
+        for format in formats {
+            tags.push(format!("macosx_{}_{}_{}", version.0, version.1, format));
+        }
+    };
+    if major >= 11 {
+        for major in (11..=major).rev() {
+            push((major, 0), mac_binary_formats((major, 0), arch));
+        }
+        for minor in (4..=16).rev() {
+            if arch == "x86_64" {
+                push((10, minor), mac_binary_formats((10, minor), arch));
+            } else {
+                // arm64 runs universal2 binaries built for older releases
+                push((10, minor), vec!["universal2"]);
+            }
+        }
+    } else {
+        for minor in (0..=minor).rev() {
+            push((10, minor), mac_binary_formats((10, minor), arch));
+        }
+    }
+    tags
+}
+
+/// Binary formats an architecture can load on a macOS version
+fn mac_binary_formats(version: (u32, u32), arch: &str) -> Vec<&str> {
+    let supported = match arch {
+        "x86_64" | "i386" => version >= (10, 4),
+        "arm64" => version >= (11, 0),
+        "ppc64" => ((10, 4)..=(10, 5)).contains(&version),
+        "ppc" => version <= (10, 6),
+        _ => true,
+    };
+    if !supported {
+        return Vec::new();
+    }
+    let mut formats = vec![arch];
+    match arch {
+        "x86_64" => formats.extend(["intel", "fat64", "fat32"]),
+        "i386" => formats.extend(["intel", "fat32", "fat"]),
+        "ppc64" => formats.push("fat64"),
+        "ppc" => formats.extend(["fat32", "fat"]),
+        _ => {}
+    }
+    if matches!(arch, "x86_64" | "arm64") {
+        formats.push("universal2");
+    }
+    if matches!(arch, "x86_64" | "i386" | "ppc64" | "ppc" | "intel") {
+        formats.push("universal");
+    }
+    formats
+}
+
+/// Normalizes a distribution name for use in a wheel file name, runs of
+/// `-`, `_` and `.` become a single `_`
+fn escape_distribution(distribution: &str) -> String {
+    let mut escaped = String::with_capacity(distribution.len());
+    let mut previous_separator = false;
+    for c in distribution.chars() {
+        if c == '-' || c == '_' || c == '.' {
+            if !previous_separator {
+                escaped.push('_');
+            }
+            previous_separator = true;
+        } else {
+            escaped.push(c.to_ascii_lowercase());
+            previous_separator = false;
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PythonConfig, Tag};
+    use std::fs;
+
+    #[test]
+    fn wheel_filename() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.platform_tag(), "manylinux_2_17_x86_64");
+        assert_eq!(
+            config.wheel_filename("foo", "1.0", false),
+            "foo-1.0-cp311-cp311-manylinux_2_17_x86_64.whl"
+        );
+        assert_eq!(
+            config.wheel_filename("Foo-Bar", "1.0", true),
+            "foo_bar-1.0-cp311-abi3-manylinux_2_17_x86_64.whl"
+        );
+
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(config.platform_tag(), "macosx_10_14_universal2");
+
+        let src = fs::read_to_string("tests/fixtures/pypy310_pp73_sysconfigdata_linux.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        assert_eq!(
+            config.wheel_filename("foo", "1.0", true),
+            "foo-1.0-pp310-pypy310_pp73-manylinux_2_17_x86_64.whl"
+        );
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.12', 'MULTIARCH': 'aarch64-linux-musl', \
+             'SOABI': 'cpython-312-aarch64-linux-musl'}",
+        )
+        .unwrap();
+        assert_eq!(config.platform_tag(), "musllinux_1_2_aarch64");
+    }
+
+    #[test]
+    fn supported_tags() {
+        let src = fs::read_to_string(
+            "tests/fixtures/cpython311_sysconfigdata__linux_x86_64-linux-gnu.py",
+        )
+        .unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        let tags: Vec<String> = config.supported_tags().iter().map(Tag::to_string).collect();
+        assert_eq!(
+            &tags[..3],
+            &[
+                "cp311-cp311-manylinux_2_17_x86_64",
+                "cp311-cp311-manylinux2014_x86_64",
+                "cp311-cp311-manylinux_2_16_x86_64",
+            ]
+        );
+        let position = |tag: &str| tags.iter().position(|x| x == tag).unwrap();
+        assert!(position("cp311-cp311-linux_x86_64") < position("cp311-abi3-manylinux1_x86_64"));
+        assert!(position("cp311-none-linux_x86_64") < position("cp310-abi3-manylinux_2_17_x86_64"));
+        assert!(position("cp32-abi3-linux_x86_64") < position("py311-none-manylinux_2_17_x86_64"));
+        assert_eq!(
+            &tags[tags.len() - 3..],
+            &["py32-none-any", "py31-none-any", "py30-none-any"]
+        );
+        assert!(tags.contains(&"cp311-none-any".to_string()));
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'ABIFLAGS': 'td', 'MACHDEP': 'darwin', \
+             'MACOSX_DEPLOYMENT_TARGET': '11.0', 'HOST_GNU_TYPE': 'aarch64-apple-darwin'}",
+        )
+        .unwrap();
+        let tags: Vec<String> = config.supported_tags().iter().map(Tag::to_string).collect();
+        assert_eq!(
+            &tags[..4],
+            &[
+                "cp313-cp313td-macosx_11_0_arm64",
+                "cp313-cp313td-macosx_11_0_universal2",
+                "cp313-cp313td-macosx_10_16_universal2",
+                "cp313-cp313td-macosx_10_15_universal2",
+            ]
+        );
+        assert!(tags.contains(&"cp313-cp313t-macosx_11_0_arm64".to_string()));
+        assert!(!tags.iter().any(|tag| tag.contains("abi3")));
+    }
+
+    #[test]
+    fn macos_deployment_target() {
+        assert_eq!(
+            super::macos_platform_tag("x86_64", "10.12").unwrap(),
+            "macosx_10_12_x86_64"
+        );
+        assert_eq!(
+            super::macos_platform_tag("aarch64", "10.12").unwrap(),
+            "macosx_11_0_arm64"
+        );
+        assert_eq!(
+            super::macos_platform_tag("arm64", "14.2").unwrap(),
+            "macosx_14_0_arm64"
+        );
+        assert!(super::macos_platform_tag("x86_64", "ten").is_err());
+
+        let src =
+            fs::read_to_string("tests/fixtures/cpython38_sysconfigdata__darwin_darwin.py").unwrap();
+        let config = PythonConfig::parse(&src).unwrap();
+        let tags = config.platform_tags_from(|name| match name {
+            "MACOSX_DEPLOYMENT_TARGET" => Some("10.9".to_string()),
+            _ => None,
+        });
+        assert_eq!(tags[0], "macosx_10_9_universal2");
+        assert_eq!(
+            config.platform_tags_from(|_| None)[0],
+            "macosx_10_14_universal2"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::Write;
+
+use crate::framework::split_framework_flags;
+use crate::{Error, LinkMode, PythonConfig};
+
+impl PythonConfig {
+    /// Returns an Xcode build configuration file (`.xcconfig`) for apps
+    /// embedding this macOS or iOS distribution: `HEADER_SEARCH_PATHS`,
+    /// `OTHER_LDFLAGS` and the deployment target, plus `-framework` for
+    /// framework builds or `LIBRARY_SEARCH_PATHS` and `-lpython3.X`
+    /// otherwise. [`PythonConfig::frameworks`] are linked last, with their
+    /// search paths in `FRAMEWORK_SEARCH_PATHS`.
+    pub fn to_xcconfig(&self) -> Result<String, Error> {
+        let ios = self.sys_platform() == "ios";
+        if self.sys_platform() != "darwin" && !ios {
+            return Err(Error::Unsupported(format!(
+                "xcconfig files need a macOS or iOS build of Python, not {}",
+                self.get_platform()
+            )));
+        }
+        let vars = &self.sys_config_data.build_time_vars;
+        let headers: Vec<String> = self
+            .include_flags()
+            .iter()
+            .map(|flag| flag[2..].to_string())
+            .collect();
+        let mut settings = vec![("HEADER_SEARCH_PATHS", quoted(&headers))];
+        let framework_dirs: Vec<String> = self
+            .framework_search_paths()
+            .iter()
+            .map(|dir| dir.display().to_string())
+            .collect();
+        if !framework_dirs.is_empty() {
+            settings.push(("FRAMEWORK_SEARCH_PATHS", quoted(&framework_dirs)));
+        }
+        let mut ldflags = Vec::new();
+        if let Some(framework) = self.python_framework() {
+            ldflags.push(framework.to_string());
+            let (_, _, flags) = split_framework_flags(&self.lib_flags(LinkMode::ExtensionModule));
+            ldflags.extend(flags);
+        } else {
+            let mut dirs = Vec::new();
+            let (_, _, flags) = split_framework_flags(&self.link_flags(LinkMode::Embed));
+            for flag in flags {
+                match flag.strip_prefix("-L") {
+                    Some(dir) => dirs.push(dir.to_string()),
+                    None => ldflags.push(flag),
+                }
+            }
+            settings.push(("LIBRARY_SEARCH_PATHS", quoted(&dirs)));
+        }
+        ldflags.extend(self.frameworks().iter().map(ToString::to_string));
+        settings.push(("OTHER_LDFLAGS", ldflags.join(" ")));
+
+        let mut out = self.generated_comment("//");
+        for (name, value) in settings {
+            writeln!(out, "{} = $(inherited) {}", name, value).unwrap();
+        }
+        let deployment_target = if ios {
+            "IPHONEOS_DEPLOYMENT_TARGET"
+        } else {
+            "MACOSX_DEPLOYMENT_TARGET"
+        };
+        if !vars.get_str(deployment_target).is_empty() {
+            writeln!(
+                out,
+                "{} = {}",
+                deployment_target,
+                vars.get_str(deployment_target)
+            )
+            .unwrap();
+        }
+        Ok(out)
+    }
+}
+
+/// `paths` as a list of double quoted xcconfig values
+fn quoted(paths: &[String]) -> String {
+    let paths: Vec<String> = paths.iter().map(|path| format!("\"{}\"", path)).collect();
+    paths.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, Error, PythonConfig};
+
+    #[test]
+    fn xcconfig() {
+        let config = fixtures::get("cpython38-darwin").unwrap().config();
+        let xcconfig = config.to_xcconfig().unwrap();
+        let frameworks = "/Applications/Xcode.app/Contents/Developer/Library/Frameworks";
+        assert!(xcconfig.starts_with("// Generated by python3-config for CPython 3.8"));
+        assert!(xcconfig.contains(&format!(
+            "HEADER_SEARCH_PATHS = $(inherited) \"{}/Python3.framework/Versions/3.8/Headers\"\n",
+            frameworks
+        )));
+        assert!(xcconfig.contains(&format!(
+            "FRAMEWORK_SEARCH_PATHS = $(inherited) \"{}\"\n",
+            frameworks
+        )));
+        assert!(xcconfig.contains(
+            "OTHER_LDFLAGS = $(inherited) -framework Python3 -ldl -lSystem -framework CoreFoundation\n"
+        ));
+        assert!(xcconfig.ends_with("MACOSX_DEPLOYMENT_TARGET = 10.14.6\n"));
+
+        let config = PythonConfig::parse(
+            "build_time_vars = {'VERSION': '3.13', 'MACHDEP': 'ios', 'Py_ENABLE_SHARED': 0, \
+             'LIBDIR': '/opt/python/lib', 'INCLUDEPY': '/opt/python/include/python3.13', \
+             'LIBS': '-ldl', 'IPHONEOS_DEPLOYMENT_TARGET': '13.0'}",
+        )
+        .unwrap();
+        let xcconfig = config.to_xcconfig().unwrap();
+        assert!(xcconfig.contains("LIBRARY_SEARCH_PATHS = $(inherited) \"/opt/python/lib\"\n"));
+        assert!(xcconfig.contains("OTHER_LDFLAGS = $(inherited) -lpython3.13 -ldl\n"));
+        assert!(xcconfig.ends_with("IPHONEOS_DEPLOYMENT_TARGET = 13.0\n"));
+
+        let config = fixtures::get("cpython311-linux").unwrap().config();
+        assert!(matches!(config.to_xcconfig(), Err(Error::Unsupported(_))));
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Runs the built `python3-config` with `args`, returning its exit status,
+/// stdout and stderr
+fn python3_config(args: &[&str]) -> (i32, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_python3-config"))
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+fn fixture(file_name: &str) -> String {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(file_name)
+        .display()
+        .to_string()
+}
+
+/// A scratch directory of its own for the test `name`
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "python3-config-cli-{}-{}",
+        name,
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+const CPYTHON311: &str = "cpython311_sysconfigdata__linux_x86_64-linux-gnu.py";
+
+#[test]
+fn guards() {
+    let source = fixture(CPYTHON311);
+    let (status, stdout, _) = python3_config(&[
+        "--max-bytes",
+        "1000000",
+        "--timeout",
+        "10",
+        "--prefix",
+        "--extension-suffix",
+        &source,
+    ]);
+    assert_eq!(status, 0);
+    assert_eq!(stdout, "/usr/local\n.cpython-311-x86_64-linux-gnu.so\n");
+
+    let (status, stdout, stderr) = python3_config(&["--max-bytes", "10", "--prefix", &source]);
+    assert_eq!(status, 2);
+    assert_eq!(stdout, "");
+    assert!(stderr.ends_with("exceeds the file size limit of 10\n"));
+
+    let (status, _, stderr) = python3_config(&["--timeout", "soon", "--prefix", &source]);
+    assert_eq!(status, 2);
+    assert_eq!(stderr, "error: invalid --timeout soon\n");
+}
+
+#[test]
+fn exit_status() {
+    let dir = temp_dir("exit-status");
+    let path = dir.join("_sysconfigdata_native.py");
+    std::fs::write(
+        &path,
+        "build_time_vars = {'VERSION': '3.12', 'LDVERSION': '3.12', \
+         'prefix': '/opt/py', 'CFLAGS': '-march=native'}",
+    )
+    .unwrap();
+    let (status, stdout, _) = python3_config(&["lint", path.to_str().unwrap()]);
+    assert_eq!(status, 1);
+    assert!(stdout.starts_with("CFLAGS: -march=native "));
+
+    let (status, stdout, _) = python3_config(&["lint", &fixture(CPYTHON311)]);
+    assert_eq!(status, 0);
+    assert_eq!(stdout, "");
+
+    let (status, _, stderr) = python3_config(&["frobnicate"]);
+    assert_eq!(status, 2);
+    assert!(stderr.starts_with("error: unknown command frobnicate\n"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Polls until `path` exists with contents matching `done`, for at most ten
+/// seconds
+#[cfg(feature = "watch")]
+fn wait_for(path: &Path, done: impl Fn(&str) -> bool) -> String {
+    for _ in 0..100 {
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            if done(&contents) {
+                return contents;
+            }
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    panic!("{} wasn't written", path.display());
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn watch() {
+    let dir = temp_dir("watch");
+    let sysroot = dir.join("sysroot");
+    std::fs::create_dir_all(&sysroot).unwrap();
+    let source = std::fs::read_to_string(fixture(CPYTHON311)).unwrap();
+    let path = sysroot.join(CPYTHON311);
+    std::fs::write(&path, &source).unwrap();
+    let out = dir.join("env.sh");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_python3-config"))
+        .args(["watch", "--emit", "env"])
+        .arg(&out)
+        .arg(&sysroot)
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    wait_for(&out, |env| {
+        env.starts_with("export PYTHONHOME='/usr/local'\n")
+    });
+    std::fs::write(&path, source.replace("'/usr/local'", "'/opt/watched'")).unwrap();
+    wait_for(&out, |env| {
+        env.starts_with("export PYTHONHOME='/opt/watched'\n")
+    });
+    child.kill().unwrap();
+    child.wait().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(not(feature = "watch"))]
+#[test]
+fn watch() {
+    let (status, _, stderr) = python3_config(&["watch", "--emit", "env", "env.sh", &fixture("")]);
+    assert_eq!(status, 2);
+    assert_eq!(stderr, "error: watch requires the watch feature\n");
+
+    let (status, _, _) = python3_config(&["watch", &fixture("")]);
+    assert_eq!(status, 2);
+}
+
+/// The CLI as the integration layer of the conformance suite: each fixture
+/// goes through `dump` and back
+#[cfg(feature = "fixtures")]
+#[test]
+fn conformance() {
+    use python3_config::{conformance, PythonConfig};
+
+    conformance::run(|bundled| {
+        let (status, stdout, stderr) = python3_config(&["dump", &fixture(bundled.file_name)]);
+        assert_eq!(status, 0, "{}", stderr);
+        PythonConfig::parse(&stdout).unwrap()
+    })
+    .unwrap();
+
+    for golden in conformance::goldens() {
+        let (status, stdout, _) =
+            python3_config(&["--extension-suffix", &fixture(golden.fixture.file_name)]);
+        assert_eq!(status, 0);
+        assert_eq!(stdout.trim_end(), golden.tags().ext_suffix);
+    }
+}
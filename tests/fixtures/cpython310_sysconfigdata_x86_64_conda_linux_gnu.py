@@ -0,0 +1,149 @@
+# system configuration generated and used by the sysconfig module
+build_time_vars = {'ABIFLAGS': '',
+ 'AR': 'x86_64-conda-linux-gnu-gcc-ar',
+ 'ARFLAGS': 'rcs',
+ 'BASECFLAGS': '-Wno-unused-result -Wsign-compare',
+ 'BINDIR': '/opt/conda/bin',
+ 'BINLIBDEST': '/opt/conda/lib/python3.10',
+ 'BLDLIBRARY': 'libpython3.10.a',
+ 'BLDSHARED': 'x86_64-conda-linux-gnu-gcc -shared -Wl,-O1 '
+              '-Wl,-Bsymbolic-functions',
+ 'BUILDEXE': '',
+ 'BUILDPYTHON': 'python',
+ 'CC': 'x86_64-conda-linux-gnu-gcc -pthread',
+ 'CCSHARED': '-fPIC',
+ 'CFLAGS': '-Wno-unused-result -Wsign-compare -DNDEBUG -fwrapv -O2 -Wall '
+           '-march=nocona -mtune=haswell -ftree-vectorize -fPIC '
+           '-fstack-protector-strong -fno-plt -O2 -ffunction-sections -pipe '
+           '-isystem /opt/conda/include',
+ 'CFLAGSFORSHARED': '-fPIC',
+ 'CONFIGURE_CFLAGS': '',
+ 'CONFIGURE_CFLAGS_NODIST': '-std=c11 -Wextra -Wno-unused-parameter '
+                            '-Wno-missing-field-initializers '
+                            '-Werror=implicit-function-declaration '
+                            '-fvisibility=hidden',
+ 'CONFIGURE_CPPFLAGS': '',
+ 'CONFIGURE_LDFLAGS': '',
+ 'CONFIGURE_LDFLAGS_NODIST': '',
+ 'CONFIG_ARGS': "'--prefix=/opt/conda' '--build=x86_64-conda-linux-gnu' "
+                "'--host=x86_64-conda-linux-gnu' '--enable-ipv6' "
+                "'--with-ensurepip=no' "
+                "'--with-tzpath=/opt/conda/share/zoneinfo' "
+                "'--with-computed-gotos' '--with-system-ffi' "
+                "'--enable-loadable-sqlite-extensions' "
+                "'--with-tcltk-includes=-I/opt/conda/include' "
+                "'--with-tcltk-libs=-L/opt/conda/lib -ltcl8.6 -ltk8.6' "
+                "'--with-platlibdir=lib' '--enable-optimizations' '--with-lto' "
+                "'--disable-shared' 'build_alias=x86_64-conda-linux-gnu' "
+                "'host_alias=x86_64-conda-linux-gnu' "
+                "'CC=x86_64-conda-linux-gnu-gcc'",
+ 'CONFINCLUDEDIR': '/opt/conda/include',
+ 'CONFINCLUDEPY': '/opt/conda/include/python3.10',
+ 'COUNT_ALLOCS': 0,
+ 'CPPFLAGS': '-I. -I./Include',
+ 'CXX': 'x86_64-conda-linux-gnu-c++ -pthread',
+ 'DESTDIRS': '/opt/conda /opt/conda/lib /opt/conda/lib/python3.10 '
+             '/opt/conda/lib/python3.10/lib-dynload',
+ 'DESTLIB': '/opt/conda/lib/python3.10',
+ 'DESTSHARED': '/opt/conda/lib/python3.10/lib-dynload',
+ 'DLLLIBRARY': '',
+ 'ENSUREPIP': 'no',
+ 'EXE': '',
+ 'EXEMODE': 755,
+ 'EXTRA_CFLAGS': '',
+ 'EXT_SUFFIX': '.cpython-310-x86_64-linux-gnu.so',
+ 'HAVE_LIBREADLINE': 1,
+ 'HOST_GNU_TYPE': 'x86_64-conda-linux-gnu',
+ 'INCLUDEDIR': '/opt/conda/include',
+ 'INCLUDEPY': '/opt/conda/include/python3.10',
+ 'INSTSONAME': 'libpython3.10.a',
+ 'LDCXXSHARED': 'x86_64-conda-linux-gnu-c++ -shared',
+ 'LDFLAGS': '-Wl,-O2 -Wl,--sort-common -Wl,--as-needed -Wl,-z,relro -Wl,-z,now '
+            '-Wl,--disable-new-dtags -Wl,--gc-sections '
+            '-Wl,-rpath,/opt/conda/lib -Wl,-rpath-link,/opt/conda/lib '
+            '-L/opt/conda/lib',
+ 'LDLIBRARY': 'libpython3.10.a',
+ 'LDLIBRARYDIR': '',
+ 'LDSHARED': 'x86_64-conda-linux-gnu-gcc -pthread -shared -Wl,-O2 '
+             '-Wl,--sort-common -Wl,--as-needed -Wl,-z,relro -Wl,-z,now '
+             '-Wl,--disable-new-dtags -Wl,--gc-sections '
+             '-Wl,-rpath,/opt/conda/lib -Wl,-rpath-link,/opt/conda/lib '
+             '-L/opt/conda/lib',
+ 'LDVERSION': '3.10',
+ 'LIBC': '',
+ 'LIBDEST': '/opt/conda/lib/python3.10',
+ 'LIBDIR': '/opt/conda/lib',
+ 'LIBFFI_INCLUDEDIR': '',
+ 'LIBM': '-lm',
+ 'LIBOBJS': '',
+ 'LIBPC': '/opt/conda/lib/pkgconfig',
+ 'LIBPL': '/opt/conda/lib/python3.10/config-3.10-x86_64-linux-gnu',
+ 'LIBPYTHON': '',
+ 'LIBRARY': 'libpython3.10.a',
+ 'LIBS': '-lcrypt -lpthread -ldl  -lutil -lrt -lm',
+ 'LINKCC': 'x86_64-conda-linux-gnu-gcc -pthread',
+ 'LINKFORSHARED': '-Xlinker -export-dynamic',
+ 'LIPO_32BIT_FLAGS': '',
+ 'MACHDEP': 'linux',
+ 'MODLIBS': '-lssl -lcrypto -lffi -lz -lbz2 -llzma -lreadline -lncursesw',
+ 'MULTIARCH': 'x86_64-linux-gnu',
+ 'MULTIARCH_CPPFLAGS': '-DMULTIARCH=\\"x86_64-linux-gnu\\"',
+ 'OPT': '-DNDEBUG -g -fwrapv -O3 -Wall',
+ 'PLATLIBDIR': 'lib',
+ 'PY_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall',
+ 'PY_CFLAGS_NODIST': '-std=c11 -Wextra -Wno-unused-parameter '
+                     '-Wno-missing-field-initializers '
+                     '-Werror=implicit-function-declaration '
+                     '-fvisibility=hidden -fprofile-use -fprofile-correction '
+                     '-flto -fuse-linker-plugin -ffat-lto-objects '
+                     '-flto-partition=none -I./Include/internal',
+ 'PY_CORE_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -std=c11 '
+                   '-Wextra -fvisibility=hidden -I./Include/internal -I. '
+                   '-I./Include -fPIC -DPy_BUILD_CORE',
+ 'PY_CPPFLAGS': '-I. -I./Include',
+ 'PY_ENABLE_SHARED': 0,
+ 'PY_LDFLAGS': '-Wl,-O1 -Wl,-Bsymbolic-functions',
+ 'PY_LDFLAGS_NODIST': '-flto -fuse-linker-plugin -ffat-lto-objects '
+                      '-flto-partition=none -g',
+ 'PY_STDMODULE_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -std=c11 '
+                        '-Wextra -fvisibility=hidden -I./Include/internal -I. '
+                        '-I./Include -fPIC',
+ 'Py_DEBUG': 0,
+ 'Py_ENABLE_SHARED': 0,
+ 'Py_HASH_ALGORITHM': 0,
+ 'Py_TRACE_REFS': 0,
+ 'SHLIBS': '-lcrypt -lpthread -ldl  -lutil -lrt -lm',
+ 'SHLIB_SUFFIX': '.so',
+ 'SIZEOF_DOUBLE': 8,
+ 'SIZEOF_FLOAT': 4,
+ 'SIZEOF_FPOS_T': 16,
+ 'SIZEOF_INT': 4,
+ 'SIZEOF_LONG': 8,
+ 'SIZEOF_LONG_DOUBLE': 16,
+ 'SIZEOF_LONG_LONG': 8,
+ 'SIZEOF_OFF_T': 8,
+ 'SIZEOF_PID_T': 4,
+ 'SIZEOF_PTHREAD_KEY_T': 4,
+ 'SIZEOF_PTHREAD_T': 8,
+ 'SIZEOF_SHORT': 2,
+ 'SIZEOF_SIZE_T': 8,
+ 'SIZEOF_TIME_T': 8,
+ 'SIZEOF_UINTPTR_T': 8,
+ 'SIZEOF_VOID_P': 8,
+ 'SIZEOF_WCHAR_T': 4,
+ 'SIZEOF__BOOL': 1,
+ 'SOABI': 'cpython-310-x86_64-linux-gnu',
+ 'SYSLIBS': '-lm',
+ 'TZPATH': '/opt/conda/share/zoneinfo',
+ 'VERSION': '3.10',
+ 'WITH_DOC_STRINGS': 1,
+ 'WITH_PYMALLOC': 1,
+ 'abs_builddir': '/home/conda/feedstock_root/build_artifacts/python-split_1696346830000/work_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_pla/build-static',
+ 'abs_srcdir': '/home/conda/feedstock_root/build_artifacts/python-split_1696346830000/work_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_pla',
+ 'datarootdir': '/opt/conda/share',
+ 'exec_prefix': '/opt/conda',
+ 'prefix': '/opt/conda',
+ 'py_version': '3.10.0',
+ 'py_version_nodot': '310',
+ 'py_version_short': '3.10',
+ 'srcdir': '/home/conda/feedstock_root/build_artifacts/python-split_1696346830000/work_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_placehold_pla'}
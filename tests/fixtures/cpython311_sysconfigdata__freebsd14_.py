@@ -0,0 +1,97 @@
+# system configuration generated and used by the sysconfig module
+build_time_vars = {'ABIFLAGS': '',
+ 'AR': 'ar',
+ 'ARFLAGS': 'rcs',
+ 'BINDIR': '/usr/local/bin',
+ 'BINLIBDEST': '/usr/local/lib/python3.11',
+ 'BLDLIBRARY': '-L. -lpython3.11',
+ 'BLDSHARED': 'cc -shared  -L/usr/local/lib -Wl,-rpath=/usr/local/lib '
+              '-fstack-protector-strong',
+ 'BUILDEXE': '',
+ 'BUILDPYTHON': 'python',
+ 'CC': 'cc',
+ 'CCSHARED': '-fPIC',
+ 'CFLAGS': '-Wsign-compare -Wunreachable-code -DNDEBUG -O2 -pipe '
+           '-fstack-protector-strong -fno-strict-aliasing',
+ 'CFLAGSFORSHARED': '-fPIC',
+ 'CONFIG_ARGS': "'--enable-shared' '--without-ensurepip' "
+                "'--with-system-ffi' '--with-system-expat' "
+                "'--enable-ipv6' '--with-lto' '--enable-optimizations' "
+                "'--prefix=/usr/local' '--localstatedir=/var' "
+                "'--mandir=/usr/local/man' '--infodir=/usr/local/share/info/' "
+                "'--build=amd64-portbld-freebsd14.0' "
+                "'build_alias=amd64-portbld-freebsd14.0' 'CC=cc' "
+                "'CFLAGS=-O2 -pipe  -fstack-protector-strong "
+                "-fno-strict-aliasing ' 'LDFLAGS= -L/usr/local/lib "
+                "-Wl,-rpath=/usr/local/lib -fstack-protector-strong ' "
+                "'LIBS=' 'CPPFLAGS=' 'CPP=cpp' 'CXX=c++' "
+                "'PKG_CONFIG=pkgconf'",
+ 'CONFINCLUDEDIR': '/usr/local/include',
+ 'CONFINCLUDEPY': '/usr/local/include/python3.11',
+ 'CPPFLAGS': '-I. -I./Include',
+ 'CXX': 'c++',
+ 'DESTLIB': '/usr/local/lib/python3.11',
+ 'DESTSHARED': '/usr/local/lib/python3.11/lib-dynload',
+ 'ENSUREPIP': 'no',
+ 'EXE': '',
+ 'EXT_SUFFIX': '.cpython-311.so',
+ 'HAVE_DYNAMIC_LOADING': 1,
+ 'HAVE_LIBREADLINE': 1,
+ 'HOST_GNU_TYPE': 'amd64-portbld-freebsd14.0',
+ 'INCLUDEDIR': '/usr/local/include',
+ 'INCLUDEPY': '/usr/local/include/python3.11',
+ 'INSTSONAME': 'libpython3.11.so.1.0',
+ 'LDFLAGS': '-L/usr/local/lib -Wl,-rpath=/usr/local/lib '
+            '-fstack-protector-strong',
+ 'LDLIBRARY': 'libpython3.11.so',
+ 'LDSHARED': 'cc -shared  -L/usr/local/lib -Wl,-rpath=/usr/local/lib '
+             '-fstack-protector-strong',
+ 'LDVERSION': '3.11',
+ 'LIBDEST': '/usr/local/lib/python3.11',
+ 'LIBDIR': '/usr/local/lib',
+ 'LIBPC': '/usr/local/libdata/pkgconfig',
+ 'LIBPL': '/usr/local/lib/python3.11/config-3.11',
+ 'LIBPYTHON': '',
+ 'LIBRARY': 'libpython3.11.a',
+ 'LIBS': '-lintl -lutil',
+ 'LINKFORSHARED': '-Xlinker -export-dynamic',
+ 'MACHDEP': 'freebsd14',
+ 'MULTIARCH': '',
+ 'OPT': '-DNDEBUG -O2 -pipe -fstack-protector-strong -fno-strict-aliasing',
+ 'PLATLIBDIR': 'lib',
+ 'PY_CFLAGS': '-Wsign-compare -Wunreachable-code -DNDEBUG -O2 -pipe '
+              '-fstack-protector-strong -fno-strict-aliasing',
+ 'PY_CFLAGS_NODIST': '-std=c11 -Wextra -Wno-unused-parameter '
+                     '-Wno-missing-field-initializers '
+                     '-Werror=implicit-function-declaration -fvisibility=hidden '
+                     '-fprofile-instr-use=code.profclangd -flto=thin '
+                     '-I./Include/internal',
+ 'PY_CPPFLAGS': '-I. -I./Include',
+ 'PY_ENABLE_SHARED': 1,
+ 'PY_LDFLAGS': '-L/usr/local/lib -Wl,-rpath=/usr/local/lib '
+               '-fstack-protector-strong',
+ 'PY_LDFLAGS_NODIST': '-flto=thin -Wl,--thinlto-cache-dir=thinlto-cache',
+ 'Py_DEBUG': 0,
+ 'Py_ENABLE_SHARED': 1,
+ 'Py_HASH_ALGORITHM': 0,
+ 'SHLIB_SUFFIX': '.so',
+ 'SIZEOF_DOUBLE': 8,
+ 'SIZEOF_FLOAT': 4,
+ 'SIZEOF_INT': 4,
+ 'SIZEOF_LONG': 8,
+ 'SIZEOF_LONG_LONG': 8,
+ 'SIZEOF_SIZE_T': 8,
+ 'SIZEOF_VOID_P': 8,
+ 'SIZEOF_WCHAR_T': 4,
+ 'SOABI': 'cpython-311',
+ 'SYSLIBS': '-lm',
+ 'TZPATH': '/usr/share/zoneinfo:/usr/lib/zoneinfo:/usr/share/lib/zoneinfo:/etc/zoneinfo',
+ 'VERSION': '3.11',
+ 'WITH_DOC_STRINGS': 1,
+ 'WITH_PYMALLOC': 1,
+ 'datarootdir': '/usr/local/share',
+ 'exec_prefix': '/usr/local',
+ 'host': 'amd64-portbld-freebsd14.0',
+ 'prefix': '/usr/local',
+ 'py_version_nodot': '311',
+ 'srcdir': '.'}
@@ -0,0 +1,95 @@
+# system configuration generated and used by the sysconfig module
+build_time_vars = {'ABIFLAGS': '',
+ 'AR': 'ar',
+ 'ARFLAGS': 'rcs',
+ 'BINDIR': 'C:/msys64/mingw64/bin',
+ 'BINLIBDEST': 'C:/msys64/mingw64/lib/python3.11',
+ 'BLDLIBRARY': '-L. -lpython3.11',
+ 'BLDSHARED': 'x86_64-w64-mingw32-gcc -shared -Wl,--enable-auto-image-base '
+              '-pipe',
+ 'BUILDEXE': '.exe',
+ 'BUILDPYTHON': 'python.exe',
+ 'CC': 'x86_64-w64-mingw32-gcc',
+ 'CCSHARED': '',
+ 'CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -march=nocona '
+           '-msahf -mtune=generic -O2 -pipe',
+ 'CFLAGSFORSHARED': '',
+ 'CONFIG_ARGS': "'--prefix=/mingw64' '--host=x86_64-w64-mingw32' "
+                "'--build=x86_64-w64-mingw32' '--enable-shared' "
+                "'--with-nt-threads' '--with-system-expat' '--with-system-ffi' "
+                "'--with-system-libmpdec' '--without-ensurepip' "
+                "'--enable-loadable-sqlite-extensions' '--with-tzpath=' "
+                "'build_alias=x86_64-w64-mingw32' "
+                "'host_alias=x86_64-w64-mingw32' "
+                "'CC=x86_64-w64-mingw32-gcc' 'CFLAGS=-march=nocona -msahf "
+                "-mtune=generic -O2 -pipe' 'LDFLAGS=-pipe'",
+ 'CONFINCLUDEDIR': 'C:/msys64/mingw64/include',
+ 'CONFINCLUDEPY': 'C:/msys64/mingw64/include/python3.11',
+ 'CPPFLAGS': '-IObjects -IInclude -IPython -I. -I../Python-3.11.9/Include',
+ 'CXX': 'x86_64-w64-mingw32-g++',
+ 'DESTLIB': 'C:/msys64/mingw64/lib/python3.11',
+ 'DLLLIBRARY': 'libpython3.11.dll',
+ 'ENSUREPIP': 'no',
+ 'EXE': '.exe',
+ 'EXT_SUFFIX': '.cp311-mingw_x86_64.pyd',
+ 'HAVE_DYNAMIC_LOADING': 1,
+ 'HAVE_LIBREADLINE': 0,
+ 'HOST_GNU_TYPE': 'x86_64-w64-mingw32',
+ 'INCLUDEDIR': 'C:/msys64/mingw64/include',
+ 'INCLUDEPY': 'C:/msys64/mingw64/include/python3.11',
+ 'INSTSONAME': 'libpython3.11.dll.a',
+ 'LDFLAGS': '-pipe',
+ 'LDLIBRARY': 'libpython3.11.dll.a',
+ 'LDSHARED': 'x86_64-w64-mingw32-gcc -shared -Wl,--enable-auto-image-base '
+             '-pipe',
+ 'LDVERSION': '3.11',
+ 'LIBDEST': 'C:/msys64/mingw64/lib/python3.11',
+ 'LIBDIR': 'C:/msys64/mingw64/lib',
+ 'LIBPC': 'C:/msys64/mingw64/lib/pkgconfig',
+ 'LIBPL': 'C:/msys64/mingw64/lib/python3.11/config-3.11',
+ 'LIBPYTHON': '',
+ 'LIBRARY': 'libpython3.11.a',
+ 'LIBS': '-lversion -lshlwapi -lpathcch -lbcrypt',
+ 'LINKFORSHARED': '',
+ 'MACHDEP': 'win32',
+ 'MULTIARCH': '',
+ 'OPT': '-DNDEBUG -g -fwrapv -O3 -Wall',
+ 'PLATLIBDIR': 'lib',
+ 'PY_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -march=nocona '
+              '-msahf -mtune=generic -O2 -pipe',
+ 'PY_CFLAGS_NODIST': '-std=c11 -Wextra -Wno-unused-parameter '
+                     '-Wno-missing-field-initializers -I./Include/internal',
+ 'PY_CORE_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall '
+                   '-march=nocona -msahf -mtune=generic -O2 -pipe -std=c11 '
+                   '-Wextra -Wno-unused-parameter '
+                   '-Wno-missing-field-initializers -I./Include/internal '
+                   '-IObjects -IInclude -IPython -I. '
+                   '-I../Python-3.11.9/Include -DPy_BUILD_CORE',
+ 'PY_CPPFLAGS': '-IObjects -IInclude -IPython -I. '
+                '-I../Python-3.11.9/Include',
+ 'PY_ENABLE_SHARED': 1,
+ 'PY_LDFLAGS': '-pipe',
+ 'PY_LDFLAGS_NODIST': '',
+ 'Py_DEBUG': 0,
+ 'Py_ENABLE_SHARED': 1,
+ 'Py_HASH_ALGORITHM': 0,
+ 'SHLIB_SUFFIX': '.pyd',
+ 'SIZEOF_DOUBLE': 8,
+ 'SIZEOF_FLOAT': 4,
+ 'SIZEOF_INT': 4,
+ 'SIZEOF_LONG': 4,
+ 'SIZEOF_LONG_LONG': 8,
+ 'SIZEOF_SIZE_T': 8,
+ 'SIZEOF_VOID_P': 8,
+ 'SIZEOF_WCHAR_T': 2,
+ 'SOABI': 'cp311-mingw_x86_64',
+ 'SYSLIBS': '',
+ 'VERSION': '3.11',
+ 'WITH_DOC_STRINGS': 1,
+ 'WITH_PYMALLOC': 1,
+ 'datarootdir': 'C:/msys64/mingw64/share',
+ 'exec_prefix': 'C:/msys64/mingw64',
+ 'host': 'x86_64-w64-mingw32',
+ 'prefix': 'C:/msys64/mingw64',
+ 'py_version_nodot': '311',
+ 'srcdir': '.'}
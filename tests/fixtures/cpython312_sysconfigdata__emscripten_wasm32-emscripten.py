@@ -0,0 +1,130 @@
+# system configuration generated and used by the sysconfig module
+build_time_vars = {'ABIFLAGS': '',
+ 'AR': 'emar',
+ 'ARFLAGS': 'rcs',
+ 'BASECFLAGS': '-Wno-unused-result -Wsign-compare',
+ 'BINDIR': '/bin',
+ 'BINLIBDEST': '/lib/python3.12',
+ 'BLDLIBRARY': 'libpython3.12.a',
+ 'BLDSHARED': 'emcc -sSIDE_MODULE=1',
+ 'BUILDEXE': '',
+ 'BUILDPYTHON': 'python',
+ 'CC': 'emcc',
+ 'CCSHARED': '',
+ 'CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall',
+ 'CFLAGSFORSHARED': '',
+ 'CONFIGURE_CFLAGS': '',
+ 'CONFIGURE_CFLAGS_NODIST': '-std=c11 -Wextra -Wno-unused-parameter '
+                            '-Wno-missing-field-initializers '
+                            '-Werror=implicit-function-declaration '
+                            '-fvisibility=hidden',
+ 'CONFIGURE_CPPFLAGS': '',
+ 'CONFIGURE_LDFLAGS': '',
+ 'CONFIGURE_LDFLAGS_NODIST': '',
+ 'CONFIG_ARGS': "'--host=wasm32-unknown-emscripten' "
+                "'--build=x86_64-pc-linux-gnu' '--prefix=/' "
+                "'--with-build-python=/src/cpython/build/Python-3.12.1/python' "
+                "'--disable-ipv6' '--enable-big-digits=30' "
+                "'--enable-optimizations' 'build_alias=x86_64-pc-linux-gnu' "
+                "'host_alias=wasm32-unknown-emscripten' 'CC=emcc'",
+ 'CONFINCLUDEDIR': '/include',
+ 'CONFINCLUDEPY': '/include/python3.12',
+ 'COUNT_ALLOCS': 0,
+ 'CPPFLAGS': '-I. -I./Include',
+ 'CXX': 'em++',
+ 'DESTDIRS': ' /lib /lib/python3.12 /lib/python3.12/lib-dynload',
+ 'DESTLIB': '/lib/python3.12',
+ 'DESTSHARED': '/lib/python3.12/lib-dynload',
+ 'DLLLIBRARY': '',
+ 'ENSUREPIP': 'no',
+ 'EXE': '',
+ 'EXEMODE': 755,
+ 'EXTRA_CFLAGS': '',
+ 'EXT_SUFFIX': '.cpython-312-wasm32-emscripten.so',
+ 'HAVE_LIBREADLINE': 1,
+ 'HOST_GNU_TYPE': 'wasm32-unknown-emscripten',
+ 'INCLUDEDIR': '/include',
+ 'INCLUDEPY': '/include/python3.12',
+ 'INSTSONAME': 'libpython3.12.a',
+ 'LDCXXSHARED': 'em++ -sSIDE_MODULE=1',
+ 'LDFLAGS': '-Wl,-O1 -Wl,-Bsymbolic-functions',
+ 'LDLIBRARY': 'libpython3.12.a',
+ 'LDLIBRARYDIR': '',
+ 'LDSHARED': 'emcc -sSIDE_MODULE=1',
+ 'LDVERSION': '3.12',
+ 'LIBC': '',
+ 'LIBDEST': '/lib/python3.12',
+ 'LIBDIR': '/lib',
+ 'LIBFFI_INCLUDEDIR': '',
+ 'LIBM': '-lm',
+ 'LIBOBJS': '',
+ 'LIBPC': '/lib/pkgconfig',
+ 'LIBPL': '/lib/python3.12/config-3.12-wasm32-emscripten',
+ 'LIBPYTHON': '',
+ 'LIBRARY': 'libpython3.12.a',
+ 'LIBS': '-ldl  -lm',
+ 'LINKCC': 'emcc',
+ 'LINKFORSHARED': '-sALLOW_MEMORY_GROWTH -sEXPORTED_RUNTIME_METHODS=FS',
+ 'LIPO_32BIT_FLAGS': '',
+ 'MACHDEP': 'emscripten',
+ 'MODLIBS': '-lz -lbz2 -lffi',
+ 'MULTIARCH': 'wasm32-emscripten',
+ 'MULTIARCH_CPPFLAGS': '-DMULTIARCH=\\"wasm32-emscripten\\"',
+ 'OPT': '-DNDEBUG -g -fwrapv -O3 -Wall',
+ 'PLATLIBDIR': 'lib',
+ 'PY_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall',
+ 'PY_CFLAGS_NODIST': '-std=c11 -Wextra -Wno-unused-parameter '
+                     '-Wno-missing-field-initializers '
+                     '-Werror=implicit-function-declaration '
+                     '-fvisibility=hidden -fprofile-use -fprofile-correction '
+                     '-flto -fuse-linker-plugin -ffat-lto-objects '
+                     '-flto-partition=none -I./Include/internal',
+ 'PY_CORE_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -std=c11 '
+                   '-Wextra -fvisibility=hidden -I./Include/internal -I. '
+                   '-I./Include -fPIC -DPy_BUILD_CORE',
+ 'PY_CPPFLAGS': '-I. -I./Include',
+ 'PY_ENABLE_SHARED': 0,
+ 'PY_LDFLAGS': '-Wl,-O1 -Wl,-Bsymbolic-functions',
+ 'PY_LDFLAGS_NODIST': '',
+ 'PY_STDMODULE_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -std=c11 '
+                        '-Wextra -fvisibility=hidden -I./Include/internal -I. '
+                        '-I./Include -fPIC',
+ 'Py_DEBUG': 0,
+ 'Py_ENABLE_SHARED': 0,
+ 'Py_HASH_ALGORITHM': 0,
+ 'Py_TRACE_REFS': 0,
+ 'SHLIBS': '',
+ 'SHLIB_SUFFIX': '.so',
+ 'SIZEOF_DOUBLE': 8,
+ 'SIZEOF_FLOAT': 4,
+ 'SIZEOF_FPOS_T': 8,
+ 'SIZEOF_INT': 4,
+ 'SIZEOF_LONG': 4,
+ 'SIZEOF_LONG_DOUBLE': 16,
+ 'SIZEOF_LONG_LONG': 8,
+ 'SIZEOF_OFF_T': 8,
+ 'SIZEOF_PID_T': 4,
+ 'SIZEOF_PTHREAD_KEY_T': 4,
+ 'SIZEOF_PTHREAD_T': 4,
+ 'SIZEOF_SHORT': 2,
+ 'SIZEOF_SIZE_T': 4,
+ 'SIZEOF_TIME_T': 8,
+ 'SIZEOF_UINTPTR_T': 4,
+ 'SIZEOF_VOID_P': 4,
+ 'SIZEOF_WCHAR_T': 4,
+ 'SIZEOF__BOOL': 1,
+ 'SOABI': 'cpython-312-wasm32-emscripten',
+ 'SYSLIBS': '-lm',
+ 'TZPATH': '/usr/share/zoneinfo:/usr/lib/zoneinfo:/usr/share/lib/zoneinfo:/etc/zoneinfo',
+ 'VERSION': '3.12',
+ 'WITH_DOC_STRINGS': 1,
+ 'WITH_PYMALLOC': 1,
+ 'abs_builddir': '/tmp/build/Python-3.12.0',
+ 'abs_srcdir': '/tmp/build/Python-3.12.0',
+ 'datarootdir': '/share',
+ 'exec_prefix': '/',
+ 'prefix': '/',
+ 'py_version': '3.12.0',
+ 'py_version_nodot': '312',
+ 'py_version_short': '3.12',
+ 'srcdir': '/tmp/build/Python-3.12.0'}
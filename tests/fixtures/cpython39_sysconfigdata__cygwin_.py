@@ -0,0 +1,92 @@
+# system configuration generated and used by the sysconfig module
+build_time_vars = {'ABIFLAGS': '',
+ 'AR': 'ar',
+ 'ARFLAGS': 'rcs',
+ 'BINDIR': '/usr/bin',
+ 'BINLIBDEST': '/usr/lib/python3.9',
+ 'BLDLIBRARY': '-L. -lpython3.9',
+ 'BLDSHARED': 'gcc -shared -Wl,--enable-auto-image-base',
+ 'BUILDEXE': '.exe',
+ 'BUILDPYTHON': 'python.exe',
+ 'CC': 'gcc',
+ 'CCSHARED': '',
+ 'CFLAGS': '-Wno-unused-result -Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall '
+           '-ggdb -O2 -pipe -Wall -Werror=format-security',
+ 'CFLAGSFORSHARED': '',
+ 'CONFIG_ARGS': "'--srcdir=/mnt/share/cygpkgs/python39/python39.x86_64/src/Python-3.9.16' "
+                "'--prefix=/usr' '--exec-prefix=/usr' '--localstatedir=/var' "
+                "'--sysconfdir=/etc' '--docdir=/usr/share/doc/python39' "
+                "'--htmldir=/usr/share/doc/python39/html' '-C' "
+                "'--enable-shared' '--enable-loadable-sqlite-extensions' "
+                "'--with-computed-gotos' '--with-dbmliborder=gdbm:ndbm' "
+                "'--without-ensurepip' '--with-system-expat' "
+                "'--with-system-ffi' '--with-tzpath=/usr/share/zoneinfo' "
+                "'build_alias=x86_64-pc-cygwin' 'host_alias=x86_64-pc-cygwin' "
+                "'CC=gcc' 'CFLAGS=-ggdb -O2 -pipe -Wall "
+                "-Werror=format-security'",
+ 'CONFINCLUDEDIR': '/usr/include',
+ 'CONFINCLUDEPY': '/usr/include/python3.9',
+ 'CPPFLAGS': '-I. -I./Include',
+ 'CXX': 'g++',
+ 'DESTLIB': '/usr/lib/python3.9',
+ 'DESTSHARED': '/usr/lib/python3.9/lib-dynload',
+ 'DLLLIBRARY': 'cygpython3.9.dll',
+ 'ENSUREPIP': 'no',
+ 'EXE': '.exe',
+ 'EXT_SUFFIX': '.cpython-39-x86_64-cygwin.dll',
+ 'HAVE_DYNAMIC_LOADING': 1,
+ 'HAVE_LIBREADLINE': 1,
+ 'HOST_GNU_TYPE': 'x86_64-pc-cygwin',
+ 'INCLUDEDIR': '/usr/include',
+ 'INCLUDEPY': '/usr/include/python3.9',
+ 'INSTSONAME': 'libpython3.9.dll.a',
+ 'LDFLAGS': '',
+ 'LDLIBRARY': 'libpython3.9.dll.a',
+ 'LDSHARED': 'gcc -shared -Wl,--enable-auto-image-base',
+ 'LDVERSION': '3.9',
+ 'LIBDEST': '/usr/lib/python3.9',
+ 'LIBDIR': '/usr/lib',
+ 'LIBPC': '/usr/lib/pkgconfig',
+ 'LIBPL': '/usr/lib/python3.9/config-3.9',
+ 'LIBPYTHON': '-lpython3.9',
+ 'LIBRARY': 'libpython3.9.a',
+ 'LIBS': '-lintl -ldl',
+ 'LINKFORSHARED': '',
+ 'MACHDEP': 'cygwin',
+ 'MULTIARCH': '',
+ 'OPT': '-DNDEBUG -g -fwrapv -O3 -Wall',
+ 'PLATLIBDIR': 'lib',
+ 'PY_CFLAGS': '-Wno-unused-result -Wsign-compare -DNDEBUG -g -fwrapv -O3 '
+              '-Wall -ggdb -O2 -pipe -Wall -Werror=format-security',
+ 'PY_CFLAGS_NODIST': '-std=c99 -Wextra -Wno-unused-parameter '
+                     '-Wno-missing-field-initializers '
+                     '-Werror=implicit-function-declaration '
+                     '-fvisibility=hidden -I./Include/internal',
+ 'PY_CPPFLAGS': '-I. -I./Include',
+ 'PY_ENABLE_SHARED': 1,
+ 'PY_LDFLAGS': '',
+ 'PY_LDFLAGS_NODIST': '',
+ 'Py_DEBUG': 0,
+ 'Py_ENABLE_SHARED': 1,
+ 'Py_HASH_ALGORITHM': 0,
+ 'SHLIB_SUFFIX': '.dll',
+ 'SIZEOF_DOUBLE': 8,
+ 'SIZEOF_FLOAT': 4,
+ 'SIZEOF_INT': 4,
+ 'SIZEOF_LONG': 8,
+ 'SIZEOF_LONG_LONG': 8,
+ 'SIZEOF_SIZE_T': 8,
+ 'SIZEOF_VOID_P': 8,
+ 'SIZEOF_WCHAR_T': 2,
+ 'SOABI': 'cpython-39-x86_64-cygwin',
+ 'SYSLIBS': '-lm',
+ 'TZPATH': '/usr/share/zoneinfo',
+ 'VERSION': '3.9',
+ 'WITH_DOC_STRINGS': 1,
+ 'WITH_PYMALLOC': 1,
+ 'datarootdir': '/usr/share',
+ 'exec_prefix': '/usr',
+ 'host': 'x86_64-pc-cygwin',
+ 'prefix': '/usr',
+ 'py_version_nodot': '39',
+ 'srcdir': '.'}
@@ -0,0 +1,125 @@
+# system configuration generated and used by the sysconfig module
+build_time_vars = {'ABIFLAGS': '',
+ 'AR': 'aarch64-linux-gnu-gcc-ar',
+ 'ARFLAGS': 'rcs',
+ 'BASECFLAGS': '-Wno-unused-result -Wsign-compare',
+ 'BINDIR': '/usr/local/bin',
+ 'BINLIBDEST': '/usr/local/lib/python3.9',
+ 'BLDLIBRARY': 'libpython3.9.a',
+ 'BLDSHARED': 'aarch64-linux-gnu-gcc -shared -Wl,-O1 -Wl,-Bsymbolic-functions',
+ 'BUILDEXE': '',
+ 'BUILDPYTHON': 'python',
+ 'CC': 'aarch64-linux-gnu-gcc -pthread',
+ 'CCSHARED': '-fPIC',
+ 'CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall',
+ 'CFLAGSFORSHARED': '-fPIC',
+ 'CONFIGURE_CFLAGS': '',
+ 'CONFIGURE_CFLAGS_NODIST': '-std=c11 -Wextra -Wno-unused-parameter '
+                            '-Wno-missing-field-initializers '
+                            '-Werror=implicit-function-declaration '
+                            '-fvisibility=hidden',
+ 'CONFIGURE_CPPFLAGS': '',
+ 'CONFIGURE_LDFLAGS': '',
+ 'CONFIGURE_LDFLAGS_NODIST': '',
+ 'CONFIG_ARGS': "'--build=aarch64-linux-gnu' '--prefix=/usr/local' "
+                "'--with-ensurepip'",
+ 'CONFINCLUDEDIR': '/usr/local/include',
+ 'CONFINCLUDEPY': '/usr/local/include/python3.9',
+ 'COUNT_ALLOCS': 0,
+ 'CPPFLAGS': '-I. -I./Include',
+ 'CXX': 'aarch64-linux-gnu-g++ -pthread',
+ 'DESTDIRS': '/usr/local /usr/local/lib /usr/local/lib/python3.9 '
+             '/usr/local/lib/python3.9/lib-dynload',
+ 'DESTLIB': '/usr/local/lib/python3.9',
+ 'DESTSHARED': '/usr/local/lib/python3.9/lib-dynload',
+ 'DLLLIBRARY': '',
+ 'ENSUREPIP': 'upgrade',
+ 'EXE': '',
+ 'EXEMODE': 755,
+ 'EXTRA_CFLAGS': '',
+ 'EXT_SUFFIX': '.cpython-39-aarch64-linux-gnu.so',
+ 'HAVE_LIBREADLINE': 1,
+ 'HOST_GNU_TYPE': 'aarch64-unknown-linux-gnu',
+ 'INCLUDEDIR': '/usr/local/include',
+ 'INCLUDEPY': '/usr/local/include/python3.9',
+ 'INSTSONAME': 'libpython3.9.a',
+ 'LDCXXSHARED': 'aarch64-linux-gnu-g++ -shared',
+ 'LDFLAGS': '-Wl,-O1 -Wl,-Bsymbolic-functions',
+ 'LDLIBRARY': 'libpython3.9.a',
+ 'LDLIBRARYDIR': '',
+ 'LDSHARED': 'aarch64-linux-gnu-gcc -shared -Wl,-O1 -Wl,-Bsymbolic-functions',
+ 'LDVERSION': '3.9',
+ 'LIBC': '',
+ 'LIBDEST': '/usr/local/lib/python3.9',
+ 'LIBDIR': '/usr/local/lib',
+ 'LIBFFI_INCLUDEDIR': '',
+ 'LIBM': '-lm',
+ 'LIBOBJS': '',
+ 'LIBPC': '/usr/local/lib/pkgconfig',
+ 'LIBPL': '/usr/local/lib/python3.9/config-3.9-aarch64-linux-gnu',
+ 'LIBPYTHON': '',
+ 'LIBRARY': 'libpython3.9.a',
+ 'LIBS': '-lcrypt -lpthread -ldl  -lutil -lm',
+ 'LINKCC': 'aarch64-linux-gnu-gcc -pthread',
+ 'LINKFORSHARED': '-Xlinker -export-dynamic',
+ 'LIPO_32BIT_FLAGS': '',
+ 'MACHDEP': 'linux',
+ 'MODLIBS': '-lssl -lcrypto -lffi -lz -lbz2 -llzma -lreadline -lncursesw',
+ 'MULTIARCH': 'aarch64-linux-gnu',
+ 'MULTIARCH_CPPFLAGS': '-DMULTIARCH=\\"aarch64-linux-gnu\\"',
+ 'OPT': '-DNDEBUG -g -fwrapv -O3 -Wall',
+ 'PLATLIBDIR': 'lib',
+ 'PY_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall',
+ 'PY_CFLAGS_NODIST': '-std=c99 -Wextra -Wno-unused-parameter '
+                     '-Wno-missing-field-initializers '
+                     '-Werror=implicit-function-declaration '
+                     '-I./Include/internal',
+ 'PY_CORE_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -std=c11 '
+                   '-Wextra -fvisibility=hidden -I./Include/internal -I. '
+                   '-I./Include -fPIC -DPy_BUILD_CORE',
+ 'PY_CPPFLAGS': '-I. -I./Include',
+ 'PY_ENABLE_SHARED': 0,
+ 'PY_LDFLAGS': '-Wl,-O1 -Wl,-Bsymbolic-functions',
+ 'PY_LDFLAGS_NODIST': '',
+ 'PY_STDMODULE_CFLAGS': '-Wsign-compare -DNDEBUG -g -fwrapv -O3 -Wall -std=c11 '
+                        '-Wextra -fvisibility=hidden -I./Include/internal -I. '
+                        '-I./Include -fPIC',
+ 'Py_DEBUG': 0,
+ 'Py_ENABLE_SHARED': 0,
+ 'Py_HASH_ALGORITHM': 0,
+ 'Py_TRACE_REFS': 0,
+ 'SHLIBS': '-lcrypt -lpthread -ldl  -lutil -lm',
+ 'SHLIB_SUFFIX': '.so',
+ 'SIZEOF_DOUBLE': 8,
+ 'SIZEOF_FLOAT': 4,
+ 'SIZEOF_FPOS_T': 16,
+ 'SIZEOF_INT': 4,
+ 'SIZEOF_LONG': 8,
+ 'SIZEOF_LONG_DOUBLE': 16,
+ 'SIZEOF_LONG_LONG': 8,
+ 'SIZEOF_OFF_T': 8,
+ 'SIZEOF_PID_T': 4,
+ 'SIZEOF_PTHREAD_KEY_T': 4,
+ 'SIZEOF_PTHREAD_T': 8,
+ 'SIZEOF_SHORT': 2,
+ 'SIZEOF_SIZE_T': 8,
+ 'SIZEOF_TIME_T': 8,
+ 'SIZEOF_UINTPTR_T': 8,
+ 'SIZEOF_VOID_P': 8,
+ 'SIZEOF_WCHAR_T': 4,
+ 'SIZEOF__BOOL': 1,
+ 'SOABI': 'cpython-39-aarch64-linux-gnu',
+ 'SYSLIBS': '-lm',
+ 'VERSION': '3.9',
+ 'WITH_DOC_STRINGS': 1,
+ 'WITH_PYMALLOC': 1,
+ 'WITH_THREAD': 1,
+ 'abs_builddir': '/tmp/build/Python-3.9.0',
+ 'abs_srcdir': '/tmp/build/Python-3.9.0',
+ 'datarootdir': '/usr/local/share',
+ 'exec_prefix': '/usr/local',
+ 'prefix': '/usr/local',
+ 'py_version': '3.9.0',
+ 'py_version_nodot': '39',
+ 'py_version_short': '3.9',
+ 'srcdir': '/tmp/build/Python-3.9.0'}
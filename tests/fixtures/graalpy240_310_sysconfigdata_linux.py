@@ -0,0 +1,22 @@
+# system configuration generated and used by the sysconfig module
+build_time_vars = {'ABIFLAGS': '',
+ 'AR': 'llvm-ar',
+ 'CC': 'clang',
+ 'CCSHARED': '-fPIC',
+ 'CFLAGS': '-DNDEBUG',
+ 'CXX': 'clang++',
+ 'EXE': '',
+ 'EXT_SUFFIX': '.graalpy240-310-native-x86_64-linux.so',
+ 'INCLUDEPY': '/opt/graalpy-24.0.0-linux-amd64/include/python3.10',
+ 'LDFLAGS': '',
+ 'LDSHARED': 'clang -shared -fPIC',
+ 'LIBDEST': '/opt/graalpy-24.0.0-linux-amd64/lib/python3.10',
+ 'LIBDIR': '/opt/graalpy-24.0.0-linux-amd64/lib',
+ 'MULTIARCH': 'x86_64-linux-gnu',
+ 'Py_DEBUG': 0,
+ 'Py_ENABLE_SHARED': 0,
+ 'SHLIB_SUFFIX': '.so',
+ 'SOABI': 'graalpy240-310-native-x86_64-linux',
+ 'WITH_THREAD': 1,
+ 'exec_prefix': '/opt/graalpy-24.0.0-linux-amd64',
+ 'prefix': '/opt/graalpy-24.0.0-linux-amd64'}
@@ -0,0 +1,20 @@
+# system configuration generated and used by the sysconfig module
+build_time_vars = {'ABIFLAGS': '',
+ 'CC': 'gcc -pthread',
+ 'CCSHARED': '-fPIC',
+ 'CFLAGS': '-DNDEBUG -O2',
+ 'EXE': '',
+ 'INCLUDEPY': '/opt/pypy3.10-v7.3.17-linux64/include/pypy3.10',
+ 'LDFLAGS': '-Wl,-Bsymbolic-functions',
+ 'LDLIBRARY': 'libpypy3.10-c.so',
+ 'LDSHARED': 'gcc -pthread -shared',
+ 'LDVERSION': '3.10',
+ 'LIBDIR': '/opt/pypy3.10-v7.3.17-linux64/bin',
+ 'MULTIARCH': 'x86_64-linux-gnu',
+ 'Py_DEBUG': 0,
+ 'Py_ENABLE_SHARED': 0,
+ 'SHLIB_SUFFIX': '.so',
+ 'SOABI': 'pypy310-pp73-x86_64-linux-gnu',
+ 'exec_prefix': '/opt/pypy3.10-v7.3.17-linux64',
+ 'implementation_version': '7.3.17',
+ 'prefix': '/opt/pypy3.10-v7.3.17-linux64'}